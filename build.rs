@@ -1,8 +1,114 @@
-use std::path::Path;
+use std::{
+    env,
+    fmt::Write,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::Command,
+};
 
+extern crate cc;
 extern crate cmake;
 
 fn main() {
+    // Trampoline used to catch exceptions thrown by QuEST without unwinding
+    // through C stack frames.  See: src/error.rs
+    cc::Build::new()
+        .file("src/error.c")
+        .flag_if_supported("-std=c11")
+        .emit_rerun_if_env_changed(false)
+        .compile("quest_bind_error");
+
+    // Lookup of the OpenMP runtime loaded with QuEST.  See: src/questenv.rs
+    cc::Build::new()
+        .file("src/openmp.c")
+        .flag_if_supported("-std=c11")
+        .emit_rerun_if_env_changed(false)
+        .compile("quest_bind_openmp");
+    if env::var("CARGO_CFG_TARGET_OS").unwrap() == "linux" {
+        // dlsym() lives in libdl before glibc 2.34
+        println!("cargo:rustc-link-lib=dylib=dl");
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/error.c");
+    println!("cargo:rerun-if-changed=src/openmp.c");
+    println!("cargo:rerun-if-env-changed=QUEST_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=QUEST_INCLUDE_DIR");
+
+    let lib_dir = env::var_os("QUEST_LIB_DIR").map(PathBuf::from);
+    let include_dir = env::var_os("QUEST_INCLUDE_DIR").map(PathBuf::from);
+
+    if let Some(include_dir) = &include_dir {
+        assert!(
+            include_dir.join("QuEST.h").is_file(),
+            "QUEST_INCLUDE_DIR={} does not contain QuEST.h",
+            include_dir.display()
+        );
+    }
+
+    println!("cargo:rustc-check-cfg=cfg(quest_bind_layout)");
+
+    if lib_dir.is_some() || cfg!(feature = "system") {
+        link_prebuilt(lib_dir.as_deref());
+        verify_layout(include_dir.as_deref());
+    } else {
+        build_vendored();
+        verify_layout(Some(Path::new("QuEST/QuEST/include")));
+    }
+}
+
+/// Link against a prebuilt `libQuEST`: found in `lib_dir`, if given, or in
+/// the default search paths of the linker.
+///
+/// The library must have been built with the same precision and the same
+/// mode (multithreaded, distributed or GPU-accelerated) as selected by the
+/// features of this crate.
+fn link_prebuilt(lib_dir: Option<&Path>) {
+    if let Some(lib_dir) = lib_dir {
+        assert!(
+            lib_dir.is_dir(),
+            "QUEST_LIB_DIR={} is not a directory",
+            lib_dir.display()
+        );
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    }
+
+    if cfg!(feature = "static") {
+        println!("cargo:rustc-link-lib=static=QuEST");
+        // Dependencies of the static library.  Their search paths, if not
+        // standard, must be passed to the linker, e.g. with RUSTFLAGS.
+        println!("cargo:rustc-link-lib=dylib=m");
+        if cfg!(feature = "gpu") {
+            println!("cargo:rustc-link-lib=dylib=cudart");
+            println!("cargo:rustc-link-lib=dylib=stdc++");
+        } else {
+            println!("cargo:rustc-link-lib=dylib=gomp");
+        }
+        if cfg!(feature = "mpi") {
+            println!("cargo:rustc-link-lib=dylib=mpi");
+        }
+    } else {
+        println!("cargo:rustc-link-lib=dylib=QuEST");
+    }
+}
+
+/// Build `libQuEST.so` from the `QuEST` submodule with CMake.
+fn build_vendored() {
+    if cfg!(feature = "static") {
+        panic!(
+            "feature \"static\" requires a prebuilt libQuEST.a: set \
+             QUEST_LIB_DIR or enable feature \"system\""
+        );
+    }
+    assert!(
+        Path::new("QuEST/CMakeLists.txt").is_file(),
+        "QuEST source code not found: run `git submodule update --init`, or \
+         set QUEST_LIB_DIR to link against a prebuilt libQuEST"
+    );
+    println!("cargo:rerun-if-changed=QuEST");
+
     let mut config = cmake::Config::new("QuEST");
 
     config
@@ -40,9 +146,188 @@ fn main() {
     // issue with `cargo`: [#8531](https://github.com/rust-lang/cargo/issues/8531).
     //
     // Create a link to libQuEST
-    let out_dir = std::env::var_os("OUT_DIR").unwrap();
+    let out_dir = env::var_os("OUT_DIR").unwrap();
     let libfile = Path::new(&out_dir).join("build/QuEST/libQuEST.so");
     let linkfile = Path::new(&out_dir).join("../../../deps/libQuEST.so");
     let _ = std::fs::remove_file(&linkfile);
     std::os::unix::fs::symlink(libfile, linkfile).unwrap();
 }
+
+/// Structs declared in `src/ffi.rs` and their fields.
+const FFI_STRUCTS: &[(&str, &[&str])] = &[
+    (
+        "QASMLogger",
+        &["buffer", "bufferSize", "bufferFill", "isLogging"],
+    ),
+    ("ComplexArray", &["real", "imag"]),
+    ("Complex", &["real", "imag"]),
+    ("ComplexMatrix2", &["real", "imag"]),
+    ("ComplexMatrix4", &["real", "imag"]),
+    ("ComplexMatrixN", &["numQubits", "real", "imag"]),
+    ("Vector", &["x", "y", "z"]),
+    (
+        "PauliHamil",
+        &["pauliCodes", "termCoeffs", "numSumTerms", "numQubits"],
+    ),
+    (
+        "DiagonalOp",
+        &[
+            "numQubits",
+            "numElemsPerChunk",
+            "numChunks",
+            "chunkId",
+            "real",
+            "imag",
+            "deviceOperator",
+        ],
+    ),
+    (
+        "Qureg",
+        &[
+            "isDensityMatrix",
+            "numQubitsRepresented",
+            "numQubitsInStateVec",
+            "numAmpsPerChunk",
+            "numAmpsTotal",
+            "chunkId",
+            "numChunks",
+            "stateVec",
+            "pairStateVec",
+            "deviceStateVec",
+            "firstLevelReduction",
+            "secondLevelReduction",
+            "qasmLog",
+        ],
+    ),
+    ("QuESTEnv", &["rank", "numRanks", "seeds", "numSeeds"]),
+];
+
+/// Other types declared in `src/ffi.rs`: Rust name and C name.
+const FFI_TYPES: &[(&str, &str)] = &[
+    ("qreal", "qreal"),
+    ("pauliOpType", "enum pauliOpType"),
+    ("phaseFunc", "enum phaseFunc"),
+    ("bitEncoding", "enum bitEncoding"),
+];
+
+/// Check sizes, alignments and field offsets of the types in `src/ffi.rs`
+/// against `QuEST.h` found in `include_dir`, or in the default search paths
+/// of the compiler.
+///
+/// A small C program prints the layout of each type as compile-time
+/// assertions, which are then included in `src/ffi.rs`, so that any
+/// mismatch fails the build.  If `include_dir` is not given and the headers
+/// cannot be found, the check is skipped with a warning.  The check is also
+/// skipped when cross-compiling, since the program cannot be run.
+fn verify_layout(include_dir: Option<&Path>) {
+    if env::var("TARGET").unwrap() != env::var("HOST").unwrap() {
+        println!(
+            "cargo:warning=cannot verify layout of QuEST types when \
+             cross-compiling"
+        );
+        return;
+    }
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let src = out_dir.join("layout_probe.c");
+    let exe = out_dir.join("layout_probe");
+    std::fs::write(&src, layout_probe_source()).unwrap();
+
+    let precision = if cfg!(feature = "f32") { 1 } else { 2 };
+    let mut cmd = cc::Build::new()
+        .flag_if_supported("-std=c11")
+        .emit_rerun_if_env_changed(false)
+        .cargo_metadata(false)
+        .get_compiler()
+        .to_command();
+    if let Some(include_dir) = include_dir {
+        println!(
+            "cargo:rerun-if-changed={}",
+            include_dir.join("QuEST.h").display()
+        );
+        cmd.arg("-I").arg(include_dir);
+    }
+    cmd.arg(format!("-DQuEST_PREC={precision}"))
+        .arg(&src)
+        .arg("-o")
+        .arg(&exe);
+
+    let status = cmd.status().unwrap();
+    if !status.success() {
+        assert!(
+            include_dir.is_none(),
+            "cannot compile {}: check QuEST headers in {}",
+            src.display(),
+            include_dir.unwrap().display()
+        );
+        println!(
+            "cargo:warning=QuEST.h not found: cannot verify layout of QuEST \
+             types. Set QUEST_INCLUDE_DIR to enable the check"
+        );
+        return;
+    }
+
+    let output = Command::new(&exe).output().unwrap();
+    assert!(output.status.success(), "{} failed", exe.display());
+    std::fs::write(out_dir.join("layout.rs"), output.stdout).unwrap();
+    println!("cargo:rustc-cfg=quest_bind_layout");
+}
+
+/// Source code of the C program printing the layout of QuEST types.
+fn layout_probe_source() -> String {
+    let mut src = String::from(
+        r#"#include <stddef.h>
+#include <stdio.h>
+
+#include "QuEST.h"
+
+static void check_type(const char *name, size_t size, size_t align) {
+    printf("const _: () = assert!(\n"
+           "    ::core::mem::size_of::<%s>() == %zu,\n"
+           "    \"size of %s does not match QuEST.h\"\n"
+           ");\n",
+           name, size, name);
+    printf("const _: () = assert!(\n"
+           "    ::core::mem::align_of::<%s>() == %zu,\n"
+           "    \"alignment of %s does not match QuEST.h\"\n"
+           ");\n",
+           name, align, name);
+}
+
+static void check_field(const char *name, const char *field, size_t offset) {
+    printf("const _: () = assert!(\n"
+           "    ::core::mem::offset_of!(%s, %s) == %zu,\n"
+           "    \"offset of %s::%s does not match QuEST.h\"\n"
+           ");\n",
+           name, field, offset, name, field);
+}
+
+int main(void) {
+    printf("// Generated by build.rs.  Do not edit.\n");
+"#,
+    );
+    for (name, c_name) in FFI_TYPES {
+        writeln!(
+            src,
+            "    check_type(\"{name}\", sizeof({c_name}), _Alignof({c_name}));"
+        )
+        .unwrap();
+    }
+    for (name, fields) in FFI_STRUCTS {
+        writeln!(
+            src,
+            "    check_type(\"{name}\", sizeof({name}), _Alignof({name}));"
+        )
+        .unwrap();
+        for field in *fields {
+            writeln!(
+                src,
+                "    check_field(\"{name}\", \"{field}\", offsetof({name}, \
+                 {field}));"
+            )
+            .unwrap();
+        }
+    }
+    src.push_str("    return 0;\n}\n");
+    src
+}
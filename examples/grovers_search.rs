@@ -9,6 +9,7 @@ use quest_bind::{
     QuestEnv,
     QuestError,
     Qureg,
+    StateVector,
     PI,
 };
 use rand::Rng;
@@ -16,19 +17,19 @@ use rand::Rng;
 const NUM_QUBITS: i32 = 0x10;
 const NUM_ELEMS: i64 = 1 << NUM_QUBITS;
 
-fn tensor_gate<'a, F>(
-    qureg: &mut Qureg<'a>,
+fn tensor_gate<F>(
+    qureg: &mut StateVector,
     gate: F,
     qubits: &[i32],
 ) -> Result<(), QuestError>
 where
-    F: for<'b> Fn(&'b mut Qureg<'a>, i32) -> Result<(), QuestError>,
+    F: Fn(&mut StateVector, i32) -> Result<(), QuestError>,
 {
     qubits.iter().try_for_each(|&q| gate(qureg, q))
 }
 
 fn apply_oracle(
-    qureg: &mut Qureg<'_>,
+    qureg: &mut StateVector,
     qubits: &[i32],
     sol_elem: i64,
 ) -> Result<(), QuestError> {
@@ -47,7 +48,7 @@ fn apply_oracle(
 }
 
 fn apply_diffuser(
-    qureg: &mut Qureg<'_>,
+    qureg: &mut StateVector,
     qubits: &[i32],
 ) -> Result<(), QuestError> {
     // apply H to transform |+> into |0>
@@ -80,7 +81,7 @@ fn main() -> Result<(), QuestError> {
     // FIXME
     const N: i32 = NUM_QUBITS;
     // prepare |+>
-    let mut qureg = Qureg::<'_>::try_new(N, env)?;
+    let mut qureg = Qureg::try_new(N, env)?;
     qureg.init_plus_state();
     // use all qubits in the register
     let qubits = &(0..NUM_QUBITS).collect::<Vec<_>>();
@@ -0,0 +1,2453 @@
+//! Quantum circuits that can be built once and applied to many registers.
+
+use crate::{
+    BitEncoding,
+    ComplexMatrix2,
+    ComplexMatrix4,
+    ComplexMatrixN,
+    DensityMatrix,
+    PauliOpType,
+    PhaseFunc,
+    Qcomplex,
+    Qreal,
+    QuestError,
+    Qureg,
+    QuregKind,
+    Vector,
+};
+
+/// A single operation on a [`Qureg`].
+///
+/// Each variant mirrors a method of [`Qureg`] of the same name, and holds
+/// the method's arguments.
+///
+/// [`Qureg`]: crate::Qureg
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Operation {
+    /// See [`Qureg::phase_shift()`].
+    PhaseShift {
+        target_qubit: i32,
+        angle:        Qreal,
+    },
+    /// See [`Qureg::controlled_phase_shift()`].
+    ControlledPhaseShift {
+        id_qubit1: i32,
+        id_qubit2: i32,
+        angle:     Qreal,
+    },
+    /// See [`Qureg::multi_controlled_phase_shift()`].
+    MultiControlledPhaseShift {
+        control_qubits: Vec<i32>,
+        angle:          Qreal,
+    },
+    /// See [`Qureg::controlled_phase_flip()`].
+    ControlledPhaseFlip { id_qubit1: i32, id_qubit2: i32 },
+    /// See [`Qureg::multi_controlled_phase_flip()`].
+    MultiControlledPhaseFlip { control_qubits: Vec<i32> },
+    /// See [`Qureg::s_gate()`].
+    SGate { target_qubit: i32 },
+    /// See [`Qureg::t_gate()`].
+    TGate { target_qubit: i32 },
+    /// See [`Qureg::compact_unitary()`].
+    CompactUnitary {
+        target_qubit: i32,
+        alpha:        Qcomplex,
+        beta:         Qcomplex,
+    },
+    /// See [`Qureg::unitary()`].
+    Unitary {
+        target_qubit: i32,
+        u:            ComplexMatrix2,
+    },
+    /// See [`Qureg::rotate_x()`].
+    RotateX { rot_qubit: i32, angle: Qreal },
+    /// See [`Qureg::rotate_y()`].
+    RotateY { rot_qubit: i32, angle: Qreal },
+    /// See [`Qureg::rotate_z()`].
+    RotateZ { rot_qubit: i32, angle: Qreal },
+    /// See [`Qureg::rotate_around_axis()`].
+    RotateAroundAxis {
+        rot_qubit: i32,
+        angle:     Qreal,
+        axis:      Vector,
+    },
+    /// See [`Qureg::controlled_rotate_x()`].
+    ControlledRotateX {
+        control_qubit: i32,
+        target_qubit:  i32,
+        angle:         Qreal,
+    },
+    /// See [`Qureg::controlled_rotate_y()`].
+    ControlledRotateY {
+        control_qubit: i32,
+        target_qubit:  i32,
+        angle:         Qreal,
+    },
+    /// See [`Qureg::controlled_rotate_z()`].
+    ControlledRotateZ {
+        control_qubit: i32,
+        target_qubit:  i32,
+        angle:         Qreal,
+    },
+    /// See [`Qureg::controlled_rotate_around_axis()`].
+    ControlledRotateAroundAxis {
+        control_qubit: i32,
+        target_qubit:  i32,
+        angle:         Qreal,
+        axis:          Vector,
+    },
+    /// See [`Qureg::controlled_compact_unitary()`].
+    ControlledCompactUnitary {
+        control_qubit: i32,
+        target_qubit:  i32,
+        alpha:         Qcomplex,
+        beta:          Qcomplex,
+    },
+    /// See [`Qureg::controlled_unitary()`].
+    ControlledUnitary {
+        control_qubit: i32,
+        target_qubit:  i32,
+        u:             ComplexMatrix2,
+    },
+    /// See [`Qureg::multi_controlled_unitary()`].
+    MultiControlledUnitary {
+        control_qubits: Vec<i32>,
+        target_qubit:   i32,
+        u:              ComplexMatrix2,
+    },
+    /// See [`Qureg::pauli_x()`].
+    PauliX { target_qubit: i32 },
+    /// See [`Qureg::pauli_y()`].
+    PauliY { target_qubit: i32 },
+    /// See [`Qureg::pauli_z()`].
+    PauliZ { target_qubit: i32 },
+    /// See [`Qureg::hadamard()`].
+    Hadamard { target_qubit: i32 },
+    /// See [`Qureg::controlled_not()`].
+    ControlledNot {
+        control_qubit: i32,
+        target_qubit:  i32,
+    },
+    /// See [`Qureg::multi_controlled_multi_qubit_not()`].
+    MultiControlledMultiQubitNot { ctrls: Vec<i32>, targs: Vec<i32> },
+    /// See [`Qureg::multi_qubit_not()`].
+    MultiQubitNot { targs: Vec<i32> },
+    /// See [`Qureg::controlled_pauli_y()`].
+    ControlledPauliY {
+        control_qubit: i32,
+        target_qubit:  i32,
+    },
+    /// See [`Qureg::swap_gate()`].
+    SwapGate { qubit1: i32, qubit2: i32 },
+    /// See [`Qureg::sqrt_swap_gate()`].
+    SqrtSwapGate { qb1: i32, qb2: i32 },
+    /// See [`Qureg::multi_state_controlled_unitary()`].
+    MultiStateControlledUnitary {
+        control_qubits: Vec<i32>,
+        control_state:  Vec<i32>,
+        target_qubit:   i32,
+        u:              ComplexMatrix2,
+    },
+    /// See [`Qureg::multi_rotate_z()`].
+    MultiRotateZ { qubits: Vec<i32>, angle: Qreal },
+    /// See [`Qureg::multi_rotate_pauli()`].
+    MultiRotatePauli {
+        target_qubits: Vec<i32>,
+        target_paulis: Vec<PauliOpType>,
+        angle:         Qreal,
+    },
+    /// See [`Qureg::multi_controlled_multi_rotate_z()`].
+    MultiControlledMultiRotateZ {
+        control_qubits: Vec<i32>,
+        target_qubits:  Vec<i32>,
+        angle:          Qreal,
+    },
+    /// See [`Qureg::multi_controlled_multi_rotate_pauli()`].
+    MultiControlledMultiRotatePauli {
+        control_qubits: Vec<i32>,
+        target_qubits:  Vec<i32>,
+        target_paulis:  Vec<PauliOpType>,
+        angle:          Qreal,
+    },
+    /// See [`Qureg::two_qubit_unitary()`].
+    TwoQubitUnitary {
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u:             ComplexMatrix4,
+    },
+    /// See [`Qureg::controlled_two_qubit_unitary()`].
+    ControlledTwoQubitUnitary {
+        control_qubit: i32,
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u:             ComplexMatrix4,
+    },
+    /// See [`Qureg::multi_controlled_two_qubit_unitary()`].
+    MultiControlledTwoQubitUnitary {
+        control_qubits: Vec<i32>,
+        target_qubit1:  i32,
+        target_qubit2:  i32,
+        u:              ComplexMatrix4,
+    },
+    /// See [`Qureg::multi_qubit_unitary()`].
+    MultiQubitUnitary {
+        targs: Vec<i32>,
+        u:     ComplexMatrixN,
+    },
+    /// See [`Qureg::controlled_multi_qubit_unitary()`].
+    ControlledMultiQubitUnitary {
+        ctrl:  i32,
+        targs: Vec<i32>,
+        u:     ComplexMatrixN,
+    },
+    /// See [`Qureg::multi_controlled_multi_qubit_unitary()`].
+    MultiControlledMultiQubitUnitary {
+        ctrls: Vec<i32>,
+        targs: Vec<i32>,
+        u:     ComplexMatrixN,
+    },
+    /// See [`Qureg::apply_matrix2()`].
+    ApplyMatrix2 {
+        target_qubit: i32,
+        u:            ComplexMatrix2,
+    },
+    /// See [`Qureg::apply_matrix4()`].
+    ApplyMatrix4 {
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u:             ComplexMatrix4,
+    },
+    /// See [`Qureg::apply_matrix_n()`].
+    ApplyMatrixN {
+        targs: Vec<i32>,
+        u:     ComplexMatrixN,
+    },
+    /// See [`Qureg::apply_multi_controlled_matrix_n()`].
+    ApplyMultiControlledMatrixN {
+        ctrls: Vec<i32>,
+        targs: Vec<i32>,
+        u:     ComplexMatrixN,
+    },
+    /// See [`Qureg::mix_dephasing()`].
+    MixDephasing {
+        target_qubit: i32,
+        prob:         Qreal,
+    },
+    /// See [`Qureg::mix_two_qubit_dephasing()`].
+    MixTwoQubitDephasing {
+        qubit1: i32,
+        qubit2: i32,
+        prob:   Qreal,
+    },
+    /// See [`Qureg::mix_depolarising()`].
+    MixDepolarising {
+        target_qubit: i32,
+        prob:         Qreal,
+    },
+    /// See [`Qureg::mix_damping()`].
+    MixDamping {
+        target_qubit: i32,
+        prob:         Qreal,
+    },
+    /// See [`Qureg::mix_two_qubit_depolarising()`].
+    MixTwoQubitDepolarising {
+        qubit1: i32,
+        qubit2: i32,
+        prob:   Qreal,
+    },
+    /// See [`Qureg::mix_pauli()`].
+    MixPauli {
+        target_qubit: i32,
+        prob_x:       Qreal,
+        prob_y:       Qreal,
+        prob_z:       Qreal,
+    },
+    /// See [`Qureg::mix_kraus_map()`].
+    MixKrausMap {
+        target: i32,
+        ops:    Vec<ComplexMatrix2>,
+    },
+    /// See [`Qureg::mix_two_qubit_kraus_map()`].
+    MixTwoQubitKrausMap {
+        target1: i32,
+        target2: i32,
+        ops:     Vec<ComplexMatrix4>,
+    },
+    /// See [`Qureg::mix_multi_qubit_kraus_map()`].
+    MixMultiQubitKrausMap {
+        targets: Vec<i32>,
+        ops:     Vec<ComplexMatrixN>,
+    },
+    /// See [`Qureg::mix_nontp_kraus_map()`].
+    MixNontpKrausMap {
+        target: i32,
+        ops:    Vec<ComplexMatrix2>,
+    },
+    /// See [`Qureg::mix_nontp_two_qubit_kraus_map()`].
+    MixNontpTwoQubitKrausMap {
+        target1: i32,
+        target2: i32,
+        ops:     Vec<ComplexMatrix4>,
+    },
+    /// See [`Qureg::mix_nontp_multi_qubit_kraus_map()`].
+    MixNontpMultiQubitKrausMap {
+        targets: Vec<i32>,
+        ops:     Vec<ComplexMatrixN>,
+    },
+    /// See [`Qureg::collapse_to_outcome()`].
+    CollapseToOutcome {
+        measure_qubit: i32,
+        outcome:       i32,
+    },
+    /// See [`Qureg::measure()`].
+    Measure { measure_qubit: i32 },
+    /// See [`Qureg::apply_projector()`].
+    ApplyProjector { qubit: i32, outcome: i32 },
+    /// See [`Qureg::apply_phase_func()`].
+    ApplyPhaseFunc {
+        qubits:    Vec<i32>,
+        encoding:  BitEncoding,
+        coeffs:    Vec<Qreal>,
+        exponents: Vec<Qreal>,
+    },
+    /// See [`Qureg::apply_phase_func_overrides()`].
+    ApplyPhaseFuncOverrides {
+        qubits:          Vec<i32>,
+        encoding:        BitEncoding,
+        coeffs:          Vec<Qreal>,
+        exponents:       Vec<Qreal>,
+        override_inds:   Vec<i64>,
+        override_phases: Vec<Qreal>,
+    },
+    /// See [`Qureg::apply_multi_var_phase_func()`].
+    ApplyMultiVarPhaseFunc {
+        qubits:             Vec<i32>,
+        num_qubits_per_reg: Vec<i32>,
+        encoding:           BitEncoding,
+        coeffs:             Vec<Qreal>,
+        exponents:          Vec<Qreal>,
+        num_terms_per_reg:  Vec<i32>,
+    },
+    /// See [`Qureg::apply_multi_var_phase_func_overrides()`].
+    ApplyMultiVarPhaseFuncOverrides {
+        qubits:             Vec<i32>,
+        num_qubits_per_reg: Vec<i32>,
+        encoding:           BitEncoding,
+        coeffs:             Vec<Qreal>,
+        exponents:          Vec<Qreal>,
+        num_terms_per_reg:  Vec<i32>,
+        override_inds:      Vec<i64>,
+        override_phases:    Vec<Qreal>,
+    },
+    /// See [`Qureg::apply_named_phase_func()`].
+    ApplyNamedPhaseFunc {
+        qubits:             Vec<i32>,
+        num_qubits_per_reg: Vec<i32>,
+        encoding:           BitEncoding,
+        function_name_code: PhaseFunc,
+    },
+    /// See [`Qureg::apply_named_phase_func_overrides()`].
+    ApplyNamedPhaseFuncOverrides {
+        qubits:             Vec<i32>,
+        num_qubits_per_reg: Vec<i32>,
+        encoding:           BitEncoding,
+        function_name_code: PhaseFunc,
+        override_inds:      Vec<i64>,
+        override_phases:    Vec<Qreal>,
+    },
+    /// See [`Qureg::apply_param_named_phase_func()`].
+    ApplyParamNamedPhaseFunc {
+        qubits:             Vec<i32>,
+        num_qubits_per_reg: Vec<i32>,
+        encoding:           BitEncoding,
+        function_name_code: PhaseFunc,
+        params:             Vec<Qreal>,
+    },
+    /// See [`Qureg::apply_param_named_phase_func_overrides()`].
+    ApplyParamNamedPhaseFuncOverrides {
+        qubits:             Vec<i32>,
+        num_qubits_per_reg: Vec<i32>,
+        encoding:           BitEncoding,
+        function_name_code: PhaseFunc,
+        params:             Vec<Qreal>,
+        override_inds:      Vec<i64>,
+        override_phases:    Vec<Qreal>,
+    },
+    /// See [`Qureg::apply_full_qft()`].
+    ApplyFullQft,
+    /// See [`Qureg::apply_qft()`].
+    ApplyQft { qubits: Vec<i32> },
+}
+
+impl Operation {
+    /// Qubits the operation acts on, including control qubits.
+    ///
+    /// The qubits are listed in the order they appear in the arguments of the
+    /// corresponding [`Qureg`] method, and may contain repetitions.  An
+    /// operation that acts on the whole register, like
+    /// [`Operation::ApplyFullQft`], returns an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let op = Operation::ControlledNot {
+    ///     control_qubit: 0,
+    ///     target_qubit:  2,
+    /// };
+    ///
+    /// assert_eq!(op.qubits(), vec![0, 2]);
+    /// assert_eq!(op.num_qubits(), 3);
+    /// ```
+    ///
+    /// [`Qureg`]: crate::Qureg
+    #[must_use]
+    pub fn qubits(&self) -> Vec<i32> {
+        match self {
+            Self::PhaseShift {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::ControlledPhaseShift {
+                id_qubit1,
+                id_qubit2,
+                ..
+            } => {
+                vec![*id_qubit1, *id_qubit2]
+            }
+            Self::MultiControlledPhaseShift {
+                control_qubits, ..
+            } => control_qubits.clone(),
+            Self::ControlledPhaseFlip {
+                id_qubit1,
+                id_qubit2,
+            } => {
+                vec![*id_qubit1, *id_qubit2]
+            }
+            Self::MultiControlledPhaseFlip {
+                control_qubits,
+            } => control_qubits.clone(),
+            Self::SGate {
+                target_qubit,
+            } => {
+                vec![*target_qubit]
+            }
+            Self::TGate {
+                target_qubit,
+            } => {
+                vec![*target_qubit]
+            }
+            Self::CompactUnitary {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::Unitary {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::RotateX {
+                rot_qubit, ..
+            } => {
+                vec![*rot_qubit]
+            }
+            Self::RotateY {
+                rot_qubit, ..
+            } => {
+                vec![*rot_qubit]
+            }
+            Self::RotateZ {
+                rot_qubit, ..
+            } => {
+                vec![*rot_qubit]
+            }
+            Self::RotateAroundAxis {
+                rot_qubit, ..
+            } => {
+                vec![*rot_qubit]
+            }
+            Self::ControlledRotateX {
+                control_qubit,
+                target_qubit,
+                ..
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::ControlledRotateY {
+                control_qubit,
+                target_qubit,
+                ..
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::ControlledRotateZ {
+                control_qubit,
+                target_qubit,
+                ..
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::ControlledRotateAroundAxis {
+                control_qubit,
+                target_qubit,
+                ..
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::ControlledCompactUnitary {
+                control_qubit,
+                target_qubit,
+                ..
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::ControlledUnitary {
+                control_qubit,
+                target_qubit,
+                ..
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::MultiControlledUnitary {
+                control_qubits,
+                target_qubit,
+                ..
+            } => [control_qubits.as_slice(), &[*target_qubit]].concat(),
+            Self::PauliX {
+                target_qubit,
+            } => {
+                vec![*target_qubit]
+            }
+            Self::PauliY {
+                target_qubit,
+            } => {
+                vec![*target_qubit]
+            }
+            Self::PauliZ {
+                target_qubit,
+            } => {
+                vec![*target_qubit]
+            }
+            Self::Hadamard {
+                target_qubit,
+            } => {
+                vec![*target_qubit]
+            }
+            Self::ControlledNot {
+                control_qubit,
+                target_qubit,
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::MultiControlledMultiQubitNot {
+                ctrls,
+                targs,
+            } => [ctrls.as_slice(), targs.as_slice()].concat(),
+            Self::MultiQubitNot {
+                targs,
+            } => targs.clone(),
+            Self::ControlledPauliY {
+                control_qubit,
+                target_qubit,
+            } => {
+                vec![*control_qubit, *target_qubit]
+            }
+            Self::SwapGate {
+                qubit1,
+                qubit2,
+            } => {
+                vec![*qubit1, *qubit2]
+            }
+            Self::SqrtSwapGate {
+                qb1,
+                qb2,
+            } => {
+                vec![*qb1, *qb2]
+            }
+            Self::MultiStateControlledUnitary {
+                control_qubits,
+                target_qubit,
+                ..
+            } => [control_qubits.as_slice(), &[*target_qubit]].concat(),
+            Self::MultiRotateZ {
+                qubits, ..
+            } => qubits.clone(),
+            Self::MultiRotatePauli {
+                target_qubits, ..
+            } => target_qubits.clone(),
+            Self::MultiControlledMultiRotateZ {
+                control_qubits,
+                target_qubits,
+                ..
+            } => [control_qubits.as_slice(), target_qubits.as_slice()].concat(),
+            Self::MultiControlledMultiRotatePauli {
+                control_qubits,
+                target_qubits,
+                ..
+            } => [control_qubits.as_slice(), target_qubits.as_slice()].concat(),
+            Self::TwoQubitUnitary {
+                target_qubit1,
+                target_qubit2,
+                ..
+            } => {
+                vec![*target_qubit1, *target_qubit2]
+            }
+            Self::ControlledTwoQubitUnitary {
+                control_qubit,
+                target_qubit1,
+                target_qubit2,
+                ..
+            } => {
+                vec![*control_qubit, *target_qubit1, *target_qubit2]
+            }
+            Self::MultiControlledTwoQubitUnitary {
+                control_qubits,
+                target_qubit1,
+                target_qubit2,
+                ..
+            } => [
+                control_qubits.as_slice(),
+                &[*target_qubit1],
+                &[*target_qubit2],
+            ]
+            .concat(),
+            Self::MultiQubitUnitary {
+                targs, ..
+            } => targs.clone(),
+            Self::ControlledMultiQubitUnitary {
+                ctrl,
+                targs,
+                ..
+            } => [&[*ctrl], targs.as_slice()].concat(),
+            Self::MultiControlledMultiQubitUnitary {
+                ctrls,
+                targs,
+                ..
+            } => [ctrls.as_slice(), targs.as_slice()].concat(),
+            Self::ApplyMatrix2 {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::ApplyMatrix4 {
+                target_qubit1,
+                target_qubit2,
+                ..
+            } => {
+                vec![*target_qubit1, *target_qubit2]
+            }
+            Self::ApplyMatrixN {
+                targs, ..
+            } => targs.clone(),
+            Self::ApplyMultiControlledMatrixN {
+                ctrls,
+                targs,
+                ..
+            } => [ctrls.as_slice(), targs.as_slice()].concat(),
+            Self::MixDephasing {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::MixTwoQubitDephasing {
+                qubit1,
+                qubit2,
+                ..
+            } => {
+                vec![*qubit1, *qubit2]
+            }
+            Self::MixDepolarising {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::MixDamping {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::MixTwoQubitDepolarising {
+                qubit1,
+                qubit2,
+                ..
+            } => {
+                vec![*qubit1, *qubit2]
+            }
+            Self::MixPauli {
+                target_qubit, ..
+            } => {
+                vec![*target_qubit]
+            }
+            Self::MixKrausMap {
+                target, ..
+            } => {
+                vec![*target]
+            }
+            Self::MixTwoQubitKrausMap {
+                target1,
+                target2,
+                ..
+            } => {
+                vec![*target1, *target2]
+            }
+            Self::MixMultiQubitKrausMap {
+                targets, ..
+            } => targets.clone(),
+            Self::MixNontpKrausMap {
+                target, ..
+            } => {
+                vec![*target]
+            }
+            Self::MixNontpTwoQubitKrausMap {
+                target1,
+                target2,
+                ..
+            } => {
+                vec![*target1, *target2]
+            }
+            Self::MixNontpMultiQubitKrausMap {
+                targets, ..
+            } => targets.clone(),
+            Self::CollapseToOutcome {
+                measure_qubit, ..
+            } => {
+                vec![*measure_qubit]
+            }
+            Self::Measure {
+                measure_qubit,
+            } => {
+                vec![*measure_qubit]
+            }
+            Self::ApplyProjector {
+                qubit, ..
+            } => {
+                vec![*qubit]
+            }
+            Self::ApplyPhaseFunc {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyPhaseFuncOverrides {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyMultiVarPhaseFunc {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyMultiVarPhaseFuncOverrides {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyNamedPhaseFunc {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyNamedPhaseFuncOverrides {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyParamNamedPhaseFunc {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyParamNamedPhaseFuncOverrides {
+                qubits, ..
+            } => qubits.clone(),
+            Self::ApplyFullQft => vec![],
+            Self::ApplyQft {
+                qubits,
+            } => qubits.clone(),
+        }
+    }
+
+    /// Minimal number of qubits a register must have for this operation to
+    /// act on it.
+    ///
+    /// This is one more than the largest qubit index the operation acts on,
+    /// or zero if the operation acts on the whole register.
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.qubits().into_iter().max().map_or(0, |q| q + 1)
+    }
+
+    /// Apply the operation to `qureg`.
+    ///
+    /// Return the measurement outcome, if the operation is
+    /// [`Operation::Measure`].
+    ///
+    /// Operations that mirror methods of [`DensityMatrix`] only, e.g.
+    /// [`Operation::MixDephasing`], can be applied to any register, but fail
+    /// at runtime if `qureg` is a state-vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as the corresponding method of [`Qureg`].
+    ///
+    /// [`Qureg`]: crate::Qureg
+    /// [`DensityMatrix`]: crate::DensityMatrix
+    pub fn apply<K: QuregKind>(
+        &self,
+        qureg: &mut Qureg<K>,
+    ) -> Result<Option<i32>, QuestError> {
+        match self {
+            Self::PhaseShift {
+                target_qubit,
+                angle,
+            } => qureg.phase_shift(*target_qubit, *angle).map(|_| None),
+            Self::ControlledPhaseShift {
+                id_qubit1,
+                id_qubit2,
+                angle,
+            } => qureg
+                .controlled_phase_shift(*id_qubit1, *id_qubit2, *angle)
+                .map(|_| None),
+            Self::MultiControlledPhaseShift {
+                control_qubits,
+                angle,
+            } => qureg
+                .multi_controlled_phase_shift(control_qubits, *angle)
+                .map(|_| None),
+            Self::ControlledPhaseFlip {
+                id_qubit1,
+                id_qubit2,
+            } => qureg
+                .controlled_phase_flip(*id_qubit1, *id_qubit2)
+                .map(|_| None),
+            Self::MultiControlledPhaseFlip {
+                control_qubits,
+            } => qureg
+                .multi_controlled_phase_flip(control_qubits)
+                .map(|_| None),
+            Self::SGate {
+                target_qubit,
+            } => qureg.s_gate(*target_qubit).map(|_| None),
+            Self::TGate {
+                target_qubit,
+            } => qureg.t_gate(*target_qubit).map(|_| None),
+            Self::CompactUnitary {
+                target_qubit,
+                alpha,
+                beta,
+            } => qureg
+                .compact_unitary(*target_qubit, *alpha, *beta)
+                .map(|_| None),
+            Self::Unitary {
+                target_qubit,
+                u,
+            } => qureg.unitary(*target_qubit, u).map(|_| None),
+            Self::RotateX {
+                rot_qubit,
+                angle,
+            } => qureg.rotate_x(*rot_qubit, *angle).map(|_| None),
+            Self::RotateY {
+                rot_qubit,
+                angle,
+            } => qureg.rotate_y(*rot_qubit, *angle).map(|_| None),
+            Self::RotateZ {
+                rot_qubit,
+                angle,
+            } => qureg.rotate_z(*rot_qubit, *angle).map(|_| None),
+            Self::RotateAroundAxis {
+                rot_qubit,
+                angle,
+                axis,
+            } => qureg
+                .rotate_around_axis(*rot_qubit, *angle, axis)
+                .map(|_| None),
+            Self::ControlledRotateX {
+                control_qubit,
+                target_qubit,
+                angle,
+            } => qureg
+                .controlled_rotate_x(*control_qubit, *target_qubit, *angle)
+                .map(|_| None),
+            Self::ControlledRotateY {
+                control_qubit,
+                target_qubit,
+                angle,
+            } => qureg
+                .controlled_rotate_y(*control_qubit, *target_qubit, *angle)
+                .map(|_| None),
+            Self::ControlledRotateZ {
+                control_qubit,
+                target_qubit,
+                angle,
+            } => qureg
+                .controlled_rotate_z(*control_qubit, *target_qubit, *angle)
+                .map(|_| None),
+            Self::ControlledRotateAroundAxis {
+                control_qubit,
+                target_qubit,
+                angle,
+                axis,
+            } => qureg
+                .controlled_rotate_around_axis(
+                    *control_qubit,
+                    *target_qubit,
+                    *angle,
+                    axis,
+                )
+                .map(|_| None),
+            Self::ControlledCompactUnitary {
+                control_qubit,
+                target_qubit,
+                alpha,
+                beta,
+            } => qureg
+                .controlled_compact_unitary(
+                    *control_qubit,
+                    *target_qubit,
+                    *alpha,
+                    *beta,
+                )
+                .map(|_| None),
+            Self::ControlledUnitary {
+                control_qubit,
+                target_qubit,
+                u,
+            } => qureg
+                .controlled_unitary(*control_qubit, *target_qubit, u)
+                .map(|_| None),
+            Self::MultiControlledUnitary {
+                control_qubits,
+                target_qubit,
+                u,
+            } => qureg
+                .multi_controlled_unitary(control_qubits, *target_qubit, u)
+                .map(|_| None),
+            Self::PauliX {
+                target_qubit,
+            } => qureg.pauli_x(*target_qubit).map(|_| None),
+            Self::PauliY {
+                target_qubit,
+            } => qureg.pauli_y(*target_qubit).map(|_| None),
+            Self::PauliZ {
+                target_qubit,
+            } => qureg.pauli_z(*target_qubit).map(|_| None),
+            Self::Hadamard {
+                target_qubit,
+            } => qureg.hadamard(*target_qubit).map(|_| None),
+            Self::ControlledNot {
+                control_qubit,
+                target_qubit,
+            } => qureg
+                .controlled_not(*control_qubit, *target_qubit)
+                .map(|_| None),
+            Self::MultiControlledMultiQubitNot {
+                ctrls,
+                targs,
+            } => qureg
+                .multi_controlled_multi_qubit_not(ctrls, targs)
+                .map(|_| None),
+            Self::MultiQubitNot {
+                targs,
+            } => qureg.multi_qubit_not(targs).map(|_| None),
+            Self::ControlledPauliY {
+                control_qubit,
+                target_qubit,
+            } => qureg
+                .controlled_pauli_y(*control_qubit, *target_qubit)
+                .map(|_| None),
+            Self::SwapGate {
+                qubit1,
+                qubit2,
+            } => qureg.swap_gate(*qubit1, *qubit2).map(|_| None),
+            Self::SqrtSwapGate {
+                qb1,
+                qb2,
+            } => qureg.sqrt_swap_gate(*qb1, *qb2).map(|_| None),
+            Self::MultiStateControlledUnitary {
+                control_qubits,
+                control_state,
+                target_qubit,
+                u,
+            } => qureg
+                .multi_state_controlled_unitary(
+                    control_qubits,
+                    control_state,
+                    *target_qubit,
+                    u,
+                )
+                .map(|_| None),
+            Self::MultiRotateZ {
+                qubits,
+                angle,
+            } => qureg.multi_rotate_z(qubits, *angle).map(|_| None),
+            Self::MultiRotatePauli {
+                target_qubits,
+                target_paulis,
+                angle,
+            } => qureg
+                .multi_rotate_pauli(target_qubits, target_paulis, *angle)
+                .map(|_| None),
+            Self::MultiControlledMultiRotateZ {
+                control_qubits,
+                target_qubits,
+                angle,
+            } => qureg
+                .multi_controlled_multi_rotate_z(
+                    control_qubits,
+                    target_qubits,
+                    *angle,
+                )
+                .map(|_| None),
+            Self::MultiControlledMultiRotatePauli {
+                control_qubits,
+                target_qubits,
+                target_paulis,
+                angle,
+            } => qureg
+                .multi_controlled_multi_rotate_pauli(
+                    control_qubits,
+                    target_qubits,
+                    target_paulis,
+                    *angle,
+                )
+                .map(|_| None),
+            Self::TwoQubitUnitary {
+                target_qubit1,
+                target_qubit2,
+                u,
+            } => qureg
+                .two_qubit_unitary(*target_qubit1, *target_qubit2, u)
+                .map(|_| None),
+            Self::ControlledTwoQubitUnitary {
+                control_qubit,
+                target_qubit1,
+                target_qubit2,
+                u,
+            } => qureg
+                .controlled_two_qubit_unitary(
+                    *control_qubit,
+                    *target_qubit1,
+                    *target_qubit2,
+                    u,
+                )
+                .map(|_| None),
+            Self::MultiControlledTwoQubitUnitary {
+                control_qubits,
+                target_qubit1,
+                target_qubit2,
+                u,
+            } => qureg
+                .multi_controlled_two_qubit_unitary(
+                    control_qubits,
+                    *target_qubit1,
+                    *target_qubit2,
+                    u,
+                )
+                .map(|_| None),
+            Self::MultiQubitUnitary {
+                targs,
+                u,
+            } => qureg.multi_qubit_unitary(targs, u).map(|_| None),
+            Self::ControlledMultiQubitUnitary {
+                ctrl,
+                targs,
+                u,
+            } => qureg
+                .controlled_multi_qubit_unitary(*ctrl, targs, u)
+                .map(|_| None),
+            Self::MultiControlledMultiQubitUnitary {
+                ctrls,
+                targs,
+                u,
+            } => qureg
+                .multi_controlled_multi_qubit_unitary(ctrls, targs, u)
+                .map(|_| None),
+            Self::ApplyMatrix2 {
+                target_qubit,
+                u,
+            } => qureg.apply_matrix2(*target_qubit, u).map(|_| None),
+            Self::ApplyMatrix4 {
+                target_qubit1,
+                target_qubit2,
+                u,
+            } => qureg
+                .apply_matrix4(*target_qubit1, *target_qubit2, u)
+                .map(|_| None),
+            Self::ApplyMatrixN {
+                targs,
+                u,
+            } => qureg.apply_matrix_n(targs, u).map(|_| None),
+            Self::ApplyMultiControlledMatrixN {
+                ctrls,
+                targs,
+                u,
+            } => qureg
+                .apply_multi_controlled_matrix_n(ctrls, targs, u)
+                .map(|_| None),
+            Self::MixDephasing {
+                target_qubit,
+                prob,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_dephasing(*target_qubit, *prob)
+                })
+                .map(|_| None),
+            Self::MixTwoQubitDephasing {
+                qubit1,
+                qubit2,
+                prob,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_two_qubit_dephasing(*qubit1, *qubit2, *prob)
+                })
+                .map(|_| None),
+            Self::MixDepolarising {
+                target_qubit,
+                prob,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_depolarising(*target_qubit, *prob)
+                })
+                .map(|_| None),
+            Self::MixDamping {
+                target_qubit,
+                prob,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_damping(*target_qubit, *prob)
+                })
+                .map(|_| None),
+            Self::MixTwoQubitDepolarising {
+                qubit1,
+                qubit2,
+                prob,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_two_qubit_depolarising(*qubit1, *qubit2, *prob)
+                })
+                .map(|_| None),
+            Self::MixPauli {
+                target_qubit,
+                prob_x,
+                prob_y,
+                prob_z,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_pauli(*target_qubit, *prob_x, *prob_y, *prob_z)
+                })
+                .map(|_| None),
+            Self::MixKrausMap {
+                target,
+                ops,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_kraus_map(*target, &ops.iter().collect::<Vec<_>>())
+                })
+                .map(|_| None),
+            Self::MixTwoQubitKrausMap {
+                target1,
+                target2,
+                ops,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_two_qubit_kraus_map(
+                        *target1,
+                        *target2,
+                        &ops.iter().collect::<Vec<_>>(),
+                    )
+                })
+                .map(|_| None),
+            Self::MixMultiQubitKrausMap {
+                targets,
+                ops,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_multi_qubit_kraus_map(
+                        targets,
+                        &ops.iter().collect::<Vec<_>>(),
+                    )
+                })
+                .map(|_| None),
+            Self::MixNontpKrausMap {
+                target,
+                ops,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_nontp_kraus_map(
+                        *target,
+                        &ops.iter().collect::<Vec<_>>(),
+                    )
+                })
+                .map(|_| None),
+            Self::MixNontpTwoQubitKrausMap {
+                target1,
+                target2,
+                ops,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_nontp_two_qubit_kraus_map(
+                        *target1,
+                        *target2,
+                        &ops.iter().collect::<Vec<_>>(),
+                    )
+                })
+                .map(|_| None),
+            Self::MixNontpMultiQubitKrausMap {
+                targets,
+                ops,
+            } => qureg
+                .with_kind_unchecked(|rho: &mut DensityMatrix| {
+                    rho.mix_nontp_multi_qubit_kraus_map(
+                        targets,
+                        &ops.iter().collect::<Vec<_>>(),
+                    )
+                })
+                .map(|_| None),
+            Self::CollapseToOutcome {
+                measure_qubit,
+                outcome,
+            } => qureg
+                .collapse_to_outcome(*measure_qubit, *outcome)
+                .map(|_| None),
+            Self::Measure {
+                measure_qubit,
+            } => qureg.measure(*measure_qubit).map(Some),
+            Self::ApplyProjector {
+                qubit,
+                outcome,
+            } => qureg.apply_projector(*qubit, *outcome).map(|_| None),
+            Self::ApplyPhaseFunc {
+                qubits,
+                encoding,
+                coeffs,
+                exponents,
+            } => qureg
+                .apply_phase_func(qubits, *encoding, coeffs, exponents)
+                .map(|_| None),
+            Self::ApplyPhaseFuncOverrides {
+                qubits,
+                encoding,
+                coeffs,
+                exponents,
+                override_inds,
+                override_phases,
+            } => qureg
+                .apply_phase_func_overrides(
+                    qubits,
+                    *encoding,
+                    coeffs,
+                    exponents,
+                    override_inds,
+                    override_phases,
+                )
+                .map(|_| None),
+            Self::ApplyMultiVarPhaseFunc {
+                qubits,
+                num_qubits_per_reg,
+                encoding,
+                coeffs,
+                exponents,
+                num_terms_per_reg,
+            } => qureg
+                .apply_multi_var_phase_func(
+                    qubits,
+                    num_qubits_per_reg,
+                    *encoding,
+                    coeffs,
+                    exponents,
+                    num_terms_per_reg,
+                )
+                .map(|_| None),
+            Self::ApplyMultiVarPhaseFuncOverrides {
+                qubits,
+                num_qubits_per_reg,
+                encoding,
+                coeffs,
+                exponents,
+                num_terms_per_reg,
+                override_inds,
+                override_phases,
+            } => qureg
+                .apply_multi_var_phase_func_overrides(
+                    qubits,
+                    num_qubits_per_reg,
+                    *encoding,
+                    coeffs,
+                    exponents,
+                    num_terms_per_reg,
+                    override_inds,
+                    override_phases,
+                )
+                .map(|_| None),
+            Self::ApplyNamedPhaseFunc {
+                qubits,
+                num_qubits_per_reg,
+                encoding,
+                function_name_code,
+            } => qureg
+                .apply_named_phase_func(
+                    qubits,
+                    num_qubits_per_reg,
+                    *encoding,
+                    *function_name_code,
+                )
+                .map(|_| None),
+            Self::ApplyNamedPhaseFuncOverrides {
+                qubits,
+                num_qubits_per_reg,
+                encoding,
+                function_name_code,
+                override_inds,
+                override_phases,
+            } => qureg
+                .apply_named_phase_func_overrides(
+                    qubits,
+                    num_qubits_per_reg,
+                    *encoding,
+                    *function_name_code,
+                    override_inds,
+                    override_phases,
+                )
+                .map(|_| None),
+            Self::ApplyParamNamedPhaseFunc {
+                qubits,
+                num_qubits_per_reg,
+                encoding,
+                function_name_code,
+                params,
+            } => qureg
+                .apply_param_named_phase_func(
+                    qubits,
+                    num_qubits_per_reg,
+                    *encoding,
+                    *function_name_code,
+                    params,
+                )
+                .map(|_| None),
+            Self::ApplyParamNamedPhaseFuncOverrides {
+                qubits,
+                num_qubits_per_reg,
+                encoding,
+                function_name_code,
+                params,
+                override_inds,
+                override_phases,
+            } => qureg
+                .apply_param_named_phase_func_overrides(
+                    qubits,
+                    num_qubits_per_reg,
+                    *encoding,
+                    *function_name_code,
+                    params,
+                    override_inds,
+                    override_phases,
+                )
+                .map(|_| None),
+            Self::ApplyFullQft => {
+                qureg.apply_full_qft();
+                Ok(None)
+            }
+            Self::ApplyQft {
+                qubits,
+            } => qureg.apply_qft(qubits).map(|_| None),
+        }
+    }
+}
+
+/// A quantum circuit: a sequence of operations to be applied to a [`Qureg`].
+///
+/// A circuit can be built once, inspected, and then applied to any number of
+/// registers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let mut circuit = Circuit::new();
+/// circuit
+///     .hadamard(0)
+///     .controlled_not(0, 1)
+///     .measure(0)
+///     .measure(1);
+///
+/// assert_eq!(circuit.len(), 4);
+/// assert_eq!(circuit.num_qubits(), 2);
+///
+/// let env = &QuestEnv::new();
+/// let qureg = &mut Qureg::try_new(2, env).unwrap();
+/// qureg.init_zero_state();
+///
+/// let outcomes = circuit.apply(qureg).unwrap();
+/// assert_eq!(outcomes[0], outcomes[1]);
+/// ```
+///
+/// [`Qureg`]: crate::Qureg
+#[derive(Debug, Default)]
+pub struct Circuit {
+    ops: Vec<Operation>,
+}
+
+impl Circuit {
+    /// Create an empty circuit.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an operation to the circuit.
+    pub fn push(
+        &mut self,
+        op: Operation,
+    ) -> &mut Self {
+        self.ops.push(op);
+        self
+    }
+
+    /// Operations in the circuit, in the order they will be applied.
+    #[must_use]
+    pub fn operations(&self) -> &[Operation] {
+        &self.ops
+    }
+
+    /// Number of operations in the circuit.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Check if the circuit contains no operations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Minimal number of qubits a register must have for the circuit to act
+    /// on it.
+    ///
+    /// See [`Operation::num_qubits()`].
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.ops
+            .iter()
+            .map(Operation::num_qubits)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Check if every operation in the circuit acts on valid qubits of
+    /// `qureg`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QubitIndexError`], if any qubit index is negative, or not
+    /// smaller than [`qureg.num_qubits()`].
+    ///
+    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
+    /// [`qureg.num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn check<K: QuregKind>(
+        &self,
+        qureg: &Qureg<K>,
+    ) -> Result<(), QuestError> {
+        let num_qubits = qureg.num_qubits();
+        if self
+            .ops
+            .iter()
+            .flat_map(Operation::qubits)
+            .all(|q| (0..num_qubits).contains(&q))
+        {
+            Ok(())
+        } else {
+            Err(QuestError::QubitIndexError)
+        }
+    }
+
+    /// Apply the circuit to `qureg`.
+    ///
+    /// The circuit is first validated with [`check()`], so that no operation
+    /// is applied if any of them acts on qubits outside of the register.
+    ///
+    /// Returns the outcomes of all [`Operation::Measure`] operations, in the
+    /// order they were performed.
+    ///
+    /// # Errors
+    ///
+    /// - [`QubitIndexError`], if [`check()`] fails
+    /// - the same errors as the methods of [`Qureg`] the operations mirror. In
+    ///   that case, `qureg` is left with all operations preceding the failed
+    ///   one applied.
+    ///
+    /// [`check()`]: crate::Circuit::check()
+    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
+    /// [`Qureg`]: crate::Qureg
+    pub fn apply<K: QuregKind>(
+        &self,
+        qureg: &mut Qureg<K>,
+    ) -> Result<Vec<i32>, QuestError> {
+        self.check(qureg)?;
+
+        let mut outcomes = Vec::new();
+        for op in &self.ops {
+            if let Some(outcome) = op.apply(qureg)? {
+                outcomes.push(outcome);
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Append [`Operation::PhaseShift`].
+    pub fn phase_shift(
+        &mut self,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::PhaseShift {
+            target_qubit,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::ControlledPhaseShift`].
+    pub fn controlled_phase_shift(
+        &mut self,
+        id_qubit1: i32,
+        id_qubit2: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::ControlledPhaseShift {
+            id_qubit1,
+            id_qubit2,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledPhaseShift`].
+    pub fn multi_controlled_phase_shift(
+        &mut self,
+        control_qubits: &[i32],
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledPhaseShift {
+            control_qubits: control_qubits.to_vec(),
+            angle,
+        })
+    }
+
+    /// Append [`Operation::ControlledPhaseFlip`].
+    pub fn controlled_phase_flip(
+        &mut self,
+        id_qubit1: i32,
+        id_qubit2: i32,
+    ) -> &mut Self {
+        self.push(Operation::ControlledPhaseFlip {
+            id_qubit1,
+            id_qubit2,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledPhaseFlip`].
+    pub fn multi_controlled_phase_flip(
+        &mut self,
+        control_qubits: &[i32],
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledPhaseFlip {
+            control_qubits: control_qubits.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::SGate`].
+    pub fn s_gate(
+        &mut self,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::SGate {
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::TGate`].
+    pub fn t_gate(
+        &mut self,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::TGate {
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::CompactUnitary`].
+    pub fn compact_unitary(
+        &mut self,
+        target_qubit: i32,
+        alpha: Qcomplex,
+        beta: Qcomplex,
+    ) -> &mut Self {
+        self.push(Operation::CompactUnitary {
+            target_qubit,
+            alpha,
+            beta,
+        })
+    }
+
+    /// Append [`Operation::Unitary`].
+    pub fn unitary(
+        &mut self,
+        target_qubit: i32,
+        u: ComplexMatrix2,
+    ) -> &mut Self {
+        self.push(Operation::Unitary {
+            target_qubit,
+            u,
+        })
+    }
+
+    /// Append [`Operation::RotateX`].
+    pub fn rotate_x(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::RotateX {
+            rot_qubit,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::RotateY`].
+    pub fn rotate_y(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::RotateY {
+            rot_qubit,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::RotateZ`].
+    pub fn rotate_z(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::RotateZ {
+            rot_qubit,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::RotateAroundAxis`].
+    pub fn rotate_around_axis(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
+        axis: Vector,
+    ) -> &mut Self {
+        self.push(Operation::RotateAroundAxis {
+            rot_qubit,
+            angle,
+            axis,
+        })
+    }
+
+    /// Append [`Operation::ControlledRotateX`].
+    pub fn controlled_rotate_x(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::ControlledRotateX {
+            control_qubit,
+            target_qubit,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::ControlledRotateY`].
+    pub fn controlled_rotate_y(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::ControlledRotateY {
+            control_qubit,
+            target_qubit,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::ControlledRotateZ`].
+    pub fn controlled_rotate_z(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::ControlledRotateZ {
+            control_qubit,
+            target_qubit,
+            angle,
+        })
+    }
+
+    /// Append [`Operation::ControlledRotateAroundAxis`].
+    pub fn controlled_rotate_around_axis(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+        axis: Vector,
+    ) -> &mut Self {
+        self.push(Operation::ControlledRotateAroundAxis {
+            control_qubit,
+            target_qubit,
+            angle,
+            axis,
+        })
+    }
+
+    /// Append [`Operation::ControlledCompactUnitary`].
+    pub fn controlled_compact_unitary(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        alpha: Qcomplex,
+        beta: Qcomplex,
+    ) -> &mut Self {
+        self.push(Operation::ControlledCompactUnitary {
+            control_qubit,
+            target_qubit,
+            alpha,
+            beta,
+        })
+    }
+
+    /// Append [`Operation::ControlledUnitary`].
+    pub fn controlled_unitary(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        u: ComplexMatrix2,
+    ) -> &mut Self {
+        self.push(Operation::ControlledUnitary {
+            control_qubit,
+            target_qubit,
+            u,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledUnitary`].
+    pub fn multi_controlled_unitary(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubit: i32,
+        u: ComplexMatrix2,
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledUnitary {
+            control_qubits: control_qubits.to_vec(),
+            target_qubit,
+            u,
+        })
+    }
+
+    /// Append [`Operation::PauliX`].
+    pub fn pauli_x(
+        &mut self,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::PauliX {
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::PauliY`].
+    pub fn pauli_y(
+        &mut self,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::PauliY {
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::PauliZ`].
+    pub fn pauli_z(
+        &mut self,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::PauliZ {
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::Hadamard`].
+    pub fn hadamard(
+        &mut self,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::Hadamard {
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::ControlledNot`].
+    pub fn controlled_not(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::ControlledNot {
+            control_qubit,
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledMultiQubitNot`].
+    pub fn multi_controlled_multi_qubit_not(
+        &mut self,
+        ctrls: &[i32],
+        targs: &[i32],
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledMultiQubitNot {
+            ctrls: ctrls.to_vec(),
+            targs: targs.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::MultiQubitNot`].
+    pub fn multi_qubit_not(
+        &mut self,
+        targs: &[i32],
+    ) -> &mut Self {
+        self.push(Operation::MultiQubitNot {
+            targs: targs.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ControlledPauliY`].
+    pub fn controlled_pauli_y(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::ControlledPauliY {
+            control_qubit,
+            target_qubit,
+        })
+    }
+
+    /// Append [`Operation::SwapGate`].
+    pub fn swap_gate(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+    ) -> &mut Self {
+        self.push(Operation::SwapGate {
+            qubit1,
+            qubit2,
+        })
+    }
+
+    /// Append [`Operation::SqrtSwapGate`].
+    pub fn sqrt_swap_gate(
+        &mut self,
+        qb1: i32,
+        qb2: i32,
+    ) -> &mut Self {
+        self.push(Operation::SqrtSwapGate {
+            qb1,
+            qb2,
+        })
+    }
+
+    /// Append [`Operation::MultiStateControlledUnitary`].
+    pub fn multi_state_controlled_unitary(
+        &mut self,
+        control_qubits: &[i32],
+        control_state: &[i32],
+        target_qubit: i32,
+        u: ComplexMatrix2,
+    ) -> &mut Self {
+        self.push(Operation::MultiStateControlledUnitary {
+            control_qubits: control_qubits.to_vec(),
+            control_state: control_state.to_vec(),
+            target_qubit,
+            u,
+        })
+    }
+
+    /// Append [`Operation::MultiRotateZ`].
+    pub fn multi_rotate_z(
+        &mut self,
+        qubits: &[i32],
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MultiRotateZ {
+            qubits: qubits.to_vec(),
+            angle,
+        })
+    }
+
+    /// Append [`Operation::MultiRotatePauli`].
+    pub fn multi_rotate_pauli(
+        &mut self,
+        target_qubits: &[i32],
+        target_paulis: &[PauliOpType],
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MultiRotatePauli {
+            target_qubits: target_qubits.to_vec(),
+            target_paulis: target_paulis.to_vec(),
+            angle,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledMultiRotateZ`].
+    pub fn multi_controlled_multi_rotate_z(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubits: &[i32],
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledMultiRotateZ {
+            control_qubits: control_qubits.to_vec(),
+            target_qubits: target_qubits.to_vec(),
+            angle,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledMultiRotatePauli`].
+    pub fn multi_controlled_multi_rotate_pauli(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubits: &[i32],
+        target_paulis: &[PauliOpType],
+        angle: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledMultiRotatePauli {
+            control_qubits: control_qubits.to_vec(),
+            target_qubits: target_qubits.to_vec(),
+            target_paulis: target_paulis.to_vec(),
+            angle,
+        })
+    }
+
+    /// Append [`Operation::TwoQubitUnitary`].
+    pub fn two_qubit_unitary(
+        &mut self,
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u: ComplexMatrix4,
+    ) -> &mut Self {
+        self.push(Operation::TwoQubitUnitary {
+            target_qubit1,
+            target_qubit2,
+            u,
+        })
+    }
+
+    /// Append [`Operation::ControlledTwoQubitUnitary`].
+    pub fn controlled_two_qubit_unitary(
+        &mut self,
+        control_qubit: i32,
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u: ComplexMatrix4,
+    ) -> &mut Self {
+        self.push(Operation::ControlledTwoQubitUnitary {
+            control_qubit,
+            target_qubit1,
+            target_qubit2,
+            u,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledTwoQubitUnitary`].
+    pub fn multi_controlled_two_qubit_unitary(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u: ComplexMatrix4,
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledTwoQubitUnitary {
+            control_qubits: control_qubits.to_vec(),
+            target_qubit1,
+            target_qubit2,
+            u,
+        })
+    }
+
+    /// Append [`Operation::MultiQubitUnitary`].
+    pub fn multi_qubit_unitary(
+        &mut self,
+        targs: &[i32],
+        u: ComplexMatrixN,
+    ) -> &mut Self {
+        self.push(Operation::MultiQubitUnitary {
+            targs: targs.to_vec(),
+            u,
+        })
+    }
+
+    /// Append [`Operation::ControlledMultiQubitUnitary`].
+    pub fn controlled_multi_qubit_unitary(
+        &mut self,
+        ctrl: i32,
+        targs: &[i32],
+        u: ComplexMatrixN,
+    ) -> &mut Self {
+        self.push(Operation::ControlledMultiQubitUnitary {
+            ctrl,
+            targs: targs.to_vec(),
+            u,
+        })
+    }
+
+    /// Append [`Operation::MultiControlledMultiQubitUnitary`].
+    pub fn multi_controlled_multi_qubit_unitary(
+        &mut self,
+        ctrls: &[i32],
+        targs: &[i32],
+        u: ComplexMatrixN,
+    ) -> &mut Self {
+        self.push(Operation::MultiControlledMultiQubitUnitary {
+            ctrls: ctrls.to_vec(),
+            targs: targs.to_vec(),
+            u,
+        })
+    }
+
+    /// Append [`Operation::ApplyMatrix2`].
+    pub fn apply_matrix2(
+        &mut self,
+        target_qubit: i32,
+        u: ComplexMatrix2,
+    ) -> &mut Self {
+        self.push(Operation::ApplyMatrix2 {
+            target_qubit,
+            u,
+        })
+    }
+
+    /// Append [`Operation::ApplyMatrix4`].
+    pub fn apply_matrix4(
+        &mut self,
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u: ComplexMatrix4,
+    ) -> &mut Self {
+        self.push(Operation::ApplyMatrix4 {
+            target_qubit1,
+            target_qubit2,
+            u,
+        })
+    }
+
+    /// Append [`Operation::ApplyMatrixN`].
+    pub fn apply_matrix_n(
+        &mut self,
+        targs: &[i32],
+        u: ComplexMatrixN,
+    ) -> &mut Self {
+        self.push(Operation::ApplyMatrixN {
+            targs: targs.to_vec(),
+            u,
+        })
+    }
+
+    /// Append [`Operation::ApplyMultiControlledMatrixN`].
+    pub fn apply_multi_controlled_matrix_n(
+        &mut self,
+        ctrls: &[i32],
+        targs: &[i32],
+        u: ComplexMatrixN,
+    ) -> &mut Self {
+        self.push(Operation::ApplyMultiControlledMatrixN {
+            ctrls: ctrls.to_vec(),
+            targs: targs.to_vec(),
+            u,
+        })
+    }
+
+    /// Append [`Operation::MixDephasing`].
+    pub fn mix_dephasing(
+        &mut self,
+        target_qubit: i32,
+        prob: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MixDephasing {
+            target_qubit,
+            prob,
+        })
+    }
+
+    /// Append [`Operation::MixTwoQubitDephasing`].
+    pub fn mix_two_qubit_dephasing(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+        prob: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MixTwoQubitDephasing {
+            qubit1,
+            qubit2,
+            prob,
+        })
+    }
+
+    /// Append [`Operation::MixDepolarising`].
+    pub fn mix_depolarising(
+        &mut self,
+        target_qubit: i32,
+        prob: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MixDepolarising {
+            target_qubit,
+            prob,
+        })
+    }
+
+    /// Append [`Operation::MixDamping`].
+    pub fn mix_damping(
+        &mut self,
+        target_qubit: i32,
+        prob: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MixDamping {
+            target_qubit,
+            prob,
+        })
+    }
+
+    /// Append [`Operation::MixTwoQubitDepolarising`].
+    pub fn mix_two_qubit_depolarising(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+        prob: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MixTwoQubitDepolarising {
+            qubit1,
+            qubit2,
+            prob,
+        })
+    }
+
+    /// Append [`Operation::MixPauli`].
+    pub fn mix_pauli(
+        &mut self,
+        target_qubit: i32,
+        prob_x: Qreal,
+        prob_y: Qreal,
+        prob_z: Qreal,
+    ) -> &mut Self {
+        self.push(Operation::MixPauli {
+            target_qubit,
+            prob_x,
+            prob_y,
+            prob_z,
+        })
+    }
+
+    /// Append [`Operation::MixKrausMap`].
+    pub fn mix_kraus_map(
+        &mut self,
+        target: i32,
+        ops: Vec<ComplexMatrix2>,
+    ) -> &mut Self {
+        self.push(Operation::MixKrausMap {
+            target,
+            ops,
+        })
+    }
+
+    /// Append [`Operation::MixTwoQubitKrausMap`].
+    pub fn mix_two_qubit_kraus_map(
+        &mut self,
+        target1: i32,
+        target2: i32,
+        ops: Vec<ComplexMatrix4>,
+    ) -> &mut Self {
+        self.push(Operation::MixTwoQubitKrausMap {
+            target1,
+            target2,
+            ops,
+        })
+    }
+
+    /// Append [`Operation::MixMultiQubitKrausMap`].
+    pub fn mix_multi_qubit_kraus_map(
+        &mut self,
+        targets: &[i32],
+        ops: Vec<ComplexMatrixN>,
+    ) -> &mut Self {
+        self.push(Operation::MixMultiQubitKrausMap {
+            targets: targets.to_vec(),
+            ops,
+        })
+    }
+
+    /// Append [`Operation::MixNontpKrausMap`].
+    pub fn mix_nontp_kraus_map(
+        &mut self,
+        target: i32,
+        ops: Vec<ComplexMatrix2>,
+    ) -> &mut Self {
+        self.push(Operation::MixNontpKrausMap {
+            target,
+            ops,
+        })
+    }
+
+    /// Append [`Operation::MixNontpTwoQubitKrausMap`].
+    pub fn mix_nontp_two_qubit_kraus_map(
+        &mut self,
+        target1: i32,
+        target2: i32,
+        ops: Vec<ComplexMatrix4>,
+    ) -> &mut Self {
+        self.push(Operation::MixNontpTwoQubitKrausMap {
+            target1,
+            target2,
+            ops,
+        })
+    }
+
+    /// Append [`Operation::MixNontpMultiQubitKrausMap`].
+    pub fn mix_nontp_multi_qubit_kraus_map(
+        &mut self,
+        targets: &[i32],
+        ops: Vec<ComplexMatrixN>,
+    ) -> &mut Self {
+        self.push(Operation::MixNontpMultiQubitKrausMap {
+            targets: targets.to_vec(),
+            ops,
+        })
+    }
+
+    /// Append [`Operation::CollapseToOutcome`].
+    pub fn collapse_to_outcome(
+        &mut self,
+        measure_qubit: i32,
+        outcome: i32,
+    ) -> &mut Self {
+        self.push(Operation::CollapseToOutcome {
+            measure_qubit,
+            outcome,
+        })
+    }
+
+    /// Append [`Operation::Measure`].
+    pub fn measure(
+        &mut self,
+        measure_qubit: i32,
+    ) -> &mut Self {
+        self.push(Operation::Measure {
+            measure_qubit,
+        })
+    }
+
+    /// Append [`Operation::ApplyProjector`].
+    pub fn apply_projector(
+        &mut self,
+        qubit: i32,
+        outcome: i32,
+    ) -> &mut Self {
+        self.push(Operation::ApplyProjector {
+            qubit,
+            outcome,
+        })
+    }
+
+    /// Append [`Operation::ApplyPhaseFunc`].
+    pub fn apply_phase_func(
+        &mut self,
+        qubits: &[i32],
+        encoding: BitEncoding,
+        coeffs: &[Qreal],
+        exponents: &[Qreal],
+    ) -> &mut Self {
+        self.push(Operation::ApplyPhaseFunc {
+            qubits: qubits.to_vec(),
+            encoding,
+            coeffs: coeffs.to_vec(),
+            exponents: exponents.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ApplyPhaseFuncOverrides`].
+    pub fn apply_phase_func_overrides(
+        &mut self,
+        qubits: &[i32],
+        encoding: BitEncoding,
+        coeffs: &[Qreal],
+        exponents: &[Qreal],
+        override_inds: &[i64],
+        override_phases: &[Qreal],
+    ) -> &mut Self {
+        self.push(Operation::ApplyPhaseFuncOverrides {
+            qubits: qubits.to_vec(),
+            encoding,
+            coeffs: coeffs.to_vec(),
+            exponents: exponents.to_vec(),
+            override_inds: override_inds.to_vec(),
+            override_phases: override_phases.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ApplyMultiVarPhaseFunc`].
+    pub fn apply_multi_var_phase_func(
+        &mut self,
+        qubits: &[i32],
+        num_qubits_per_reg: &[i32],
+        encoding: BitEncoding,
+        coeffs: &[Qreal],
+        exponents: &[Qreal],
+        num_terms_per_reg: &[i32],
+    ) -> &mut Self {
+        self.push(Operation::ApplyMultiVarPhaseFunc {
+            qubits: qubits.to_vec(),
+            num_qubits_per_reg: num_qubits_per_reg.to_vec(),
+            encoding,
+            coeffs: coeffs.to_vec(),
+            exponents: exponents.to_vec(),
+            num_terms_per_reg: num_terms_per_reg.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ApplyMultiVarPhaseFuncOverrides`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_multi_var_phase_func_overrides(
+        &mut self,
+        qubits: &[i32],
+        num_qubits_per_reg: &[i32],
+        encoding: BitEncoding,
+        coeffs: &[Qreal],
+        exponents: &[Qreal],
+        num_terms_per_reg: &[i32],
+        override_inds: &[i64],
+        override_phases: &[Qreal],
+    ) -> &mut Self {
+        self.push(Operation::ApplyMultiVarPhaseFuncOverrides {
+            qubits: qubits.to_vec(),
+            num_qubits_per_reg: num_qubits_per_reg.to_vec(),
+            encoding,
+            coeffs: coeffs.to_vec(),
+            exponents: exponents.to_vec(),
+            num_terms_per_reg: num_terms_per_reg.to_vec(),
+            override_inds: override_inds.to_vec(),
+            override_phases: override_phases.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ApplyNamedPhaseFunc`].
+    pub fn apply_named_phase_func(
+        &mut self,
+        qubits: &[i32],
+        num_qubits_per_reg: &[i32],
+        encoding: BitEncoding,
+        function_name_code: PhaseFunc,
+    ) -> &mut Self {
+        self.push(Operation::ApplyNamedPhaseFunc {
+            qubits: qubits.to_vec(),
+            num_qubits_per_reg: num_qubits_per_reg.to_vec(),
+            encoding,
+            function_name_code,
+        })
+    }
+
+    /// Append [`Operation::ApplyNamedPhaseFuncOverrides`].
+    pub fn apply_named_phase_func_overrides(
+        &mut self,
+        qubits: &[i32],
+        num_qubits_per_reg: &[i32],
+        encoding: BitEncoding,
+        function_name_code: PhaseFunc,
+        override_inds: &[i64],
+        override_phases: &[Qreal],
+    ) -> &mut Self {
+        self.push(Operation::ApplyNamedPhaseFuncOverrides {
+            qubits: qubits.to_vec(),
+            num_qubits_per_reg: num_qubits_per_reg.to_vec(),
+            encoding,
+            function_name_code,
+            override_inds: override_inds.to_vec(),
+            override_phases: override_phases.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ApplyParamNamedPhaseFunc`].
+    pub fn apply_param_named_phase_func(
+        &mut self,
+        qubits: &[i32],
+        num_qubits_per_reg: &[i32],
+        encoding: BitEncoding,
+        function_name_code: PhaseFunc,
+        params: &[Qreal],
+    ) -> &mut Self {
+        self.push(Operation::ApplyParamNamedPhaseFunc {
+            qubits: qubits.to_vec(),
+            num_qubits_per_reg: num_qubits_per_reg.to_vec(),
+            encoding,
+            function_name_code,
+            params: params.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ApplyParamNamedPhaseFuncOverrides`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_param_named_phase_func_overrides(
+        &mut self,
+        qubits: &[i32],
+        num_qubits_per_reg: &[i32],
+        encoding: BitEncoding,
+        function_name_code: PhaseFunc,
+        params: &[Qreal],
+        override_inds: &[i64],
+        override_phases: &[Qreal],
+    ) -> &mut Self {
+        self.push(Operation::ApplyParamNamedPhaseFuncOverrides {
+            qubits: qubits.to_vec(),
+            num_qubits_per_reg: num_qubits_per_reg.to_vec(),
+            encoding,
+            function_name_code,
+            params: params.to_vec(),
+            override_inds: override_inds.to_vec(),
+            override_phases: override_phases.to_vec(),
+        })
+    }
+
+    /// Append [`Operation::ApplyFullQft`].
+    pub fn apply_full_qft(&mut self) -> &mut Self {
+        self.push(Operation::ApplyFullQft)
+    }
+
+    /// Append [`Operation::ApplyQft`].
+    pub fn apply_qft(
+        &mut self,
+        qubits: &[i32],
+    ) -> &mut Self {
+        self.push(Operation::ApplyQft {
+            qubits: qubits.to_vec(),
+        })
+    }
+}
+
+impl From<Vec<Operation>> for Circuit {
+    fn from(ops: Vec<Operation>) -> Self {
+        Self {
+            ops,
+        }
+    }
+}
+
+impl Extend<Operation> for Circuit {
+    fn extend<T: IntoIterator<Item = Operation>>(
+        &mut self,
+        iter: T,
+    ) {
+        self.ops.extend(iter);
+    }
+}
+
+impl<'a> IntoIterator for &'a Circuit {
+    type IntoIter = std::slice::Iter<'a, Operation>;
+    type Item = &'a Operation;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ops.iter()
+    }
+}
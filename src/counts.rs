@@ -0,0 +1,140 @@
+//! Histograms of measurement outcomes.
+
+use std::collections::BTreeMap;
+
+/// Histogram of measurement outcomes, keyed by bitstrings.
+///
+/// Each bitstring has one character, `'0'` or `'1'`, per measured qubit.
+/// The *last* character corresponds to the first qubit in the list of
+/// measured qubits, i.e. bitstrings are written with the most significant
+/// bit first.
+///
+/// See [`Qureg::sample()`].
+///
+/// [`Qureg::sample()`]: crate::Qureg::sample()
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    num_bits: usize,
+    counts:   BTreeMap<String, usize>,
+}
+
+impl Counts {
+    /// Build a histogram from the number of occurrences of each outcome.
+    ///
+    /// `hist[i]` is the number of occurrences of outcome `i`.
+    pub(crate) fn from_histogram(
+        num_bits: usize,
+        hist: &[usize],
+    ) -> Self {
+        let counts = hist
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(outcome, &count)| (format!("{outcome:0num_bits$b}"), count))
+            .collect();
+        Self {
+            num_bits,
+            counts,
+        }
+    }
+
+    /// Number of measured qubits, i.e. the length of each bitstring.
+    #[must_use]
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Total number of shots.
+    #[must_use]
+    pub fn shots(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Number of occurrences of an outcome.
+    ///
+    /// Returns 0 if `bitstring` was never observed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(1).unwrap();
+    ///
+    /// let counts = qureg.sample(10, &[0, 1]).unwrap();
+    /// assert_eq!(counts.get("01"), 10);
+    /// assert_eq!(counts.get("10"), 0);
+    /// ```
+    #[must_use]
+    pub fn get(
+        &self,
+        bitstring: &str,
+    ) -> usize {
+        self.counts.get(bitstring).copied().unwrap_or_default()
+    }
+
+    /// Number of distinct outcomes observed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns `true` if no outcomes were observed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The most frequent outcome and its number of occurrences.
+    ///
+    /// Ties are resolved in favour of the lexicographically smallest
+    /// bitstring.
+    #[must_use]
+    pub fn most_frequent(&self) -> Option<(&str, usize)> {
+        self.iter().rev().max_by_key(|&(_, count)| count)
+    }
+
+    /// Iterate over observed outcomes and their numbers of occurrences,
+    /// in lexicographic order of bitstrings.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&str, usize)> + '_ {
+        self.counts
+            .iter()
+            .map(|(bitstring, &count)| (bitstring.as_str(), count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_01() {
+        let counts = Counts::from_histogram(3, &[2, 0, 0, 5, 0, 1, 0, 0]);
+
+        assert_eq!(counts.num_bits(), 3);
+        assert_eq!(counts.shots(), 8);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts.get("000"), 2);
+        assert_eq!(counts.get("011"), 5);
+        assert_eq!(counts.get("101"), 1);
+        assert_eq!(counts.get("111"), 0);
+        assert_eq!(counts.most_frequent(), Some(("011", 5)));
+        assert_eq!(
+            counts.iter().collect::<Vec<_>>(),
+            [("000", 2), ("011", 5), ("101", 1)]
+        );
+    }
+
+    #[test]
+    fn counts_02() {
+        let counts = Counts::from_histogram(2, &[3, 3, 0, 0]);
+        assert_eq!(counts.most_frequent(), Some(("00", 3)));
+
+        let counts = Counts::from_histogram(2, &[0, 0, 0, 0]);
+        assert!(counts.is_empty());
+        assert_eq!(counts.shots(), 0);
+        assert_eq!(counts.most_frequent(), None);
+    }
+}
@@ -0,0 +1,46 @@
+/*
+ * Non-unwinding trampoline for catching QuEST exceptions.
+ *
+ * QuEST reports invalid input by calling invalidQuESTInputError(), which must
+ * not return.  Rather than unwinding the Rust stack through C frames, we
+ * record a jump target here before each API call and long-jump back to it
+ * when an error is reported.  See: src/error.rs
+ */
+
+#include <setjmp.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+static _Thread_local jmp_buf *catch_point = NULL;
+
+/*
+ * Call `callback(data)`.  Return 0 if the callback returned normally, or 1 if
+ * quest_bind_throw() was called in the meantime on the same thread.
+ */
+int quest_bind_try(void (*callback)(void *), void *data) {
+    jmp_buf env;
+    jmp_buf *prev = catch_point;
+
+    catch_point = &env;
+    if (setjmp(env) == 0) {
+        callback(data);
+        catch_point = prev;
+        return 0;
+    }
+    catch_point = prev;
+    return 1;
+}
+
+/*
+ * Jump back to the innermost active quest_bind_try() on this thread.
+ *
+ * If there is none, there is nowhere to report the error to, and we abort,
+ * just as QuEST does by default.
+ */
+void quest_bind_throw(void) {
+    if (catch_point == NULL) {
+        fprintf(stderr, "quest_bind: QuEST error outside of a catch point\n");
+        abort();
+    }
+    longjmp(*catch_point, 1);
+}
@@ -5,19 +5,37 @@
 //! message and aborts, which is problematic in a large distributed setup. We
 //! opt for catching all exceptions early.
 //!
+//! Unwinding a Rust panic through `QuEST`'s C stack frames is undefined
+//! behaviour, so we never do that. Instead, every call to `QuEST` API goes
+//! through a small trampoline written in C (`src/error.c`) that sets a jump
+//! target with `setjmp()`. When `QuEST` reports an error, we store it in a
+//! thread-local slot and `longjmp()` back to the trampoline, which returns
+//! normally to Rust. Rust panics raised inside the call are caught before
+//! they reach C and resumed only once we are back on the Rust side.
+//!
 //! This is an internal module that doesn't contain any useful user interface.
 //!
 //! [`invalidQuESTInputError()`]: https://quest-kit.github.io/QuEST/group__debug.html#ga51a64b05d31ef9bcf6a63ce26c0092db
 
 use std::{
+    cell::RefCell,
+    error::Error,
     ffi::{
         c_char,
+        c_int,
+        c_void,
         CStr,
     },
+    fmt,
     panic::{
         self,
         UnwindSafe,
     },
+    sync::atomic::{
+        AtomicBool,
+        Ordering,
+    },
+    thread,
 };
 
 #[derive(Debug, PartialEq)]
@@ -44,20 +62,301 @@ pub enum QuestError {
     },
     NulError(std::ffi::NulError),
     IntoStringError(std::ffi::IntoStringError),
+    /// Text returned by `QuEST` is not valid UTF-8.
+    Utf8Error(std::string::FromUtf8Error),
     ArrayLengthError,
+    /// Qubit index outside of the register, detected before calling
+    /// `QuEST`.
+    QubitIndexError,
+    /// The same qubit passed more than once, e.g. as both control and
+    /// target, detected before calling `QuEST`.
+    RepeatedQubitsError,
+    /// Invalid number of qubits, detected before calling `QuEST`.
+    NumQubitsError,
+    /// Amplitude, state or outcome index out of range, detected before
+    /// calling `QuEST`.
+    IndexError,
+    /// Invalid input text, e.g. an `OpenQASM` program.
+    ///
+    /// Lines and columns are counted from 1.
+    ParseError {
+        line:   usize,
+        column: usize,
+        msg:    String,
+    },
+    /// Matrix is not Hermitian, detected before calling `QuEST`.
+    NonHermitianError,
+    /// Matrix is not unitary, detected before calling `QuEST`.
+    NonUnitaryError,
+    /// Trace of a density matrix is not 1, detected before calling `QuEST`.
+    TraceError,
+    /// Probabilities of measurement outcomes are not finite or sum to zero.
+    ProbabilityError,
+    /// Error reading or writing a file.
+    IoError {
+        kind: std::io::ErrorKind,
+        msg:  String,
+    },
+    /// The process-wide `QuEST` environment cannot be created.
+    ///
+    /// See [`QuestEnv::try_new()`](crate::QuestEnv::try_new()).
+    EnvError {
+        msg: String,
+    },
+}
+
+impl QuestError {
+    /// Classify the error.
+    ///
+    /// For [`InvalidQuESTInputError`], the kind is inferred from the error
+    /// message reported by `QuEST`.  Messages that don't fall into any of the
+    /// known categories are classified as [`QuestErrorKind::Other`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let qureg = &mut Qureg::try_new(2, env).unwrap();
+    ///
+    /// let err = qureg.hadamard(3).unwrap_err();
+    /// assert_eq!(err.kind(), QuestErrorKind::InvalidQubitIndex);
+    ///
+    /// let err = qureg.controlled_not(0, 0).unwrap_err();
+    /// assert_eq!(err.kind(), QuestErrorKind::RepeatedQubits);
+    /// ```
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    #[must_use]
+    pub fn kind(&self) -> QuestErrorKind {
+        match self {
+            Self::InvalidQuESTInputError {
+                err_msg, ..
+            } => QuestErrorKind::from_message(err_msg),
+            Self::NulError(_)
+            | Self::IntoStringError(_)
+            | Self::Utf8Error(_) => QuestErrorKind::InvalidString,
+            Self::ArrayLengthError => QuestErrorKind::ArrayLength,
+            Self::QubitIndexError => QuestErrorKind::InvalidQubitIndex,
+            Self::RepeatedQubitsError => QuestErrorKind::RepeatedQubits,
+            Self::NumQubitsError => QuestErrorKind::InvalidNumQubits,
+            Self::IndexError => QuestErrorKind::InvalidIndex,
+            Self::ParseError {
+                ..
+            } => QuestErrorKind::Parse,
+            Self::NonHermitianError => QuestErrorKind::NonHermitianMatrix,
+            Self::NonUnitaryError => QuestErrorKind::NonUnitaryMatrix,
+            Self::TraceError | Self::ProbabilityError => {
+                QuestErrorKind::InvalidProbability
+            }
+            Self::IoError {
+                ..
+            } => QuestErrorKind::File,
+            Self::EnvError {
+                ..
+            } => QuestErrorKind::Environment,
+        }
+    }
+}
+
+impl fmt::Display for QuestError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::InvalidQuESTInputError {
+                err_msg,
+                err_func,
+            } => write!(f, "QuEST error in function {err_func}: {err_msg}"),
+            Self::NulError(e) => write!(f, "invalid C string: {e}"),
+            Self::IntoStringError(e) => write!(f, "invalid C string: {e}"),
+            Self::Utf8Error(e) => write!(f, "invalid UTF-8 string: {e}"),
+            Self::ArrayLengthError => write!(f, "invalid array length"),
+            Self::QubitIndexError => write!(f, "invalid qubit index"),
+            Self::RepeatedQubitsError => write!(f, "repeated qubit index"),
+            Self::NumQubitsError => write!(f, "invalid number of qubits"),
+            Self::IndexError => write!(f, "index out of range"),
+            Self::ParseError {
+                line,
+                column,
+                msg,
+            } => {
+                write!(f, "parse error at line {line}, column {column}: {msg}")
+            }
+            Self::NonHermitianError => write!(f, "matrix is not Hermitian"),
+            Self::NonUnitaryError => write!(f, "matrix is not unitary"),
+            Self::TraceError => write!(f, "trace of density matrix is not 1"),
+            Self::ProbabilityError => {
+                write!(f, "invalid probabilities of measurement outcomes")
+            }
+            Self::IoError {
+                msg, ..
+            } => write!(f, "I/O error: {msg}"),
+            Self::EnvError {
+                msg,
+            } => write!(f, "QuEST environment error: {msg}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for QuestError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IoError {
+            kind: err.kind(),
+            msg:  err.to_string(),
+        }
+    }
+}
+
+impl Error for QuestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NulError(e) => Some(e),
+            Self::IntoStringError(e) => Some(e),
+            Self::Utf8Error(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Category of a [`QuestError`].
+///
+/// See [`QuestError::kind()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum QuestErrorKind {
+    /// Qubit index (target or control) outside `[0, num_qubits)`.
+    InvalidQubitIndex,
+    /// The same qubit was passed more than once, e.g. as both control and
+    /// target.
+    RepeatedQubits,
+    /// Invalid number of qubits, targets or controls.
+    InvalidNumQubits,
+    /// Amplitude, state, element or outcome index (or number of amplitudes)
+    /// out of range.
+    InvalidIndex,
+    /// Operation valid only for density matrices.
+    NotDensityMatrix,
+    /// Operation valid only for state-vectors.
+    NotStateVector,
+    /// Probability outside of its allowed range, or probabilities not
+    /// normalized.
+    InvalidProbability,
+    /// Matrix is not unitary.
+    NonUnitaryMatrix,
+    /// Matrix is not Hermitian.
+    NonHermitianMatrix,
+    /// Kraus map is not completely positive and trace preserving.
+    NonCptpMap,
+    /// Dimensions of registers, operators or matrices don't match.
+    MismatchedDimensions,
+    /// Invalid Pauli code.
+    InvalidPauliCode,
+    /// File could not be opened or parsed.
+    File,
+    /// See [`QuestError::ParseError`].
+    Parse,
+    /// See [`QuestError::ArrayLengthError`].
+    ArrayLength,
+    /// See [`QuestError::NulError`], [`QuestError::IntoStringError`] and
+    /// [`QuestError::Utf8Error`].
+    InvalidString,
+    /// See [`QuestError::EnvError`].
+    Environment,
+    /// Any other error reported by `QuEST`.
+    Other,
+}
+
+impl QuestErrorKind {
+    /// Classify an error message reported by `QuEST`.
+    fn from_message(err_msg: &str) -> Self {
+        let msg = err_msg.to_lowercase();
+        let has = |pat: &str| msg.contains(pat);
+
+        if has("invalid number of qubits")
+            || has("invalid number of target qubits")
+            || has("invalid number of control qubits")
+        {
+            Self::InvalidNumQubits
+        } else if has("invalid qubit index")
+            || has("invalid target qubit")
+            || has("invalid control qubit")
+        {
+            Self::InvalidQubitIndex
+        } else if has("unique")
+            || has("disjoint")
+            || has("cannot equal target")
+            || has("cannot include target")
+        {
+            Self::RepeatedQubits
+        } else if has("invalid amplitude index")
+            || has("invalid state index")
+            || has("invalid element index")
+            || has("invalid number of amplitudes")
+            || has("invalid number of elements")
+            || has("invalid measurement outcome")
+        {
+            Self::InvalidIndex
+        } else if has("only for density matri") {
+            Self::NotDensityMatrix
+        } else if has("only for state-vector") || has("be a state-vector") {
+            Self::NotStateVector
+        } else if has("not unitary") {
+            Self::NonUnitaryMatrix
+        } else if has("completely positive") {
+            Self::NonCptpMap
+        } else if has("probabilit") {
+            Self::InvalidProbability
+        } else if has("pauli code") {
+            Self::InvalidPauliCode
+        } else if has("file") {
+            Self::File
+        } else if has("don't match")
+            || has("does not match")
+            || has("same number of qubits")
+            || has("equal number of qubits")
+        {
+            Self::MismatchedDimensions
+        } else {
+            Self::Other
+        }
+    }
+}
+
+extern "C" {
+    fn quest_bind_try(
+        callback: unsafe extern "C" fn(*mut c_void),
+        data: *mut c_void,
+    ) -> c_int;
+
+    fn quest_bind_throw() -> !;
+}
+
+/// Whether errors reported by `QuEST` are logged with [`log::error!`].
+static LOG_ERRORS: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable logging of errors reported by `QuEST`.
+pub(crate) fn set_log_errors(enabled: bool) {
+    LOG_ERRORS.store(enabled, Ordering::Relaxed);
+}
+
+thread_local! {
+    /// The last error reported by `QuEST` on this thread, waiting to be picked
+    /// up by `catch_quest_exception()`.
+    static QUEST_ERROR: RefCell<Option<QuestError>> = const { RefCell::new(None) };
 }
 
 /// Report error in a `QuEST` API call.
 ///
 /// This function is called by `QuEST` whenever an error occurs.
 /// We redefine it to put the error message and site reported into
-/// `QuestError::InvalidQuESTInputError` and start unwinding the stack.  The
-/// function `catch_quest_exception()` should be able to catch it.
-///
-/// # Panics
+/// `QuestError::InvalidQuESTInputError` and jump back to the innermost call to
+/// `catch_quest_exception()` on the current thread.
 ///
-/// This function will panic if strings returned by `QuEST` are not properly
-/// formatted (null terminated) C strings.
+/// This function never returns.  The frames it jumps over must not hold any
+/// values with destructors, which is why all owned data is moved into
+/// `QUEST_ERROR` before the jump.
 #[allow(non_snake_case)]
 #[no_mangle]
 unsafe extern "C" fn invalidQuESTInputError(
@@ -66,39 +365,96 @@ unsafe extern "C" fn invalidQuESTInputError(
 ) {
     // SAFETY: errMsg and errFunc are always non-null as a result of
     // a call to QuEST's function: QuESTAssert()
-    let err_msg = unsafe { CStr::from_ptr(errMsg) }.to_str().expect(
-        "String (errMsg) returned by QuEST should be properly formatted",
-    );
-    let err_func = unsafe { CStr::from_ptr(errFunc) }.to_str().expect(
-        "String (errFunc) returned by QuEST should be properly formatted",
-    );
-    log::error!("QueST Error in function {err_func}: {err_msg}");
-
-    panic::resume_unwind(Box::new(QuestError::InvalidQuESTInputError {
-        err_msg:  err_msg.to_owned(),
-        err_func: err_func.to_owned(),
-    }));
+    let err_msg = unsafe { CStr::from_ptr(errMsg) }
+        .to_string_lossy()
+        .into_owned();
+    let err_func = unsafe { CStr::from_ptr(errFunc) }
+        .to_string_lossy()
+        .into_owned();
+    if LOG_ERRORS.load(Ordering::Relaxed) {
+        log::error!("QueST Error in function {err_func}: {err_msg}");
+    }
+
+    QUEST_ERROR.with(|e| {
+        *e.borrow_mut() = Some(QuestError::InvalidQuESTInputError {
+            err_msg,
+            err_func,
+        });
+    });
+
+    // SAFETY: There are no values left in this frame that need dropping.
+    unsafe { quest_bind_throw() }
+}
+
+/// Data passed through the C trampoline to `call_once()`.
+struct Call<F, T> {
+    f:   Option<F>,
+    ret: Option<thread::Result<T>>,
+}
+
+/// Invoke the closure stored in `data` and save its result.
+///
+/// Rust panics are caught here, so that they never unwind into C.
+unsafe extern "C" fn call_once<F, T>(data: *mut c_void)
+where
+    F: FnOnce() -> T + UnwindSafe,
+{
+    // SAFETY: data points to Call<F, T> owned by catch_quest_exception()
+    let call = unsafe { &mut *data.cast::<Call<F, T>>() };
+    if let Some(f) = call.f.take() {
+        call.ret = Some(panic::catch_unwind(f));
+    }
 }
 
 /// Execute a call to `QuEST` API and catch exceptions.
+///
+/// The closure `f` should do little more than call `QuEST`.  If `QuEST`
+/// reports an error, the stack frame of `f` is discarded without running any
+/// destructors, so `f` must not own any values that need dropping.
 pub fn catch_quest_exception<T, F>(f: F) -> Result<T, QuestError>
 where
     F: FnOnce() -> T + UnwindSafe,
 {
-    // Call QuEST API, unwrap the error sent by invalidInputQuestError()
-    panic::catch_unwind(f).map_err(|e| match e.downcast::<QuestError>() {
-        Ok(boxed_err) => *boxed_err,
-        Err(e) => panic::resume_unwind(e),
-    })
+    let mut call = Call {
+        f:   Some(f),
+        ret: None,
+    };
+
+    // SAFETY: call_once::<F, T> is called with a pointer to `call`, which
+    // lives until quest_bind_try() returns.
+    let jumped = unsafe {
+        quest_bind_try(
+            call_once::<F, T>,
+            std::ptr::addr_of_mut!(call).cast::<c_void>(),
+        )
+    };
+
+    if jumped != 0 {
+        return Err(QUEST_ERROR
+            .with(|e| e.borrow_mut().take())
+            .expect("QuEST error should be recorded before jumping back"));
+    }
+
+    match call.ret {
+        Some(Ok(ret)) => Ok(ret),
+        Some(Err(payload)) => panic::resume_unwind(payload),
+        None => unreachable!("closure should be called exactly once"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::thread;
 
+    use super::catch_quest_exception;
     use crate::{
         ComplexMatrixN,
+        Operation,
         PauliHamil,
+        QuestEnv,
+        QuestError,
+        QuestErrorKind,
+        Qureg,
     };
 
     #[test]
@@ -115,6 +471,119 @@ mod tests {
         let _ = PauliHamil::try_new(2, 2).unwrap();
     }
 
+    #[test]
+    fn catch_exception_03() {
+        let _ = ComplexMatrixN::try_new(0).unwrap_err();
+        let _ = PauliHamil::try_new(-11, -3).unwrap_err();
+        let _ = ComplexMatrixN::try_new(1).unwrap();
+        let _ = PauliHamil::try_new(2, 2).unwrap();
+    }
+
+    #[test]
+    fn catch_exception_nested_01() {
+        let err = catch_quest_exception(|| {
+            let _ = ComplexMatrixN::try_new(1).unwrap();
+            ComplexMatrixN::try_new(0).unwrap_err()
+        })
+        .unwrap();
+
+        assert!(matches!(err, QuestError::InvalidQuESTInputError { .. }));
+    }
+
+    #[test]
+    #[should_panic(expected = "rust panic")]
+    fn catch_exception_rust_panic_01() {
+        let _ = catch_quest_exception(|| panic!("rust panic"));
+    }
+
+    #[test]
+    fn error_kind_01() {
+        let env = &QuestEnv::new();
+        let qureg = &mut Qureg::try_new(2, env).unwrap();
+
+        let err = qureg.controlled_not(0, 0).unwrap_err();
+        assert_eq!(err.kind(), QuestErrorKind::RepeatedQubits);
+
+        let err = qureg.get_amp(4).unwrap_err();
+        assert_eq!(err.kind(), QuestErrorKind::InvalidIndex);
+
+        // density-matrix-only operations are dispatched at runtime by circuits
+        let op = Operation::MixDephasing {
+            target_qubit: 0,
+            prob:         0.1,
+        };
+        let err = op.apply(qureg).unwrap_err();
+        assert_eq!(err.kind(), QuestErrorKind::NotDensityMatrix);
+
+        let density = &mut Qureg::try_new_density(2, env).unwrap();
+        let err = density.mix_depolarising(0, 1.5).unwrap_err();
+        assert_eq!(err.kind(), QuestErrorKind::InvalidProbability);
+
+        let other = &Qureg::try_new(3, env).unwrap();
+        let err = density.calc_fidelity(other).unwrap_err();
+        assert_eq!(err.kind(), QuestErrorKind::MismatchedDimensions);
+    }
+
+    #[test]
+    fn error_kind_02() {
+        let kind = |msg: &str| QuestErrorKind::from_message(msg);
+
+        assert_eq!(
+            kind("Invalid number of qubits. Must create >0."),
+            QuestErrorKind::InvalidNumQubits
+        );
+        assert_eq!(
+            kind("Invalid target qubit. Must be >=0 and <numQubits."),
+            QuestErrorKind::InvalidQubitIndex
+        );
+        assert_eq!(
+            kind("Matrix is not unitary."),
+            QuestErrorKind::NonUnitaryMatrix
+        );
+        assert_eq!(
+            kind("Dimensions of the qubit registers don't match."),
+            QuestErrorKind::MismatchedDimensions
+        );
+        assert_eq!(
+            kind(
+                "Invalid number of amplitudes. Must be >=0 and <=2^numQubits."
+            ),
+            QuestErrorKind::InvalidIndex
+        );
+        assert_eq!(
+            kind("Invalid measurement outcome -- must be either 0 or 1."),
+            QuestErrorKind::InvalidIndex
+        );
+        assert_eq!(kind("Something else."), QuestErrorKind::Other);
+
+        assert_eq!(
+            QuestError::ArrayLengthError.kind(),
+            QuestErrorKind::ArrayLength
+        );
+    }
+
+    #[test]
+    fn error_display_01() {
+        let err = QuestError::InvalidQuESTInputError {
+            err_msg:  "Matrix is not unitary.".to_owned(),
+            err_func: "unitary".to_owned(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "QuEST error in function unitary: Matrix is not unitary."
+        );
+
+        let err: Box<dyn std::error::Error> =
+            Box::new(QuestError::ArrayLengthError);
+        assert_eq!(err.to_string(), "invalid array length");
+
+        let err = QuestError::Utf8Error(
+            String::from_utf8(vec![b'h', 0xff]).unwrap_err(),
+        );
+        assert_eq!(err.kind(), QuestErrorKind::InvalidString);
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
     #[test]
     fn catch_exception_parallel_01() {
         thread::scope(|s| {
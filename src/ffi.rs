@@ -32,24 +32,24 @@ pub enum phaseGateType {
 #[derive(Debug, Clone, Copy)]
 pub struct QASMLogger {
     /// generated QASM string
-    buffer:     SendPtr<c_char>,
+    pub buffer:     SendPtr<c_char>,
     /// maximum number of chars before overflow
-    bufferSize: c_int,
+    pub bufferSize: c_int,
     /// number of chars currently in buffer
-    bufferFill: c_int,
+    pub bufferFill: c_int,
     /// whether gates are being added to buffer
-    isLogging:  c_int,
+    pub isLogging:  c_int,
 }
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct ComplexArray {
-    real: SendPtr<qreal>,
-    imag: SendPtr<qreal>,
+    pub real: SendPtr<qreal>,
+    pub imag: SendPtr<qreal>,
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum pauliOpType {
     PAULI_I = 0,
     PAULI_X = 1,
@@ -147,31 +147,36 @@ pub struct Qureg {
     pub isDensityMatrix:      c_int,
     pub numQubitsRepresented: c_int,
     numQubitsInStateVec:      c_int,
-    numAmpsPerChunk:          c_longlong,
+    pub numAmpsPerChunk:      c_longlong,
     pub numAmpsTotal:         c_longlong,
-    chunkId:                  c_int,
+    pub chunkId:              c_int,
 
-    numChunks: c_int,
+    pub numChunks: c_int,
 
-    stateVec:     ComplexArray,
+    pub stateVec: ComplexArray,
     pairStateVec: ComplexArray,
 
     deviceStateVec:       ComplexArray,
     firstLevelReduction:  SendPtr<qreal>,
     secondLevelReduction: SendPtr<qreal>,
 
-    qasmLog: SendPtr<QASMLogger>,
+    pub qasmLog: SendPtr<QASMLogger>,
 }
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct QuESTEnv {
-    rank:     c_int,
-    numRanks: c_int,
-    seeds:    SendPtr<c_ulong>,
-    numSeeds: c_int,
+    pub rank:     c_int,
+    pub numRanks: c_int,
+    seeds:        SendPtr<c_ulong>,
+    numSeeds:     c_int,
 }
 
+// Sizes, alignments and field offsets of the types above, checked at compile
+// time against the QuEST headers.  See: build.rs
+#[cfg(quest_bind_layout)]
+include!(concat!(env!("OUT_DIR"), "/layout.rs"));
+
 #[link(name = "QuEST")]
 extern "C" {
 
@@ -0,0 +1,352 @@
+//! Standard quantum gates as unitary matrices.
+//!
+//! Single-qubit gates are returned as [`ComplexMatrix2`], two-qubit gates as
+//! [`ComplexMatrix4`] and gates on an arbitrary number of qubits as
+//! [`ComplexMatrixN`].  The matrices can be passed to e.g.
+//! [`Qureg::unitary()`], [`Qureg::two_qubit_unitary()`] and
+//! [`Qureg::multi_qubit_unitary()`].
+//!
+//! For two-qubit gates, the first target qubit is treated as the least
+//! significant one, in the same way as in [`Qureg::two_qubit_unitary()`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use quest_bind::*;
+//! let env = QuestEnv::new();
+//! let mut qureg =
+//!     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+//!
+//! // sqrt(X) applied twice is a NOT gate
+//! qureg.unitary(0, &gates::sqrt_x()).unwrap();
+//! qureg.unitary(0, &gates::sqrt_x()).unwrap();
+//! qureg.two_qubit_unitary(0, 1, &gates::iswap()).unwrap();
+//!
+//! let amp = qureg.get_imag_amp(2).unwrap();
+//! assert!((amp - 1.).abs() < EPSILON);
+//! ```
+//!
+//! [`Qureg::unitary()`]: crate::Qureg::unitary()
+//! [`Qureg::two_qubit_unitary()`]: crate::Qureg::two_qubit_unitary()
+//! [`Qureg::multi_qubit_unitary()`]: crate::Qureg::multi_qubit_unitary()
+
+use num::One;
+
+use crate::{
+    ComplexMatrix2,
+    ComplexMatrix4,
+    ComplexMatrixN,
+    Qcomplex,
+    Qreal,
+    QuestError,
+    SQRT_2,
+    TAU,
+};
+
+fn matrix2(elems: [[Qcomplex; 2]; 2]) -> ComplexMatrix2 {
+    ComplexMatrix2::new(
+        elems.map(|row| row.map(|z| z.re)),
+        elems.map(|row| row.map(|z| z.im)),
+    )
+}
+
+fn matrix4(elems: [[Qcomplex; 4]; 4]) -> ComplexMatrix4 {
+    ComplexMatrix4::new(
+        elems.map(|row| row.map(|z| z.re)),
+        elems.map(|row| row.map(|z| z.im)),
+    )
+}
+
+/// Real complex number.
+fn re(x: Qreal) -> Qcomplex {
+    Qcomplex::new(x, 0.)
+}
+
+/// Imaginary complex number.
+fn im(y: Qreal) -> Qcomplex {
+    Qcomplex::new(0., y)
+}
+
+/// Single-qubit identity.
+#[must_use]
+pub fn identity() -> ComplexMatrix2 {
+    matrix2([[re(1.), re(0.)], [re(0.), re(1.)]])
+}
+
+/// Hadamard gate.
+///
+/// ```text
+/// [ 1  1 ] / sqrt(2)
+/// [ 1 -1 ]
+/// ```
+#[must_use]
+pub fn hadamard() -> ComplexMatrix2 {
+    let h = SQRT_2.recip();
+    matrix2([[re(h), re(h)], [re(h), re(-h)]])
+}
+
+/// Pauli X gate.
+#[must_use]
+pub fn pauli_x() -> ComplexMatrix2 {
+    matrix2([[re(0.), re(1.)], [re(1.), re(0.)]])
+}
+
+/// Pauli Y gate.
+#[must_use]
+pub fn pauli_y() -> ComplexMatrix2 {
+    matrix2([[re(0.), im(-1.)], [im(1.), re(0.)]])
+}
+
+/// Pauli Z gate.
+#[must_use]
+pub fn pauli_z() -> ComplexMatrix2 {
+    matrix2([[re(1.), re(0.)], [re(0.), re(-1.)]])
+}
+
+/// S gate: phase shift by `pi/2`.
+#[must_use]
+pub fn s() -> ComplexMatrix2 {
+    matrix2([[re(1.), re(0.)], [re(0.), im(1.)]])
+}
+
+/// T gate: phase shift by `pi/4`.
+#[must_use]
+pub fn t() -> ComplexMatrix2 {
+    phase_shift(TAU / 8.)
+}
+
+/// Square root of the Pauli X gate.
+///
+/// ```text
+/// [ 1+i  1-i ] / 2
+/// [ 1-i  1+i ]
+/// ```
+#[must_use]
+pub fn sqrt_x() -> ComplexMatrix2 {
+    let (a, b) = (Qcomplex::new(0.5, 0.5), Qcomplex::new(0.5, -0.5));
+    matrix2([[a, b], [b, a]])
+}
+
+/// Phase shift by `angle`.
+///
+/// ```text
+/// [ 1  0              ]
+/// [ 0  exp(i * angle) ]
+/// ```
+#[must_use]
+pub fn phase_shift(angle: Qreal) -> ComplexMatrix2 {
+    matrix2([[re(1.), re(0.)], [re(0.), Qcomplex::cis(angle)]])
+}
+
+/// Rotation by `angle` around the X-axis of the Bloch sphere.
+///
+/// Same as [`Qureg::rotate_x()`].
+///
+/// [`Qureg::rotate_x()`]: crate::Qureg::rotate_x()
+#[must_use]
+pub fn rotate_x(angle: Qreal) -> ComplexMatrix2 {
+    let (c, s) = ((angle / 2.).cos(), (angle / 2.).sin());
+    matrix2([[re(c), im(-s)], [im(-s), re(c)]])
+}
+
+/// Rotation by `angle` around the Y-axis of the Bloch sphere.
+///
+/// Same as [`Qureg::rotate_y()`].
+///
+/// [`Qureg::rotate_y()`]: crate::Qureg::rotate_y()
+#[must_use]
+pub fn rotate_y(angle: Qreal) -> ComplexMatrix2 {
+    let (c, s) = ((angle / 2.).cos(), (angle / 2.).sin());
+    matrix2([[re(c), re(-s)], [re(s), re(c)]])
+}
+
+/// Rotation by `angle` around the Z-axis of the Bloch sphere.
+///
+/// Same as [`Qureg::rotate_z()`].
+///
+/// [`Qureg::rotate_z()`]: crate::Qureg::rotate_z()
+#[must_use]
+pub fn rotate_z(angle: Qreal) -> ComplexMatrix2 {
+    matrix2([
+        [Qcomplex::cis(-angle / 2.), re(0.)],
+        [re(0.), Qcomplex::cis(angle / 2.)],
+    ])
+}
+
+/// General single-qubit gate in the `U3` parametrization of OpenQASM.
+///
+/// ```text
+/// [ cos(theta/2)             -exp(i*lambda) sin(theta/2)     ]
+/// [ exp(i*phi) sin(theta/2)   exp(i*(phi+lambda)) cos(theta/2) ]
+/// ```
+#[must_use]
+pub fn u3(
+    theta: Qreal,
+    phi: Qreal,
+    lambda: Qreal,
+) -> ComplexMatrix2 {
+    let (c, s) = ((theta / 2.).cos(), (theta / 2.).sin());
+    matrix2([
+        [re(c), -Qcomplex::cis(lambda) * s],
+        [Qcomplex::cis(phi) * s, Qcomplex::cis(phi + lambda) * c],
+    ])
+}
+
+/// SWAP gate.
+#[must_use]
+pub fn swap() -> ComplexMatrix4 {
+    let (o, l) = (re(0.), re(1.));
+    matrix4([[l, o, o, o], [o, o, l, o], [o, l, o, o], [o, o, o, l]])
+}
+
+/// Square root of the SWAP gate.
+///
+/// Same as [`Qureg::sqrt_swap_gate()`].
+///
+/// [`Qureg::sqrt_swap_gate()`]: crate::Qureg::sqrt_swap_gate()
+#[must_use]
+pub fn sqrt_swap() -> ComplexMatrix4 {
+    let (o, l) = (re(0.), re(1.));
+    let (a, b) = (Qcomplex::new(0.5, 0.5), Qcomplex::new(0.5, -0.5));
+    matrix4([[l, o, o, o], [o, a, b, o], [o, b, a, o], [o, o, o, l]])
+}
+
+/// Controlled Z gate.
+#[must_use]
+pub fn controlled_z() -> ComplexMatrix4 {
+    let (o, l) = (re(0.), re(1.));
+    matrix4([[l, o, o, o], [o, l, o, o], [o, o, l, o], [o, o, o, -l]])
+}
+
+/// Controlled phase shift by `angle`.
+///
+/// ```text
+/// [ 1 0 0 0              ]
+/// [ 0 1 0 0              ]
+/// [ 0 0 1 0              ]
+/// [ 0 0 0 exp(i * angle) ]
+/// ```
+#[must_use]
+pub fn controlled_phase_shift(angle: Qreal) -> ComplexMatrix4 {
+    let (o, l) = (re(0.), re(1.));
+    matrix4([
+        [l, o, o, o],
+        [o, l, o, o],
+        [o, o, l, o],
+        [o, o, o, Qcomplex::cis(angle)],
+    ])
+}
+
+/// iSWAP gate.
+///
+/// ```text
+/// [ 1 0 0 0 ]
+/// [ 0 0 i 0 ]
+/// [ 0 i 0 0 ]
+/// [ 0 0 0 1 ]
+/// ```
+#[must_use]
+pub fn iswap() -> ComplexMatrix4 {
+    let (o, l, i) = (re(0.), re(1.), im(1.));
+    matrix4([[l, o, o, o], [o, o, i, o], [o, i, o, o], [o, o, o, l]])
+}
+
+/// Fermionic simulation gate.
+///
+/// ```text
+/// [ 1  0             0             0               ]
+/// [ 0  cos(theta)    -i sin(theta) 0               ]
+/// [ 0  -i sin(theta) cos(theta)    0               ]
+/// [ 0  0             0             exp(-i * phi)   ]
+/// ```
+#[must_use]
+pub fn fsim(
+    theta: Qreal,
+    phi: Qreal,
+) -> ComplexMatrix4 {
+    let (o, l) = (re(0.), re(1.));
+    let (c, s) = (re(theta.cos()), im(-theta.sin()));
+    matrix4([
+        [l, o, o, o],
+        [o, c, s, o],
+        [o, s, c, o],
+        [o, o, o, Qcomplex::cis(-phi)],
+    ])
+}
+
+/// XY gate: `exp(i * theta/4 * (XX + YY))`.
+///
+/// ```text
+/// [ 1  0                0                0 ]
+/// [ 0  cos(theta/2)     i sin(theta/2)   0 ]
+/// [ 0  i sin(theta/2)   cos(theta/2)     0 ]
+/// [ 0  0                0                1 ]
+/// ```
+///
+/// `xy(pi)` is the [`iswap()`] gate, up to rounding errors.
+#[must_use]
+pub fn xy(theta: Qreal) -> ComplexMatrix4 {
+    let (o, l) = (re(0.), re(1.));
+    let (c, s) = (re((theta / 2.).cos()), im((theta / 2.).sin()));
+    matrix4([[l, o, o, o], [o, c, s, o], [o, s, c, o], [o, o, o, l]])
+}
+
+/// Mølmer–Sørensen gate: `exp(-i * theta/2 * XX)`.
+///
+/// ```text
+/// [ cos(theta/2)     0                0                -i sin(theta/2) ]
+/// [ 0                cos(theta/2)     -i sin(theta/2)  0               ]
+/// [ 0                -i sin(theta/2)  cos(theta/2)     0               ]
+/// [ -i sin(theta/2)  0                0                cos(theta/2)    ]
+/// ```
+///
+/// `ms(pi/2)` is maximally entangling.
+#[must_use]
+pub fn ms(theta: Qreal) -> ComplexMatrix4 {
+    let o = re(0.);
+    let (c, s) = (re((theta / 2.).cos()), im(-(theta / 2.).sin()));
+    matrix4([[c, o, o, s], [o, c, s, o], [o, s, c, o], [s, o, o, c]])
+}
+
+/// Quantum Fourier transform on `num_qubits` qubits.
+///
+/// The matrix element at `(j, k)` is `exp(2*pi*i * j*k / N) / sqrt(N)`,
+/// where `N = 2.pow(num_qubits)`.  Same as [`Qureg::apply_qft()`].
+///
+/// # Errors
+///
+/// Returns [`InvalidQuESTInputError`], if `num_qubits` is not positive or
+/// the matrix cannot be allocated.
+///
+/// [`Qureg::apply_qft()`]: crate::Qureg::apply_qft()
+/// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+#[allow(clippy::cast_precision_loss)]
+pub fn qft(num_qubits: i32) -> Result<ComplexMatrixN, QuestError> {
+    let mut m = ComplexMatrixN::try_new(num_qubits)?;
+    let dim = 1_usize << num_qubits;
+    let norm = (dim as Qreal).sqrt().recip();
+    for j in 0..dim {
+        for k in 0..dim {
+            // reduce mod dim first to keep the angle small and accurate
+            let angle = TAU * ((j * k) % dim) as Qreal / dim as Qreal;
+            m.set_elem(j, k, Qcomplex::cis(angle) * norm);
+        }
+    }
+    Ok(m)
+}
+
+/// Identity on `num_qubits` qubits.
+///
+/// # Errors
+///
+/// Returns [`InvalidQuESTInputError`], if `num_qubits` is not positive or
+/// the matrix cannot be allocated.
+///
+/// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+pub fn identity_n(num_qubits: i32) -> Result<ComplexMatrixN, QuestError> {
+    let mut m = ComplexMatrixN::try_new(num_qubits)?;
+    for i in 0..1_usize << num_qubits {
+        m.set_elem(i, i, Qcomplex::one());
+    }
+    Ok(m)
+}
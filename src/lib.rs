@@ -5,17 +5,31 @@
 
 use error::catch_quest_exception;
 
+mod circuit;
+mod counts;
 mod error;
 mod ffi;
+pub mod gates;
 mod matrices;
 mod numbers;
 mod operators;
+mod pauli;
+mod qasm;
 mod questenv;
 mod qureg;
+mod simulator;
 #[cfg(test)]
 mod tests;
 
-pub use error::QuestError;
+pub use circuit::{
+    Circuit,
+    Operation,
+};
+pub use counts::Counts;
+pub use error::{
+    QuestError,
+    QuestErrorKind,
+};
 pub use ffi::{
     bitEncoding as BitEncoding,
     pauliOpType as PauliOpType,
@@ -37,6 +51,7 @@ pub use numbers::{
     LN_10,
     LN_2,
     PI,
+    REAL_EPS,
     SQRT_2,
     TAU,
 };
@@ -51,7 +66,16 @@ pub use operators::{
     DiagonalOp,
     PauliHamil,
 };
-pub use questenv::QuestEnv;
+pub use pauli::PauliSum;
+pub use qasm::{
+    ClassicalRegister,
+    QasmProgram,
+};
+pub use questenv::{
+    EnvInfo,
+    QuestEnv,
+    QuestEnvBuilder,
+};
 pub use qureg::{
     apply_pauli_hamil,
     apply_pauli_sum,
@@ -61,10 +85,23 @@ pub use qureg::{
     // create_density_qureg,
     // create_qureg,
     set_weighted_qureg,
+    DensityMatrix,
+    DensityMatrixKind,
     Qureg,
+    QuregKind,
+    StateVector,
+    StateVectorKind,
+};
+pub use simulator::{
+    DenseStateVector,
+    Simulator,
 };
 
 /// Print the Hamiltonian `hamil` to screen.
+///
+/// To format the Hamiltonian as a string in sparse notation, e.g.
+/// `0.5 X0 Z1 - 1.2 Y2`, use its [`Display`](std::fmt::Display) impl
+/// instead.
 pub fn report_pauli_hamil(hamil: &PauliHamil) -> Result<(), QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::reportPauliHamil(hamil.0);
@@ -106,9 +143,11 @@ pub fn report_pauli_hamil(hamil: &PauliHamil) -> Result<(), QuestError> {
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
 pub fn seed_quest_default(env: &mut QuestEnv) {
-    let env_ptr = std::ptr::addr_of_mut!(env.0);
-    catch_quest_exception(|| unsafe {
-        ffi::seedQuESTDefault(env_ptr);
+    env.with_raw_mut(|env_raw| {
+        let env_ptr = std::ptr::from_mut(env_raw);
+        catch_quest_exception(|| unsafe {
+            ffi::seedQuESTDefault(env_ptr);
+        })
     })
     .expect("seed_quest_default should always succeed");
 }
@@ -150,22 +189,25 @@ pub fn seed_quest_default(env: &mut QuestEnv) {
 /// [Mersenne Twister]: http://www.math.sci.hiroshima-u.ac.jp/~m-mat/MT/MT2002/emt19937ar.html
 /// [`QuestEnv`]: crate::QuestEnv
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+#[allow(clippy::needless_pass_by_ref_mut)]
 pub fn seed_quest(
     env: &mut QuestEnv,
     seed_array: &[u64],
 ) {
     let num_seeds = seed_array.len() as i32; // QuEST's function signature is`c_ulong`. Let's use u64 for now...
-    let env_ptr = std::ptr::addr_of_mut!(env.0);
     let seed_array_ptr = seed_array.as_ptr();
-    catch_quest_exception(|| unsafe {
-        ffi::seedQuEST(env_ptr, seed_array_ptr, num_seeds);
+    env.with_raw_mut(|env_raw| {
+        let env_ptr = std::ptr::from_mut(env_raw);
+        catch_quest_exception(|| unsafe {
+            ffi::seedQuEST(env_ptr, seed_array_ptr, num_seeds);
+        })
     })
     .expect("seed_quest should always succeed");
 }
 
 /// Obtain the seeds presently used in random number generation.
 ///
-/// This function returns a reference to the internal array of keys
+/// This function returns a copy of the internal array of keys
 /// which have seeded `QuEST`'s PRNG. These are the seeds which inform the
 /// outcomes of random functions like [`measure()`] and
 /// [`measure_with_stats()`], and are set using [`seed_quest()`] and
@@ -176,10 +218,9 @@ pub fn seed_quest(
 /// your own random decisions) can be precisely repeated later, just by
 /// calling [`seed_quest()`].
 ///
-/// One should not rely, however, upon the reference returned to be
-/// automatically updated after a subsequent call to [`seed_quest()`] or
-/// [`seed_quest_default()`]. Instead, the present function should be
-/// recalled.
+/// The returned seeds are not updated after a subsequent call to
+/// [`seed_quest()`] or [`seed_quest_default()`]. Instead, the present
+/// function should be recalled.
 ///
 /// # Parameters
 ///
@@ -194,7 +235,7 @@ pub fn seed_quest(
 /// seed_quest(&mut env, seeds);
 ///
 /// let check_seeds = get_quest_seeds(&env);
-/// assert_eq!(seeds, check_seeds);
+/// assert_eq!(check_seeds, seeds);
 /// ```
 ///
 /// See [QuEST API] for more information.
@@ -208,15 +249,19 @@ pub fn seed_quest(
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::cast_sign_loss)]
 #[must_use]
-pub fn get_quest_seeds<'a: 'b, 'b>(env: &'a QuestEnv) -> &'b [u64] {
-    catch_quest_exception(|| unsafe {
-        let seeds_ptr = &mut std::ptr::null_mut();
-        let num_seeds = &mut 0_i32;
-        ffi::getQuESTSeeds(env.0, seeds_ptr, num_seeds);
+pub fn get_quest_seeds(env: &QuestEnv) -> Vec<u64> {
+    // Hold the lock while copying, so that the seeds cannot be replaced
+    // through another handle in the meantime.
+    env.with_raw_mut(|env_raw| {
+        catch_quest_exception(|| unsafe {
+            let seeds_ptr = &mut std::ptr::null_mut();
+            let num_seeds = &mut 0_i32;
+            ffi::getQuESTSeeds(*env_raw, seeds_ptr, num_seeds);
 
-        // SAFETY: The lifetime bound assures that seed_ptr points
-        // to the correct address as long as env is in scope.
-        std::slice::from_raw_parts(*seeds_ptr, *num_seeds as usize)
+            // SAFETY: seeds_ptr points to the array of seeds owned by
+            // env_raw, which cannot change while the lock is held.
+            std::slice::from_raw_parts(*seeds_ptr, *num_seeds as usize).to_vec()
+        })
     })
     .expect("get_quest_seeds should always succeed")
 }
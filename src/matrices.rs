@@ -1,12 +1,143 @@
-use num::Complex;
+use num::{
+    Complex,
+    One,
+    Zero,
+};
 
 use crate::{
     error::catch_quest_exception,
     ffi,
+    Qcomplex,
     Qreal,
     QuestError,
 };
 
+/// Methods shared by fixed-size matrices, with elements stored in row-major
+/// `real` and `imag` arrays of dimension `$dim`.
+macro_rules! impl_fixed_size_matrix {
+    ($Matrix:ident, $dim:literal) => {
+        impl $Matrix {
+            /// Get the matrix element at `(row, col)`.
+            ///
+            /// Returns `None` if either index is out of bounds.
+            ///
+            /// The matrix does not implement `Index<(usize, usize)>`, since
+            /// the real and imaginary parts are stored in separate arrays.
+            #[must_use]
+            pub fn get_elem(
+                &self,
+                row: usize,
+                col: usize,
+            ) -> Option<Qcomplex> {
+                (row < $dim && col < $dim).then(|| {
+                    Qcomplex::new(self.0.real[row][col], self.0.imag[row][col])
+                })
+            }
+
+            /// Set the matrix element at `(row, col)`.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if either index is out of bounds.
+            pub fn set_elem(
+                &mut self,
+                row: usize,
+                col: usize,
+                value: Qcomplex,
+            ) {
+                self.0.real[row][col] = value.re;
+                self.0.imag[row][col] = value.im;
+            }
+
+            /// Build a matrix from a flat vector of concatenated rows.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`ArrayLengthError`], if `elems.len()` is not equal to
+            /// the number of matrix elements.
+            ///
+            /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+            #[allow(clippy::needless_pass_by_value)]
+            pub fn from_vec(elems: Vec<Qcomplex>) -> Result<Self, QuestError> {
+                if elems.len() != $dim * $dim {
+                    return Err(QuestError::ArrayLengthError);
+                }
+                Ok(Self::from_dense(&elems))
+            }
+
+            /// Flat vector of concatenated rows.
+            #[must_use]
+            pub fn to_vec(&self) -> Vec<Qcomplex> {
+                (0..$dim)
+                    .flat_map(|row| {
+                        (0..$dim).map(move |col| {
+                            Qcomplex::new(
+                                self.0.real[row][col],
+                                self.0.imag[row][col],
+                            )
+                        })
+                    })
+                    .collect()
+            }
+
+            /// Conjugate transpose of the matrix.
+            #[must_use]
+            pub fn adjoint(&self) -> Self {
+                Self::from_dense(&dense_adjoint(&self.to_vec(), $dim))
+            }
+
+            /// Matrix product `self * other`.
+            #[must_use]
+            pub fn matmul(
+                &self,
+                other: &Self,
+            ) -> Self {
+                Self::from_dense(&dense_matmul(
+                    &self.to_vec(),
+                    &other.to_vec(),
+                    $dim,
+                ))
+            }
+
+            /// Sum of diagonal elements.
+            #[must_use]
+            pub fn trace(&self) -> Qcomplex {
+                (0..$dim)
+                    .map(|i| {
+                        Qcomplex::new(self.0.real[i][i], self.0.imag[i][i])
+                    })
+                    .sum()
+            }
+
+            /// Check if the matrix is unitary.
+            ///
+            /// Returns `true` if every element of `self * self.adjoint()`
+            /// differs from the identity matrix by at most `tol`.  `QuEST`
+            /// validates unitary matrices with tolerance [`REAL_EPS`].
+            ///
+            /// [`REAL_EPS`]: crate::REAL_EPS
+            #[must_use]
+            pub fn is_unitary(
+                &self,
+                tol: Qreal,
+            ) -> bool {
+                dense_is_unitary(&self.to_vec(), $dim, tol)
+            }
+
+            /// Build a matrix from a row-major slice of all its elements.
+            fn from_dense(elems: &[Qcomplex]) -> Self {
+                let mut real = [[0.; $dim]; $dim];
+                let mut imag = [[0.; $dim]; $dim];
+                for (i, z) in elems.iter().enumerate() {
+                    real[i / $dim][i % $dim] = z.re;
+                    imag[i / $dim][i % $dim] = z.im;
+                }
+                Self::new(real, imag)
+            }
+        }
+    };
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ComplexMatrix2(pub(crate) ffi::ComplexMatrix2);
 
@@ -21,8 +152,39 @@ impl ComplexMatrix2 {
             imag,
         })
     }
+
+    /// Tensor product `self ⊗ other`.
+    ///
+    /// When applied to a register, `other` acts on the first (least
+    /// significant) target qubit, and `self` on the second.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let x = ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    /// let id = ComplexMatrix2::new([[1., 0.], [0., 1.]], [[0., 0.], [0., 0.]]);
+    ///
+    /// let m = x.kron(&id);
+    /// assert_eq!(m.get_elem(2, 0), Some(Qcomplex::new(1., 0.)));
+    /// assert_eq!(m.get_elem(1, 0), Some(Qcomplex::new(0., 0.)));
+    /// ```
+    #[must_use]
+    pub fn kron(
+        &self,
+        other: &Self,
+    ) -> ComplexMatrix4 {
+        ComplexMatrix4::from_dense(&dense_kron(
+            &self.to_vec(),
+            2,
+            &other.to_vec(),
+            2,
+        ))
+    }
 }
 
+impl_fixed_size_matrix!(ComplexMatrix2, 2);
+
 #[derive(Debug)]
 pub struct ComplexMatrix4(pub(crate) ffi::ComplexMatrix4);
 
@@ -37,8 +199,33 @@ impl ComplexMatrix4 {
             imag,
         })
     }
+
+    /// Tensor product `self ⊗ other`.
+    ///
+    /// When applied to a register, `other` acts on the first two (least
+    /// significant) target qubits, and `self` on the remaining two.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if the resulting matrix cannot be
+    /// allocated.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn kron(
+        &self,
+        other: &Self,
+    ) -> Result<ComplexMatrixN, QuestError> {
+        ComplexMatrixN::from_vec(dense_kron(
+            &self.to_vec(),
+            4,
+            &other.to_vec(),
+            4,
+        ))
+    }
 }
 
+impl_fixed_size_matrix!(ComplexMatrix4, 4);
+
 #[derive(Debug)]
 pub struct ComplexMatrixN(pub(crate) ffi::ComplexMatrixN);
 
@@ -276,6 +463,255 @@ impl ComplexMatrixN {
             )
         }
     }
+
+    /// Matrix dimension, i.e. `2.pow(num_qubits)`.
+    #[allow(clippy::cast_sign_loss)]
+    fn dim(&self) -> usize {
+        1 << self.0.numQubits as usize
+    }
+
+    /// Get the matrix element at `(row, col)`.
+    ///
+    /// Returns `None` if either index is out of bounds.
+    ///
+    /// The matrix does not implement `Index<(usize, usize)>`, since the real
+    /// and imaginary parts are stored in separate arrays: there is no
+    /// `Qcomplex` to return a reference to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let mtr = ComplexMatrixN::from_vec(vec![
+    ///     Qcomplex::new(1., 2.),
+    ///     Qcomplex::new(3., 4.),
+    ///     Qcomplex::new(5., 6.),
+    ///     Qcomplex::new(7., 8.),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(mtr.get_elem(1, 0), Some(Qcomplex::new(5., 6.)));
+    /// assert_eq!(mtr.get_elem(2, 0), None);
+    /// ```
+    #[must_use]
+    pub fn get_elem(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> Option<Qcomplex> {
+        (row < self.dim() && col < self.dim()).then(|| {
+            // SAFETY: Both indices have just been checked.
+            unsafe { self.get_elem_unchecked(row, col) }
+        })
+    }
+
+    /// Get the matrix element at `(row, col)` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// Both `row` and `col` must be less than `2.pow(num_qubits)`.
+    unsafe fn get_elem_unchecked(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> Qcomplex {
+        // SAFETY: Guaranteed by the caller.  QuEST allocates
+        // `2.pow(num_qubits)` rows, each of `2.pow(num_qubits)` elements, for
+        // both `real` and `imag`.
+        unsafe {
+            Qcomplex::new(
+                *(*self.0.real.add(row)).add(col),
+                *(*self.0.imag.add(row)).add(col),
+            )
+        }
+    }
+
+    /// Set the matrix element at `(row, col)`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if either index is out of bounds.
+    pub fn set_elem(
+        &mut self,
+        row: usize,
+        col: usize,
+        value: Qcomplex,
+    ) {
+        assert!(row < self.dim() && col < self.dim());
+
+        self.row_real_as_mut_slice(row)[col] = value.re;
+        self.row_imag_as_mut_slice(row)[col] = value.im;
+    }
+
+    /// Build a matrix from a flat vector of concatenated rows.
+    ///
+    /// The number of qubits is inferred from the length of `elems`, which
+    /// must be equal to `2.pow(2 * num_qubits)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrayLengthError`], if `elems.len()` is not a power of 4
+    /// greater than 1.  Returns [`InvalidQuESTInputError`], if the matrix
+    /// cannot be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let elems = vec![
+    ///     Qcomplex::new(0., 0.),
+    ///     Qcomplex::new(1., 0.),
+    ///     Qcomplex::new(1., 0.),
+    ///     Qcomplex::new(0., 0.),
+    /// ];
+    /// let mtr = ComplexMatrixN::from_vec(elems.clone()).unwrap();
+    ///
+    /// assert_eq!(mtr.num_qubits(), 1);
+    /// assert_eq!(mtr.to_vec(), elems);
+    /// ```
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    #[allow(clippy::needless_pass_by_value)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn from_vec(elems: Vec<Qcomplex>) -> Result<Self, QuestError> {
+        // Check the length first: for an empty `elems`, the shift overflows
+        let num_qubits = elems.len().trailing_zeros() / 2;
+        if elems.len() < 4 || elems.len() != 1 << (2 * num_qubits) {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let mut m = Self::try_new(num_qubits as i32)?;
+        init_complex_matrix_from_slice(&mut m, &elems)?;
+        Ok(m)
+    }
+
+    /// Flat vector of concatenated rows.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Qcomplex> {
+        (0..self.dim())
+            .flat_map(|row| {
+                self.row_real_as_slice(row)
+                    .iter()
+                    .zip(self.row_imag_as_slice(row))
+                    .map(|(&re, &im)| Qcomplex::new(re, im))
+            })
+            .collect()
+    }
+
+    /// Conjugate transpose of the matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if the resulting matrix cannot be
+    /// allocated.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn adjoint(&self) -> Result<Self, QuestError> {
+        Self::from_vec(dense_adjoint(&self.to_vec(), self.dim()))
+    }
+
+    /// Matrix product `self * other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrayLengthError`], if the matrices act on different numbers
+    /// of qubits.  Returns [`InvalidQuESTInputError`] if the resulting matrix
+    /// cannot be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let (zero, one) = (Qcomplex::new(0., 0.), Qcomplex::new(1., 0.));
+    /// let x = ComplexMatrixN::from_vec(vec![zero, one, one, zero]).unwrap();
+    ///
+    /// let id = x.matmul(&x).unwrap();
+    /// assert_eq!(id.to_vec(), [one, zero, zero, one]);
+    /// ```
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn matmul(
+        &self,
+        other: &Self,
+    ) -> Result<Self, QuestError> {
+        if self.num_qubits() != other.num_qubits() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        Self::from_vec(dense_matmul(
+            &self.to_vec(),
+            &other.to_vec(),
+            self.dim(),
+        ))
+    }
+
+    /// Tensor product `self ⊗ other`.
+    ///
+    /// When applied to a register, `other` acts on the first (least
+    /// significant) `other.num_qubits()` target qubits, and `self` on the
+    /// remaining ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if the resulting matrix cannot be
+    /// allocated.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn kron(
+        &self,
+        other: &Self,
+    ) -> Result<Self, QuestError> {
+        Self::from_vec(dense_kron(
+            &self.to_vec(),
+            self.dim(),
+            &other.to_vec(),
+            other.dim(),
+        ))
+    }
+
+    /// Sum of diagonal elements.
+    #[must_use]
+    pub fn trace(&self) -> Qcomplex {
+        (0..self.dim())
+            .map(|i| {
+                Qcomplex::new(
+                    self.row_real_as_slice(i)[i],
+                    self.row_imag_as_slice(i)[i],
+                )
+            })
+            .sum()
+    }
+
+    /// Check if the matrix is unitary.
+    ///
+    /// Returns `true` if every element of `self * self.adjoint()` differs
+    /// from the identity matrix by at most `tol`.  `QuEST` validates unitary
+    /// matrices with tolerance [`REAL_EPS`], so checking
+    /// `is_unitary(REAL_EPS)` before calling e.g.
+    /// [`multi_qubit_unitary()`] tells precisely why that call would fail.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let (zero, one) = (Qcomplex::new(0., 0.), Qcomplex::new(1., 0.));
+    /// let x = ComplexMatrixN::from_vec(vec![zero, one, one, zero]).unwrap();
+    /// assert!(x.is_unitary(REAL_EPS));
+    ///
+    /// let m = ComplexMatrixN::from_vec(vec![one, one, one, zero]).unwrap();
+    /// assert!(!m.is_unitary(REAL_EPS));
+    /// ```
+    ///
+    /// [`REAL_EPS`]: crate::REAL_EPS
+    /// [`multi_qubit_unitary()`]: crate::Qureg::multi_qubit_unitary()
+    #[must_use]
+    pub fn is_unitary(
+        &self,
+        tol: Qreal,
+    ) -> bool {
+        dense_is_unitary(&self.to_vec(), self.dim(), tol)
+    }
 }
 
 impl Drop for ComplexMatrixN {
@@ -419,3 +855,67 @@ pub fn init_complex_matrix_from_slice(
     }
     Ok(())
 }
+
+/// Conjugate transpose of a dense `dim`x`dim` matrix in row-major order.
+fn dense_adjoint(
+    a: &[Qcomplex],
+    dim: usize,
+) -> Vec<Qcomplex> {
+    (0..dim * dim)
+        .map(|i| a[(i % dim) * dim + i / dim].conj())
+        .collect()
+}
+
+/// Product of dense `dim`x`dim` matrices in row-major order.
+fn dense_matmul(
+    a: &[Qcomplex],
+    b: &[Qcomplex],
+    dim: usize,
+) -> Vec<Qcomplex> {
+    let mut c = vec![Qcomplex::zero(); dim * dim];
+    for i in 0..dim {
+        for k in 0..dim {
+            let a_ik = a[i * dim + k];
+            for j in 0..dim {
+                c[i * dim + j] += a_ik * b[k * dim + j];
+            }
+        }
+    }
+    c
+}
+
+/// Tensor product of dense matrices in row-major order.
+fn dense_kron(
+    a: &[Qcomplex],
+    dim_a: usize,
+    b: &[Qcomplex],
+    dim_b: usize,
+) -> Vec<Qcomplex> {
+    let dim = dim_a * dim_b;
+    (0..dim * dim)
+        .map(|i| {
+            let (row, col) = (i / dim, i % dim);
+            a[(row / dim_b) * dim_a + col / dim_b]
+                * b[(row % dim_b) * dim_b + col % dim_b]
+        })
+        .collect()
+}
+
+/// Check if `a * a^dagger` is the identity, up to `tol` elementwise.
+fn dense_is_unitary(
+    a: &[Qcomplex],
+    dim: usize,
+    tol: Qreal,
+) -> bool {
+    dense_matmul(a, &dense_adjoint(a, dim), dim)
+        .iter()
+        .enumerate()
+        .all(|(i, z)| {
+            let id = if i / dim == i % dim {
+                Qcomplex::one()
+            } else {
+                Qcomplex::zero()
+            };
+            (z - id).norm() <= tol
+        })
+}
@@ -12,6 +12,8 @@ mod _precision {
     };
     /// Machine epsilon value for [`Qreal`](crate::Qreal)
     pub const EPSILON: Qreal = f64::EPSILON;
+    /// Tolerance used by `QuEST` to validate e.g. unitarity of matrices
+    pub const REAL_EPS: Qreal = 1e-13;
 }
 
 #[cfg(feature = "f32")]
@@ -28,6 +30,8 @@ mod _precision {
     };
     /// Machine epsilon value for [`Qreal`](crate::Qreal)
     pub const EPSILON: Qreal = f32::EPSILON;
+    /// Tolerance used by `QuEST` to validate e.g. unitarity of matrices
+    pub const REAL_EPS: Qreal = 1e-5;
 }
 
 pub use _precision::{
@@ -37,6 +41,7 @@ pub use _precision::{
     LN_10,
     LN_2,
     PI,
+    REAL_EPS,
     SQRT_2,
     TAU,
 };
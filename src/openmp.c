@@ -0,0 +1,38 @@
+/*
+ * Access to the OpenMP runtime used by QuEST.
+ *
+ * QuEST may be linked against any OpenMP runtime (libgomp, libomp), or
+ * against none at all if it was built without multithreading.  Rather than
+ * linking a particular runtime here, we look up its functions among the
+ * libraries already loaded with QuEST.  See: src/questenv.rs
+ */
+
+#include <dlfcn.h>
+#include <stddef.h>
+
+typedef void (*set_num_threads_fn)(int);
+
+static set_num_threads_fn lookup_set_num_threads(void) {
+    return (set_num_threads_fn)dlsym(RTLD_DEFAULT, "omp_set_num_threads");
+}
+
+/*
+ * Return 1 if an OpenMP runtime is loaded, or 0 otherwise.
+ */
+int quest_bind_has_openmp(void) {
+    return lookup_set_num_threads() != NULL;
+}
+
+/*
+ * Call omp_set_num_threads(num_threads).  Return 0 on success, or 1 if no
+ * OpenMP runtime is loaded.
+ */
+int quest_bind_set_num_threads(int num_threads) {
+    set_num_threads_fn set_num_threads = lookup_set_num_threads();
+
+    if (set_num_threads == NULL) {
+        return 1;
+    }
+    set_num_threads(num_threads);
+    return 0;
+}
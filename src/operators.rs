@@ -1,4 +1,13 @@
-use std::ffi::CString;
+use std::{
+    fs::File,
+    io::{
+        BufRead,
+        BufReader,
+        BufWriter,
+        Write,
+    },
+    path::Path,
+};
 
 use crate::{
     error::catch_quest_exception,
@@ -11,6 +20,7 @@ use crate::{
     QuestEnv,
     QuestError,
     Qureg,
+    QuregKind,
 };
 
 #[derive(Debug)]
@@ -41,24 +51,269 @@ impl PauliHamil {
         num_qubits: i32,
         num_sum_terms: i32,
     ) -> Result<Self, QuestError> {
-        catch_quest_exception(|| {
+        let hamil = catch_quest_exception(|| {
             Self(unsafe { ffi::createPauliHamil(num_qubits, num_sum_terms) })
-        })
+        })?;
+        let num_codes = (num_qubits * num_sum_terms) as usize;
+        // SAFETY: QuEST allocated `num_sum_terms` coefficients and
+        // `num_qubits * num_sum_terms` codes.  Zero bits are valid values for
+        // both: `0.` and `PAULI_I`.
+        unsafe {
+            std::ptr::write_bytes(
+                hamil.0.termCoeffs,
+                0,
+                num_sum_terms as usize,
+            );
+            std::ptr::write_bytes(hamil.0.pauliCodes, 0, num_codes);
+        }
+        Ok(hamil)
     }
 
     /// Creates a [`PauliHamil`] instance
-    /// populated with the data in filename `fn_`.
+    /// populated with the data in the file at `path`.
     ///
-    /// # Bugs
+    /// The file format is the same as in `QuEST`: each line specifies one
+    /// term of the Hamiltonian, consisting of a real coefficient followed
+    /// by one Pauli code (`0`, `1`, `2`, `3` for `I`, `X`, `Y`, `Z`) per
+    /// qubit, all separated by whitespace.  The number of qubits is inferred
+    /// from the first term.  Blank lines are ignored.
     ///
-    /// This function calls its C equivalent which unfortunately behaves
-    /// erratically when the file specified is incorrectly formatted or
-    /// inaccessible, often leading to seg-faults.  Use at your own risk.
-    pub fn try_new_from_file(fn_: &str) -> Result<Self, QuestError> {
-        let filename = CString::new(fn_).map_err(QuestError::NulError)?;
-        catch_quest_exception(|| {
-            Self(unsafe { ffi::createPauliHamilFromFile((*filename).as_ptr()) })
-        })
+    /// The file is parsed in Rust, so malformed files are reported as errors.
+    ///
+    /// # Errors
+    ///
+    /// - [`IoError`], if the file cannot be read
+    /// - [`ParseError`], if the file is incorrectly formatted
+    ///
+    /// [`IoError`]: crate::QuestError::IoError
+    /// [`ParseError`]: crate::QuestError::ParseError
+    pub fn try_new_from_file<P: AsRef<Path>>(
+        path: P
+    ) -> Result<Self, QuestError> {
+        Self::try_new_from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Creates a [`PauliHamil`] instance
+    /// populated with data read from `reader`.
+    ///
+    /// See [`try_new_from_file()`][api-try-new-from-file] for the format.
+    ///
+    /// # Errors
+    ///
+    /// - [`IoError`], if `reader` fails
+    /// - [`ParseError`], if the data is incorrectly formatted
+    ///
+    /// [api-try-new-from-file]: crate::PauliHamil::try_new_from_file()
+    /// [`IoError`]: crate::QuestError::IoError
+    /// [`ParseError`]: crate::QuestError::ParseError
+    pub fn try_new_from_reader<R: BufRead>(
+        reader: R
+    ) -> Result<Self, QuestError> {
+        let mut num_qubits = 0_usize;
+        let mut coeffs = Vec::new();
+        let mut codes = Vec::new();
+
+        for (line_idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            let error = |column: usize, msg: String| QuestError::ParseError {
+                line: line_idx + 1,
+                column,
+                msg,
+            };
+            let mut fields = line.split_whitespace().map(|field| {
+                (field.as_ptr() as usize - line.as_ptr() as usize + 1, field)
+            });
+
+            let Some((column, field)) = fields.next() else {
+                continue;
+            };
+            let coeff = field.parse::<Qreal>().map_err(|_| {
+                error(column, format!("invalid coefficient `{field}`"))
+            })?;
+
+            let mut num_codes = 0;
+            for (column, field) in fields {
+                let code = match field {
+                    "0" => PauliOpType::PAULI_I,
+                    "1" => PauliOpType::PAULI_X,
+                    "2" => PauliOpType::PAULI_Y,
+                    "3" => PauliOpType::PAULI_Z,
+                    _ => {
+                        return Err(error(
+                            column,
+                            format!("invalid Pauli code `{field}`"),
+                        ))
+                    }
+                };
+                codes.push(code);
+                num_codes += 1;
+            }
+
+            if coeffs.is_empty() {
+                if num_codes == 0 {
+                    return Err(error(
+                        line.len() + 1,
+                        "expected Pauli codes".into(),
+                    ));
+                }
+                num_qubits = num_codes;
+            } else if num_codes != num_qubits {
+                return Err(error(
+                    line.len() + 1,
+                    format!(
+                        "expected {num_qubits} Pauli codes, found {num_codes}"
+                    ),
+                ));
+            }
+            coeffs.push(coeff);
+        }
+
+        if coeffs.is_empty() {
+            return Err(QuestError::ParseError {
+                line:   1,
+                column: 1,
+                msg:    "expected at least one term".into(),
+            });
+        }
+
+        let mut hamil = Self::try_new(num_qubits as i32, coeffs.len() as i32)?;
+        init_pauli_hamil(&mut hamil, &coeffs, &codes)?;
+        Ok(hamil)
+    }
+
+    /// Creates a [`PauliHamil`] instance
+    /// populated with the data in `contents`.
+    ///
+    /// See [`try_new_from_file()`][api-try-new-from-file] for the format.
+    ///
+    /// # Errors
+    ///
+    /// - [`ParseError`], if `contents` is incorrectly formatted
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let hamil =
+    ///     PauliHamil::try_new_from_file_str("0.5 1 0 3\n-1.2 0 2 0\n").unwrap();
+    ///
+    /// assert_eq!(hamil.num_qubits(), 3);
+    /// assert_eq!(hamil.num_sum_terms(), 2);
+    /// assert_eq!(hamil.term_coeffs(), &[0.5, -1.2]);
+    /// ```
+    ///
+    /// [api-try-new-from-file]: crate::PauliHamil::try_new_from_file()
+    /// [`ParseError`]: crate::QuestError::ParseError
+    pub fn try_new_from_file_str(contents: &str) -> Result<Self, QuestError> {
+        Self::try_new_from_reader(contents.as_bytes())
+    }
+
+    /// Number of qubits the Hamiltonian acts on.
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.0.numQubits
+    }
+
+    /// Number of terms in the Hamiltonian.
+    #[must_use]
+    pub fn num_sum_terms(&self) -> i32 {
+        self.0.numSumTerms
+    }
+
+    /// Coefficients of the terms in the Hamiltonian.
+    #[must_use]
+    pub fn term_coeffs(&self) -> &[Qreal] {
+        // SAFETY: QuEST allocated `numSumTerms` coefficients, initialized in
+        // `try_new()`.
+        unsafe {
+            std::slice::from_raw_parts(
+                self.0.termCoeffs,
+                self.0.numSumTerms as usize,
+            )
+        }
+    }
+
+    /// Pauli codes of the terms in the Hamiltonian.
+    ///
+    /// The codes of term `i` are found at indices
+    /// `i * num_qubits()..(i + 1) * num_qubits()`.
+    #[must_use]
+    pub fn pauli_codes(&self) -> &[PauliOpType] {
+        // SAFETY: QuEST allocated `numQubits * numSumTerms` codes,
+        // initialized in `try_new()`.
+        unsafe {
+            std::slice::from_raw_parts(
+                self.0.pauliCodes,
+                (self.0.numQubits * self.0.numSumTerms) as usize,
+            )
+        }
+    }
+
+    /// Write the Hamiltonian in the `QuEST` file format.
+    ///
+    /// See [`try_new_from_file()`][api-try-new-from-file] for the format.
+    /// Coefficients are written with enough digits to be read back
+    /// exactly.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported by `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use quest_bind::PauliOpType::*;
+    ///
+    /// let hamil = &mut PauliHamil::try_new(2, 2).unwrap();
+    /// init_pauli_hamil(
+    ///     hamil,
+    ///     &[0.5, -0.25],
+    ///     &[PAULI_X, PAULI_I, PAULI_Z, PAULI_Y],
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut buf = Vec::new();
+    /// hamil.write_to(&mut buf).unwrap();
+    /// assert_eq!(String::from_utf8(buf).unwrap(), "0.5 1 0\n-0.25 3 2\n");
+    /// ```
+    ///
+    /// [api-try-new-from-file]: crate::PauliHamil::try_new_from_file()
+    pub fn write_to<W: Write>(
+        &self,
+        mut writer: W,
+    ) -> std::io::Result<()> {
+        let num_qubits = self.num_qubits() as usize;
+        for (coeff, codes) in self
+            .term_coeffs()
+            .iter()
+            .zip(self.pauli_codes().chunks(num_qubits))
+        {
+            write!(writer, "{coeff}")?;
+            for &code in codes {
+                write!(writer, " {}", code as i32)?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Write the Hamiltonian to a file in the `QuEST` file format.
+    ///
+    /// See [`write_to()`][api-write-to].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError`] if the file cannot be written.
+    ///
+    /// [api-write-to]: crate::PauliHamil::write_to()
+    /// [`IoError`]: crate::QuestError::IoError
+    pub fn write_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<(), QuestError> {
+        self.write_to(BufWriter::new(File::create(path)?))
+            .map_err(Into::into)
     }
 }
 
@@ -70,46 +325,710 @@ impl Drop for PauliHamil {
 }
 
 #[derive(Debug)]
-pub struct DiagonalOp<'a> {
-    pub(crate) env: &'a QuestEnv,
+pub struct DiagonalOp {
+    pub(crate) env: QuestEnv,
     pub(crate) op:  ffi::DiagonalOp,
 }
 
-impl<'a> DiagonalOp<'a> {
+impl DiagonalOp {
     pub fn try_new(
         num_qubits: i32,
-        env: &'a QuestEnv,
+        env: &QuestEnv,
     ) -> Result<Self, QuestError> {
         Ok(Self {
-            env,
-            op: catch_quest_exception(|| unsafe {
-                ffi::createDiagonalOp(num_qubits, env.0)
+            env: env.clone(),
+            op:  catch_quest_exception(|| unsafe {
+                ffi::createDiagonalOp(num_qubits, env.raw())
             })?,
         })
     }
 
-    pub fn try_new_from_file(
-        fn_: &str,
-        env: &'a QuestEnv,
+    /// Creates a [`DiagonalOp`] from a Pauli Hamiltonian file.
+    ///
+    /// The file is read with [`PauliHamil::try_new_from_file()`], and the
+    /// operator is initialized with [`init_diagonal_op_from_pauli_hamil()`].
+    ///
+    /// # Errors
+    ///
+    /// - [`IoError`], if the file cannot be read
+    /// - [`ParseError`], if the file is incorrectly formatted
+    /// - [`InvalidQuESTInputError`], if the Hamiltonian contains Pauli
+    ///   operators other than `I` and `Z`
+    ///
+    /// [`PauliHamil::try_new_from_file()`]: crate::PauliHamil::try_new_from_file()
+    /// [`init_diagonal_op_from_pauli_hamil()`]: crate::init_diagonal_op_from_pauli_hamil()
+    /// [`IoError`]: crate::QuestError::IoError
+    /// [`ParseError`]: crate::QuestError::ParseError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn try_new_from_file<P: AsRef<Path>>(
+        path: P,
+        env: &QuestEnv,
     ) -> Result<Self, QuestError> {
-        let filename = CString::new(fn_).map_err(QuestError::NulError)?;
+        let hamil = PauliHamil::try_new_from_file(path)?;
+        let mut op = Self::try_new(hamil.num_qubits(), env)?;
+        init_diagonal_op_from_pauli_hamil(&mut op, &hamil)?;
+        Ok(op)
+    }
 
-        Ok(Self {
-            env,
-            op: catch_quest_exception(|| unsafe {
-                ffi::createDiagonalOpFromPauliHamilFile(
-                    (*filename).as_ptr(),
-                    env.0,
-                )
-            })?,
+    /// Creates a [`DiagonalOp`] with elements given by a function of the
+    /// basis state index.
+    ///
+    /// The element at index `i` is set to `f(i)`, for every `i` in
+    /// `[0, 2^num_qubits)`.  The function is evaluated in order of increasing
+    /// index and its values are written directly into the operator, so no
+    /// temporary buffers are allocated.  In distributed mode, each node
+    /// evaluates `f` only for the indices of the elements it stores.
+    ///
+    /// # Parameters
+    ///
+    /// - `num_qubits`: number of qubits the operator acts on
+    /// - `env`: the [`QuestEnv`] runtime environment
+    /// - `f`: function from the index of a basis state to the element
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if the operator cannot be
+    /// created, e.g. if `num_qubits` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    ///
+    /// // phase oracle marking the state |101>
+    /// let op = DiagonalOp::from_fn(3, env, |i| {
+    ///     Qcomplex::new(if i == 0b101 { -1. } else { 1. }, 0.)
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(op.get_elem(5), Some(Qcomplex::new(-1., 0.)));
+    /// assert_eq!(op.get_elem(4), Some(Qcomplex::new(1., 0.)));
+    /// ```
+    ///
+    /// [`QuestEnv`]: crate::QuestEnv
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn from_fn<F>(
+        num_qubits: i32,
+        env: &QuestEnv,
+        mut f: F,
+    ) -> Result<Self, QuestError>
+    where
+        F: FnMut(i64) -> Qcomplex,
+    {
+        let mut op = Self::try_new(num_qubits, env)?;
+        let start = op.chunk_start();
+        op.modify_elems(|reals, imags| {
+            for (index, (re, im)) in
+                (start..).zip(reals.iter_mut().zip(imags.iter_mut()))
+            {
+                let elem = f(index);
+                *re = elem.re;
+                *im = elem.im;
+            }
+        })?;
+        Ok(op)
+    }
+
+    /// Creates a [`DiagonalOp`] with elements given by a function of the
+    /// basis state index, evaluated in parallel.
+    ///
+    /// This is the parallel version of [`from_fn()`][api-from-fn], using the
+    /// [`rayon`] thread pool.  The order in which `f` is evaluated is
+    /// unspecified.
+    ///
+    /// This function is available with the `rayon` feature.
+    ///
+    /// # Errors
+    ///
+    /// See [`from_fn()`][api-from-fn].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    ///
+    /// // cost function diagonal for QAOA: number of ones in the bitstring
+    /// let op = DiagonalOp::par_from_fn(3, env, |i| {
+    ///     Qcomplex::new(i.count_ones() as Qreal, 0.)
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(op.get_elem(7), Some(Qcomplex::new(3., 0.)));
+    /// ```
+    ///
+    /// [api-from-fn]: crate::DiagonalOp::from_fn()
+    /// [`rayon`]: https://crates.io/crates/rayon
+    #[cfg(feature = "rayon")]
+    pub fn par_from_fn<F>(
+        num_qubits: i32,
+        env: &QuestEnv,
+        f: F,
+    ) -> Result<Self, QuestError>
+    where
+        F: Fn(i64) -> Qcomplex + Sync,
+    {
+        use rayon::prelude::*;
+
+        let mut op = Self::try_new(num_qubits, env)?;
+        let start = op.chunk_start();
+        op.modify_elems(|reals, imags| {
+            reals
+                .par_iter_mut()
+                .zip(imags.par_iter_mut())
+                .enumerate()
+                .for_each(|(offset, (re, im))| {
+                    let elem = f(start + offset as i64);
+                    *re = elem.re;
+                    *im = elem.im;
+                });
+        })?;
+        Ok(op)
+    }
+
+    /// Number of qubits the operator acts on.
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.op.numQubits
+    }
+
+    /// Get the element at a given index.
+    ///
+    /// Returns `None` if `index` is outside `[0, 2^N)`, or, in distributed
+    /// mode, if the element is not stored on this node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let op = &mut DiagonalOp::try_new(1, env).unwrap();
+    /// init_diagonal_op(op, &[1., 2.], &[3., 4.]).unwrap();
+    ///
+    /// assert_eq!(op.get_elem(1), Some(Qcomplex::new(2., 4.)));
+    /// assert_eq!(op.get_elem(2), None);
+    /// ```
+    #[must_use]
+    pub fn get_elem(
+        &self,
+        index: i64,
+    ) -> Option<Qcomplex> {
+        let local_index = index - self.chunk_start();
+        if local_index < 0 {
+            return None;
+        }
+        let (reals, imags) = self.elems_view();
+        let local_index = local_index as usize;
+        Some(Qcomplex::new(
+            *reals.get(local_index)?,
+            *imags.get(local_index)?,
+        ))
+    }
+
+    /// Borrow the elements of the operator stored on this node.
+    ///
+    /// Returns a pair of slices `(reals, imags)` holding the real and
+    /// imaginary parts of the elements.  No data is copied.  In distributed
+    /// mode, the slices hold only the contiguous chunk of elements stored on
+    /// this node.
+    ///
+    /// The elements are read from RAM, which `QuEST` keeps up to date in GPU
+    /// mode as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let op = &mut DiagonalOp::try_new(1, env).unwrap();
+    /// init_diagonal_op(op, &[1., 2.], &[3., 4.]).unwrap();
+    ///
+    /// let (reals, imags) = op.elems_view();
+    /// assert_eq!(reals, &[1., 2.]);
+    /// assert_eq!(imags, &[3., 4.]);
+    /// ```
+    #[must_use]
+    pub fn elems_view(&self) -> (&[Qreal], &[Qreal]) {
+        let len = self.op.numElemsPerChunk as usize;
+        // SAFETY: QuEST allocates (and zero-initializes) `numElemsPerChunk`
+        // elements in RAM for each of `real` and `imag`.  The memory lives as
+        // long as `self` and can only be modified through `&mut self`.
+        unsafe {
+            (
+                std::slice::from_raw_parts(self.op.real, len),
+                std::slice::from_raw_parts(self.op.imag, len),
+            )
+        }
+    }
+
+    /// Iterate over the elements of the operator stored on this node.
+    ///
+    /// See [`elems_view()`][api-elems-view].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let op = &mut DiagonalOp::try_new(1, env).unwrap();
+    /// init_diagonal_op(op, &[1., 2.], &[3., 4.]).unwrap();
+    ///
+    /// let elems = op.iter().collect::<Vec<_>>();
+    /// assert_eq!(elems, &[Qcomplex::new(1., 3.), Qcomplex::new(2., 4.)]);
+    /// ```
+    ///
+    /// [api-elems-view]: crate::DiagonalOp::elems_view()
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Qcomplex> + '_ {
+        let (reals, imags) = self.elems_view();
+        reals
+            .iter()
+            .zip(imags)
+            .map(|(&re, &im)| Qcomplex::new(re, im))
+    }
+
+    /// Modify the elements of the operator stored on this node in place.
+    ///
+    /// The closure `f` receives the real and imaginary parts of the
+    /// elements, as in [`elems_view()`][api-elems-view].  Afterwards, the
+    /// elements are copied to GPU memory with
+    /// [`sync_diagonal_op()`][api-sync-diagonal-op].
+    ///
+    /// # Returns
+    ///
+    /// The value returned by `f`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if the synchronization fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let op = &mut DiagonalOp::try_new(2, env).unwrap();
+    ///
+    /// op.modify_elems(|reals, _| {
+    ///     for (i, re) in reals.iter_mut().enumerate() {
+    ///         *re = i as Qreal;
+    ///     }
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(op.get_elem(3), Some(Qcomplex::new(3., 0.)));
+    /// ```
+    ///
+    /// [api-elems-view]: crate::DiagonalOp::elems_view()
+    /// [api-sync-diagonal-op]: crate::sync_diagonal_op()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn modify_elems<F, T>(
+        &mut self,
+        f: F,
+    ) -> Result<T, QuestError>
+    where
+        F: FnOnce(&mut [Qreal], &mut [Qreal]) -> T,
+    {
+        let len = self.op.numElemsPerChunk as usize;
+        // SAFETY: See `elems_view()`.  The real and imaginary parts are
+        // stored in separate allocations, so the slices do not overlap.
+        let ret = unsafe {
+            f(
+                std::slice::from_raw_parts_mut(self.op.real, len),
+                std::slice::from_raw_parts_mut(self.op.imag, len),
+            )
+        };
+        sync_diagonal_op(self)?;
+        Ok(ret)
+    }
+
+    /// Creates a copy of the operator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if the new operator cannot be
+    /// allocated.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn try_clone(&self) -> Result<Self, QuestError> {
+        self.map(|elem| elem)
+    }
+
+    /// Apply a function to each element of the operator in place.
+    ///
+    /// This is a convenience wrapper around
+    /// [`modify_elems()`][api-modify-elems].
+    ///
+    /// # Errors
+    ///
+    /// See [`modify_elems()`][api-modify-elems].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let op = &mut DiagonalOp::try_new(1, env).unwrap();
+    /// init_diagonal_op(op, &[1., 2.], &[0., 0.]).unwrap();
+    ///
+    /// op.map_in_place(|elem| elem * elem + 1.).unwrap();
+    /// assert_eq!(op.get_elem(1), Some(Qcomplex::new(5., 0.)));
+    /// ```
+    ///
+    /// [api-modify-elems]: crate::DiagonalOp::modify_elems()
+    pub fn map_in_place<F>(
+        &mut self,
+        mut f: F,
+    ) -> Result<(), QuestError>
+    where
+        F: FnMut(Qcomplex) -> Qcomplex,
+    {
+        self.modify_elems(|reals, imags| {
+            for (re, im) in reals.iter_mut().zip(imags) {
+                let elem = f(Qcomplex::new(*re, *im));
+                *re = elem.re;
+                *im = elem.im;
+            }
+        })
+    }
+
+    /// Creates a new operator by applying a function to each element.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if the new operator cannot be
+    /// allocated.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn map<F>(
+        &self,
+        mut f: F,
+    ) -> Result<Self, QuestError>
+    where
+        F: FnMut(Qcomplex) -> Qcomplex,
+    {
+        Self::from_fn(self.num_qubits(), &self.env, |index| {
+            f(self
+                .get_elem(index)
+                .expect("index should be within the local chunk"))
+        })
+    }
+
+    /// Combine the elements of two operators in place.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`], if the operators act on different numbers of
+    ///   qubits
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    fn zip_map_in_place<F>(
+        &mut self,
+        other: &Self,
+        mut f: F,
+    ) -> Result<(), QuestError>
+    where
+        F: FnMut(Qcomplex, Qcomplex) -> Qcomplex,
+    {
+        if self.num_qubits() != other.num_qubits() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let mut others = other.iter();
+        self.map_in_place(|elem| {
+            f(
+                elem,
+                others.next().expect("operators should have equal size"),
+            )
         })
     }
+
+    /// Add another operator to `self`, elementwise.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`], if the operators act on different numbers of
+    ///   qubits
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn add_in_place(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), QuestError> {
+        self.zip_map_in_place(other, |a, b| a + b)
+    }
+
+    /// Subtract another operator from `self`, elementwise.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`], if the operators act on different numbers of
+    ///   qubits
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn sub_in_place(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), QuestError> {
+        self.zip_map_in_place(other, |a, b| a - b)
+    }
+
+    /// Multiply `self` by another operator.
+    ///
+    /// Since both operators are diagonal, this is the elementwise product.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`], if the operators act on different numbers of
+    ///   qubits
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn mul_in_place(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), QuestError> {
+        self.zip_map_in_place(other, |a, b| a * b)
+    }
+
+    /// Creates the sum of two operators.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`], if the operators act on different numbers of
+    ///   qubits
+    /// - [`InvalidQuESTInputError`], if the new operator cannot be allocated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let d1 =
+    ///     DiagonalOp::from_fn(1, env, |i| Qcomplex::new(i as Qreal, 0.)).unwrap();
+    /// let d2 = DiagonalOp::from_fn(1, env, |_| Qcomplex::new(0., 1.)).unwrap();
+    ///
+    /// let sum = d1.try_add(&d2).unwrap();
+    /// assert_eq!(sum.get_elem(1), Some(Qcomplex::new(1., 1.)));
+    /// ```
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn try_add(
+        &self,
+        other: &Self,
+    ) -> Result<Self, QuestError> {
+        let mut op = self.try_clone()?;
+        op.add_in_place(other)?;
+        Ok(op)
+    }
+
+    /// Creates the difference of two operators.
+    ///
+    /// # Errors
+    ///
+    /// See [`try_add()`][api-try-add].
+    ///
+    /// [api-try-add]: crate::DiagonalOp::try_add()
+    pub fn try_sub(
+        &self,
+        other: &Self,
+    ) -> Result<Self, QuestError> {
+        let mut op = self.try_clone()?;
+        op.sub_in_place(other)?;
+        Ok(op)
+    }
+
+    /// Creates the product of two operators.
+    ///
+    /// # Errors
+    ///
+    /// See [`try_add()`][api-try-add].
+    ///
+    /// [api-try-add]: crate::DiagonalOp::try_add()
+    pub fn try_mul(
+        &self,
+        other: &Self,
+    ) -> Result<Self, QuestError> {
+        let mut op = self.try_clone()?;
+        op.mul_in_place(other)?;
+        Ok(op)
+    }
+
+    /// Multiply the operator by a scalar in place.
+    ///
+    /// # Errors
+    ///
+    /// See [`modify_elems()`][api-modify-elems].
+    ///
+    /// [api-modify-elems]: crate::DiagonalOp::modify_elems()
+    pub fn scale_in_place(
+        &mut self,
+        factor: Qcomplex,
+    ) -> Result<(), QuestError> {
+        self.map_in_place(|elem| elem * factor)
+    }
+
+    /// Creates the operator multiplied by a scalar.
+    ///
+    /// # Errors
+    ///
+    /// See [`map()`][api-map].
+    ///
+    /// [api-map]: crate::DiagonalOp::map()
+    pub fn scale(
+        &self,
+        factor: Qcomplex,
+    ) -> Result<Self, QuestError> {
+        self.map(|elem| elem * factor)
+    }
+
+    /// Replace the operator by its complex conjugate (equivalently, its
+    /// adjoint) in place.
+    ///
+    /// # Errors
+    ///
+    /// See [`modify_elems()`][api-modify-elems].
+    ///
+    /// [api-modify-elems]: crate::DiagonalOp::modify_elems()
+    pub fn conj_in_place(&mut self) -> Result<(), QuestError> {
+        self.map_in_place(|elem| elem.conj())
+    }
+
+    /// Creates the complex conjugate (equivalently, the adjoint) of the
+    /// operator.
+    ///
+    /// # Errors
+    ///
+    /// See [`map()`][api-map].
+    ///
+    /// [api-map]: crate::DiagonalOp::map()
+    pub fn conj(&self) -> Result<Self, QuestError> {
+        self.map(|elem| elem.conj())
+    }
+
+    /// Raise the operator to an integer power in place.
+    ///
+    /// # Errors
+    ///
+    /// See [`modify_elems()`][api-modify-elems].
+    ///
+    /// [api-modify-elems]: crate::DiagonalOp::modify_elems()
+    pub fn powi_in_place(
+        &mut self,
+        exp: i32,
+    ) -> Result<(), QuestError> {
+        self.map_in_place(|elem| elem.powi(exp))
+    }
+
+    /// Creates the operator raised to an integer power.
+    ///
+    /// # Errors
+    ///
+    /// See [`map()`][api-map].
+    ///
+    /// [api-map]: crate::DiagonalOp::map()
+    pub fn powi(
+        &self,
+        exp: i32,
+    ) -> Result<Self, QuestError> {
+        self.map(|elem| elem.powi(exp))
+    }
+
+    /// Raise the operator to a real power in place.
+    ///
+    /// The principal branch of the complex power is used.
+    ///
+    /// # Errors
+    ///
+    /// See [`modify_elems()`][api-modify-elems].
+    ///
+    /// [api-modify-elems]: crate::DiagonalOp::modify_elems()
+    pub fn powf_in_place(
+        &mut self,
+        exp: Qreal,
+    ) -> Result<(), QuestError> {
+        self.map_in_place(|elem| elem.powf(exp))
+    }
+
+    /// Creates the operator raised to a real power.
+    ///
+    /// The principal branch of the complex power is used.
+    ///
+    /// # Errors
+    ///
+    /// See [`map()`][api-map].
+    ///
+    /// [api-map]: crate::DiagonalOp::map()
+    pub fn powf(
+        &self,
+        exp: Qreal,
+    ) -> Result<Self, QuestError> {
+        self.map(|elem| elem.powf(exp))
+    }
+
+    /// Replace the operator `D` by `exp(-i t D)` in place.
+    ///
+    /// For a Hermitian (i.e. real) operator `D`, the result is the unitary
+    /// time evolution generated by `D` for time `t`.
+    ///
+    /// # Errors
+    ///
+    /// See [`modify_elems()`][api-modify-elems].
+    ///
+    /// [api-modify-elems]: crate::DiagonalOp::modify_elems()
+    pub fn exp_in_place(
+        &mut self,
+        time: Qreal,
+    ) -> Result<(), QuestError> {
+        self.map_in_place(|elem| (elem * Qcomplex::new(0., -time)).exp())
+    }
+
+    /// Creates the operator `exp(-i t D)` from the operator `D`.
+    ///
+    /// For a Hermitian (i.e. real) operator `D`, the result is the unitary
+    /// time evolution generated by `D` for time `t`.
+    ///
+    /// # Errors
+    ///
+    /// See [`map()`][api-map].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let qureg = &mut Qureg::try_new(2, env).unwrap();
+    /// qureg.init_plus_state();
+    ///
+    /// // QAOA cost layer: evolve under the number of ones in the bitstring
+    /// let cost = DiagonalOp::from_fn(2, env, |i| {
+    ///     Qcomplex::new(i.count_ones() as Qreal, 0.)
+    /// })
+    /// .unwrap();
+    /// let step = cost.exp(PI / 2.).unwrap();
+    /// apply_diagonal_op(qureg, &step).unwrap();
+    ///
+    /// let amp = qureg.get_amp(3).unwrap();
+    /// assert!((amp.re + 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [api-map]: crate::DiagonalOp::map()
+    pub fn exp(
+        &self,
+        time: Qreal,
+    ) -> Result<Self, QuestError> {
+        self.map(|elem| (elem * Qcomplex::new(0., -time)).exp())
+    }
+
+    /// Index of the first element stored on this node.
+    fn chunk_start(&self) -> i64 {
+        i64::from(self.op.chunkId) * self.op.numElemsPerChunk
+    }
 }
 
-impl<'a> Drop for DiagonalOp<'a> {
+impl Drop for DiagonalOp {
     fn drop(&mut self) {
         catch_quest_exception(|| unsafe {
-            ffi::destroyDiagonalOp(self.op, self.env.0);
+            ffi::destroyDiagonalOp(self.op, self.env.raw());
         })
         .expect("dropping DiagonalOp should always succeed");
     }
@@ -163,7 +1082,7 @@ pub fn init_pauli_hamil(
 ///
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
-pub fn sync_diagonal_op(op: &mut DiagonalOp<'_>) -> Result<(), QuestError> {
+pub fn sync_diagonal_op(op: &mut DiagonalOp) -> Result<(), QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::syncDiagonalOp(op.op);
     })
@@ -193,7 +1112,7 @@ pub fn sync_diagonal_op(op: &mut DiagonalOp<'_>) -> Result<(), QuestError> {
 #[allow(clippy::cast_sign_loss)]
 #[allow(clippy::needless_pass_by_ref_mut)]
 pub fn init_diagonal_op(
-    op: &mut DiagonalOp<'_>,
+    op: &mut DiagonalOp,
     real: &[Qreal],
     imag: &[Qreal],
 ) -> Result<(), QuestError> {
@@ -235,7 +1154,7 @@ pub fn init_diagonal_op(
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
 pub fn init_diagonal_op_from_pauli_hamil(
-    op: &mut DiagonalOp<'_>,
+    op: &mut DiagonalOp,
     hamil: &PauliHamil,
 ) -> Result<(), QuestError> {
     catch_quest_exception(|| unsafe {
@@ -274,7 +1193,7 @@ pub fn init_diagonal_op_from_pauli_hamil(
 #[allow(clippy::cast_possible_truncation)]
 #[allow(clippy::needless_pass_by_ref_mut)]
 pub fn set_diagonal_op_elems(
-    op: &mut DiagonalOp<'_>,
+    op: &mut DiagonalOp,
     start_ind: i64,
     real: &[Qreal],
     imag: &[Qreal],
@@ -313,9 +1232,9 @@ pub fn set_diagonal_op_elems(
 ///
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
-pub fn apply_diagonal_op(
-    qureg: &mut Qureg<'_>,
-    op: &DiagonalOp<'_>,
+pub fn apply_diagonal_op<K: QuregKind>(
+    qureg: &mut Qureg<K>,
+    op: &DiagonalOp,
 ) -> Result<(), QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::applyDiagonalOp(qureg.reg, op.op);
@@ -347,9 +1266,9 @@ pub fn apply_diagonal_op(
 /// See [QuEST API] for more information.
 ///
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-pub fn calc_expec_diagonal_op(
-    qureg: &Qureg<'_>,
-    op: &DiagonalOp<'_>,
+pub fn calc_expec_diagonal_op<K: QuregKind>(
+    qureg: &Qureg<K>,
+    op: &DiagonalOp,
 ) -> Result<Qcomplex, QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::calcExpecDiagonalOp(qureg.reg, op.op)
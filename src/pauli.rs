@@ -0,0 +1,927 @@
+//! Pauli Hamiltonians in sparse notation, e.g. `0.5 X0 Z1 - 1.2 Y2`, and
+//! their algebra.
+
+use std::{
+    collections::{
+        hash_map::Entry,
+        HashMap,
+    },
+    fmt,
+    ops::{
+        Add,
+        Mul,
+        Neg,
+        Sub,
+    },
+    str::FromStr,
+};
+
+use crate::{
+    init_pauli_hamil,
+    PauliHamil,
+    PauliOpType,
+    Qcomplex,
+    Qreal,
+    QuestError,
+    REAL_EPS,
+};
+
+/// A term of a Hamiltonian: coefficient and (qubit, Pauli operator) pairs.
+type Term = (Qreal, Vec<(i32, PauliOpType)>);
+
+/// Upper bound on qubit indices.  Amplitudes are indexed with 64-bit
+/// integers, so no register has more qubits.
+const MAX_NUM_QUBITS: i32 = 64;
+
+impl PauliHamil {
+    /// Creates a [`PauliHamil`] from a list of terms in sparse notation.
+    ///
+    /// Each term is a pair of a real coefficient and a product of Pauli
+    /// operators, written as a letter `I`, `X`, `Y` or `Z` followed by the
+    /// index of the qubit it acts on, e.g. `"X0 Z3"`.  Qubits not mentioned
+    /// are acted on by the identity, and an empty string denotes the
+    /// identity on all qubits.
+    ///
+    /// The number of qubits is inferred from the largest qubit index.
+    ///
+    /// # Errors
+    ///
+    /// - [`ParseError`], if a Pauli string is incorrectly formatted, mentions a
+    ///   qubit twice, or a qubit index is not less than 64
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use quest_bind::PauliOpType::*;
+    ///
+    /// let hamil =
+    ///     PauliHamil::from_terms(&[(0.5, "X0 Z2"), (-1.2, "Y1")]).unwrap();
+    ///
+    /// assert_eq!(hamil.num_qubits(), 3);
+    /// assert_eq!(hamil.term_coeffs(), &[0.5, -1.2]);
+    /// assert_eq!(
+    ///     hamil.pauli_codes(),
+    ///     &[PAULI_X, PAULI_I, PAULI_Z, PAULI_I, PAULI_Y, PAULI_I]
+    /// );
+    /// ```
+    ///
+    /// [`ParseError`]: crate::QuestError::ParseError
+    pub fn from_terms<S: AsRef<str>>(
+        terms: &[(Qreal, S)]
+    ) -> Result<Self, QuestError> {
+        let terms = terms
+            .iter()
+            .map(|(coeff, ops)| {
+                let mut parser = Parser::new(ops.as_ref());
+                let ops = parser.ops()?;
+                parser.skip_whitespace();
+                if parser.peek().is_some() {
+                    return Err(parser.error("expected Pauli operator"));
+                }
+                Ok((*coeff, ops))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::try_new_from_sparse_terms(&terms)
+    }
+
+    fn try_new_from_sparse_terms(terms: &[Term]) -> Result<Self, QuestError> {
+        let num_qubits = terms
+            .iter()
+            .flat_map(|(_, ops)| ops.iter().map(|&(qubit, _)| qubit))
+            .try_fold(1, |num_qubits, qubit| {
+                qubit.checked_add(1).map(|n| n.max(num_qubits))
+            })
+            .ok_or(QuestError::QubitIndexError)?;
+        let mut coeffs = Vec::with_capacity(terms.len());
+        let mut codes =
+            vec![PauliOpType::PAULI_I; num_qubits as usize * terms.len()];
+        for (term, (coeff, ops)) in terms.iter().enumerate() {
+            coeffs.push(*coeff);
+            for &(qubit, op) in ops {
+                codes[term * num_qubits as usize + qubit as usize] = op;
+            }
+        }
+
+        let mut hamil = Self::try_new(num_qubits, terms.len() as i32)?;
+        init_pauli_hamil(&mut hamil, &coeffs, &codes)?;
+        Ok(hamil)
+    }
+}
+
+/// Parse a Hamiltonian in sparse notation.
+///
+/// The Hamiltonian is written as a sum of terms separated by `+` or `-`.
+/// Each term consists of an optional real coefficient (1 by default)
+/// followed by a product of Pauli operators, as in
+/// [`PauliHamil::from_terms()`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let hamil: PauliHamil = "0.5 X0 Z1 - 1.2 Y2 + Z0 Z2".parse().unwrap();
+///
+/// assert_eq!(hamil.num_qubits(), 3);
+/// assert_eq!(hamil.term_coeffs(), &[0.5, -1.2, 1.]);
+/// ```
+///
+/// [`PauliHamil::from_terms()`]: crate::PauliHamil::from_terms()
+impl FromStr for PauliHamil {
+    type Err = QuestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_new_from_sparse_terms(&Parser::new(s).sum()?)
+    }
+}
+
+/// Print the Hamiltonian in sparse notation.
+///
+/// The output can be parsed back with [`FromStr`], although identity
+/// operators are omitted, so the number of qubits may be smaller.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let hamil: PauliHamil = "0.5 X0 Z1 - 1.2 Y2 + 3".parse().unwrap();
+///
+/// assert_eq!(hamil.to_string(), "0.5 X0 Z1 - 1.2 Y2 + 3");
+/// ```
+impl fmt::Display for PauliHamil {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let num_qubits = self.num_qubits() as usize;
+        let terms = self
+            .term_coeffs()
+            .iter()
+            .zip(self.pauli_codes().chunks(num_qubits));
+        for (i, (&coeff, codes)) in terms.enumerate() {
+            fmt_real_coeff(f, i == 0, coeff)?;
+            fmt_ops(f, (0..).zip(codes.iter().copied()))?;
+        }
+        Ok(())
+    }
+}
+
+fn fmt_real_coeff(
+    f: &mut fmt::Formatter<'_>,
+    first: bool,
+    coeff: Qreal,
+) -> fmt::Result {
+    match (first, coeff.is_sign_negative()) {
+        (true, false) => {}
+        (true, true) => write!(f, "-")?,
+        (false, false) => write!(f, " + ")?,
+        (false, true) => write!(f, " - ")?,
+    }
+    write!(f, "{}", coeff.abs())
+}
+
+fn fmt_ops(
+    f: &mut fmt::Formatter<'_>,
+    ops: impl IntoIterator<Item = (i32, PauliOpType)>,
+) -> fmt::Result {
+    for (qubit, op) in ops {
+        match op {
+            PauliOpType::PAULI_I => {}
+            PauliOpType::PAULI_X => write!(f, " X{qubit}")?,
+            PauliOpType::PAULI_Y => write!(f, " Y{qubit}")?,
+            PauliOpType::PAULI_Z => write!(f, " Z{qubit}")?,
+        }
+    }
+    Ok(())
+}
+
+/// A sum of Pauli strings with complex coefficients.
+///
+/// Unlike [`PauliHamil`], which lives in `QuEST`'s memory, a `PauliSum` is a
+/// plain Rust value supporting arithmetic: addition, subtraction, scalar
+/// multiplication and the operator product, with the phases of products of
+/// Pauli operators tracked exactly, e.g. `X0 * Y0 = i Z0`.  The results of
+/// arithmetic operations are [simplified](PauliSum::simplify).
+///
+/// A Hermitian `PauliSum` (one with real coefficients) converts into a
+/// [`PauliHamil`], to be used with e.g.
+/// [`calc_expec_pauli_hamil()`][api-calc-expec-pauli-hamil] or
+/// [`apply_trotter_circuit()`][api-apply-trotter-circuit].
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let a: PauliSum = "X0 + Z1".parse().unwrap();
+/// let b: PauliSum = "Y0".parse().unwrap();
+///
+/// let c = &a * &b * 2.;
+/// assert_eq!(c.to_string(), "(0+2i) Z0 + 2 Y0 Z1");
+/// assert!(!c.is_hermitian());
+///
+/// let h = &a + &b.commutator(&a) * Qcomplex::new(0., 0.5);
+/// assert!(h.is_hermitian());
+///
+/// let env = QuestEnv::new();
+/// let qureg = &mut Qureg::try_new(2, &env).unwrap();
+/// let workspace = &mut Qureg::try_new(2, &env).unwrap();
+/// qureg.init_zero_state();
+///
+/// let hamil = h.to_pauli_hamil(qureg.num_qubits()).unwrap();
+/// let expec = qureg.calc_expec_pauli_hamil(&hamil, workspace).unwrap();
+/// assert!((expec - 2.).abs() < EPSILON);
+/// ```
+///
+/// [api-calc-expec-pauli-hamil]: crate::Qureg::calc_expec_pauli_hamil()
+/// [api-apply-trotter-circuit]: crate::Qureg::apply_trotter_circuit()
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PauliSum {
+    /// Terms with Pauli strings sorted by qubit, without identities.
+    terms: Vec<(Qcomplex, Vec<(i32, PauliOpType)>)>,
+}
+
+impl PauliSum {
+    /// Creates an empty sum, i.e. the zero operator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a sum with a single term.
+    ///
+    /// The term is the product of the Pauli operators in `ops`, in order,
+    /// given as pairs `(qubit, operator)`.  A qubit may appear more than
+    /// once.
+    ///
+    /// # Errors
+    ///
+    /// - [`QubitIndexError`], if any qubit index is negative, or not less than
+    ///   64
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use quest_bind::PauliOpType::*;
+    ///
+    /// let sum =
+    ///     PauliSum::from_term(0.5, &[(2, PAULI_Z), (0, PAULI_X), (2, PAULI_X)])
+    ///         .unwrap();
+    /// assert_eq!(sum.to_string(), "(0+0.5i) X0 Y2");
+    /// ```
+    ///
+    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
+    pub fn from_term<C: Into<Qcomplex>>(
+        coeff: C,
+        ops: &[(i32, PauliOpType)],
+    ) -> Result<Self, QuestError> {
+        if ops
+            .iter()
+            .any(|&(qubit, _)| !(0..MAX_NUM_QUBITS).contains(&qubit))
+        {
+            return Err(QuestError::QubitIndexError);
+        }
+        let mut coeff = coeff.into();
+        let mut string = Vec::new();
+        for &op in ops {
+            let (phase, product) = mul_strings(&string, &[op]);
+            coeff *= phase;
+            string = product;
+        }
+        Ok(Self {
+            terms: vec![(coeff, string)],
+        })
+    }
+
+    /// Number of qubits the sum acts on non-trivially, i.e. the largest
+    /// qubit index plus one, or zero if there are no Pauli operators.
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.terms
+            .iter()
+            .filter_map(|(_, string)| string.last())
+            .map(|&(qubit, _)| {
+                // Qubit indices are less than `MAX_NUM_QUBITS`
+                qubit.checked_add(1).expect("qubit index should be bounded")
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Number of terms.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns `true` if the sum has no terms.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Iterate over the terms of the sum.
+    ///
+    /// Each term is given as a coefficient and a list of `(qubit, operator)`
+    /// pairs sorted by qubit, without identity operators.
+    pub fn iter(
+        &self
+    ) -> impl Iterator<Item = (Qcomplex, &[(i32, PauliOpType)])> + '_ {
+        self.terms
+            .iter()
+            .map(|(coeff, string)| (*coeff, string.as_slice()))
+    }
+
+    /// Merge terms with equal Pauli strings and drop terms with
+    /// coefficients smaller than [`REAL_EPS`] in absolute value.
+    ///
+    /// The order of the remaining terms is that of their first occurrence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let mut sum: PauliSum = "0.5 X0 Z1 + Y2 + 0.5 Z1 X0 - Y2".parse().unwrap();
+    /// assert_eq!(sum.len(), 4);
+    ///
+    /// sum.simplify();
+    /// assert_eq!(sum.to_string(), "1 X0 Z1");
+    /// ```
+    ///
+    /// [`REAL_EPS`]: crate::REAL_EPS
+    pub fn simplify(&mut self) {
+        let mut index: HashMap<_, usize> = HashMap::new();
+        let mut terms: Vec<(Qcomplex, Vec<_>)> = Vec::new();
+        for (coeff, string) in self.terms.drain(..) {
+            match index.entry(string) {
+                Entry::Occupied(entry) => terms[*entry.get()].0 += coeff,
+                Entry::Vacant(entry) => {
+                    terms.push((coeff, entry.key().clone()));
+                    entry.insert(terms.len() - 1);
+                }
+            }
+        }
+        terms.retain(|(coeff, _)| coeff.norm() >= REAL_EPS);
+        self.terms = terms;
+    }
+
+    /// The commutator `[self, other] = self * other - other * self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let x: PauliSum = "X0".parse().unwrap();
+    /// let y: PauliSum = "Y0".parse().unwrap();
+    ///
+    /// assert_eq!(x.commutator(&y).to_string(), "(0+2i) Z0");
+    /// assert!(x.commutator(&x).is_empty());
+    /// ```
+    #[must_use]
+    pub fn commutator(
+        &self,
+        other: &Self,
+    ) -> Self {
+        &(self * other) - &(other * self)
+    }
+
+    /// Returns `true` if the sum is a Hermitian operator, i.e. if all
+    /// coefficients of the simplified sum are real up to [`REAL_EPS`].
+    ///
+    /// [`REAL_EPS`]: crate::REAL_EPS
+    #[must_use]
+    pub fn is_hermitian(&self) -> bool {
+        let mut sum = self.clone();
+        sum.simplify();
+        sum.terms.iter().all(|(coeff, _)| coeff.im.abs() < REAL_EPS)
+    }
+
+    /// Converts the sum into a [`PauliHamil`] acting on `num_qubits` qubits.
+    ///
+    /// The sum is simplified first.  The zero operator is represented by a
+    /// single identity term with coefficient zero.
+    ///
+    /// # Errors
+    ///
+    /// - [`NonHermitianError`], if the sum is not Hermitian (see
+    ///   [`is_hermitian()`][api-is-hermitian])
+    /// - [`QubitIndexError`], if the sum acts on more than `num_qubits` qubits
+    /// - [`InvalidQuESTInputError`], if the Hamiltonian cannot be created, e.g.
+    ///   if `num_qubits` is not positive
+    ///
+    /// [api-is-hermitian]: crate::PauliSum::is_hermitian()
+    /// [`NonHermitianError`]: crate::QuestError::NonHermitianError
+    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn to_pauli_hamil(
+        &self,
+        num_qubits: i32,
+    ) -> Result<PauliHamil, QuestError> {
+        let mut sum = self.clone();
+        sum.simplify();
+        if sum
+            .terms
+            .iter()
+            .any(|(coeff, _)| coeff.im.abs() >= REAL_EPS)
+        {
+            return Err(QuestError::NonHermitianError);
+        }
+        if sum.num_qubits() > num_qubits {
+            return Err(QuestError::QubitIndexError);
+        }
+
+        let mut hamil =
+            PauliHamil::try_new(num_qubits, sum.len().max(1) as i32)?;
+        let coeffs = if sum.is_empty() {
+            vec![0.]
+        } else {
+            sum.terms.iter().map(|(coeff, _)| coeff.re).collect()
+        };
+        let mut codes = vec![PauliOpType::PAULI_I; hamil.pauli_codes().len()];
+        for (term, (_, string)) in sum.terms.iter().enumerate() {
+            for &(qubit, op) in string {
+                codes[term * num_qubits as usize + qubit as usize] = op;
+            }
+        }
+        init_pauli_hamil(&mut hamil, &coeffs, &codes)?;
+        Ok(hamil)
+    }
+}
+
+/// Multiply two Pauli strings sorted by qubit.
+///
+/// Returns the phase and the product string.
+fn mul_strings(
+    lhs: &[(i32, PauliOpType)],
+    rhs: &[(i32, PauliOpType)],
+) -> (Qcomplex, Vec<(i32, PauliOpType)>) {
+    // Number of factors of `i` in the phase
+    let mut phase = 0;
+    let mut product = Vec::with_capacity(lhs.len() + rhs.len());
+    let (mut lhs, mut rhs) = (lhs.iter().peekable(), rhs.iter().peekable());
+    loop {
+        let next = match (lhs.peek(), rhs.peek()) {
+            (Some(&&(q, a)), Some(&&(r, b))) if q == r => {
+                lhs.next();
+                rhs.next();
+                let (a, b) = (a as i32, b as i32);
+                if a == b {
+                    continue;
+                }
+                if a == 0 || b == 0 {
+                    (q, PAULI_OPS[(a + b) as usize])
+                } else {
+                    // X Y = i Z, Y Z = i X, Z X = i Y and their reverses
+                    phase += if (b - a).rem_euclid(3) == 1 { 1 } else { 3 };
+                    (q, PAULI_OPS[(6 - a - b) as usize])
+                }
+            }
+            (Some(&&(q, a)), Some(&&(r, _))) if q < r => {
+                lhs.next();
+                (q, a)
+            }
+            (_, Some(_)) => *rhs.next().expect("rhs is not empty"),
+            (Some(_), None) => *lhs.next().expect("lhs is not empty"),
+            (None, None) => break,
+        };
+        if next.1 != PauliOpType::PAULI_I {
+            product.push(next);
+        }
+    }
+    let phase = match phase % 4 {
+        0 => Qcomplex::new(1., 0.),
+        1 => Qcomplex::new(0., 1.),
+        2 => Qcomplex::new(-1., 0.),
+        _ => Qcomplex::new(0., -1.),
+    };
+    (phase, product)
+}
+
+const PAULI_OPS: [PauliOpType; 4] = [
+    PauliOpType::PAULI_I,
+    PauliOpType::PAULI_X,
+    PauliOpType::PAULI_Y,
+    PauliOpType::PAULI_Z,
+];
+
+impl From<&PauliHamil> for PauliSum {
+    fn from(hamil: &PauliHamil) -> Self {
+        let num_qubits = hamil.num_qubits() as usize;
+        let terms = hamil
+            .term_coeffs()
+            .iter()
+            .zip(hamil.pauli_codes().chunks(num_qubits))
+            .map(|(&coeff, codes)| {
+                let string = (0..)
+                    .zip(codes.iter().copied())
+                    .filter(|&(_, op)| op != PauliOpType::PAULI_I)
+                    .collect();
+                (coeff.into(), string)
+            })
+            .collect();
+        Self {
+            terms,
+        }
+    }
+}
+
+/// Converts the sum into a [`PauliHamil`] acting on
+/// [`num_qubits()`][api-num-qubits] qubits, or on one qubit if the sum is
+/// proportional to the identity.
+///
+/// See [`PauliSum::to_pauli_hamil()`][api-to-pauli-hamil].
+///
+/// [api-num-qubits]: crate::PauliSum::num_qubits()
+/// [api-to-pauli-hamil]: crate::PauliSum::to_pauli_hamil()
+impl TryFrom<&PauliSum> for PauliHamil {
+    type Error = QuestError;
+
+    fn try_from(sum: &PauliSum) -> Result<Self, Self::Error> {
+        sum.to_pauli_hamil(sum.num_qubits().max(1))
+    }
+}
+
+/// Parse a sum in sparse notation, as for [`PauliHamil`].
+///
+/// The terms are not simplified.
+impl FromStr for PauliSum {
+    type Err = QuestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = Parser::new(s).sum()?;
+        Ok(Self {
+            terms: terms
+                .into_iter()
+                .map(|(coeff, mut string)| {
+                    string.retain(|&(_, op)| op != PauliOpType::PAULI_I);
+                    string.sort_by_key(|&(qubit, _)| qubit);
+                    (coeff.into(), string)
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Print the sum in sparse notation.
+///
+/// Real coefficients are printed as for [`PauliHamil`], and complex ones in
+/// parentheses, e.g. `(0.5+1i) X0`.  The empty sum is printed as `0`.
+impl fmt::Display for PauliSum {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, (coeff, string)) in self.terms.iter().enumerate() {
+            if coeff.im == 0. {
+                fmt_real_coeff(f, i == 0, coeff.re)?;
+            } else {
+                if i > 0 {
+                    write!(f, " + ")?;
+                }
+                write!(f, "({coeff})")?;
+            }
+            fmt_ops(f, string.iter().copied())?;
+        }
+        Ok(())
+    }
+}
+
+impl Neg for PauliSum {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        for (coeff, _) in &mut self.terms {
+            *coeff = -*coeff;
+        }
+        self
+    }
+}
+
+impl Add for &PauliSum {
+    type Output = PauliSum;
+
+    fn add(
+        self,
+        rhs: Self,
+    ) -> PauliSum {
+        let mut sum = PauliSum {
+            terms: self.terms.iter().chain(&rhs.terms).cloned().collect(),
+        };
+        sum.simplify();
+        sum
+    }
+}
+
+impl Sub for &PauliSum {
+    type Output = PauliSum;
+
+    fn sub(
+        self,
+        rhs: Self,
+    ) -> PauliSum {
+        self + &-rhs.clone()
+    }
+}
+
+impl Mul for &PauliSum {
+    type Output = PauliSum;
+
+    fn mul(
+        self,
+        rhs: Self,
+    ) -> PauliSum {
+        let mut sum = PauliSum {
+            terms: Vec::with_capacity(self.len() * rhs.len()),
+        };
+        for (a, lhs_string) in &self.terms {
+            for (b, rhs_string) in &rhs.terms {
+                let (phase, string) = mul_strings(lhs_string, rhs_string);
+                sum.terms.push((a * b * phase, string));
+            }
+        }
+        sum.simplify();
+        sum
+    }
+}
+
+impl Mul<Qcomplex> for &PauliSum {
+    type Output = PauliSum;
+
+    fn mul(
+        self,
+        rhs: Qcomplex,
+    ) -> PauliSum {
+        let mut sum = PauliSum {
+            terms: self
+                .terms
+                .iter()
+                .map(|(coeff, string)| (coeff * rhs, string.clone()))
+                .collect(),
+        };
+        sum.simplify();
+        sum
+    }
+}
+
+impl Mul<Qreal> for &PauliSum {
+    type Output = PauliSum;
+
+    fn mul(
+        self,
+        rhs: Qreal,
+    ) -> PauliSum {
+        self * Qcomplex::from(rhs)
+    }
+}
+
+macro_rules! forward_binop {
+    ($Trait:ident, $method:ident, $Rhs:ty) => {
+        impl $Trait<$Rhs> for PauliSum {
+            type Output = PauliSum;
+
+            fn $method(
+                self,
+                rhs: $Rhs,
+            ) -> PauliSum {
+                (&self).$method(rhs)
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add, &PauliSum);
+forward_binop!(Sub, sub, &PauliSum);
+forward_binop!(Mul, mul, &PauliSum);
+forward_binop!(Mul, mul, Qcomplex);
+forward_binop!(Mul, mul, Qreal);
+
+macro_rules! forward_ref_binop {
+    ($Trait:ident, $method:ident) => {
+        impl $Trait<PauliSum> for &PauliSum {
+            type Output = PauliSum;
+
+            fn $method(
+                self,
+                rhs: PauliSum,
+            ) -> PauliSum {
+                self.$method(&rhs)
+            }
+        }
+    };
+}
+
+forward_ref_binop!(Add, add);
+forward_ref_binop!(Sub, sub);
+forward_ref_binop!(Mul, mul);
+
+impl Add for PauliSum {
+    type Output = Self;
+
+    fn add(
+        self,
+        rhs: Self,
+    ) -> Self {
+        &self + &rhs
+    }
+}
+
+impl Sub for PauliSum {
+    type Output = Self;
+
+    fn sub(
+        self,
+        rhs: Self,
+    ) -> Self {
+        &self - &rhs
+    }
+}
+
+impl Mul for PauliSum {
+    type Output = Self;
+
+    fn mul(
+        self,
+        rhs: Self,
+    ) -> Self {
+        &self * &rhs
+    }
+}
+
+impl Mul<PauliSum> for Qreal {
+    type Output = PauliSum;
+
+    fn mul(
+        self,
+        rhs: PauliSum,
+    ) -> PauliSum {
+        &rhs * self
+    }
+}
+
+impl Mul<PauliSum> for Qcomplex {
+    type Output = PauliSum;
+
+    fn mul(
+        self,
+        rhs: PauliSum,
+    ) -> PauliSum {
+        &rhs * self
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn peek_at(
+        &self,
+        offset: usize,
+    ) -> Option<u8> {
+        self.src.as_bytes().get(self.pos + offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(
+        &self,
+        msg: impl Into<String>,
+    ) -> QuestError {
+        self.error_at(self.pos, msg)
+    }
+
+    fn error_at(
+        &self,
+        pos: usize,
+        msg: impl Into<String>,
+    ) -> QuestError {
+        let before = &self.src[..pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        QuestError::ParseError {
+            line:   before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            msg:    msg.into(),
+        }
+    }
+
+    /// Parse a sum of terms, e.g. `0.5 X0 Z1 - 1.2 Y2`.
+    fn sum(&mut self) -> Result<Vec<Term>, QuestError> {
+        let mut terms = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return Err(self.error("expected at least one term"));
+        }
+        while self.peek().is_some() {
+            let sign = match self.peek() {
+                Some(b'+') => 1.,
+                Some(b'-') => -1.,
+                _ if terms.is_empty() => 1.,
+                _ => return Err(self.error("expected `+` or `-`")),
+            };
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            self.skip_whitespace();
+
+            let coeff = self.coeff()?;
+            let ops = self.ops()?;
+            if coeff.is_none() && ops.is_empty() {
+                return Err(
+                    self.error("expected coefficient or Pauli operator")
+                );
+            }
+            terms.push((sign * coeff.unwrap_or(1.), ops));
+            self.skip_whitespace();
+        }
+        Ok(terms)
+    }
+
+    /// Parse an optional coefficient, e.g. `0.5` or `1e-3`.
+    fn coeff(&mut self) -> Result<Option<Qreal>, QuestError> {
+        if !self.peek().is_some_and(|c| c.is_ascii_digit() || c == b'.') {
+            return Ok(None);
+        }
+        let start = self.pos;
+        self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.skip_digits();
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let sign =
+                usize::from(matches!(self.peek_at(1), Some(b'+' | b'-')));
+            if self.peek_at(1 + sign).is_some_and(|c| c.is_ascii_digit()) {
+                self.pos += 1 + sign;
+                self.skip_digits();
+            }
+        }
+        let number = &self.src[start..self.pos];
+        number.parse().map(Some).map_err(|_| {
+            self.error_at(start, format!("invalid number `{number}`"))
+        })
+    }
+
+    /// Parse a (possibly empty) product of Pauli operators, e.g. `X0 Z1`.
+    fn ops(&mut self) -> Result<Vec<(i32, PauliOpType)>, QuestError> {
+        let mut ops = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek() {
+                Some(b'I') => PauliOpType::PAULI_I,
+                Some(b'X') => PauliOpType::PAULI_X,
+                Some(b'Y') => PauliOpType::PAULI_Y,
+                Some(b'Z') => PauliOpType::PAULI_Z,
+                _ => return Ok(ops),
+            };
+            let start = self.pos;
+            self.pos += 1;
+            self.skip_digits();
+            let index = &self.src[start + 1..self.pos];
+            if index.is_empty() {
+                return Err(self.error("expected qubit index"));
+            }
+            let qubit = index
+                .parse::<i32>()
+                .ok()
+                .filter(|&qubit| qubit < MAX_NUM_QUBITS)
+                .ok_or_else(|| {
+                    self.error_at(
+                        start + 1,
+                        format!("invalid qubit index `{index}`"),
+                    )
+                })?;
+            if ops.iter().any(|&(q, _)| q == qubit) {
+                return Err(
+                    self.error_at(start, format!("repeated qubit {qubit}"))
+                );
+            }
+            ops.push((qubit, op));
+        }
+    }
+}
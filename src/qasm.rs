@@ -0,0 +1,1386 @@
+//! Import quantum circuits written in `OpenQASM` 2.0.
+//!
+//! The parser supports the full `OpenQASM` 2.0 language: register
+//! declarations, the standard gate library `qelib1.inc`, user-defined
+//! `gate` blocks, `opaque` declarations, `measure`, `reset`, `barrier` and
+//! classically controlled operations with `if`.
+//!
+//! Gate definitions are expanded when the program is parsed. Gates from
+//! `qelib1.inc` are mapped directly onto the corresponding methods of
+//! [`Qureg`], e.g. `h` onto [`Qureg::hadamard()`], `cx` onto
+//! [`Qureg::controlled_not()`] and `u3` onto [`Qureg::compact_unitary()`].
+//! Since `OpenQASM` 2.0 has no way of controlling a user-defined gate, the
+//! gates are implemented only up to a global phase.
+//!
+//! See the [`OpenQASM` 2.0 specification][spec].
+//!
+//! [spec]: https://arxiv.org/abs/1707.03429
+
+use std::{
+    collections::HashMap,
+    ops::Range,
+    str::FromStr,
+};
+
+use crate::{
+    circuit::Operation,
+    ComplexMatrix2,
+    ComplexMatrix4,
+    PauliOpType,
+    Qcomplex,
+    Qreal,
+    QuestError,
+    Qureg,
+    QuregKind,
+};
+
+/// Gates from `qelib1.inc` that are not mapped onto a single `QuEST` call,
+/// but defined in terms of other gates, exactly as in `qelib1.inc`.
+const QELIB1_DEFS: &str = "
+gate rccx a,b,c {
+    u2(0,pi) c; u1(pi/4) c; cx b,c; u1(-pi/4) c; cx a,c;
+    u1(pi/4) c; cx b,c; u1(-pi/4) c; u2(0,pi) c;
+}
+gate rc3x a,b,c,d {
+    u2(0,pi) d; u1(pi/4) d; cx c,d; u1(-pi/4) d; u2(0,pi) d;
+    cx a,d; u1(pi/4) d; cx b,d; u1(-pi/4) d; cx a,d;
+    u1(pi/4) d; cx b,d; u1(-pi/4) d; u2(0,pi) d; u1(pi/4) d;
+    cx c,d; u1(-pi/4) d; u2(0,pi) d;
+}
+";
+
+/// Number of parameters and qubit arguments of a built-in gate.
+///
+/// The gates `U` and `CX` are always available. The remaining gates require
+/// `include \"qelib1.inc\";`
+fn builtin_gate(
+    name: &str,
+    qelib1: bool,
+) -> Option<(usize, usize)> {
+    match name {
+        "U" => Some((3, 1)),
+        "CX" => Some((0, 2)),
+        _ if !qelib1 => None,
+        "u3" | "u" => Some((3, 1)),
+        "u2" => Some((2, 1)),
+        "u1" | "p" | "u0" | "rx" | "ry" | "rz" => Some((1, 1)),
+        "id" | "x" | "y" | "z" | "h" | "s" | "sdg" | "t" | "tdg" | "sx"
+        | "sxdg" => Some((0, 1)),
+        "cx" | "cy" | "cz" | "ch" | "swap" | "csx" => Some((0, 2)),
+        "crx" | "cry" | "crz" | "cu1" | "cp" | "rxx" | "rzz" => Some((1, 2)),
+        "cu3" => Some((3, 2)),
+        "cu" => Some((4, 2)),
+        "ccx" | "cswap" => Some((0, 3)),
+        "c3x" | "c3sqrtx" => Some((0, 4)),
+        "c4x" => Some((0, 5)),
+        _ => None,
+    }
+}
+
+/// Matrix of the gate `U(theta, phi, lambda)`, multiplied by `exp(i gamma)`.
+fn u_matrix(
+    theta: Qreal,
+    phi: Qreal,
+    lambda: Qreal,
+    gamma: Qreal,
+) -> ComplexMatrix2 {
+    let (sin, cos) = (theta / 2.).sin_cos();
+    let elems = [
+        Qcomplex::cis(gamma) * cos,
+        -Qcomplex::cis(gamma + lambda) * sin,
+        Qcomplex::cis(gamma + phi) * sin,
+        Qcomplex::cis(gamma + phi + lambda) * cos,
+    ];
+    ComplexMatrix2::new(
+        [[elems[0].re, elems[1].re], [elems[2].re, elems[3].re]],
+        [[elems[0].im, elems[1].im], [elems[2].im, elems[3].im]],
+    )
+}
+
+/// Matrix of the square root of Pauli X gate, or its inverse.
+fn sx_matrix(dagger: bool) -> ComplexMatrix2 {
+    let s = if dagger { -0.5 } else { 0.5 };
+    ComplexMatrix2::new([[0.5, 0.5], [0.5, 0.5]], [[s, -s], [-s, s]])
+}
+
+/// Append operations implementing a built-in gate.
+///
+/// The number of parameters and qubits must agree with [`builtin_gate()`].
+#[allow(clippy::too_many_lines)]
+fn builtin_ops(
+    name: &str,
+    p: &[Qreal],
+    q: &[i32],
+    out: &mut Vec<Instruction>,
+) {
+    let mut push = |op| out.push(Instruction::Op(Box::new(op)));
+    let compact = |target_qubit, theta: Qreal, phi: Qreal, lambda: Qreal| {
+        // U(theta, phi, lambda) up to the global phase: (phi + lambda)/2
+        Operation::CompactUnitary {
+            target_qubit,
+            alpha: Qcomplex::from_polar(
+                (theta / 2.).cos(),
+                -(phi + lambda) / 2.,
+            ),
+            beta: Qcomplex::from_polar((theta / 2.).sin(), (phi - lambda) / 2.),
+        }
+    };
+
+    match name {
+        "U" | "u3" | "u" => push(compact(q[0], p[0], p[1], p[2])),
+        "u2" => push(compact(q[0], crate::PI / 2., p[0], p[1])),
+        "u1" | "p" => push(Operation::PhaseShift {
+            target_qubit: q[0],
+            angle:        p[0],
+        }),
+        "id" | "u0" => {}
+        "x" => push(Operation::PauliX {
+            target_qubit: q[0]
+        }),
+        "y" => push(Operation::PauliY {
+            target_qubit: q[0]
+        }),
+        "z" => push(Operation::PauliZ {
+            target_qubit: q[0]
+        }),
+        "h" => push(Operation::Hadamard {
+            target_qubit: q[0]
+        }),
+        "s" => push(Operation::SGate {
+            target_qubit: q[0]
+        }),
+        "sdg" => push(Operation::PhaseShift {
+            target_qubit: q[0],
+            angle:        -crate::PI / 2.,
+        }),
+        "t" => push(Operation::TGate {
+            target_qubit: q[0]
+        }),
+        "tdg" => push(Operation::PhaseShift {
+            target_qubit: q[0],
+            angle:        -crate::PI / 4.,
+        }),
+        "rx" => push(Operation::RotateX {
+            rot_qubit: q[0],
+            angle:     p[0],
+        }),
+        "ry" => push(Operation::RotateY {
+            rot_qubit: q[0],
+            angle:     p[0],
+        }),
+        "rz" => push(Operation::RotateZ {
+            rot_qubit: q[0],
+            angle:     p[0],
+        }),
+        "sx" | "sxdg" => push(Operation::Unitary {
+            target_qubit: q[0],
+            u:            sx_matrix(name == "sxdg"),
+        }),
+        "CX" | "cx" => push(Operation::ControlledNot {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+        }),
+        "cy" => push(Operation::ControlledPauliY {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+        }),
+        "cz" => push(Operation::ControlledPhaseFlip {
+            id_qubit1: q[0],
+            id_qubit2: q[1],
+        }),
+        "ch" => {
+            let r = std::f64::consts::FRAC_1_SQRT_2 as Qreal;
+            push(Operation::ControlledUnitary {
+                control_qubit: q[0],
+                target_qubit:  q[1],
+                u:             ComplexMatrix2::new(
+                    [[r, r], [r, -r]],
+                    [[0., 0.], [0., 0.]],
+                ),
+            });
+        }
+        "swap" => push(Operation::SwapGate {
+            qubit1: q[0],
+            qubit2: q[1],
+        }),
+        "csx" => push(Operation::ControlledUnitary {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+            u:             sx_matrix(false),
+        }),
+        "crx" => push(Operation::ControlledRotateX {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+            angle:         p[0],
+        }),
+        "cry" => push(Operation::ControlledRotateY {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+            angle:         p[0],
+        }),
+        "crz" => push(Operation::ControlledRotateZ {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+            angle:         p[0],
+        }),
+        "cu1" | "cp" => push(Operation::ControlledPhaseShift {
+            id_qubit1: q[0],
+            id_qubit2: q[1],
+            angle:     p[0],
+        }),
+        "cu3" => push(Operation::ControlledUnitary {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+            u:             u_matrix(p[0], p[1], p[2], 0.),
+        }),
+        "cu" => push(Operation::ControlledUnitary {
+            control_qubit: q[0],
+            target_qubit:  q[1],
+            u:             u_matrix(p[0], p[1], p[2], p[3]),
+        }),
+        "rxx" => push(Operation::MultiRotatePauli {
+            target_qubits: q.to_vec(),
+            target_paulis: vec![PauliOpType::PAULI_X, PauliOpType::PAULI_X],
+            angle:         p[0],
+        }),
+        "rzz" => push(Operation::MultiRotateZ {
+            qubits: q.to_vec(),
+            angle:  p[0],
+        }),
+        "ccx" | "c3x" | "c4x" => {
+            push(Operation::MultiControlledMultiQubitNot {
+                ctrls: q[..q.len() - 1].to_vec(),
+                targs: vec![q[q.len() - 1]],
+            })
+        }
+        "cswap" => {
+            let mut real = [[0.; 4]; 4];
+            real[0][0] = 1.;
+            real[1][2] = 1.;
+            real[2][1] = 1.;
+            real[3][3] = 1.;
+            push(Operation::ControlledTwoQubitUnitary {
+                control_qubit: q[0],
+                target_qubit1: q[1],
+                target_qubit2: q[2],
+                u:             ComplexMatrix4::new(real, [[0.; 4]; 4]),
+            });
+        }
+        "c3sqrtx" => push(Operation::MultiControlledUnitary {
+            control_qubits: q[..3].to_vec(),
+            target_qubit:   q[3],
+            u:              sx_matrix(false),
+        }),
+        _ => unreachable!("unknown built-in gate: {name}"),
+    }
+}
+
+/// Expanded `OpenQASM` statement.
+#[derive(Debug)]
+enum Instruction {
+    Op(Box<Operation>),
+    Measure {
+        qubit: i32,
+        clbit: usize,
+    },
+    Reset {
+        qubit: i32,
+    },
+    If {
+        clbits: Range<usize>,
+        value:  u64,
+        body:   Vec<Instruction>,
+    },
+}
+
+/// Classical register declared in an `OpenQASM` program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassicalRegister {
+    /// Name of the register
+    pub name: String,
+    /// Values of the bits in the register: `0` or `1`
+    pub bits: Vec<i32>,
+}
+
+impl ClassicalRegister {
+    /// Value of the register, as an unsigned integer.
+    ///
+    /// The bit `bits[0]` is the least significant one, as in `OpenQASM`
+    /// `if` statements.
+    #[must_use]
+    pub fn value(&self) -> u64 {
+        self.bits
+            .iter()
+            .rev()
+            .fold(0, |acc, &b| (acc << 1) | u64::from(b != 0))
+    }
+}
+
+/// `OpenQASM` 2.0 program, parsed and ready to run on a [`Qureg`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let program = QasmProgram::parse(
+///     r#"
+///     OPENQASM 2.0;
+///     include "qelib1.inc";
+///
+///     qreg q[2];
+///     creg c[2];
+///
+///     h q[0];
+///     cx q[0], q[1];
+///     measure q -> c;
+///     "#,
+/// )
+/// .unwrap();
+///
+/// let env = &QuestEnv::new();
+/// let qureg = &mut Qureg::try_new(program.num_qubits(), env).unwrap();
+/// qureg.init_zero_state();
+///
+/// let cregs = program.run(qureg).unwrap();
+/// assert_eq!(cregs[0].name, "c");
+/// assert_eq!(cregs[0].bits[0], cregs[0].bits[1]);
+/// ```
+#[derive(Debug)]
+pub struct QasmProgram {
+    num_qubits:   i32,
+    cregs:        Vec<(String, Range<usize>)>,
+    instructions: Vec<Instruction>,
+}
+
+impl QasmProgram {
+    /// Parse an `OpenQASM` 2.0 program.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError`] with the line and column of the offending token,
+    /// if the program is not valid `OpenQASM` 2.0.  This includes semantic
+    /// errors, like undeclared registers, unknown gates or qubit indices out
+    /// of range.
+    ///
+    /// [`ParseError`]: crate::QuestError::ParseError
+    pub fn parse(src: &str) -> Result<Self, QuestError> {
+        let tokens = lex(src)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos:    0,
+            state:  State::default(),
+        };
+        parser.program()?;
+
+        let state = parser.state;
+        let mut cregs = state
+            .cregs
+            .into_iter()
+            .map(|(name, (offset, size))| (name, offset..offset + size))
+            .collect::<Vec<_>>();
+        cregs.sort_by_key(|(_, range)| range.start);
+
+        Ok(Self {
+            num_qubits: state.num_qubits,
+            cregs,
+            instructions: state.instructions,
+        })
+    }
+
+    /// Total number of qubits in all quantum registers of the program.
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.num_qubits
+    }
+
+    /// Total number of bits in all classical registers of the program.
+    #[must_use]
+    pub fn num_clbits(&self) -> usize {
+        self.cregs.last().map_or(0, |(_, range)| range.end)
+    }
+
+    /// Run the program on `qureg`.
+    ///
+    /// Quantum registers are laid out in `qureg` one after another, in the
+    /// order of their declaration.  Classical registers are initialized to
+    /// zero.  The state of `qureg` is not initialized.
+    ///
+    /// The operation `reset` is implemented as a measurement, followed by a
+    /// Pauli X gate, if the outcome was `1`.
+    ///
+    /// Returns classical registers, in the order of their declaration.
+    ///
+    /// # Errors
+    ///
+    /// - [`QubitIndexError`], if `qureg` has fewer qubits than [`num_qubits()`]
+    /// - errors returned by [`Operation::apply()`]
+    ///
+    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
+    /// [`num_qubits()`]: crate::QasmProgram::num_qubits()
+    pub fn run<K: QuregKind>(
+        &self,
+        qureg: &mut Qureg<K>,
+    ) -> Result<Vec<ClassicalRegister>, QuestError> {
+        if qureg.num_qubits() < self.num_qubits {
+            return Err(QuestError::QubitIndexError);
+        }
+
+        let mut clbits = vec![0; self.num_clbits()];
+        run_instructions(&self.instructions, qureg, &mut clbits)?;
+
+        Ok(self
+            .cregs
+            .iter()
+            .map(|(name, range)| ClassicalRegister {
+                name: name.clone(),
+                bits: clbits[range.clone()].to_vec(),
+            })
+            .collect())
+    }
+}
+
+impl FromStr for QasmProgram {
+    type Err = QuestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn run_instructions<K: QuregKind>(
+    instructions: &[Instruction],
+    qureg: &mut Qureg<K>,
+    clbits: &mut [i32],
+) -> Result<(), QuestError> {
+    for instr in instructions {
+        match instr {
+            Instruction::Op(op) => {
+                op.apply(qureg)?;
+            }
+            Instruction::Measure {
+                qubit,
+                clbit,
+            } => clbits[*clbit] = qureg.measure(*qubit)?,
+            Instruction::Reset {
+                qubit,
+            } => {
+                if qureg.measure(*qubit)? == 1 {
+                    qureg.pauli_x(*qubit)?;
+                }
+            }
+            Instruction::If {
+                clbits: range,
+                value,
+                body,
+            } => {
+                let reg = ClassicalRegister {
+                    name: String::new(),
+                    bits: clbits[range.clone()].to_vec(),
+                };
+                if reg.value() == *value {
+                    run_instructions(body, qureg, clbits)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Int(u64),
+    Real(f64),
+    Str(String),
+    Sym(&'static str),
+    Eof,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok:    Tok,
+    line:   usize,
+    column: usize,
+}
+
+impl Token {
+    fn error(
+        &self,
+        msg: impl Into<String>,
+    ) -> QuestError {
+        QuestError::ParseError {
+            line:   self.line,
+            column: self.column,
+            msg:    msg.into(),
+        }
+    }
+}
+
+const SYMBOLS: &[&str] = &[
+    "->", "==", ";", ",", "(", ")", "[", "]", "{", "}", "+", "-", "*", "/", "^",
+];
+
+fn lex(src: &str) -> Result<Vec<Token>, QuestError> {
+    let mut tokens = Vec::new();
+
+    for (line_no, line) in src.lines().enumerate() {
+        let line = line.split("//").next().unwrap_or_default();
+        let chars = line.char_indices().collect::<Vec<_>>();
+        let mut i = 0;
+        while i < chars.len() {
+            let (start, c) = chars[i];
+            let pos = |tok| Token {
+                tok,
+                line: line_no + 1,
+                column: line[..start].chars().count() + 1,
+            };
+            let take_while = |i: &mut usize, f: &dyn Fn(char) -> bool| {
+                while *i < chars.len() && f(chars[*i].1) {
+                    *i += 1;
+                }
+                chars.get(*i).map_or(line.len(), |&(j, _)| j)
+            };
+
+            if c.is_whitespace() {
+                i += 1;
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let end = take_while(&mut i, &|c| {
+                    c.is_ascii_alphanumeric() || c == '_'
+                });
+                tokens.push(pos(Tok::Ident(line[start..end].to_owned())));
+            } else if c.is_ascii_digit() || c == '.' {
+                let mut end =
+                    take_while(&mut i, &|c| c.is_ascii_digit() || c == '.');
+                if i < chars.len() && matches!(chars[i].1, 'e' | 'E') {
+                    i += 1;
+                    if i < chars.len() && matches!(chars[i].1, '+' | '-') {
+                        i += 1;
+                    }
+                    end = take_while(&mut i, &|c| c.is_ascii_digit());
+                }
+                let text = &line[start..end];
+                let tok = if let Ok(n) = text.parse::<u64>() {
+                    Tok::Int(n)
+                } else {
+                    Tok::Real(text.parse::<f64>().map_err(|_| {
+                        pos(Tok::Eof).error(format!("invalid number: {text}"))
+                    })?)
+                };
+                tokens.push(pos(tok));
+            } else if c == '"' {
+                i += 1;
+                let end = take_while(&mut i, &|c| c != '"');
+                if i == chars.len() {
+                    return Err(pos(Tok::Eof).error("unterminated string"));
+                }
+                i += 1;
+                tokens.push(pos(Tok::Str(line[start + 1..end].to_owned())));
+            } else if let Some(sym) =
+                SYMBOLS.iter().find(|s| line[start..].starts_with(**s))
+            {
+                i += sym.len();
+                tokens.push(pos(Tok::Sym(sym)));
+            } else {
+                return Err(
+                    pos(Tok::Eof).error(format!("unexpected character: {c}"))
+                );
+            }
+        }
+    }
+
+    let line = src.lines().count().max(1);
+    let column = src.lines().last().map_or(0, |l| l.chars().count()) + 1;
+    tokens.push(Token {
+        tok: Tok::Eof,
+        line,
+        column,
+    });
+    Ok(tokens)
+}
+
+/// Real-valued expression in a gate body.
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(f64),
+    Param(usize),
+    Neg(Box<Expr>),
+    Bin(&'static str, Box<Expr>, Box<Expr>),
+    Call(String, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(
+        &self,
+        params: &[f64],
+    ) -> f64 {
+        match self {
+            Self::Num(x) => *x,
+            Self::Param(i) => params[*i],
+            Self::Neg(e) => -e.eval(params),
+            Self::Bin(op, a, b) => {
+                let (a, b) = (a.eval(params), b.eval(params));
+                match *op {
+                    "+" => a + b,
+                    "-" => a - b,
+                    "*" => a * b,
+                    "/" => a / b,
+                    _ => a.powf(b),
+                }
+            }
+            Self::Call(f, e) => {
+                let x = e.eval(params);
+                match f.as_str() {
+                    "sin" => x.sin(),
+                    "cos" => x.cos(),
+                    "tan" => x.tan(),
+                    "exp" => x.exp(),
+                    "ln" => x.ln(),
+                    _ => x.sqrt(),
+                }
+            }
+        }
+    }
+}
+
+/// Gate call inside a gate body, with qubit arguments given as indices into
+/// the gate's argument list.
+#[derive(Debug, Clone)]
+struct GateCall {
+    token:  Token,
+    name:   String,
+    params: Vec<Expr>,
+    args:   Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum GateDef {
+    Opaque {
+        num_params: usize,
+        num_args:   usize,
+    },
+    Defined {
+        num_params: usize,
+        num_args:   usize,
+        body:       Vec<GateCall>,
+    },
+}
+
+#[derive(Debug, Default)]
+struct State {
+    qelib1:       bool,
+    num_qubits:   i32,
+    qregs:        HashMap<String, (i32, i32)>,
+    cregs:        HashMap<String, (usize, usize)>,
+    gates:        HashMap<String, GateDef>,
+    instructions: Vec<Instruction>,
+}
+
+impl State {
+    fn gate_signature(
+        &self,
+        name: &str,
+    ) -> Option<(usize, usize)> {
+        builtin_gate(name, self.qelib1).or_else(|| {
+            self.gates.get(name).map(|def| match def {
+                GateDef::Opaque {
+                    num_params,
+                    num_args,
+                }
+                | GateDef::Defined {
+                    num_params,
+                    num_args,
+                    ..
+                } => (*num_params, *num_args),
+            })
+        })
+    }
+
+    /// Expand a gate call into operations.
+    fn expand(
+        &self,
+        token: &Token,
+        name: &str,
+        params: &[f64],
+        qubits: &[i32],
+        out: &mut Vec<Instruction>,
+    ) -> Result<(), QuestError> {
+        if builtin_gate(name, self.qelib1).is_some() {
+            let params = params.iter().map(|&x| x as Qreal).collect::<Vec<_>>();
+            builtin_ops(name, &params, qubits, out);
+            return Ok(());
+        }
+        match self.gates.get(name) {
+            Some(GateDef::Defined {
+                body, ..
+            }) => {
+                for call in body {
+                    let call_params = call
+                        .params
+                        .iter()
+                        .map(|e| e.eval(params))
+                        .collect::<Vec<_>>();
+                    let call_qubits = call
+                        .args
+                        .iter()
+                        .map(|&a| qubits[a])
+                        .collect::<Vec<_>>();
+                    self.expand(
+                        &call.token,
+                        &call.name,
+                        &call_params,
+                        &call_qubits,
+                        out,
+                    )?;
+                }
+                Ok(())
+            }
+            Some(GateDef::Opaque {
+                ..
+            }) => Err(token.error(format!("cannot apply opaque gate: {name}"))),
+            None => Err(token.error(format!("unknown gate: {name}"))),
+        }
+    }
+}
+
+/// Quantum or classical argument: a whole register, or a single bit.
+#[derive(Debug, Clone, Copy)]
+struct Arg {
+    offset: usize,
+    size:   usize,
+    index:  Option<usize>,
+}
+
+impl Arg {
+    fn bit(
+        &self,
+        i: usize,
+    ) -> usize {
+        self.offset + self.index.unwrap_or(i)
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos:    usize,
+    state:  State,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &'a Token {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> &'a Token {
+        let token = &self.tokens[self.pos];
+        if token.tok != Tok::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn is_sym(
+        &self,
+        sym: &str,
+    ) -> bool {
+        matches!(self.peek().tok, Tok::Sym(s) if s == sym)
+    }
+
+    fn eat_sym(
+        &mut self,
+        sym: &str,
+    ) -> bool {
+        if self.is_sym(sym) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_sym(
+        &mut self,
+        sym: &str,
+    ) -> Result<(), QuestError> {
+        if self.eat_sym(sym) {
+            Ok(())
+        } else {
+            Err(self.peek().error(format!("expected '{sym}'")))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<(&'a Token, String), QuestError> {
+        let token = self.next();
+        match &token.tok {
+            Tok::Ident(name) => Ok((token, name.clone())),
+            _ => Err(token.error("expected identifier")),
+        }
+    }
+
+    fn expect_int(&mut self) -> Result<u64, QuestError> {
+        let token = self.next();
+        match token.tok {
+            Tok::Int(n) => Ok(n),
+            _ => Err(token.error("expected integer")),
+        }
+    }
+
+    fn program(&mut self) -> Result<(), QuestError> {
+        if self.peek().tok == Tok::Ident("OPENQASM".to_owned()) {
+            self.next();
+            let token = self.next();
+            match token.tok {
+                Tok::Real(v) if (2.0..3.0).contains(&v) => {}
+                Tok::Int(2) => {}
+                _ => return Err(token.error("unsupported OpenQASM version")),
+            }
+            self.expect_sym(";")?;
+        }
+
+        while self.peek().tok != Tok::Eof {
+            self.statement()?;
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self) -> Result<(), QuestError> {
+        let (token, keyword) = self.expect_ident()?;
+        match keyword.as_str() {
+            "include" => self.include(token),
+            "qreg" | "creg" => self.register(&keyword),
+            "gate" => self.gate_def(false),
+            "opaque" => self.gate_def(true),
+            "if" => self.if_statement(),
+            _ => {
+                let mut out = Vec::new();
+                self.quantum_op(token, &keyword, &mut out)?;
+                self.state.instructions.append(&mut out);
+                Ok(())
+            }
+        }
+    }
+
+    fn include(
+        &mut self,
+        token: &Token,
+    ) -> Result<(), QuestError> {
+        let file = self.next();
+        match &file.tok {
+            Tok::Str(name) if name == "qelib1.inc" => {}
+            Tok::Str(name) => {
+                return Err(token.error(format!("cannot include file: {name}")))
+            }
+            _ => return Err(file.error("expected file name")),
+        }
+        self.expect_sym(";")?;
+
+        if !self.state.qelib1 {
+            self.state.qelib1 = true;
+            let tokens = lex(QELIB1_DEFS)?;
+            let mut parser = Parser {
+                tokens: &tokens,
+                pos:    0,
+                state:  std::mem::take(&mut self.state),
+            };
+            let res = parser.program();
+            self.state = parser.state;
+            res?;
+        }
+        Ok(())
+    }
+
+    fn register(
+        &mut self,
+        keyword: &str,
+    ) -> Result<(), QuestError> {
+        let (token, name) = self.expect_ident()?;
+        self.expect_sym("[")?;
+        let size_token = self.peek();
+        let size = self.expect_int()?;
+        self.expect_sym("]")?;
+        self.expect_sym(";")?;
+
+        if size == 0 {
+            return Err(size_token.error("register size must be positive"));
+        }
+        if self.state.qregs.contains_key(&name)
+            || self.state.cregs.contains_key(&name)
+        {
+            return Err(
+                token.error(format!("register already declared: {name}"))
+            );
+        }
+
+        if keyword == "qreg" {
+            let size = i32::try_from(size)
+                .ok()
+                .filter(|s| self.state.num_qubits.checked_add(*s).is_some())
+                .ok_or_else(|| size_token.error("register too large"))?;
+            self.state.qregs.insert(name, (self.state.num_qubits, size));
+            self.state.num_qubits += size;
+        } else {
+            let size = usize::try_from(size)
+                .map_err(|_| size_token.error("register too large"))?;
+            let offset = self.state.cregs.values().map(|(o, s)| o + s).max();
+            self.state.cregs.insert(name, (offset.unwrap_or(0), size));
+        }
+        Ok(())
+    }
+
+    /// Parse a comma-separated list of identifiers, until `end`.
+    fn ident_list(
+        &mut self,
+        end: &str,
+    ) -> Result<Vec<String>, QuestError> {
+        let mut idents = Vec::new();
+        if self.is_sym(end) {
+            return Ok(idents);
+        }
+        loop {
+            let (token, name) = self.expect_ident()?;
+            if idents.contains(&name) {
+                return Err(token.error(format!("repeated identifier: {name}")));
+            }
+            idents.push(name);
+            if !self.eat_sym(",") {
+                return Ok(idents);
+            }
+        }
+    }
+
+    fn gate_def(
+        &mut self,
+        opaque: bool,
+    ) -> Result<(), QuestError> {
+        let (token, name) = self.expect_ident()?;
+        if self.state.gate_signature(&name).is_some() {
+            return Err(token.error(format!("gate already defined: {name}")));
+        }
+
+        let params = if self.eat_sym("(") {
+            let params = self.ident_list(")")?;
+            self.expect_sym(")")?;
+            params
+        } else {
+            Vec::new()
+        };
+        let args = self.ident_list("{")?;
+        if args.is_empty() {
+            return Err(self.peek().error("expected qubit argument"));
+        }
+
+        let def = if opaque {
+            self.expect_sym(";")?;
+            GateDef::Opaque {
+                num_params: params.len(),
+                num_args:   args.len(),
+            }
+        } else {
+            self.expect_sym("{")?;
+            let mut body = Vec::new();
+            while !self.eat_sym("}") {
+                if let Some(call) = self.gate_call(&params, &args)? {
+                    body.push(call);
+                }
+            }
+            GateDef::Defined {
+                num_params: params.len(),
+                num_args: args.len(),
+                body,
+            }
+        };
+        self.state.gates.insert(name, def);
+        Ok(())
+    }
+
+    /// Parse a statement in a gate body. Returns `None` for `barrier`.
+    fn gate_call(
+        &mut self,
+        params: &[String],
+        args: &[String],
+    ) -> Result<Option<GateCall>, QuestError> {
+        let (token, name) = self.expect_ident()?;
+        if name == "barrier" {
+            self.ident_list(";")?;
+            self.expect_sym(";")?;
+            return Ok(None);
+        }
+        let (num_params, num_args) = self
+            .state
+            .gate_signature(&name)
+            .ok_or_else(|| token.error(format!("unknown gate: {name}")))?;
+
+        let exprs = self.gate_params(params)?;
+        if exprs.len() != num_params {
+            return Err(token.error(format!(
+                "gate {name} takes {num_params} parameter(s), found {}",
+                exprs.len()
+            )));
+        }
+
+        let mut call_args = Vec::new();
+        for arg in self.ident_list(";")? {
+            let index =
+                args.iter().position(|a| *a == arg).ok_or_else(|| {
+                    token.error(format!("unknown qubit argument: {arg}"))
+                })?;
+            call_args.push(index);
+        }
+        self.expect_sym(";")?;
+        if call_args.len() != num_args {
+            return Err(token.error(format!(
+                "gate {name} takes {num_args} qubit(s), found {}",
+                call_args.len()
+            )));
+        }
+
+        Ok(Some(GateCall {
+            token: token.clone(),
+            name,
+            params: exprs,
+            args: call_args,
+        }))
+    }
+
+    /// Parse optional parameter list of a gate call.
+    fn gate_params(
+        &mut self,
+        params: &[String],
+    ) -> Result<Vec<Expr>, QuestError> {
+        let mut exprs = Vec::new();
+        if self.eat_sym("(") && !self.eat_sym(")") {
+            loop {
+                exprs.push(self.expr(params, 0)?);
+                if !self.eat_sym(",") {
+                    break;
+                }
+            }
+            self.expect_sym(")")?;
+        }
+        Ok(exprs)
+    }
+
+    /// Parse an expression with operators of precedence at least `min_prec`.
+    fn expr(
+        &mut self,
+        params: &[String],
+        min_prec: u8,
+    ) -> Result<Expr, QuestError> {
+        let mut lhs = self.unary(params)?;
+        loop {
+            let (op, prec, right_assoc) = match self.peek().tok {
+                Tok::Sym(op @ ("+" | "-")) => (op, 1, false),
+                Tok::Sym(op @ ("*" | "/")) => (op, 2, false),
+                Tok::Sym(op @ "^") => (op, 4, true),
+                _ => return Ok(lhs),
+            };
+            if prec < min_prec {
+                return Ok(lhs);
+            }
+            self.next();
+            let rhs =
+                self.expr(params, if right_assoc { prec } else { prec + 1 })?;
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn unary(
+        &mut self,
+        params: &[String],
+    ) -> Result<Expr, QuestError> {
+        if self.eat_sym("-") {
+            // Unary minus binds weaker than power: -x^2 == -(x^2)
+            return Ok(Expr::Neg(Box::new(self.expr(params, 3)?)));
+        }
+        if self.eat_sym("+") {
+            return self.expr(params, 3);
+        }
+        let token = self.next();
+        match &token.tok {
+            Tok::Int(n) => Ok(Expr::Num(*n as f64)),
+            Tok::Real(x) => Ok(Expr::Num(*x)),
+            Tok::Sym("(") => {
+                let e = self.expr(params, 0)?;
+                self.expect_sym(")")?;
+                Ok(e)
+            }
+            Tok::Ident(name) if name == "pi" => {
+                Ok(Expr::Num(std::f64::consts::PI))
+            }
+            Tok::Ident(name)
+                if matches!(
+                    name.as_str(),
+                    "sin" | "cos" | "tan" | "exp" | "ln" | "sqrt"
+                ) =>
+            {
+                self.expect_sym("(")?;
+                let e = self.expr(params, 0)?;
+                self.expect_sym(")")?;
+                Ok(Expr::Call(name.clone(), Box::new(e)))
+            }
+            Tok::Ident(name) => params
+                .iter()
+                .position(|p| p == name)
+                .map(Expr::Param)
+                .ok_or_else(|| {
+                    token.error(format!("unknown parameter: {name}"))
+                }),
+            _ => Err(token.error("expected expression")),
+        }
+    }
+
+    /// Parse a register argument: `name` or `name[index]`.
+    fn arg(
+        &mut self,
+        quantum: bool,
+    ) -> Result<Arg, QuestError> {
+        let (token, name) = self.expect_ident()?;
+        let (offset, size) = if quantum {
+            self.state
+                .qregs
+                .get(&name)
+                .map(|&(o, s)| (o as usize, s as usize))
+        } else {
+            self.state.cregs.get(&name).copied()
+        }
+        .ok_or_else(|| token.error(format!("unknown register: {name}")))?;
+
+        let index = if self.eat_sym("[") {
+            let index_token = self.peek();
+            let index = self.expect_int()?;
+            self.expect_sym("]")?;
+            match usize::try_from(index) {
+                Ok(i) if i < size => Some(i),
+                _ => return Err(index_token.error("index out of range")),
+            }
+        } else {
+            None
+        };
+        Ok(Arg {
+            offset,
+            size,
+            index,
+        })
+    }
+
+    /// Parse a comma-separated list of quantum arguments, until `;`.
+    fn qargs(&mut self) -> Result<Vec<Arg>, QuestError> {
+        let mut args = vec![self.arg(true)?];
+        while self.eat_sym(",") {
+            args.push(self.arg(true)?);
+        }
+        self.expect_sym(";")?;
+        Ok(args)
+    }
+
+    /// Number of times a statement with arguments `args` is to be repeated.
+    fn broadcast_len(
+        token: &Token,
+        args: &[Arg],
+    ) -> Result<usize, QuestError> {
+        let mut sizes =
+            args.iter().filter(|a| a.index.is_none()).map(|a| a.size);
+        let len = sizes.next().unwrap_or(1);
+        if sizes.all(|s| s == len) {
+            Ok(len)
+        } else {
+            Err(token.error("registers of different sizes"))
+        }
+    }
+
+    /// Parse quantum operation: a gate call, `measure`, `reset` or
+    /// `barrier`.
+    fn quantum_op(
+        &mut self,
+        token: &Token,
+        name: &str,
+        out: &mut Vec<Instruction>,
+    ) -> Result<(), QuestError> {
+        match name {
+            "measure" => {
+                let qarg = self.arg(true)?;
+                self.expect_sym("->")?;
+                let carg = self.arg(false)?;
+                self.expect_sym(";")?;
+                if qarg.index.is_none() != carg.index.is_none()
+                    || (qarg.index.is_none() && qarg.size != carg.size)
+                {
+                    return Err(token.error("registers of different sizes"));
+                }
+                for i in 0..Self::broadcast_len(token, &[qarg])? {
+                    out.push(Instruction::Measure {
+                        qubit: qarg.bit(i) as i32,
+                        clbit: carg.bit(i),
+                    });
+                }
+            }
+            "reset" => {
+                let qarg = self.arg(true)?;
+                self.expect_sym(";")?;
+                for i in 0..Self::broadcast_len(token, &[qarg])? {
+                    out.push(Instruction::Reset {
+                        qubit: qarg.bit(i) as i32,
+                    });
+                }
+            }
+            "barrier" => {
+                self.qargs()?;
+            }
+            _ => {
+                let (num_params, num_args) =
+                    self.state.gate_signature(name).ok_or_else(|| {
+                        token.error(format!("unknown gate: {name}"))
+                    })?;
+
+                let params = self
+                    .gate_params(&[])?
+                    .iter()
+                    .map(|e| e.eval(&[]))
+                    .collect::<Vec<_>>();
+                if params.len() != num_params {
+                    return Err(token.error(format!(
+                        "gate {name} takes {num_params} parameter(s), found {}",
+                        params.len()
+                    )));
+                }
+                let args = self.qargs()?;
+                if args.len() != num_args {
+                    return Err(token.error(format!(
+                        "gate {name} takes {num_args} qubit(s), found {}",
+                        args.len()
+                    )));
+                }
+
+                for i in 0..Self::broadcast_len(token, &args)? {
+                    let qubits = args
+                        .iter()
+                        .map(|a| a.bit(i) as i32)
+                        .collect::<Vec<_>>();
+                    if (1..qubits.len())
+                        .any(|j| qubits[..j].contains(&qubits[j]))
+                    {
+                        return Err(token.error("repeated qubit argument"));
+                    }
+                    self.state.expand(token, name, &params, &qubits, out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn if_statement(&mut self) -> Result<(), QuestError> {
+        self.expect_sym("(")?;
+        let (token, name) = self.expect_ident()?;
+        let &(offset, size) =
+            self.state.cregs.get(&name).ok_or_else(|| {
+                token.error(format!("unknown register: {name}"))
+            })?;
+        self.expect_sym("==")?;
+        let value = self.expect_int()?;
+        self.expect_sym(")")?;
+
+        let (token, op) = self.expect_ident()?;
+        let mut body = Vec::new();
+        self.quantum_op(token, &op, &mut body)?;
+        self.state.instructions.push(Instruction::If {
+            clbits: offset..offset + size,
+            value,
+            body,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_err(src: &str) -> (usize, usize) {
+        match QasmProgram::parse(src).unwrap_err() {
+            QuestError::ParseError {
+                line,
+                column,
+                ..
+            } => (line, column),
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn parse_01() {
+        let program = QasmProgram::parse(
+            "OPENQASM 2.0;
+            include \"qelib1.inc\";
+            qreg a[2];
+            qreg b[3];
+            creg c[3];
+            creg d[1];
+            gate foo(theta) x, y { rx(theta/2) x; cx x, y; barrier x; }
+            foo(pi) a[0], b[1];
+            h b;
+            cx a, b[2];
+            measure b -> c;
+            if (c == 5) x a[1];
+            reset a;
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(program.num_qubits(), 5);
+        assert_eq!(program.num_clbits(), 4);
+        assert_eq!(program.cregs[1], ("d".to_owned(), 3..4));
+        // foo: 2, h: 3, cx: 2, measure: 3, if: 1, reset: 2
+        assert_eq!(program.instructions.len(), 13);
+    }
+
+    #[test]
+    fn parse_02() {
+        let program = QasmProgram::parse(
+            "OPENQASM 2.0;
+            include \"qelib1.inc\";
+            qreg q[4];
+            rccx q[0], q[1], q[2];
+            rc3x q[0], q[1], q[2], q[3];
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(program.instructions.len(), 9 + 18);
+    }
+
+    #[test]
+    fn parse_expr_01() {
+        let tokens = lex("-2^2 + 3*(1 - pi/2) + sqrt(4)^3^0 - -1").unwrap();
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos:    0,
+            state:  State::default(),
+        };
+        let expected = -4. + 3. * (1. - std::f64::consts::PI / 2.) + 2. + 1.;
+        let e = parser.expr(&[], 0).unwrap();
+        assert!((e.eval(&[]) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn parse_error_01() {
+        assert_eq!(parse_err("qreg q[2];\nh q[0];"), (2, 1));
+        assert_eq!(parse_err("qreg q[2];\n  CX q[0], q[2];"), (2, 14));
+        assert_eq!(parse_err("qreg q[2]\nU(0,0,0) q[0];"), (2, 1));
+        assert_eq!(parse_err("qreg q[2];\nCX q[0], q[0];"), (2, 1));
+        assert_eq!(parse_err("qreg q[2]; qreg r[3];\nCX q, r;"), (2, 1));
+        assert_eq!(parse_err("include \"other.inc\";"), (1, 1));
+        assert_eq!(parse_err("qreg q[1];\nU(0, 0) q;"), (2, 1));
+        assert_eq!(parse_err("gate g a { U(0, 0, t) a; }"), (1, 20));
+        assert_eq!(parse_err("opaque g a;\nqreg q[1];\ng q[0];"), (3, 1));
+        assert_eq!(parse_err("qreg q[1];\nmeasure q -> c;"), (2, 14));
+        assert_eq!(parse_err("qreg q[1]; @"), (1, 12));
+    }
+
+    #[test]
+    fn classical_register_value_01() {
+        let reg = ClassicalRegister {
+            name: "c".to_owned(),
+            bits: vec![1, 0, 1, 1],
+        };
+        assert_eq!(reg.value(), 0b1101);
+    }
+}
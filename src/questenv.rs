@@ -1,8 +1,22 @@
-use std::ffi::CString;
+use std::{
+    ffi::{
+        c_int,
+        CString,
+    },
+    sync::{
+        Arc,
+        Condvar,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+        Weak,
+    },
+};
 
 use crate::{
     error::catch_quest_exception,
     ffi,
+    Qreal,
     QuestError,
 };
 
@@ -10,11 +24,84 @@ use crate::{
 ///
 /// In practice, this holds info about MPI ranks and helps to hide MPI
 /// initialization code.
+///
+/// `QuestEnv` is a cheap, reference-counted handle: cloning it does not
+/// create a new environment, but shares the existing one.  Every [`Qureg`]
+/// and [`DiagonalOp`] keeps its own handle, so the underlying `QuEST`
+/// environment is destroyed only after the last handle and the last object
+/// allocated in it have been dropped.
+///
+/// There is at most one environment per process.  [`QuestEnv::new()`] and
+/// [`QuestEnv::get_or_init()`] return a handle to the existing environment,
+/// if there is one, and [`QuestEnv::try_new()`] fails in that case.  Once
+/// the environment has been destroyed, a new one can be created, except in
+/// distributed mode (the `mpi` feature), where MPI cannot be initialized
+/// twice.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// fn make_register(num_qubits: i32) -> StateVector {
+///     let env = QuestEnv::new();
+///     Qureg::try_new(num_qubits, &env).unwrap()
+/// }
+///
+/// let qureg = make_register(2);
+/// assert_eq!(qureg.num_qubits(), 2);
+/// ```
+///
+/// [`Qureg`]: crate::Qureg
+/// [`DiagonalOp`]: crate::DiagonalOp
+#[derive(Debug, Clone)]
+pub struct QuestEnv(Arc<EnvInner>);
+
+/// The environment shared by all handles.
+///
+/// Seeding the random number generator replaces the array of seeds
+/// stored in `ffi::QuESTEnv`, hence the lock.
+#[derive(Debug)]
+struct EnvInner(Mutex<ffi::QuESTEnv>);
+
+/// The process-wide environment.
 #[derive(Debug)]
-pub struct QuestEnv(pub(crate) ffi::QuESTEnv);
+struct GlobalEnv {
+    /// Weak reference, so that the environment is destroyed together with
+    /// its last handle.
+    env:     Weak<EnvInner>,
+    /// Whether an environment has ever been created.
+    created: bool,
+    /// Whether the environment referenced by `env` has not been destroyed
+    /// yet.  It may already be unreachable through `env`, while its last
+    /// handle is being dropped.
+    alive:   bool,
+}
+
+static GLOBAL_ENV: Mutex<GlobalEnv> = Mutex::new(GlobalEnv {
+    env:     Weak::new(),
+    created: false,
+    alive:   false,
+});
+
+/// Signaled when the process-wide environment has been destroyed.
+static GLOBAL_ENV_DESTROYED: Condvar = Condvar::new();
+
+// See: src/openmp.c
+extern "C" {
+    fn quest_bind_has_openmp() -> c_int;
+
+    fn quest_bind_set_num_threads(num_threads: c_int) -> c_int;
+}
 
 impl QuestEnv {
-    /// Create a new environment.
+    /// Get a handle to the process-wide environment.
+    ///
+    /// Same as [`QuestEnv::get_or_init()`].
+    ///
+    /// # Panics
+    ///
+    /// In distributed mode, panics if the environment has already been
+    /// destroyed.
     ///
     /// # Examples
     ///
@@ -25,7 +112,139 @@ impl QuestEnv {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        Self(unsafe { ffi::createQuESTEnv() })
+        Self::get_or_init()
+    }
+
+    /// Get a handle to the process-wide environment, creating it if there
+    /// is none.
+    ///
+    /// Libraries built on `quest_bind` should use this function to share
+    /// the environment with the application.
+    ///
+    /// # Panics
+    ///
+    /// In distributed mode, panics if the environment has already been
+    /// destroyed.  MPI cannot be initialized twice in the same process.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::get_or_init();
+    /// let qureg = Qureg::try_new(2, &env).unwrap();
+    ///
+    /// // the environment is shared, not created again
+    /// assert!(QuestEnv::try_new().is_err());
+    /// ```
+    #[must_use]
+    pub fn get_or_init() -> Self {
+        Self::acquire(false).expect("QuEST environment cannot be re-created")
+    }
+
+    /// Create the process-wide environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QuestError::EnvError`] if the environment already exists,
+    /// or, in distributed mode, if it has already been destroyed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::get_or_init();
+    ///
+    /// let err = QuestEnv::try_new().unwrap_err();
+    /// assert_eq!(err.kind(), QuestErrorKind::Environment);
+    /// ```
+    ///
+    /// [`QuestError::EnvError`]: crate::QuestError::EnvError
+    pub fn try_new() -> Result<Self, QuestError> {
+        Self::acquire(true)
+    }
+
+    /// Configure the process-wide environment.
+    ///
+    /// See [`QuestEnvBuilder`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::builder()
+    ///     .seeds(&[1, 2, 3])
+    ///     .log_errors(false)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(get_quest_seeds(&env), [1, 2, 3]);
+    /// ```
+    #[must_use]
+    pub fn builder() -> QuestEnvBuilder {
+        QuestEnvBuilder::default()
+    }
+
+    /// Get a handle to the process-wide environment.
+    ///
+    /// If `exclusive` is set, fail if the environment already exists.
+    ///
+    /// If the previous environment is still being destroyed, wait until it
+    /// is gone, so that creating and destroying an environment never
+    /// overlap.
+    fn acquire(exclusive: bool) -> Result<Self, QuestError> {
+        let mut global =
+            GLOBAL_ENV.lock().unwrap_or_else(PoisonError::into_inner);
+
+        loop {
+            if let Some(inner) = global.env.upgrade() {
+                // `inner` may turn out to be the last handle, whose drop
+                // takes the lock.
+                drop(global);
+                return if exclusive {
+                    Err(QuestError::EnvError {
+                        msg: "environment already exists".to_string(),
+                    })
+                } else {
+                    Ok(Self(inner))
+                };
+            }
+            if !global.alive {
+                break;
+            }
+            global = GLOBAL_ENV_DESTROYED
+                .wait(global)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        if cfg!(feature = "mpi") && global.created {
+            return Err(QuestError::EnvError {
+                msg: "cannot initialize MPI environment twice".to_string(),
+            });
+        }
+
+        let env = unsafe { ffi::createQuESTEnv() };
+        let inner = Arc::new(EnvInner(Mutex::new(env)));
+        global.env = Arc::downgrade(&inner);
+        global.created = true;
+        global.alive = true;
+        Ok(Self(inner))
+    }
+
+    /// A copy of the underlying `QuEST` environment.
+    ///
+    /// The copy is valid as long as `self` is alive and no new seeds
+    /// have been set.
+    pub(crate) fn raw(&self) -> ffi::QuESTEnv {
+        *self.0.lock()
+    }
+
+    /// Modify the underlying `QuEST` environment in place.
+    ///
+    /// The environment stays locked while `f` runs.
+    pub(crate) fn with_raw_mut<T>(
+        &self,
+        f: impl FnOnce(&mut ffi::QuESTEnv) -> T,
+    ) -> T {
+        f(&mut self.0.lock())
     }
 
     /// Sync environment in distributed mode.
@@ -42,7 +261,7 @@ impl QuestEnv {
     /// ```
     pub fn sync(&self) {
         unsafe {
-            ffi::syncQuESTEnv(self.0);
+            ffi::syncQuESTEnv(self.raw());
         }
     }
 
@@ -55,7 +274,7 @@ impl QuestEnv {
     /// [quest-api]: https://quest-kit.github.io/QuEST/modules.html
     pub fn report_quest_env(&self) {
         catch_quest_exception(|| unsafe {
-            ffi::reportQuESTEnv(self.0);
+            ffi::reportQuESTEnv(self.raw());
         })
         .expect("report_quest_env should always succeed");
     }
@@ -80,19 +299,218 @@ impl QuestEnv {
     ///
     /// [quest-api]: https://quest-kit.github.io/QuEST/modules.html
     pub fn get_environment_string(&self) -> Result<String, QuestError> {
-        let mut cstr =
+        let cstr_ptr =
             CString::new("CUDA=x OpenMP=x MPI=x threads=xxxxxxx ranks=xxxxxxx")
-                .map_err(QuestError::NulError)?;
-        catch_quest_exception(|| {
-            unsafe {
-                let cstr_ptr = cstr.into_raw();
-                ffi::getEnvironmentString(self.0, cstr_ptr);
-                cstr = CString::from_raw(cstr_ptr);
-            }
-
-            cstr.into_string().map_err(QuestError::IntoStringError)
+                .map_err(QuestError::NulError)?
+                .into_raw();
+        catch_quest_exception(|| unsafe {
+            ffi::getEnvironmentString(self.raw(), cstr_ptr);
         })
-        .expect("get_environment_string should always succeed")
+        .expect("get_environment_string should always succeed");
+
+        // SAFETY: cstr_ptr was obtained from CString::into_raw() above, and
+        // QuEST writes no more than the length of the placeholder string.
+        let cstr = unsafe { CString::from_raw(cstr_ptr) };
+        cstr.into_string().map_err(QuestError::IntoStringError)
+    }
+
+    /// Information about the runtime environment.
+    ///
+    /// This is the information reported by
+    /// [`get_environment_string()`](QuestEnv::get_environment_string()),
+    /// together with the rank of this process and the floating point
+    /// precision.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let info = env.info();
+    ///
+    /// assert!(info.rank < info.num_ranks);
+    /// assert_eq!(info.precision, std::mem::size_of::<Qreal>());
+    /// ```
+    #[must_use]
+    pub fn info(&self) -> EnvInfo {
+        let env_str = self
+            .get_environment_string()
+            .expect("environment string should be valid UTF-8");
+        let env = self.raw();
+        EnvInfo::from_environment_string(&env_str, env.rank, env.numRanks)
+            .expect("environment string should be well-formed")
+    }
+}
+
+/// Information about the runtime environment.
+///
+/// See [`QuestEnv::info()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct EnvInfo {
+    /// `QuEST` was compiled with GPU acceleration.
+    pub cuda:        bool,
+    /// `QuEST` was compiled with multithreading.
+    pub openmp:      bool,
+    /// `QuEST` runs in distributed mode.
+    pub mpi:         bool,
+    /// Number of threads available to each process.
+    pub num_threads: usize,
+    /// Number of processes.
+    pub num_ranks:   usize,
+    /// Rank of this process.
+    pub rank:        usize,
+    /// Size of [`Qreal`] in bytes.
+    ///
+    /// [`Qreal`]: crate::Qreal
+    pub precision:   usize,
+}
+
+/// Builder of [`QuestEnv`] with custom options.
+///
+/// Options that are not set are left unchanged.  Since there is at most one
+/// environment per process, [`build()`](QuestEnvBuilder::build()) applies
+/// the options to the existing environment, if there is one, just as
+/// [`QuestEnv::get_or_init()`] does.
+///
+/// See [`QuestEnv::builder()`].
+#[derive(Debug, Clone, Default)]
+pub struct QuestEnvBuilder {
+    seeds:       Option<Vec<u64>>,
+    num_threads: Option<usize>,
+    log_errors:  Option<bool>,
+}
+
+impl QuestEnvBuilder {
+    /// Seed the random number generator.
+    ///
+    /// See [`seed_quest()`](crate::seed_quest()).
+    pub fn seeds(
+        &mut self,
+        seeds: &[u64],
+    ) -> &mut Self {
+        self.seeds = Some(seeds.to_vec());
+        self
+    }
+
+    /// Set the number of threads used by `QuEST`.
+    ///
+    /// The number of threads is set with `omp_set_num_threads()` in the
+    /// OpenMP runtime that `QuEST` is linked against.  OpenMP applies it
+    /// only to the thread that calls [`build()`](QuestEnvBuilder::build()):
+    /// `QuEST` functions called from any other thread, e.g. on a register
+    /// moved into a new thread, still use the default number of threads.
+    /// To set the number of threads for the whole process, set the
+    /// environment variable `OMP_NUM_THREADS` before the program starts.
+    pub fn num_threads(
+        &mut self,
+        num_threads: usize,
+    ) -> &mut Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    /// Log errors reported by `QuEST` with [`log::error!`].
+    ///
+    /// Errors are logged by default.  The setting is global.
+    pub fn log_errors(
+        &mut self,
+        enabled: bool,
+    ) -> &mut Self {
+        self.log_errors = Some(enabled);
+        self
+    }
+
+    /// Get a handle to the process-wide environment, creating it if there
+    /// is none, and apply the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QuestError::EnvError`] if:
+    ///
+    /// - the number of threads is 0 or too large, or `QuEST` was built without
+    ///   multithreading (e.g. with the `gpu` feature)
+    /// - in distributed mode, the environment has already been destroyed
+    ///
+    /// No options are applied in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let err = QuestEnv::builder().num_threads(0).build().unwrap_err();
+    /// assert_eq!(err.kind(), QuestErrorKind::Environment);
+    /// ```
+    ///
+    /// [`QuestError::EnvError`]: crate::QuestError::EnvError
+    pub fn build(&self) -> Result<QuestEnv, QuestError> {
+        let num_threads = self
+            .num_threads
+            .map(|n| {
+                i32::try_from(n).ok().filter(|&n| n > 0).ok_or_else(|| {
+                    QuestError::EnvError {
+                        msg: format!("invalid number of threads: {n}"),
+                    }
+                })
+            })
+            .transpose()?;
+        // Check everything that can fail before creating the environment:
+        // in distributed mode, dropping it again would finalize MPI.
+        if num_threads.is_some()
+            && (cfg!(feature = "gpu")
+                || unsafe { quest_bind_has_openmp() } == 0)
+        {
+            return Err(QuestError::EnvError {
+                msg: "multithreading is not available".to_string(),
+            });
+        }
+
+        let mut env = QuestEnv::acquire(false)?;
+
+        if let Some(n) = num_threads {
+            let res = unsafe { quest_bind_set_num_threads(n) };
+            assert_eq!(res, 0, "OpenMP runtime should be available");
+        }
+        if let Some(enabled) = self.log_errors {
+            crate::error::set_log_errors(enabled);
+        }
+        if let Some(seeds) = &self.seeds {
+            crate::seed_quest(&mut env, seeds);
+        }
+        Ok(env)
+    }
+}
+
+impl EnvInfo {
+    /// Parse a string like `"CUDA=0 OpenMP=1 MPI=0 threads=8 ranks=1"`.
+    ///
+    /// The number of ranks is taken from the string, if present.
+    fn from_environment_string(
+        env_str: &str,
+        rank: i32,
+        num_ranks: i32,
+    ) -> Option<Self> {
+        let mut info = Self {
+            cuda:        false,
+            openmp:      false,
+            mpi:         false,
+            num_threads: 1,
+            num_ranks:   usize::try_from(num_ranks).ok()?,
+            rank:        usize::try_from(rank).ok()?,
+            precision:   std::mem::size_of::<Qreal>(),
+        };
+        for field in env_str.split_whitespace() {
+            let (key, value) = field.split_once('=')?;
+            match key {
+                "CUDA" => info.cuda = value.parse::<u8>().ok()? != 0,
+                "OpenMP" => info.openmp = value.parse::<u8>().ok()? != 0,
+                "MPI" => info.mpi = value.parse::<u8>().ok()? != 0,
+                "threads" => info.num_threads = value.parse().ok()?,
+                "ranks" => info.num_ranks = value.parse().ok()?,
+                _ => (),
+            }
+        }
+        Some(info)
     }
 }
 
@@ -102,9 +520,99 @@ impl Default for QuestEnv {
     }
 }
 
-impl Drop for QuestEnv {
+impl EnvInner {
+    fn lock(&self) -> MutexGuard<'_, ffi::QuESTEnv> {
+        // The guarded value is plain data: a panic while holding the lock
+        // cannot leave it in an inconsistent state.
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for EnvInner {
     fn drop(&mut self) {
-        catch_quest_exception(|| unsafe { ffi::destroyQuESTEnv(self.0) })
-            .expect("dropping QuestEnv should always succeed");
+        let env = *self.0.get_mut().unwrap_or_else(PoisonError::into_inner);
+
+        // Hold the lock, so that no new environment is created meanwhile.
+        let mut global =
+            GLOBAL_ENV.lock().unwrap_or_else(PoisonError::into_inner);
+        let res =
+            catch_quest_exception(|| unsafe { ffi::destroyQuESTEnv(env) });
+        global.alive = false;
+        drop(global);
+        GLOBAL_ENV_DESTROYED.notify_all();
+
+        res.expect("dropping QuestEnv should always succeed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quest_env_singleton_01() {
+        let env = QuestEnv::get_or_init();
+        let other = QuestEnv::new();
+        assert!(Arc::ptr_eq(&env.0, &other.0));
+
+        let err = QuestEnv::try_new().unwrap_err();
+        assert!(matches!(err, QuestError::EnvError { .. }));
+    }
+
+    #[test]
+    #[cfg(not(feature = "mpi"))]
+    fn quest_env_singleton_02() {
+        // Drop and re-create the environment concurrently
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..100 {
+                        let env = QuestEnv::new();
+                        assert!(env.raw().numRanks > 0);
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn quest_env_builder_01() {
+        let err = QuestEnv::builder().num_threads(0).build().unwrap_err();
+        assert!(matches!(err, QuestError::EnvError { .. }));
+
+        let err = QuestEnv::builder()
+            .num_threads(usize::MAX)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, QuestError::EnvError { .. }));
+    }
+
+    #[test]
+    fn env_info_01() {
+        let info = EnvInfo::from_environment_string(
+            "CUDA=0 OpenMP=1 MPI=1 threads=8 ranks=4",
+            2,
+            4,
+        )
+        .unwrap();
+
+        assert!(!info.cuda);
+        assert!(info.openmp);
+        assert!(info.mpi);
+        assert_eq!(info.num_threads, 8);
+        assert_eq!(info.num_ranks, 4);
+        assert_eq!(info.rank, 2);
+        assert_eq!(info.precision, std::mem::size_of::<Qreal>());
+    }
+
+    #[test]
+    fn env_info_02() {
+        let parse = |s| EnvInfo::from_environment_string(s, 0, 1);
+
+        assert!(parse("").is_some());
+        assert!(parse("CUDA=x").is_none());
+        assert!(parse("threads").is_none());
+        assert!(parse("threads=-1").is_none());
+        assert!(EnvInfo::from_environment_string("", -1, 1).is_none());
     }
 }
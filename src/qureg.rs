@@ -1,12 +1,27 @@
-use std::ffi::CString;
+use std::{
+    ffi::CString,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::Range,
+};
+
+use rand::{
+    distributions::{
+        Distribution,
+        WeightedIndex,
+    },
+    Rng,
+};
 
 use super::{
     catch_quest_exception,
     ffi,
+    gates,
     BitEncoding,
     ComplexMatrix2,
     ComplexMatrix4,
     ComplexMatrixN,
+    Counts,
     PauliHamil,
     PauliOpType,
     PhaseFunc,
@@ -15,15 +30,70 @@ use super::{
     QuestEnv,
     QuestError,
     Vector,
+    REAL_EPS,
 };
 
+mod private {
+    pub trait Sealed {}
+}
+
+/// Kind of a quantum register: state-vector or density matrix.
+///
+/// This trait is sealed and implemented only by [`StateVectorKind`] and
+/// [`DensityMatrixKind`].
+pub trait QuregKind: private::Sealed {}
+
+/// Marker type for state-vector registers.  See [`StateVector`].
+#[derive(Debug)]
+pub enum StateVectorKind {}
+
+/// Marker type for density matrix registers.  See [`DensityMatrix`].
+#[derive(Debug)]
+pub enum DensityMatrixKind {}
+
+impl private::Sealed for StateVectorKind {}
+impl QuregKind for StateVectorKind {}
+impl private::Sealed for DensityMatrixKind {}
+impl QuregKind for DensityMatrixKind {}
+
+/// Quantum register of kind `K`: a [`StateVector`] or a [`DensityMatrix`].
+///
+/// Methods available for both kinds of registers, e.g. all unitary gates,
+/// are implemented for any `K: QuregKind`.  Methods that `QuEST` supports
+/// only for state-vectors, e.g. [`get_amp()`][api-get-amp], or only for
+/// density matrices, e.g. [`mix_dephasing()`][api-mix-dephasing], are
+/// implemented only for the respective kind, so that misusing them is a
+/// compile-time error:
+///
+/// ```compile_fail
+/// # use quest_bind::*;
+/// let env = QuestEnv::new();
+/// let mut qureg =
+///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+///
+/// // state-vectors cannot be mixed
+/// qureg.mix_dephasing(0, 0.5).unwrap();
+/// ```
+///
+/// [`Qureg::try_new()`] creates a [`StateVector`] and
+/// [`Qureg::try_new_density()`] creates a [`DensityMatrix`].
+///
+/// [api-get-amp]: crate::Qureg::get_amp()
+/// [api-mix-dephasing]: crate::Qureg::mix_dephasing()
 #[derive(Debug)]
-pub struct Qureg<'a> {
-    pub(crate) env: &'a QuestEnv,
+pub struct Qureg<K> {
+    pub(crate) env: QuestEnv,
     pub(crate) reg: ffi::Qureg,
+    kind:           PhantomData<fn() -> K>,
 }
 
-impl<'a> Qureg<'a> {
+/// State-vector quantum register.
+pub type StateVector = Qureg<StateVectorKind>;
+
+/// Density matrix quantum register.
+pub type DensityMatrix = Qureg<DensityMatrixKind>;
+
+impl Qureg<StateVectorKind> {
     /// Creates a state-vector Qureg object.
     ///
     /// # Examples
@@ -45,182 +115,257 @@ impl<'a> Qureg<'a> {
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     pub fn try_new(
         num_qubits: i32,
-        env: &'a QuestEnv,
+        env: &QuestEnv,
     ) -> Result<Self, QuestError> {
         Ok(Self {
-            env,
-            reg: catch_quest_exception(|| unsafe {
-                ffi::createQureg(num_qubits, env.0)
+            env:  env.clone(),
+            reg:  catch_quest_exception(|| unsafe {
+                ffi::createQureg(num_qubits, env.raw())
             })?,
+            kind: PhantomData,
         })
     }
 
-    ///  Creates a density matrix Qureg object.
+    /// Initialize `qureg` by specifying all amplitudes.
+    ///
+    /// For density matrices, it is assumed the amplitudes have been flattened
+    /// column-wise into the given arrays.
+    ///
+    /// The real and imaginary components of the amplitudes are passed in
+    /// separate arrays, `reals` and `imags`, each of which must have length
+    /// [`qureg.num_amps_total()`]. There is no automatic checking that the
+    /// passed arrays are L2 normalized, so this can be used to prepare `qureg`
+    /// in a non-physical state.
+    ///
+    /// In distributed mode, this would require the complete state to fit in
+    /// every node. To manually prepare a state for which all amplitudes cannot
+    /// fit into a single node, use [`set_amps()`]
+    ///
+    /// # Parameters
+    ///
+    /// - `reals`: array of the real components of the new amplitudes
+    /// - `imags`: array of the imaginary components of the new amplitudes
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if either `reals` or `imags` have fewer than
+    ///     [`qureg.num_amps_total()`] elements
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// ```
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// See [QuEST API] for more information.
+    /// qureg.init_state_from_amps(&[1., 0., 0., 0.], &[0., 0., 0., 0.]);
+    /// let prob = qureg.get_prob_amp(0).unwrap();
     ///
-    /// # Errors
+    /// assert!((prob - 1.).abs() < EPSILON);
+    /// ```
     ///
-    /// Returns [`QuestError::InvalidQuESTInputError`](crate::QuestError::InvalidQuESTInputError)
-    /// on failure.  This is an exception thrown by `QuEST`.
+    /// See [QuEST API] for more information.
     ///
-    /// [Quest API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn try_new_density(
-        num_qubits: i32,
-        env: &'a QuestEnv,
-    ) -> Result<Self, QuestError> {
-        Ok(Self {
-            env,
-            reg: catch_quest_exception(|| unsafe {
-                ffi::createDensityQureg(num_qubits, env.0)
-            })?,
+    /// [`qureg.num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`set_amps()`]: crate::Qureg::set_amps()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn init_state_from_amps(
+        &mut self,
+        reals: &[Qreal],
+        imags: &[Qreal],
+    ) -> Result<(), QuestError> {
+        let num_amps_total = self.num_amps_total() as usize;
+        if reals.len() < num_amps_total || imags.len() < num_amps_total {
+            return Err(QuestError::ArrayLengthError);
+        }
+        catch_quest_exception(|| unsafe {
+            ffi::initStateFromAmps(self.reg, reals.as_ptr(), imags.as_ptr());
         })
     }
 
-    #[must_use]
-    pub fn is_density_matrix(&self) -> bool {
-        self.reg.isDensityMatrix != 0
-    }
-
-    /// Print the current state vector of probability amplitudes to file.
+    /// Overwrites a contiguous subset of the amplitudes in a state-vector.
     ///
-    /// ## File format:
+    /// Only amplitudes with indices in `[start_ind,  start_ind + reals.len()]`
+    /// will be changed. The resulting `qureg` may not necessarily be in an
+    /// L2 normalized state.
     ///
-    /// ```text
-    /// real, imag
-    /// realComponent1, imagComponent1
-    /// realComponent2, imagComponent2
-    /// ...
-    /// realComponentN, imagComponentN
-    /// ```
+    /// In distributed mode, this function assumes the subset `reals` and
+    /// `imags` exist (at least) on the node containing the ultimately
+    /// updated elements. For example, below is the correct way to modify
+    /// the full 8 elements of `qureg` when split between 2 nodes:
     ///
-    ///  ## File naming convention:
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// For each node that the program runs on, a file
-    /// `state_rank_[node_rank].csv` is generated. If there is  more than
-    /// one node, ranks after the first do not include the header:
+    /// let re = &mut [1., 2., 3., 4.];
+    /// let im = &mut [1., 2., 3., 4.];
+    /// qureg.set_amps(0, re, im);
     ///
-    /// ```text
-    /// real, imag
+    /// // modify re and im to the next set of elements
+    /// for i in 0..4 {
+    ///     re[i] += 4.;
+    ///     im[i] += 4.;
+    /// }
+    /// qureg.set_amps(4, re, im);
     /// ```
     ///
-    /// so that files are easier to combine.
-    ///
-    /// See [QuEST API] for more information.
+    /// # Parameters
     ///
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn report_state(&self) {
-        catch_quest_exception(|| unsafe { ffi::reportState(self.reg) })
-            .expect("report_state should never fail");
-    }
-
-    /// Print the current state vector of probability amplitudes.
+    /// - `start_ind`: the index of the first amplitude in `qureg` to modify
+    /// - `reals`: array of the real components of the new amplitudes
+    /// - `imags`: array of the imaginary components of the new amplitudes
     ///
-    /// Print the current state vector of probability amplitudes for a set of
-    /// qubits to standard out. For debugging purposes. Each rank should
-    /// print output serially.  Only print output for systems <= 5 qubits.
+    /// # Errors
     ///
-    /// See [QuEST API] for more information.
+    /// - [`ArrayLengthError`]
+    ///   - if `reals.len()` and `imags.len()` are different
     ///
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn report_state_to_screen(
-        &self,
-        report_rank: i32,
-    ) {
-        catch_quest_exception(|| unsafe {
-            ffi::reportStateToScreen(self.reg, self.env.0, report_rank);
-        })
-        .expect("report_state_to screen should never fail");
-    }
-
-    /// Returns the number of qubits represented.
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `qureg` is not a state-vector (i.e. is a density matrix)
+    ///   - if `start_ind` is outside [0, [`qureg.num_amps_total()`]]
+    ///   - if `reals.len()` is outside [0, `qureg.num_amps_total()`]
+    ///   - if `reals.len()` + `start_ind` >= `qureg.num_amps_total()`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// assert_eq!(qureg.num_qubits(), 3);
+    /// let re = &[1., 2., 3.];
+    /// let im = &[4., 5., 6.];
+    /// let start_ind = 1;
+    /// qureg.set_amps(start_ind, re, im);
+    ///
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!((amp - 3.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`qureg.num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[must_use]
-    pub const fn num_qubits(&self) -> i32 {
-        self.reg.numQubitsRepresented
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn set_amps(
+        &mut self,
+        start_ind: i64,
+        reals: &[Qreal],
+        imags: &[Qreal],
+    ) -> Result<(), QuestError> {
+        if reals.len() != imags.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_amps = reals.len() as i64;
+        catch_quest_exception(|| unsafe {
+            ffi::setAmps(
+                self.reg,
+                start_ind,
+                reals.as_ptr(),
+                imags.as_ptr(),
+                num_amps,
+            );
+        })
     }
 
-    /// Return the total number of amplitudes in the register.
+    /// Get the complex amplitude at a given index in the state vector.
     ///
-    /// - If `Qureg` is a state-vector, this is equal to: `2^N`, where `N` is
-    ///   the number of qubits in the register: [`num_qubits()`]
-    /// - If `Qureg` is a density matrix, this is equal to `2^(2N)`
+    /// # Parameters
+    ///
+    /// - `index`: index in state vector of probability amplitudes
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `self` is a density matrix
+    ///   - if `index` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let qureg = Qureg::try_new_density(3, &env)
-    ///     .expect("cannot allocate memory for Qureg");
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// assert_eq!(qureg.num_amps_total(), 64);
+    /// let amp = qureg.get_amp(0).unwrap().re;
+    /// assert!((amp - 0.5).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[must_use]
-    pub fn num_amps_total(&self) -> i64 {
-        self.reg.numAmpsTotal
+    pub fn get_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qcomplex, QuestError> {
+        catch_quest_exception(|| unsafe { ffi::getAmp(self.reg, index) })
+            .map(Into::into)
     }
 
-    /// Report information about a set of qubits.
+    /// Get the real part of the probability amplitude at an index in
+    /// the state vector.
     ///
-    /// This function prints to stdout: number of qubits, number of probability
-    /// amplitudes.
+    /// # Parameters
+    ///
+    /// - `index`: index in state vector of probability amplitudes
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a density matrix
+    ///   - if `index` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let qureg =
+    /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// qureg.report_qureg_params();
+    /// let amp = qureg.get_real_amp(0).unwrap();
+    /// assert!((amp - 0.5).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn report_qureg_params(&self) {
-        catch_quest_exception(|| unsafe {
-            ffi::reportQuregParams(self.reg);
-        })
-        .expect("report_qureg_params should never fail");
+    pub fn get_real_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qreal, QuestError> {
+        catch_quest_exception(|| unsafe { ffi::getRealAmp(self.reg, index) })
     }
 
-    /// Initializes a `Qureg` to have all-zero-amplitudes.
+    /// Get the imaginary part of the probability amplitude at an index
+    /// in the state vector.
     ///
-    /// This is an unphysical state, useful for iteratively building a state
-    /// with functions like
-    /// [`set_weighted_qureg()`][api-set-weighted-qureg], and should
-    /// not be confused with [`init_zero_state()`][api-init-zero-state].
+    /// # Parameters
+    ///
+    /// - `index`: index in state vector of probability amplitudes
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a density matrix
+    ///   - if `index` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
@@ -229,38 +374,36 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// qureg.init_blank_state();
-    ///
-    /// assert!(qureg.get_prob_amp(0).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(1).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(2).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(3).unwrap().abs() < EPSILON);
+    /// let amp = qureg.get_imag_amp(0).unwrap();
+    /// assert!(amp.abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [api-set-weighted-qureg]: crate::set_weighted_qureg()
-    /// [api-init-zero-state]: crate::Qureg::init_zero_state()
-    /// [api-qureg]: crate::Qureg
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_blank_state(&mut self) {
-        catch_quest_exception(|| unsafe {
-            ffi::initBlankState(self.reg);
-        })
-        .expect("init_blank_state should always succeed");
+    pub fn get_imag_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qreal, QuestError> {
+        catch_quest_exception(|| unsafe { ffi::getImagAmp(self.reg, index) })
     }
 
-    /// Initialize `qureg` into the zero state.
-    ///
-    /// If `qureg` is a state-vector of `N` qubits, it is modified to state
-    /// `|0>^{\otimes N}`.  If `qureg` is a density matrix of `N` qubits, it is
-    /// modified to state `|0><0|^{\otimes N}`.
+    /// Get the probability of a state-vector at an index in the full state
+    /// vector.
     ///
     /// # Parameters
     ///
+    /// - `index`: index in state vector of probability amplitudes
+    ///
+    /// # Errors
     ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a density matrix
+    ///   - if `index` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
@@ -269,382 +412,461 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// qureg.init_zero_state();
-    ///
-    /// assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(1).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(2).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(3).unwrap().abs() < EPSILON);
+    /// let amp = qureg.get_prob_amp(0).unwrap();
+    /// assert!((amp - 0.25).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [api-qureg]: crate::Qureg
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_zero_state(&mut self) {
-        catch_quest_exception(|| unsafe {
-            ffi::initZeroState(self.reg);
-        })
-        .expect("init_zero_state should always succeed");
+    pub fn get_prob_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qreal, QuestError> {
+        catch_quest_exception(|| unsafe { ffi::getProbAmp(self.reg, index) })
     }
+}
 
-    /// Initialize `qureg` into the plus state.
-    ///
-    /// If `qureg` is a state-vector of `N` qubits, it is modified to state:
-    ///
-    /// ```latex
-    ///   {| + \rangle}^{\otimes N} = \frac{1}{\sqrt{2^N}} (| 0 \rangle + | 1 \rangle)^{\otimes N}.
-    /// ```
-    ///
-    /// If `qureg` is a density matrix of `N`, it is modified to state:
-    ///
-    /// ```latex
-    ///   {| + \rangle\langle+|}^{\otimes N} = \frac{1}{{2^N}} \sum_i\sum_j |i\rangle\langle j|.
-    /// ```
+impl Qureg<DensityMatrixKind> {
+    ///  Creates a density matrix Qureg object.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// qureg.init_plus_state();
-    ///
-    /// assert!((qureg.get_prob_amp(0).unwrap() - 0.25).abs() < EPSILON);
-    /// assert!((qureg.get_prob_amp(1).unwrap() - 0.25).abs() < EPSILON);
-    /// assert!((qureg.get_prob_amp(2).unwrap() - 0.25).abs() < EPSILON);
-    /// assert!((qureg.get_prob_amp(3).unwrap() - 0.25).abs() < EPSILON);
+    /// let qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [api-qureg]: crate::Qureg
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_plus_state(&mut self) {
-        catch_quest_exception(|| unsafe {
-            ffi::initPlusState(self.reg);
+    /// # Errors
+    ///
+    /// Returns [`QuestError::InvalidQuESTInputError`](crate::QuestError::InvalidQuESTInputError)
+    /// on failure.  This is an exception thrown by `QuEST`.
+    ///
+    /// [Quest API]: https://quest-kit.github.io/QuEST/modules.html
+    pub fn try_new_density(
+        num_qubits: i32,
+        env: &QuestEnv,
+    ) -> Result<Self, QuestError> {
+        Ok(Self {
+            env:  env.clone(),
+            reg:  catch_quest_exception(|| unsafe {
+                ffi::createDensityQureg(num_qubits, env.raw())
+            })?,
+            kind: PhantomData,
         })
-        .expect("init_plus_state should always succeed");
     }
 
-    /// Initialize `qureg` into a classical state.
+    /// Creates a density matrix Qureg object initialized to a given matrix.
     ///
-    /// This state is also known as a "computational basis state" with index
-    /// `state_ind`.
+    /// The number of qubits is inferred from the dimension of `matrix`, which
+    /// must be a square matrix of size `2^N`, given as a slice of rows.
+    /// See [`init_density_matrix()`][api-init-density-matrix] for details.
     ///
-    /// If `qureg` is a state-vector, it will become: `|state_ind>`. If
-    /// `qureg`is a density matrix, it will become:
+    /// # Errors
     ///
-    /// ```text
-    ///   |state_ind> <state_ind|
-    /// ````
+    /// - [`ArrayLengthError`]
+    ///   - if `matrix` is not a square matrix of size `2^N`
+    /// - [`NonHermitianError`]
+    ///   - if `matrix` is not Hermitian
+    /// - [`TraceError`]
+    ///   - if the trace of `matrix` is not 1
+    /// - [`InvalidQuESTInputError`]
+    ///   - if the register cannot be created
     ///
-    /// Classical states are indexed from zero, so that `state_ind=0` produces
-    /// `|0..00>`,  and  `state_ind=1` produces `|00..01>`, and `state_ind=2^N -
-    /// 1` produces `|11..11>`. Subsequent calls to
-    /// [`get_prob_amp()`][api-get-prob-amp] will yield `0` for all indices
-    /// except `state_ind`,  and the phase of `state_ind`'s amplitude will
-    /// be `1` (real).
+    /// # Examples
     ///
-    /// This function can be used to initialise `qureg` into a specific binary
-    /// state  (e.g. `11001`) using a binary literal.
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let half = Qcomplex::new(0.5, 0.);
+    /// let qureg =
+    ///     Qureg::try_new_density_from_matrix(&[[half, half], [half, half]], &env)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(qureg.num_qubits(), 1);
+    /// assert!((qureg.calc_purity().unwrap() - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [api-init-density-matrix]: crate::Qureg::init_density_matrix()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`NonHermitianError`]: crate::QuestError::NonHermitianError
+    /// [`TraceError`]: crate::QuestError::TraceError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn try_new_density_from_matrix<R: AsRef<[Qcomplex]>>(
+        matrix: &[R],
+        env: &QuestEnv,
+    ) -> Result<Self, QuestError> {
+        let dim = matrix.len();
+        if !dim.is_power_of_two() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let mut qureg =
+            Self::try_new_density(dim.trailing_zeros() as i32, env)?;
+        qureg.init_density_matrix(matrix)?;
+        Ok(qureg)
+    }
+
+    /// Overwrites a contiguous subset of the amplitudes in a density-matrix.
+    ///
+    /// Only the first `reals.len()` amplitudes starting from row-column index
+    /// `(start_row, start_col)`, and proceeding down the column (wrapping
+    /// around between rows) will be modified. The resulting `qureg` may not
+    /// necessarily be in an L2 normalized state.
+    ///
+    /// In distributed mode, this function assumes the subset `reals` and
+    /// `imags` exist (at least) on the node containing the ultimately
+    /// updated elements. See also [`set_amps()`] for more details.
     ///
     /// # Parameters
     ///
-    ///  - `state_ind` the index of the basis state to modify `qureg` into
+    /// - `start_row`: the row-index of the first amplitude in `qureg` to modify
+    /// - `start_col`: the column-index of the first amplitude in `qureg` to
+    ///   modify
+    /// - `reals`: array of the real components of the new amplitudes
+    /// - `imags`: array of the imaginary components of the new amplitudes
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `state_ind` is outside [0, qureg.[`num_amps_total()`]).
+    /// - [`ArrayLengthError`]
+    ///   - if `reals.len()` and `imags.len()` are different
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `qureg` is not a density-matrix (i.e. is a state vector)
+    ///   - if `start_row` is outside [0, 1 << [`qureg.num_qubits()`]]
+    ///   - if `start_col` is outside [0, 1 << [`qureg.num_qubits()`]]
+    ///   - if `reals.len()` is outside [0, `qureg.num_amps_total()`]
+    ///   - if `reals.len()` is larger than the remaining number of amplitudes
+    ///     from (`start_row`, `start_col`), column-wise
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.init_classical_state(8);
-    /// let prob = qureg.get_prob_amp(0).unwrap();
+    /// let re = &[1., 2., 3.];
+    /// let im = &[4., 5., 6.];
+    /// let start_row = 1;
+    /// let start_col = 1;
+    /// qureg.set_density_amps(start_row, start_col, re, im);
     ///
-    /// assert!((prob.abs() - 1.) < EPSILON);
-    /// ```
+    /// let amp = qureg.get_density_amp(2, 1).unwrap();
     ///
+    /// assert!((amp.re - 2.).abs() < EPSILON);
+    /// assert!((amp.im - 5.).abs() < EPSILON);
+    /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [api-get-prob-amp]: crate::Qureg::get_prob_amp()
-    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`set_amps()`]: crate::Qureg::set_amps()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_classical_state(
+    pub fn set_density_amps(
         &mut self,
-        state_ind: i64,
+        start_row: i64,
+        start_col: i64,
+        reals: &[Qreal],
+        imags: &[Qreal],
     ) -> Result<(), QuestError> {
+        if reals.len() != imags.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_amps = reals.len() as i64;
         catch_quest_exception(|| unsafe {
-            ffi::initClassicalState(self.reg, state_ind);
+            ffi::setDensityAmps(
+                self.reg,
+                start_row,
+                start_col,
+                reals.as_ptr(),
+                imags.as_ptr(),
+                num_amps,
+            );
         })
     }
 
-    /// Initialize `qureg` into a pure state.
+    /// Overwrites the density matrix with a given matrix.
     ///
-    /// - If `qureg` is a state-vector, this merely clones `pure` into `qureg`.
-    /// - If `qureg` is a density matrix, this makes `qureg` 100% likely to be
-    ///   in the `pure` state.
+    /// The `matrix` is given as a slice of rows. Before any amplitudes are
+    /// set, `matrix` is checked to be Hermitian and to have unit trace, up
+    /// to [`REAL_EPS`] absolute tolerance. Positivity is not checked.
     ///
     /// # Parameters
     ///
-    /// - `pure`: a state-vector containing the pure state into which to
-    ///   initialize `qureg`
+    /// - `matrix`: square matrix of size `2^N`, where `N` is the number of
+    ///   qubits in `self`
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `self` and `pure` have mismatching dimensions
-    ///   - if `pure` is a density matrix
+    /// - [`ArrayLengthError`]
+    ///   - if `matrix` is not a square matrix of size `2^N`
+    /// - [`NonHermitianError`]
+    ///   - if `matrix` is not Hermitian
+    /// - [`TraceError`]
+    ///   - if the trace of `matrix` is not 1
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `self` is not a density matrix
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(3, &env)
+    /// let mut qureg = Qureg::try_new_density(1, &env)
     ///     .expect("cannot allocate memory for Qureg");
-    /// let pure_state =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.init_pure_state(&pure_state).unwrap();
+    /// let zero = Qcomplex::new(0., 0.);
+    /// let half = Qcomplex::new(0.5, 0.);
+    /// qureg
+    ///     .init_density_matrix(&[[half, zero], [zero, half]])
+    ///     .unwrap();
     ///
-    /// assert!((qureg.calc_purity().unwrap() - 1.0).abs() < EPSILON);
+    /// assert!((qureg.calc_purity().unwrap() - 0.5).abs() < EPSILON);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
+    /// [`REAL_EPS`]: crate::REAL_EPS
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`NonHermitianError`]: crate::QuestError::NonHermitianError
+    /// [`TraceError`]: crate::QuestError::TraceError
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_pure_state(
+    pub fn init_density_matrix<R: AsRef<[Qcomplex]>>(
         &mut self,
-        pure_: &Qureg<'_>,
+        matrix: &[R],
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::initPureState(self.reg, pure_.reg);
-        })
+        let dim = 1_usize << self.num_qubits();
+        if matrix.len() != dim
+            || matrix.iter().any(|row| row.as_ref().len() != dim)
+        {
+            return Err(QuestError::ArrayLengthError);
+        }
+
+        let elem = |row: usize, col: usize| matrix[row].as_ref()[col];
+        for row in 0..dim {
+            for col in row..dim {
+                if (elem(row, col) - elem(col, row).conj()).norm() > REAL_EPS {
+                    return Err(QuestError::NonHermitianError);
+                }
+            }
+        }
+        let trace: Qcomplex = (0..dim).map(|i| elem(i, i)).sum();
+        if (trace - 1.).norm() > REAL_EPS {
+            return Err(QuestError::TraceError);
+        }
+
+        // QuEST stores density matrices in column-major order
+        let (reals, imags): (Vec<_>, Vec<_>) = (0..dim)
+            .flat_map(|col| (0..dim).map(move |row| (row, col)))
+            .map(|(row, col)| (elem(row, col).re, elem(row, col).im))
+            .unzip();
+        self.set_density_amps(0, 0, &reals, &imags)
     }
 
-    /// Initialize `qureg` to be in a debug state.
+    /// Get an amplitude from a density matrix at a given row and column.
     ///
-    /// Set `qureg` to be in the un-normalized, non-physical state with
-    /// with `n`th complex amplitude given by:
+    /// # Parameters
     ///
-    /// ```text
-    ///   2n/10 + i*(2n+1)/10.
-    /// ```
+    /// - `row`: row of the desired amplitude in the density matrix
+    /// - `col`: column of the desired amplitude in the density matrix
     ///
-    /// This is used internally for debugging and testing.
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a state vector
+    ///   - if `row` or `col` are outside [0, [`num_qubits()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let amp = qureg.get_density_amp(0, 0).unwrap().re;
+    /// assert!((amp - 0.25).abs() < EPSILON);
+    /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_debug_state(&mut self) {
+    pub fn get_density_amp(
+        &self,
+        row: i64,
+        col: i64,
+    ) -> Result<Qcomplex, QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::initDebugState(self.reg);
+            ffi::getDensityAmp(self.reg, row, col)
         })
-        .expect("init_debug_state() should always succeed");
+        .map(Into::into)
     }
 
-    /// Initialize `qureg` by specifying all amplitudes.
+    /// Mixes a density matrix to induce single-qubit dephasing noise.
     ///
-    /// For density matrices, it is assumed the amplitudes have been flattened
-    /// column-wise into the given arrays.
+    /// With probability `prob`, applies Pauli Z to `target_qubit` in `qureg`.
     ///
-    /// The real and imaginary components of the amplitudes are passed in
-    /// separate arrays, `reals` and `imags`, each of which must have length
-    /// [`qureg.num_amps_total()`]. There is no automatic checking that the
-    /// passed arrays are L2 normalized, so this can be used to prepare `qureg`
-    /// in a non-physical state.
+    /// This transforms `qureg = rho` into the mixed state:
     ///
-    /// In distributed mode, this would require the complete state to fit in
-    /// every node. To manually prepare a state for which all amplitudes cannot
-    /// fit into a single node, use [`set_amps()`]
+    /// ```text
+    /// (1 - prob) * rho  +  prob * Z_q rho Z_q,
+    /// ```
+    ///
+    /// where `q = target_qubit`. The coefficient `prob` cannot exceed `1/2`,
+    /// which maximally mixes `target_qubit`.
     ///
     /// # Parameters
     ///
-    /// - `reals`: array of the real components of the new amplitudes
-    /// - `imags`: array of the imaginary components of the new amplitudes
+    /// - `target_qubit`: qubit upon which to induce dephasing noise
+    /// - `prob`: the probability of the phase error occurring
     ///
     /// # Errors
     ///
-    /// - [`ArrayLengthError`],
-    ///   - if either `reals` or `imags` have fewer than
-    ///     [`qureg.num_amps_total()`] elements
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is not a density matrix
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///   - if `prob` is not in `[0, 1/2]`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// qureg.init_state_from_amps(&[1., 0., 0., 0.], &[0., 0., 0., 0.]);
-    /// let prob = qureg.get_prob_amp(0).unwrap();
+    /// qureg.mix_dephasing(0, 0.5).unwrap();
     ///
-    /// assert!((prob - 1.).abs() < EPSILON);
+    /// let amp = qureg.get_density_amp(0, 0).unwrap();
+    /// assert!((amp.re - 0.25).abs() < EPSILON);
+    /// let amp = qureg.get_density_amp(0, 1).unwrap();
+    /// assert!(amp.re.abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`qureg.num_amps_total()`]: crate::Qureg::num_amps_total()
-    /// [`set_amps()`]: crate::Qureg::set_amps()
-    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_state_from_amps(
+    pub fn mix_dephasing(
         &mut self,
-        reals: &[Qreal],
-        imags: &[Qreal],
+        target_qubit: i32,
+        prob: Qreal,
     ) -> Result<(), QuestError> {
-        let num_amps_total = self.num_amps_total() as usize;
-        if reals.len() < num_amps_total || imags.len() < num_amps_total {
-            return Err(QuestError::ArrayLengthError);
-        }
         catch_quest_exception(|| unsafe {
-            ffi::initStateFromAmps(self.reg, reals.as_ptr(), imags.as_ptr());
+            ffi::mixDephasing(self.reg, target_qubit, prob);
         })
     }
 
-    /// Overwrites a contiguous subset of the amplitudes in a state-vector.
-    ///
-    /// Only amplitudes with indices in `[start_ind,  start_ind + reals.len()]`
-    /// will be changed. The resulting `qureg` may not necessarily be in an
-    /// L2 normalized state.
-    ///
-    /// In distributed mode, this function assumes the subset `reals` and
-    /// `imags` exist (at least) on the node containing the ultimately
-    /// updated elements. For example, below is the correct way to modify
-    /// the full 8 elements of `qureg` when split between 2 nodes:
+    /// Mixes a density matrix `qureg` to induce two-qubit dephasing noise.
     ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// With probability `prob`, applies Pauli Z to either or both in `qureg`.
     ///
-    /// let re = &mut [1., 2., 3., 4.];
-    /// let im = &mut [1., 2., 3., 4.];
-    /// qureg.set_amps(0, re, im);
+    /// This transforms `qureg = rho` into the mixed state:
     ///
-    /// // modify re and im to the next set of elements
-    /// for i in 0..4 {
-    ///     re[i] += 4.;
-    ///     im[i] += 4.;
-    /// }
-    /// qureg.set_amps(4, re, im);
+    /// ```text
+    /// (1 - prob) * rho  +  prob * 1/3 * (
+    ///         Z_a  rho  Z_a +
+    ///         Z_b  rho  Z_b +
+    ///         Z_a Z_b rho Z_a Z_b
+    ///    )
     /// ```
     ///
+    /// where `a = qubit1`, `b=qubit2`. The coefficient `prob` cannot exceed
+    /// `3/4`, at which maximal mixing occurs.
+    ///
     /// # Parameters
     ///
-    /// - `start_ind`: the index of the first amplitude in `qureg` to modify
-    /// - `reals`: array of the real components of the new amplitudes
-    /// - `imags`: array of the imaginary components of the new amplitudes
+    /// - `qubit1`: qubit upon which to induce dephasing noise
+    /// - `qubit2`: qubit upon which to induce dephasing noise
+    /// - `prob`: the probability of the phase error occurring
     ///
     /// # Errors
     ///
-    /// - [`ArrayLengthError`]
-    ///   - if `reals.len()` and `imags.len()` are different
-    ///
-    /// - [`InvalidQuESTInputError`]
-    ///   - if `qureg` is not a state-vector (i.e. is a density matrix)
-    ///   - if `start_ind` is outside [0, [`qureg.num_amps_total()`]]
-    ///   - if `reals.len()` is outside [0, `qureg.num_amps_total()`]
-    ///   - if `reals.len()` + `start_ind` >= `qureg.num_amps_total()`
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is not a density matrix
+    ///   - if `qubit1` or `qubit2` are outside [0, [`num_qubits()`]).
+    ///   - if `qubit1 = qubit2`
+    ///   - if `prob` is not in `[0, 3/4]`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// let re = &[1., 2., 3.];
-    /// let im = &[4., 5., 6.];
-    /// let start_ind = 1;
-    /// qureg.set_amps(start_ind, re, im);
+    /// qureg.mix_two_qubit_dephasing(0, 1, 0.75).unwrap();
     ///
-    /// let amp = qureg.get_real_amp(3).unwrap();
-    /// assert!((amp - 3.).abs() < EPSILON);
+    /// let amp = qureg.get_density_amp(0, 0).unwrap();
+    /// assert!((amp.re - 0.125).abs() < EPSILON);
+    /// let amp = qureg.get_density_amp(0, 1).unwrap();
+    /// assert!(amp.re.abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`qureg.num_amps_total()`]: crate::Qureg::num_amps_total()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn set_amps(
+    pub fn mix_two_qubit_dephasing(
         &mut self,
-        start_ind: i64,
-        reals: &[Qreal],
-        imags: &[Qreal],
+        qubit1: i32,
+        qubit2: i32,
+        prob: Qreal,
     ) -> Result<(), QuestError> {
-        if reals.len() != imags.len() {
-            return Err(QuestError::ArrayLengthError);
-        }
-        let num_amps = reals.len() as i64;
         catch_quest_exception(|| unsafe {
-            ffi::setAmps(
-                self.reg,
-                start_ind,
-                reals.as_ptr(),
-                imags.as_ptr(),
-                num_amps,
-            );
+            ffi::mixTwoQubitDephasing(self.reg, qubit1, qubit2, prob);
         })
     }
 
-    /// Overwrites a contiguous subset of the amplitudes in a density-matrix.
+    /// Mixes a density matrix to induce single-qubit homogeneous
+    /// depolarising noise.
+    /// This is equivalent to, with probability `prob`, uniformly randomly
+    /// applying either Pauli X, Y, or Z to `target_qubit`.
     ///
-    /// Only the first `reals.len()` amplitudes starting from row-column index
-    /// `(start_row, start_col)`, and proceeding down the column (wrapping
-    /// around between rows) will be modified. The resulting `qureg` may not
-    /// necessarily be in an L2 normalized state.
+    /// This transforms `qureg = rho` into the mixed state:
     ///
-    /// In distributed mode, this function assumes the subset `reals` and
-    /// `imags` exist (at least) on the node containing the ultimately
-    /// updated elements. See also [`set_amps()`] for more details.
+    /// ```text
+    /// (1 - prob) * rho  +  prob * 1/3 * (
+    ///      X_q rho X_q +
+    ///      Y_q rho Y_q +
+    ///      Z_q rho Z_q
+    /// )
+    /// ```
+    ///
+    /// where `q = target_qubit`. The coefficient `prob` cannot exceed `3/4`, at
+    /// which maximal mixing occurs.
     ///
     /// # Parameters
     ///
-    /// - `start_row`: the row-index of the first amplitude in `qureg` to modify
-    /// - `start_col`: the column-index of the first amplitude in `qureg` to
-    ///   modify
-    /// - `reals`: array of the real components of the new amplitudes
-    /// - `imags`: array of the imaginary components of the new amplitudes
+    /// - `target_qubit`: qubit upon which to induce depolarizing noise
+    /// - `prob`: the probability of the depolarizing error occurring
     ///
     /// # Errors
     ///
-    /// - [`ArrayLengthError`]
-    ///   - if `reals.len()` and `imags.len()` are different
-    ///
-    /// - [`InvalidQuESTInputError`]
-    ///   - if `qureg` is not a density-matrix (i.e. is a state vector)
-    ///   - if `start_row` is outside [0, 1 << [`qureg.num_qubits()`]]
-    ///   - if `start_col` is outside [0, 1 << [`qureg.num_qubits()`]]
-    ///   - if `reals.len()` is outside [0, `qureg.num_amps_total()`]
-    ///   - if `reals.len()` is larger than the remaining number of amplitudes
-    ///     from (`start_row`, `start_col`), column-wise
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is not a density matrix
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///   - if `prob` is not in `[0, 3/4]`
     ///
     /// # Examples
     ///
@@ -654,245 +876,247 @@ impl<'a> Qureg<'a> {
     /// let mut qureg = Qureg::try_new_density(2, &env)
     ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// let re = &[1., 2., 3.];
-    /// let im = &[4., 5., 6.];
-    /// let start_row = 1;
-    /// let start_col = 1;
-    /// qureg.set_density_amps(start_row, start_col, re, im);
-    ///
-    /// let amp = qureg.get_density_amp(2, 1).unwrap();
+    /// qureg.mix_depolarising(0, 0.75).unwrap();
+    /// let amp = qureg.get_density_amp(0, 0).unwrap();
     ///
-    /// assert!((amp.re - 2.).abs() < EPSILON);
-    /// assert!((amp.im - 5.).abs() < EPSILON);
+    /// assert!((amp.re - 0.5) < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`set_amps()`]: crate::Qureg::set_amps()
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn set_density_amps(
+    pub fn mix_depolarising(
         &mut self,
-        start_row: i64,
-        start_col: i64,
-        reals: &[Qreal],
-        imags: &[Qreal],
+        target_qubit: i32,
+        prob: Qreal,
     ) -> Result<(), QuestError> {
-        if reals.len() != imags.len() {
-            return Err(QuestError::ArrayLengthError);
-        }
-        let num_amps = reals.len() as i64;
         catch_quest_exception(|| unsafe {
-            ffi::setDensityAmps(
-                self.reg,
-                start_row,
-                start_col,
-                reals.as_ptr(),
-                imags.as_ptr(),
-                num_amps,
-            );
+            ffi::mixDepolarising(self.reg, target_qubit, prob);
         })
     }
 
-    /// Shift the phase of a single qubit by a given angle.
+    ///  Mixes a density matrix to induce single-qubit amplitude damping.
     ///
-    /// This is equivalent to a Z-axis rotation of the Bloch-sphere up to a
-    /// global phase factor.
+    /// With probability `prob`, applies damping (transition from `1` to `0`
+    /// state). This transforms `qureg = rho` into the mixed state:
     ///
-    /// For angle `theta`, this effects single-qubit unitary
+    /// ```text
+    ///  K_0 rho K_0^\dagger + K_1 rho K_1^\dagger
+    /// ```
+    ///
+    /// where `q = target_qubit` and `K_0` and `$K_1` are Kraus operators:
     ///
     /// ```text
-    ///   [ 1      0        ]
-    ///   [ 0  exp(i theta) ]
+    ///      K_0 =  [ 1       0       ]   K_1 = [ 0  sqrt(prob) ]
+    ///             [ 0  sqrt(1-prob) ]         [ 0      0      ]
     /// ```
     ///
-    /// # Parameters
+    /// The coefficient `prob` cannot exceed 1, at which total damping/decay
+    /// occurs.
     ///
-    /// - `target_qubit`: qubit to undergo a phase shift
-    /// - `angle`: amount by which to shift the phase in radians
+    /// Note that unlike [`mix_dephasing()`] and [`mix_depolarising()`], this
+    /// function can increase the purity of a mixed state (by, as `prob` becomes
+    /// `1`, gaining certainty that the qubit is in the 0 state).
+    ///
+    /// # Parameters
     ///
+    /// - `target_qubit`: qubit upon which to induce amplitude damping
+    /// - `prob`: the probability of the damping
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`], if
-    ///   - `target_qubit` is outside `[0, N)`.
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is not a density matrix
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///   - if `prob` is not in `[0, 1]`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// let target_qubit = 1;
-    /// let angle = 0.5;
+    /// qureg.mix_damping(0, 1.).unwrap();
     ///
-    /// qureg.phase_shift(target_qubit, angle).unwrap();
+    /// let amp = qureg.get_density_amp(0, 0).unwrap();
+    /// assert!((amp.re - 1.) < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`mix_dephasing()`]: crate::Qureg::mix_dephasing()
+    /// [`mix_depolarising()`]: crate::Qureg::mix_depolarising()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn phase_shift(
+    pub fn mix_damping(
         &mut self,
         target_qubit: i32,
-        angle: Qreal,
+        prob: Qreal,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::phaseShift(self.reg, target_qubit, angle);
+            ffi::mixDamping(self.reg, target_qubit, prob);
         })
     }
 
-    /// Controlled shift of the phase of a single qubit by a given angle.
+    /// Mixes a density matrix to induce two-qubit homogeneous depolarising
+    /// noise.
     ///
-    /// Introduce a phase factor `exp(i theta)` on state `|11>` of qubits
-    /// `id_qubit1` and `id_qubit2`.  For angle `theta`, this effects
-    /// the unitary
+    /// With probability `prob`, applies to `qubit1` and `qubit2` any operator
+    /// of the set:
     ///
     /// ```text
-    ///  [ 1  0  0        0      ]
-    ///  [ 0  1  0        0      ]
-    ///  [ 0  0  1        0      ]
-    ///  [ 0  0  0  exp(i theta) ]
+    /// { IX, IY, IZ, XI, YI, ZI, XX, XY, XZ, YX, YY, YZ, ZX, ZY, ZZ }.
     /// ```
     ///
+    /// Note this is the set of all two-qubit Pauli gates excluding `II`.
+    ///
     /// # Parameters
     ///
-    /// - `id_qubit1`: first qubit in the state to phase shift
-    /// - `id_qubit2`: second qubit in the state to phase shift
-    /// - `angle`: amount by which to shift the phase in radians
+    /// - `qubit1`: qubit upon which to induce depolarizing noise
+    /// - `qubit2`: qubit upon which to induce depolarizing noise
+    /// - `prob`: the probability of the phase error occurring
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`], if
-    ///   - if `id_qubit1` or `id_qubit2` are outside `[0, N)`
-    ///   - if `id_qubit1` and `id_qubit2` are equal
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is not a density matrix
+    ///   - if `qubit1` or `qubit2` are outside [0, [`num_qubits()`]).
+    ///   - if `qubit1 = qubit2`
+    ///   - if `prob` is not in `[0, 15/16]`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// let id_qubit1 = 0;
-    /// let id_qubit2 = 2;
-    /// let angle = 0.5;
-    /// qureg
-    ///     .controlled_phase_shift(id_qubit1, id_qubit2, angle)
-    ///     .unwrap();
+    /// qureg.mix_two_qubit_depolarising(0, 1, 15. / 16.).unwrap();
+    ///
+    /// let amp = qureg.get_density_amp(0, 0).unwrap();
+    /// assert!((amp.re - 0.125).abs() < EPSILON);
+    /// let amp = qureg.get_density_amp(0, 1).unwrap();
+    /// assert!(amp.re.abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_phase_shift(
+    pub fn mix_two_qubit_depolarising(
         &mut self,
-        id_qubit1: i32,
-        id_qubit2: i32,
-        angle: Qreal,
+        qubit1: i32,
+        qubit2: i32,
+        prob: Qreal,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledPhaseShift(self.reg, id_qubit1, id_qubit2, angle);
+            ffi::mixTwoQubitDepolarising(self.reg, qubit1, qubit2, prob);
         })
     }
 
-    /// Introduce a phase factor of the passed qubits.
+    /// Mixes a density matrix to induce general single-qubit Pauli noise.
     ///
-    /// The phase factor is `exp(i theta)`, controlled by the state `|1..1>` of
-    /// the passed qubits.
+    /// With probabilities `prob_x`, `prob_y` and `prob_z`, applies Pauli X, Y,
+    /// and Z respectively to `target_qubit`.
+    ///
+    /// This function operates by first converting the given Pauli
+    /// probabilities into a single-qubit Kraus map (four 2x2 operators).
     ///
     /// # Parameters
     ///
-    /// - `control_qubits`: array of qubits to phase shift
-    /// - `angle`: amount by which to shift the phase in radians
+    /// - `target_qubit`: qubit to decohere
+    /// - `prob_x`: the probability of inducing an X error
+    /// - `prob_y`: the probability of inducing an Y error
+    /// - `prob_z`: the probability of inducing an Z error
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`], if
-    ///   - if `control_qubits.len()` is outside `[0, N)`
-    ///   - if any qubit index in `control_qubits` is outside `[0, N)`
-    ///   - if qubits in `control_qubits` are not unique
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is not a density matrix
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`])
+    ///   - if any of `prob_x`, `prob_y`, `prob_z` are not in `[0, 1]`
+    ///   - if any of p in `{prob_x, prob_y or prob_z}` don't satisfy `p <= (1 -
+    ///     prob_x - prob_y - prob_z)`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// let control_qubits = &[0, 1, 3];
-    /// let angle = 0.5;
-    /// qureg
-    ///     .multi_controlled_phase_shift(control_qubits, angle)
-    ///     .unwrap();
+    /// let (prob_x, prob_y, prob_z) = (0.25, 0.25, 0.25);
+    /// qureg.mix_pauli(0, prob_x, prob_y, prob_z).unwrap();
+    ///
+    /// let mut outcome_prob = -1.;
+    /// let _ = qureg.measure_with_stats(0, &mut outcome_prob).unwrap();
+    ///
+    /// assert!((outcome_prob - 0.5).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_phase_shift(
+    pub fn mix_pauli(
         &mut self,
-        control_qubits: &[i32],
-        angle: Qreal,
+        target_qubit: i32,
+        prob_x: Qreal,
+        prob_y: Qreal,
+        prob_z: Qreal,
     ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::multiControlledPhaseShift(
-                self.reg,
-                control_qubits.as_ptr(),
-                num_control_qubits,
-                angle,
-            );
+            ffi::mixPauli(self.reg, target_qubit, prob_x, prob_y, prob_z);
         })
     }
 
-    /// Apply the (two-qubit) controlled phase flip gate.
-    ///
-    /// Also known as the controlled pauliZ gate. For each state, if both input
-    /// qubits have value one, multiply the amplitude of that state by `-1`.
-    /// This applies the two-qubit unitary:
+    /// Modifies register with `other_qureg`.
     ///
-    /// ```text
-    ///   [ 1  0  0   0 ]
-    ///   [ 0  1  0   0 ]
-    ///   [ 0  0  1   0 ]
-    ///   [ 0  0  0  -1 ]
-    /// ```
+    /// The state becomes `(1-prob) * self +  prob * other_qureg`.  The
+    /// probability `prob` must be in `[0,1]`.
     ///
     /// # Parameters
     ///
-    /// - `id_qubit1`: first qubit in the state to operate on
-    /// - `id_qubit2`: second qubit in the state to operate on
+    /// - `prob`: the probability of `other_qureg` in the modified register
+    /// - `other_qureg`: a density matrix to be mixed into
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`], if
-    ///   - if `id_qubit1` or `id_qubit2` are outside `[0, N)`
-    ///   - if `id_qubit1` and `id_qubit2` are equal
+    /// - [`InvalidQuESTInputError`],
+    ///   - if either `self` or `other_qureg` are not density matrices
+    ///   - if the dimensions of `self` and `other_qureg` do not match
+    ///   - if `prob` is not in `[0, 1]`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_zero_state();
+    /// let mut combine_qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// let other_qureg = {
+    ///     let mut other_qureg = Qureg::try_new_density(2, &env)
+    ///         .expect("cannot allocate memory for Qureg");
+    ///     other_qureg.init_classical_state(3).unwrap();
+    ///     other_qureg
+    /// };
     ///
-    /// qureg.controlled_phase_flip(0, 1);
+    /// combine_qureg.mix_density_matrix(0.5, &other_qureg).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
@@ -900,618 +1124,625 @@ impl<'a> Qureg<'a> {
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_phase_flip(
+    pub fn mix_density_matrix(
         &mut self,
-        id_qubit1: i32,
-        id_qubit2: i32,
+        prob: Qreal,
+        other_qureg: &DensityMatrix,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledPhaseFlip(self.reg, id_qubit1, id_qubit2);
+            ffi::mixDensityMatrix(self.reg, prob, other_qureg.reg);
         })
     }
 
-    /// Apply the (multiple-qubit) controlled phase flip gate.
-    ///
-    /// Also known as the multiple-qubit controlled pauliZ gate. For each state,
-    /// if all control qubits have value one, multiply the amplitude of that
-    /// state by `-1`. This applies the many-qubit unitary:
+    /// Calculate the purity of a density matrix.
     ///
-    /// ```text
-    ///   [  1  0  0  0   0  ]
-    ///   [  0  1  0  0   0  ]
-    ///   [  0  0   ...   0  ]
-    ///   [  0  0  0  1   0  ]
-    ///   [  0  0  0  0  -1  ]
-    /// ```
-    /// on the control qubits.
+    /// The purity of a density matrix is calculated by taking the trace of the
+    /// density matrix squared. Returns `Tr (\rho^2)`.
+    /// For a pure state, this =1.
+    /// For a mixed state, the purity is less than 1 and is lower bounded by
+    /// `1/2^n`, where n is the number of qubits. The minimum purity is achieved
+    /// for the maximally mixed state `identity/2^n`.
+    ///
+    /// This function does not accept state-vectors, which clearly have purity
+    /// 1.
+    ///
+    /// Note this function will give incorrect results for non-Hermitian Quregs
+    /// (i.e. invalid density matrices), which will disagree with
+    /// `Tr(\rho^2)`. Instead, this function returns `\sum_{ij}
+    /// |\rho_{ij}|^2`.
     ///
     /// # Parameters
     ///
-    /// - `control_qubits`: array of input qubits
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`], if
-    ///   - if `control_qubits.len()` is outside `[0, N)`
-    ///   - if any qubit index in `control_qubits` is outside `[0, N)`
-    ///   - if qubits in `control_qubits` are not unique
+    /// Returns [`InvalidQuESTInputError`],
+    ///
+    /// - if the argument `qureg` is not a density matrix
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_zero_state();
+    /// let qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// let control_qubits = &[0, 1, 3];
-    /// qureg.multi_controlled_phase_flip(control_qubits);
+    /// let purity = qureg.calc_purity().unwrap();
+    /// assert!((purity - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_phase_flip(
-        &mut self,
-        control_qubits: &[i32],
-    ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::multiControlledPhaseFlip(
-                self.reg,
-                control_qubits.as_ptr(),
-                control_qubits.len() as i32,
-            );
-        })
+    pub fn calc_purity(&self) -> Result<Qreal, QuestError> {
+        catch_quest_exception(|| unsafe { ffi::calcPurity(self.reg) })
     }
 
-    /// Apply the single-qubit S gate.
-    ///
-    /// This is a rotation of `PI/2` around the Z-axis on the Bloch sphere, or
-    /// the unitary:
-    ///
-    /// ```text
-    ///   [ 1  0 ]
-    ///   [ 0  i ]
-    /// ```
-    ///
-    /// # Parameters
-    ///
-    /// - `target_qubit`: qubit to operate upon
-    ///
-    /// # Errors
+    /// Apply a general single-qubit Kraus map to a density matrix.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    /// The map is specified by at most four Kraus operators.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_zero_state();
-    /// qureg.pauli_x(0).unwrap();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.s_gate(0).unwrap();
+    /// let m = &ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    /// let target = 1;
+    /// qureg.mix_kraus_map(target, &[m]).unwrap();
     ///
-    /// let amp = qureg.get_imag_amp(1).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// // Check is the register is now in the state |01>
+    /// let amp = qureg.get_density_amp(2, 2).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn s_gate(
+    pub fn mix_kraus_map(
         &mut self,
-        target_qubit: i32,
+        target: i32,
+        ops: &[&ComplexMatrix2],
     ) -> Result<(), QuestError> {
+        let num_ops = ops.len() as i32;
+        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
         catch_quest_exception(|| unsafe {
-            ffi::sGate(self.reg, target_qubit);
+            ffi::mixKrausMap(self.reg, target, ops_inner.as_ptr(), num_ops);
         })
     }
 
-    /// Apply the single-qubit T gate.
-    ///
-    /// This is a rotation of `PI/4` around the Z-axis on the Bloch sphere, or
-    /// the unitary:
-    ///
-    /// ```text
-    ///   [ 1       0       ]
-    ///   [ 0  e^(i PI / 4) ]
-    /// ```
-    ///
-    /// # Parameters
-    ///
-    /// - `target_qubit`: qubit to operate upon
-    ///
-    /// # Errors
-    ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    /// Apply a general two-qubit Kraus map to a density matrix.
     ///
+    /// The map is specified by at most sixteen Kraus operators.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_zero_state();
-    /// qureg.pauli_x(0).unwrap();
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.t_gate(0).unwrap();
+    /// let m = &ComplexMatrix4::new(
+    ///     [
+    ///         [0., 0., 0., 1.],
+    ///         [0., 1., 0., 0.],
+    ///         [0., 0., 1., 0.],
+    ///         [1., 0., 0., 0.],
+    ///     ],
+    ///     [
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///     ],
+    /// );
+    /// let target1 = 1;
+    /// let target2 = 2;
+    /// qureg
+    ///     .mix_two_qubit_kraus_map(target1, target2, &[m])
+    ///     .unwrap();
     ///
-    /// let amp = qureg.get_imag_amp(1).unwrap();
-    /// assert!((amp - SQRT_2 / 2.).abs() < EPSILON);
+    /// // Check is the register is now in the state |011>
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn t_gate(
+    pub fn mix_two_qubit_kraus_map(
         &mut self,
-        target_qubit: i32,
+        target1: i32,
+        target2: i32,
+        ops: &[&ComplexMatrix4],
     ) -> Result<(), QuestError> {
+        let num_ops = ops.len() as i32;
+        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
         catch_quest_exception(|| unsafe {
-            ffi::tGate(self.reg, target_qubit);
+            ffi::mixTwoQubitKrausMap(
+                self.reg,
+                target1,
+                target2,
+                ops_inner.as_ptr(),
+                num_ops,
+            );
         })
     }
 
-    /// Overwrite the amplitudes of `target_qureg` with those from `copy_qureg`.
-    ///
-    /// # Parameters
-    ///
-    /// - `copy_qureg`: the `Qureg` to have its quantum state clone into `self`
-    ///
-    /// # Errors
+    /// Apply a general N-qubit Kraus map to a density matrix.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `self` is a state-vector while `copy_qureg` is a density matrix
-    ///     (and vice versa)
-    ///   - if `self` and `copy_qureg` have different dimensions
+    /// The map is specified by at most `(2N)^2` Kraus operators.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    /// let copy_qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.clone_qureg(&copy_qureg);
+    /// let m = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// init_complex_matrix_n(
+    ///     m,
+    ///     &[
+    ///         &[0., 0., 0., 1.],
+    ///         &[0., 1., 0., 0.],
+    ///         &[0., 0., 1., 0.],
+    ///         &[1., 0., 0., 0.],
+    ///     ],
+    ///     &[
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///     ],
+    /// )
+    /// .unwrap();
+    /// let targets = &[1, 2];
+    /// qureg.mix_multi_qubit_kraus_map(targets, &[m]).unwrap();
+    ///
+    /// // Check if the register is now in the state |011>
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn clone_qureg(
+    pub fn mix_multi_qubit_kraus_map(
         &mut self,
-        copy_qureg: &Qureg<'_>,
+        targets: &[i32],
+        ops: &[&ComplexMatrixN],
     ) -> Result<(), QuestError> {
+        let num_targets = targets.len() as i32;
+        let num_ops = ops.len() as i32;
+        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
         catch_quest_exception(|| unsafe {
-            ffi::cloneQureg(self.reg, copy_qureg.reg);
+            ffi::mixMultiQubitKrausMap(
+                self.reg,
+                targets.as_ptr(),
+                num_targets,
+                ops_inner.as_ptr(),
+                num_ops,
+            );
         })
     }
 
-    /// Performs a logical AND on all successCodes held by all processes.
+    /// Apply a general non-trace-preserving single-qubit Kraus map.
     ///
-    /// If any one process has a zero `success_code`, all processes will return
-    /// a zero success code.
+    /// The state must be a density matrix, and the map is specified by at most
+    /// four operators.
     ///
-    /// # Parameters
+    /// # Examples
     ///
-    /// - `success_code`: `1` if process task succeeded, `0` if process task
-    ///   failed
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// # Returns
+    /// let m = &ComplexMatrix2::new([[0., 1.], [0., 0.]], [[0., 0.], [0., 0.]]);
+    /// let target = 1;
+    /// qureg.mix_nontp_kraus_map(target, &[m]).unwrap();
     ///
-    /// `1` if all processes succeeded, `0` if any one process failed
+    /// // The register is in an unphysical null state
+    /// let amp = qureg.get_density_amp(2, 2).unwrap();
+    /// assert!(amp.re.abs() < EPSILON);
+    /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[must_use]
-    pub fn sync_quest_success(success_code: i32) -> i32 {
-        catch_quest_exception(|| unsafe { ffi::syncQuESTSuccess(success_code) })
-            .expect("sync_quest_success should always succeed")
-    }
-
-    /// Copy the state-vector (or density matrix) into GPU memory.
-    ///
-    /// In GPU mode, this copies the state-vector (or density matrix) from RAM
-    /// to VRAM / GPU-memory, which is the version operated upon by other calls
-    /// to the API.
-    ///
-    /// In CPU mode, this function has no effect.
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn mix_nontp_kraus_map(
+        &mut self,
+        target: i32,
+        ops: &[&ComplexMatrix2],
+    ) -> Result<(), QuestError> {
+        let num_ops = ops.len() as i32;
+        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
+        catch_quest_exception(|| unsafe {
+            ffi::mixNonTPKrausMap(
+                self.reg,
+                target,
+                ops_inner.as_ptr(),
+                num_ops,
+            );
+        })
+    }
+
+    /// Apply a general non-trace-preserving two-qubit Kraus map.
     ///
-    /// In conjunction with [`copy_state_from_gpu()`][api-copy-state-from-gpu]
-    /// (which should be called first), this allows a user to directly modify
-    /// the state-vector in a hardware agnostic way. Note though that users
-    /// should instead use [`set_amps()`][api-set-amps] if possible.
+    /// The state must be a density matrix, and the map is specified
+    /// by at most 16 operators.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.copy_state_to_gpu();
+    /// let m = &ComplexMatrix4::new(
+    ///     [
+    ///         [0., 0., 0., 1.],
+    ///         [0., 1., 0., 0.],
+    ///         [0., 0., 1., 0.],
+    ///         [0., 0., 0., 0.],
+    ///     ],
+    ///     [
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///     ],
+    /// );
+    /// let target1 = 1;
+    /// let target2 = 2;
+    /// qureg
+    ///     .mix_nontp_two_qubit_kraus_map(target1, target2, &[m])
+    ///     .unwrap();
+    ///
+    /// // The register is in an unphysical null state
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!(amp.re.abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [api-copy-state-from-gpu]: crate::Qureg::copy_state_from_gpu()
-    /// [api-set-amps]: crate::Qureg::set_amps()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn copy_state_to_gpu(&mut self) {
+    pub fn mix_nontp_two_qubit_kraus_map(
+        &mut self,
+        target1: i32,
+        target2: i32,
+        ops: &[&ComplexMatrix4],
+    ) -> Result<(), QuestError> {
+        let num_ops = ops.len() as i32;
+        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
         catch_quest_exception(|| unsafe {
-            ffi::copyStateToGPU(self.reg);
+            ffi::mixNonTPTwoQubitKrausMap(
+                self.reg,
+                target1,
+                target2,
+                ops_inner.as_ptr(),
+                num_ops,
+            );
         })
-        .expect("copy_state_to_gpu should always succeed");
     }
 
-    /// Copy the state-vector (or density matrix) from GPU memory.
-    ///
-    /// In GPU mode, this copies the state-vector (or density matrix) from GPU
-    /// memory to RAM , where it can be accessed/modified  by the user.
-    ///
-    /// In CPU mode, this function has no effect.
+    /// Apply a general N-qubit non-trace-preserving Kraus map.
     ///
-    /// In conjunction with [`copy_state_to_gpu()`][api-copy-state-to-gpu] ,
-    /// this allows a user to directly modify the state-vector in a hardware
-    /// agnostic way. Note though that users should instead use
-    /// [`set_amps()`][api-set-amps] if possible.
+    /// The state must be a density matrix, and the map is specified
+    /// by at most `2^(2N)` operators.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// let m = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// init_complex_matrix_n(
+    ///     m,
+    ///     &[
+    ///         &[0., 0., 0., 1.],
+    ///         &[0., 1., 0., 0.],
+    ///         &[0., 0., 1., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///     ],
+    ///     &[
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///     ],
+    /// )
+    /// .unwrap();
+    /// let targets = &[1, 2];
+    /// qureg
+    ///     .mix_nontp_multi_qubit_kraus_map(targets, &[m])
+    ///     .unwrap();
     ///
-    /// qureg.copy_state_from_gpu();
+    /// // The register is in an unphysical null state
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!(amp.re.abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [api-copy-state-to-gpu]: crate::Qureg::copy_state_to_gpu()
-    /// [api-set-amps]: crate::Qureg::set_amps()
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn copy_state_from_gpu(&mut self) {
-        catch_quest_exception(|| unsafe { ffi::copyStateFromGPU(self.reg) })
-            .expect("copy_state_from_gpu should always succeed");
-    }
-
-    /// Copy a part the state-vector (or density matrix) into GPU memory.
-    ///
-    /// In GPU mode, this copies a substate of the state-vector (or density
-    /// matrix) from RAM to VRAM / GPU-memory.
-    ///
-    /// In CPU mode, this function has no effect.
-    ///
-    /// In conjunction with
-    /// [`copy_substate_from_gpu()`][api-copy-substate-from-gpu], this allows a
-    /// user to directly modify a subset of the amplitudes the state-vector
-    /// in a hardware agnostic way, without having to load the entire state
-    /// via [`copy_state_to_gpu()`][api-copy-state-to-gpu].
-    ///
-    /// Note though that users should instead use [`set_amps()`][api-set-amps]
-    /// if possible.
-    ///
-    /// # Parameters
-    ///
-    /// - `start_ind` the index of the first amplitude to copy
-    /// - `num_amps` the number of contiguous amplitudes to copy (starting with
-    ///   `start_ind`)
-    ///
-    /// # Errors
-    ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `start_ind` is an invalid amplitude index
-    ///   - if `num_amps` is greater than the remaining amplitudes in the state,
-    ///     from `start_ind`
-    ///
-    ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [api-copy-substate-from-gpu]: crate::Qureg::copy_substate_from_gpu()
-    /// [api-copy-state-to-gpu]: crate::Qureg::copy_state_to_gpu()
-    /// [api-set-amps]: crate::Qureg::set_amps()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn copy_substate_to_gpu(
+    pub fn mix_nontp_multi_qubit_kraus_map(
         &mut self,
-        start_ind: i64,
-        num_amps: i64,
+        targets: &[i32],
+        ops: &[&ComplexMatrixN],
     ) -> Result<(), QuestError> {
+        let num_targets = targets.len() as i32;
+        let num_ops = ops.len() as i32;
+        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
         catch_quest_exception(|| unsafe {
-            ffi::copySubstateToGPU(self.reg, start_ind, num_amps);
+            ffi::mixNonTPMultiQubitKrausMap(
+                self.reg,
+                targets.as_ptr(),
+                num_targets,
+                ops_inner.as_ptr(),
+                num_ops,
+            );
         })
     }
+}
 
-    /// Copy a part the state-vector (or density matrix) from GPU memory.
+impl<K: QuregKind> Qureg<K> {
+    /// Creates a new Qureg object that is an exact copy of `self`.
     ///
-    /// In GPU mode, this copies a substate of the state-vector (or density
-    /// matrix) from  to VRAM / GPU-memory to RAM, which is the version
-    /// operated upon by other calls to the API.
+    /// The new register is of the same type (state-vector or density
+    /// matrix), has the same number of qubits, holds the same quantum state
+    /// and belongs to the same [`QuestEnv`].  Subsequent changes to either
+    /// register do not affect the other one.
     ///
-    /// In CPU mode, this function has no effect.
+    /// # Examples
     ///
-    /// In conjunction with
-    /// [`copy_substate_to_gpu()`][api-copy-substate-to-gpu], this allows a user
-    /// to directly modify a subset of the amplitudes the state-vector in a
-    /// hardware agnostic way, without having to load the entire state via
-    /// [`copy_state_from_gpu()`][api-copy-state-from-gpu].
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.hadamard(0).unwrap();
     ///
-    /// Note though that users should instead use [`set_amps()`][api-set-amps]
-    /// if possible.
+    /// // snapshot the state before measurement
+    /// let snapshot = qureg.try_clone().unwrap();
+    /// qureg.measure(0).unwrap();
     ///
-    /// # Parameters
+    /// let prob = snapshot.calc_prob_of_outcome(0, 1).unwrap();
+    /// assert!((prob - 0.5).abs() < EPSILON);
+    /// ```
     ///
-    /// - `start_ind` the index of the first amplitude to copy
-    /// - `num_amps` the number of contiguous amplitudes to copy (starting with
-    ///   `start_ind`)
+    /// See [QuEST API] for more information.
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `start_ind` is an invalid amplitude index
-    ///   - if `num_amps` is greater than the remaining amplitudes in the state,
-    ///     from `start_ind`
-    ///
-    ///
-    /// See [QuEST API] for more information.
+    /// Returns [`QuestError::InvalidQuESTInputError`](crate::QuestError::InvalidQuESTInputError)
+    /// on failure.  This is an exception thrown by `QuEST`.
     ///
-    /// [api-copy-substate-to-gpu]: crate::Qureg::copy_substate_to_gpu()
-    /// [api-copy-state-from-gpu]: crate::Qureg::copy_state_from_gpu()
-    /// [api-set-amps]: crate::Qureg::set_amps()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`QuestEnv`]: crate::QuestEnv
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn copy_substate_from_gpu(
-        &mut self,
-        start_ind: i64,
-        num_amps: i64,
-    ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::copySubstateToGPU(self.reg, start_ind, num_amps);
+    pub fn try_clone(&self) -> Result<Self, QuestError> {
+        Ok(Self {
+            env:  self.env.clone(),
+            reg:  catch_quest_exception(|| unsafe {
+                ffi::createCloneQureg(self.reg, self.env.raw())
+            })?,
+            kind: PhantomData,
         })
     }
 
-    /// Get the complex amplitude at a given index in the state vector.
+    /// Performs a logical AND on all successCodes held by all processes.
+    ///
+    /// If any one process has a zero `success_code`, all processes will return
+    /// a zero success code.
     ///
     /// # Parameters
     ///
-    /// - `index`: index in state vector of probability amplitudes
+    /// - `success_code`: `1` if process task succeeded, `0` if process task
+    ///   failed
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `self` is a density matrix
-    ///   - if `index` is outside [0, [`num_qubits()`]).
+    /// `1` if all processes succeeded, `0` if any one process failed
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
+    /// env.sync();
     ///
-    /// let amp = qureg.get_amp(0).unwrap().re;
-    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// assert_eq!(StateVector::sync_quest_success(1), 1);
+    /// assert_eq!(DensityMatrix::sync_quest_success(0), 0);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn get_amp(
-        &self,
-        index: i64,
-    ) -> Result<Qcomplex, QuestError> {
-        catch_quest_exception(|| unsafe { ffi::getAmp(self.reg, index) })
-            .map(Into::into)
+    #[must_use]
+    pub fn sync_quest_success(success_code: i32) -> i32 {
+        catch_quest_exception(|| unsafe { ffi::syncQuESTSuccess(success_code) })
+            .expect("sync_quest_success should always succeed")
     }
 
-    /// Get the real part of the probability amplitude at an index in
-    /// the state vector.
+    /// Call `f` on this register viewed as a register of kind `L`.
     ///
-    /// # Parameters
+    /// This is an escape hatch for operations that are dispatched at
+    /// runtime, e.g. by [`Circuit::apply()`].  `QuEST` validates the kind of
+    /// the register in each call, so calling a method of the wrong kind
+    /// returns an error.  Methods implemented in Rust must not rely on `L`
+    /// matching the kind of the register.
     ///
-    /// - `index`: index in state vector of probability amplitudes
+    /// [`Circuit::apply()`]: crate::Circuit::apply()
+    pub(crate) fn with_kind_unchecked<L, T>(
+        &mut self,
+        f: impl FnOnce(&mut Qureg<L>) -> T,
+    ) -> T {
+        // The view shares the `QuEST` register with `self` and must never be
+        // dropped.  Only its handle to the environment is released.
+        let mut view = ManuallyDrop::new(Qureg {
+            env:  self.env.clone(),
+            reg:  self.reg,
+            kind: PhantomData,
+        });
+        let res = f(&mut view);
+        // SAFETY: `view.env` is not used again.
+        unsafe { std::ptr::drop_in_place(&mut view.env) };
+        res
+    }
+
+    #[must_use]
+    pub fn is_density_matrix(&self) -> bool {
+        self.reg.isDensityMatrix != 0
+    }
+
+    /// Print the current state vector of probability amplitudes to file.
     ///
-    /// # Errors
+    /// ## File format:
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is a density matrix
-    ///   - if `index` is outside [0, [`num_qubits()`]).
+    /// ```text
+    /// real, imag
+    /// realComponent1, imagComponent1
+    /// realComponent2, imagComponent2
+    /// ...
+    /// realComponentN, imagComponentN
+    /// ```
     ///
-    /// # Examples
+    ///  ## File naming convention:
     ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
+    /// For each node that the program runs on, a file
+    /// `state_rank_[node_rank].csv` is generated. If there is  more than
+    /// one node, ranks after the first do not include the header:
     ///
-    /// let amp = qureg.get_real_amp(0).unwrap();
-    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// ```text
+    /// real, imag
     /// ```
     ///
+    /// so that files are easier to combine.
+    ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn get_real_amp(
-        &self,
-        index: i64,
-    ) -> Result<Qreal, QuestError> {
-        catch_quest_exception(|| unsafe { ffi::getRealAmp(self.reg, index) })
+    pub fn report_state(&self) {
+        catch_quest_exception(|| unsafe { ffi::reportState(self.reg) })
+            .expect("report_state should never fail");
     }
 
-    /// Get the imaginary part of the probability amplitude at an index
-    /// in the state vector.
-    ///
-    /// # Parameters
+    /// Print the current state vector of probability amplitudes.
     ///
-    /// - `index`: index in state vector of probability amplitudes
+    /// Print the current state vector of probability amplitudes for a set of
+    /// qubits to standard out. For debugging purposes. Each rank should
+    /// print output serially.  Only print output for systems <= 5 qubits.
     ///
-    /// # Errors
+    /// See [QuEST API] for more information.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is a density matrix
-    ///   - if `index` is outside [0, [`num_qubits()`]).
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    pub fn report_state_to_screen(
+        &self,
+        report_rank: i32,
+    ) {
+        catch_quest_exception(|| unsafe {
+            ffi::reportStateToScreen(self.reg, self.env.raw(), report_rank);
+        })
+        .expect("report_state_to screen should never fail");
+    }
+
+    /// Returns the number of qubits represented.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
+    /// let qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let amp = qureg.get_imag_amp(0).unwrap();
-    /// assert!(amp.abs() < EPSILON);
+    /// assert_eq!(qureg.num_qubits(), 3);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn get_imag_amp(
-        &self,
-        index: i64,
-    ) -> Result<Qreal, QuestError> {
-        catch_quest_exception(|| unsafe { ffi::getImagAmp(self.reg, index) })
+    #[must_use]
+    pub const fn num_qubits(&self) -> i32 {
+        self.reg.numQubitsRepresented
     }
 
-    /// Get the probability of a state-vector at an index in the full state
-    /// vector.
-    ///
-    /// # Parameters
-    ///
-    /// - `index`: index in state vector of probability amplitudes
-    ///
-    /// # Errors
+    /// Return the total number of amplitudes in the register.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is a density matrix
-    ///   - if `index` is outside [0, [`num_qubits()`]).
+    /// - If `Qureg` is a state-vector, this is equal to: `2^N`, where `N` is
+    ///   the number of qubits in the register: [`num_qubits()`]
+    /// - If `Qureg` is a density matrix, this is equal to `2^(2N)`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
+    /// let qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// let amp = qureg.get_prob_amp(0).unwrap();
-    /// assert!((amp - 0.25).abs() < EPSILON);
+    /// assert_eq!(qureg.num_amps_total(), 64);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn get_prob_amp(
-        &self,
-        index: i64,
-    ) -> Result<Qreal, QuestError> {
-        catch_quest_exception(|| unsafe { ffi::getProbAmp(self.reg, index) })
+    #[must_use]
+    pub fn num_amps_total(&self) -> i64 {
+        self.reg.numAmpsTotal
     }
 
-    /// Get an amplitude from a density matrix at a given row and column.
-    ///
-    /// # Parameters
-    ///
-    /// - `row`: row of the desired amplitude in the density matrix
-    /// - `col`: column of the desired amplitude in the density matrix
-    ///
-    /// # Errors
+    /// Report information about a set of qubits.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is a state vector
-    ///   - if `row` or `col` are outside [0, [`num_qubits()`]).
+    /// This function prints to stdout: number of qubits, number of probability
+    /// amplitudes.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
+    /// let qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let amp = qureg.get_density_amp(0, 0).unwrap().re;
-    /// assert!((amp - 0.25).abs() < EPSILON);
+    /// qureg.report_qureg_params();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn get_density_amp(
-        &self,
-        row: i64,
-        col: i64,
-    ) -> Result<Qcomplex, QuestError> {
+    pub fn report_qureg_params(&self) {
         catch_quest_exception(|| unsafe {
-            ffi::getDensityAmp(self.reg, row, col)
+            ffi::reportQuregParams(self.reg);
         })
-        .map(Into::into)
+        .expect("report_qureg_params should never fail");
     }
 
-    /// A debugging function which calculates the total probability of the
-    /// qubits.
-    ///
-    /// This function should always be 1 for correctly normalized states
-    /// (hence returning a real number).
-    /// For state-vectors, this is the norm of the entire state-vector
-    /// (the sum of the absolute-value-squared of every amplitude).
-    /// For density matrices, it is the trace.
-    /// For un-normalized density matrices (those directly modified or
-    /// initialized by the user),  this function returns the real component
-    /// of the trace.
-    ///
-    /// Note this calculation utilizes Kahan summation for greater accuracy,
-    /// and hence is not parallelized and so will be slower than other
-    /// functions.
-    ///
-    /// # Returns
+    /// Initializes a `Qureg` to have all-zero-amplitudes.
     ///
-    /// The total probability of the qubits in this `Qureg` being in any state.
+    /// This is an unphysical state, useful for iteratively building a state
+    /// with functions like
+    /// [`set_weighted_qureg()`][api-set-weighted-qureg], and should
+    /// not be confused with [`init_zero_state()`][api-init-zero-state].
     ///
     /// # Examples
     ///
@@ -1520,44 +1751,38 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
     ///
-    /// let amp = qureg.calc_total_prob();
-    /// assert!((amp - 1.).abs() < EPSILON)
+    /// qureg.init_blank_state();
+    ///
+    /// assert!(qureg.get_prob_amp(0).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(1).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(2).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(3).unwrap().abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [api-set-weighted-qureg]: crate::set_weighted_qureg()
+    /// [api-init-zero-state]: crate::Qureg::init_zero_state()
+    /// [api-qureg]: crate::Qureg
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[must_use]
-    pub fn calc_total_prob(&self) -> Qreal {
-        catch_quest_exception(|| unsafe { ffi::calcTotalProb(self.reg) })
-            .expect("calc_total_prop should always succeed")
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn init_blank_state(&mut self) {
+        catch_quest_exception(|| unsafe {
+            ffi::initBlankState(self.reg);
+        })
+        .expect("init_blank_state should always succeed");
     }
 
-    /// Apply a single-qubit unitary parameterized by two given complex scalars.
-    ///
-    /// Given valid complex numbers `alpha` and `beta`, applies the unitary:
-    ///
-    /// ```text
-    /// [ alpha -beta.conj() ]
-    /// [ beta  alpha.conj() ]
-    /// ```
+    /// Initialize `qureg` into the zero state.
     ///
-    /// Valid `alpha`, `beta` satisfy `|alpha|^2 + |beta|^2 = 1`.
-    /// The target unitary is general up to a global phase factor.
+    /// If `qureg` is a state-vector of `N` qubits, it is modified to state
+    /// `|0>^{\otimes N}`.  If `qureg` is a density matrix of `N` qubits, it is
+    /// modified to state `|0><0|^{\otimes N}`.
     ///
     /// # Parameters
     ///
-    /// - `target_qubit`: qubit to operate on
-    /// - `alpha`: complex unitary parameter (row 1, column 1)
-    /// - `beta`: complex unitary parameter (row 2, column 1)
-    ///
-    /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
-    ///   - if  `alpha`, `beta` don't satisfy: `|alpha|^2 + |beta|^2 = 1`.
     ///
     /// # Examples
     ///
@@ -1566,62 +1791,40 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_zero_state();
-    ///
-    /// let norm = SQRT_2.recip();
-    /// let alpha = Qcomplex::new(0., norm);
-    /// let beta = Qcomplex::new(0., norm);
-    /// qureg.compact_unitary(0, alpha, beta).unwrap();
     ///
-    /// let other_qureg = {
-    ///     let mut other_qureg =
-    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///     other_qureg.init_zero_state();
-    ///     other_qureg.hadamard(0).unwrap();
-    ///     other_qureg
-    /// };
+    /// qureg.init_zero_state();
     ///
-    /// let fidelity = qureg.calc_fidelity(&other_qureg).unwrap();
-    /// assert!((fidelity - 1.).abs() < 10. * EPSILON,);
+    /// assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(1).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(2).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(3).unwrap().abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [api-qureg]: crate::Qureg
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn compact_unitary(
-        &mut self,
-        target_qubit: i32,
-        alpha: Qcomplex,
-        beta: Qcomplex,
-    ) -> Result<(), QuestError> {
+    pub fn init_zero_state(&mut self) {
         catch_quest_exception(|| unsafe {
-            ffi::compactUnitary(
-                self.reg,
-                target_qubit,
-                alpha.into(),
-                beta.into(),
-            );
+            ffi::initZeroState(self.reg);
         })
+        .expect("init_zero_state should always succeed");
     }
 
-    /// Apply a general single-qubit unitary (including a global phase factor).
-    ///
-    /// The passed 2x2 `ComplexMatrix` must be unitary, otherwise an error is
-    /// thrown.
+    /// Initialize `qureg` into the plus state.
     ///
-    /// # Parameters
+    /// If `qureg` is a state-vector of `N` qubits, it is modified to state:
     ///
-    /// - `target_qubit`: qubit to operate on
-    /// - `u`: single-qubit unitary matrix to apply
+    /// ```latex
+    ///   {| + \rangle}^{\otimes N} = \frac{1}{\sqrt{2^N}} (| 0 \rangle + | 1 \rangle)^{\otimes N}.
+    /// ```
     ///
-    /// # Errors
+    /// If `qureg` is a density matrix of `N`, it is modified to state:
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
-    ///   - if `u` is not unitary
+    /// ```latex
+    ///   {| + \rangle\langle+|}^{\otimes N} = \frac{1}{{2^N}} \sum_i\sum_j |i\rangle\langle j|.
+    /// ```
     ///
     /// # Examples
     ///
@@ -1630,59 +1833,57 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_zero_state();
     ///
-    /// let norm = SQRT_2.recip();
-    /// let mtr = ComplexMatrix2::new(
-    ///     [[norm, norm], [norm, -norm]],
-    ///     [[0., 0.], [0., 0.]],
-    /// );
-    /// qureg.unitary(0, &mtr).unwrap();
+    /// qureg.init_plus_state();
     ///
-    /// let other_qureg = {
-    ///     let mut other_qureg =
-    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///     other_qureg.hadamard(0).unwrap();
-    ///     other_qureg
-    /// };
-    /// let fidelity = qureg.calc_fidelity(&other_qureg).unwrap();
-    /// assert!((fidelity - 1.).abs() < 10. * EPSILON,);
+    /// assert!((qureg.get_prob_amp(0).unwrap() - 0.25).abs() < EPSILON);
+    /// assert!((qureg.get_prob_amp(1).unwrap() - 0.25).abs() < EPSILON);
+    /// assert!((qureg.get_prob_amp(2).unwrap() - 0.25).abs() < EPSILON);
+    /// assert!((qureg.get_prob_amp(3).unwrap() - 0.25).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [api-qureg]: crate::Qureg
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn unitary(
-        &mut self,
-        target_qubit: i32,
-        u: &ComplexMatrix2,
-    ) -> Result<(), QuestError> {
+    pub fn init_plus_state(&mut self) {
         catch_quest_exception(|| unsafe {
-            ffi::unitary(self.reg, target_qubit, u.0);
+            ffi::initPlusState(self.reg);
         })
+        .expect("init_plus_state should always succeed");
     }
 
-    /// Rotate a single qubit by a given angle around the X-axis of the
-    /// Bloch-sphere.
+    /// Initialize `qureg` into a classical state.
+    ///
+    /// This state is also known as a "computational basis state" with index
+    /// `state_ind`.
+    ///
+    /// If `qureg` is a state-vector, it will become: `|state_ind>`. If
+    /// `qureg`is a density matrix, it will become:
     ///
-    /// For angle `theta`, this applies
     /// ```text
-    /// [    cos(theta/2)   -i sin(theta/2) ]
-    /// [ -i sin(theta/2)      cos(theta/2) ]
-    /// ```
+    ///   |state_ind> <state_ind|
+    /// ````
+    ///
+    /// Classical states are indexed from zero, so that `state_ind=0` produces
+    /// `|0..00>`,  and  `state_ind=1` produces `|00..01>`, and `state_ind=2^N -
+    /// 1` produces `|11..11>`. Subsequent calls to
+    /// [`get_prob_amp()`][api-get-prob-amp] will yield `0` for all indices
+    /// except `state_ind`,  and the phase of `state_ind`'s amplitude will
+    /// be `1` (real).
+    ///
+    /// This function can be used to initialise `qureg` into a specific binary
+    /// state  (e.g. `11001`) using a binary literal.
     ///
     /// # Parameters
     ///
-    /// - `rot_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate in radians
+    ///  - `state_ind` the index of the basis state to modify `qureg` into
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `rot_qubit` is outside [0, [`num_qubits()`]).
+    ///   - if `state_ind` is outside [0, qureg.[`num_amps_total()`]).
     ///
     /// # Examples
     ///
@@ -1691,139 +1892,160 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    /// let theta = PI;
     ///
-    /// qureg.rotate_x(0, theta).unwrap();
+    /// qureg.init_classical_state(8);
+    /// let prob = qureg.get_prob_amp(0).unwrap();
+    ///
+    /// assert!((prob.abs() - 1.) < EPSILON);
     /// ```
     ///
+    ///
     /// See [QuEST API] for more information.
     ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [api-get-prob-amp]: crate::Qureg::get_prob_amp()
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn rotate_x(
+    pub fn init_classical_state(
         &mut self,
-        rot_qubit: i32,
-        angle: Qreal,
+        state_ind: i64,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::rotateX(self.reg, rot_qubit, angle);
+            ffi::initClassicalState(self.reg, state_ind);
         })
     }
 
-    /// Rotate a single qubit by a given angle around the Y-axis of the
-    /// Bloch-sphere.
+    /// Initialize `qureg` into a pure state.
     ///
-    /// For angle `theta`, this applies
-    /// ```text
-    /// [  cos(theta/2)   -sin(theta/2) ]
-    /// [ -sin(theta/2)    cos(theta/2) ]
-    /// ```
+    /// - If `qureg` is a state-vector, this merely clones `pure` into `qureg`.
+    /// - If `qureg` is a density matrix, this makes `qureg` 100% likely to be
+    ///   in the `pure` state.
     ///
     /// # Parameters
     ///
-    /// - `rot_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate in radians
+    /// - `pure`: a state-vector containing the pure state into which to
+    ///   initialize `qureg`
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `rot_qubit` is outside [0, [`num_qubits()`]).
+    ///   - if `self` and `pure` have mismatching dimensions
+    ///   - if `pure` is a density matrix
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// let pure_state =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    /// let theta = PI;
     ///
-    /// qureg.rotate_y(0, theta).unwrap();
+    /// qureg.init_pure_state(&pure_state).unwrap();
+    ///
+    /// assert!((qureg.calc_purity().unwrap() - 1.0).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn rotate_y(
+    pub fn init_pure_state(
         &mut self,
-        rot_qubit: i32,
-        angle: Qreal,
+        pure_: &StateVector,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::rotateY(self.reg, rot_qubit, angle);
+            ffi::initPureState(self.reg, pure_.reg);
         })
     }
 
-    /// Rotate a single qubit by a given angle around the Z-axis of the
-    /// Bloch-sphere.
+    /// Initialize `qureg` to be in a debug state.
+    ///
+    /// Set `qureg` to be in the un-normalized, non-physical state with
+    /// with `n`th complex amplitude given by:
     ///
-    /// For angle `theta`, this applies
     /// ```text
-    /// [ exp(-i theta/2)         0     ]
-    /// [       0          exp(theta/2) ]
+    ///   2n/10 + i*(2n+1)/10.
     /// ```
     ///
-    /// # Parameters
+    /// This is used internally for debugging and testing.
     ///
-    /// - `rot_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate in radians
+    /// See [QuEST API] for more information.
     ///
-    /// # Errors
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn init_debug_state(&mut self) {
+        catch_quest_exception(|| unsafe {
+            ffi::initDebugState(self.reg);
+        })
+        .expect("init_debug_state() should always succeed");
+    }
+
+    /// Get the full density matrix of the register.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `rot_qubit` is outside [0, [`num_qubits()`]).
+    /// The matrix is returned as a vector of rows, so that `rho[row][col]`
+    /// equals [`get_density_amp(row, col)`][api-get-density-amp]. For a
+    /// state-vector `|psi>`, this returns the density matrix of the pure state
+    /// `|psi><psi|`.
+    ///
+    /// See [`amplitudes()`][api-amplitudes] for performance notes.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    /// let theta = PI;
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(1).unwrap();
     ///
-    /// qureg.rotate_z(0, theta).unwrap();
+    /// let rho = qureg.density_matrix();
+    /// assert_eq!(rho.len(), 4);
+    /// assert!((rho[1][1].re - 1.).abs() < EPSILON);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn rotate_z(
-        &mut self,
-        rot_qubit: i32,
-        angle: Qreal,
-    ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::rotateZ(self.reg, rot_qubit, angle);
-        })
+    /// [api-get-density-amp]: crate::Qureg::get_density_amp()
+    /// [api-amplitudes]: crate::Qureg::amplitudes()
+    #[must_use]
+    pub fn density_matrix(&self) -> Vec<Vec<Qcomplex>> {
+        let amps = self.amplitudes();
+        if self.is_density_matrix() {
+            let dim = 1 << self.num_qubits();
+            (0..dim)
+                .map(|row| (0..dim).map(|col| amps[row + col * dim]).collect())
+                .collect()
+        } else {
+            amps.iter()
+                .map(|a| amps.iter().map(|b| a * b.conj()).collect())
+                .collect()
+        }
     }
 
-    /// Rotate a single qubit by a given angle around a given axis.
+    /// Shift the phase of a single qubit by a given angle.
     ///
-    /// The axis of rotation is given by a [`Vector`] on the Bloch-sphere.      
-    /// The vector must not be zero (or else an error is thrown), but needn't be
-    /// unit magnitude, since the normalization will be computed by by `QuEST`.
+    /// This is equivalent to a Z-axis rotation of the Bloch-sphere up to a
+    /// global phase factor.
+    ///
+    /// For angle `theta`, this effects single-qubit unitary
+    ///
+    /// ```text
+    ///   [ 1      0        ]
+    ///   [ 0  exp(i theta) ]
+    /// ```
     ///
     /// # Parameters
     ///
-    /// - `rot_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate in radians
-    /// - `axis`: vector around which to rotate (can be non-unit; will be
-    ///   normalized)
+    /// - `target_qubit`: qubit to undergo a phase shift
+    /// - `angle`: amount by which to shift the phase in radians
+    ///
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`]
-    ///   - if `rot_qubit` is outside [0, [`num_qubits()`])
-    ///   - if `axis` is the zero vector
+    /// - [`InvalidQuESTInputError`], if
+    ///   - `target_qubit` is outside `[0, N)`.
     ///
     /// # Examples
     ///
@@ -1833,47 +2055,51 @@ impl<'a> Qureg<'a> {
     /// let mut qureg =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let angle = 2.0 * PI;
-    /// let axis = &Vector::new(0., 0., 1.);
-    /// qureg.rotate_around_axis(0, angle, axis).unwrap();
+    /// let target_qubit = 1;
+    /// let angle = 0.5;
+    ///
+    /// qureg.phase_shift(target_qubit, angle).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`Vector`]: crate::Vector
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn rotate_around_axis(
+    pub fn phase_shift(
         &mut self,
-        rot_qubit: i32,
+        target_qubit: i32,
         angle: Qreal,
-        axis: &Vector,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::rotateAroundAxis(self.reg, rot_qubit, angle, axis.0);
+            ffi::phaseShift(self.reg, target_qubit, angle);
         })
     }
 
-    /// Applies a controlled rotation by a given angle around the X-axis of the
-    /// Bloch-sphere.
-    ///
-    /// The target qubit is rotated in states where the control qubit has value
-    /// `1`.
-    ///
-    /// # Parameters
+    /// Controlled shift of the phase of a single qubit by a given angle.
     ///
-    /// - `control_qubit`: qubit which has value `1` in the rotated states
-    /// - `target_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate the target qubit in radians
+    /// Introduce a phase factor `exp(i theta)` on state `|11>` of qubits
+    /// `id_qubit1` and `id_qubit2`.  For angle `theta`, this effects
+    /// the unitary
+    ///
+    /// ```text
+    ///  [ 1  0  0        0      ]
+    ///  [ 0  1  0        0      ]
+    ///  [ 0  0  1        0      ]
+    ///  [ 0  0  0  exp(i theta) ]
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// - `id_qubit1`: first qubit in the state to phase shift
+    /// - `id_qubit2`: second qubit in the state to phase shift
+    /// - `angle`: amount by which to shift the phase in radians
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`]
-    ///   - if either `control_qubit` or `target_qubit` are outside [0,
-    ///     [`num_qubits()`])
-    ///   - if `control_qubit` and `target_qubit` are equal
+    /// - [`InvalidQuESTInputError`], if
+    ///   - if `id_qubit1` or `id_qubit2` are outside `[0, N)`
+    ///   - if `id_qubit1` and `id_qubit2` are equal
     ///
     /// # Examples
     ///
@@ -1883,54 +2109,46 @@ impl<'a> Qureg<'a> {
     /// let mut qureg =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let control_qubit = 1;
-    /// let target_qubit = 0;
-    /// let angle = PI;
+    /// let id_qubit1 = 0;
+    /// let id_qubit2 = 2;
+    /// let angle = 0.5;
     /// qureg
-    ///     .controlled_rotate_x(control_qubit, target_qubit, angle)
+    ///     .controlled_phase_shift(id_qubit1, id_qubit2, angle)
     ///     .unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_rotate_x(
+    pub fn controlled_phase_shift(
         &mut self,
-        control_qubit: i32,
-        target_qubit: i32,
+        id_qubit1: i32,
+        id_qubit2: i32,
         angle: Qreal,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledRotateX(
-                self.reg,
-                control_qubit,
-                target_qubit,
-                angle,
-            );
+            ffi::controlledPhaseShift(self.reg, id_qubit1, id_qubit2, angle);
         })
     }
 
-    /// Applies a controlled rotation by a given angle around the Y-axis of the
-    /// Bloch-sphere.
+    /// Introduce a phase factor of the passed qubits.
     ///
-    /// The target qubit is rotated in states where the control qubit has value
-    /// `1`.
+    /// The phase factor is `exp(i theta)`, controlled by the state `|1..1>` of
+    /// the passed qubits.
     ///
     /// # Parameters
     ///
-    /// - `control_qubit`: qubit which has value `1` in the rotated states
-    /// - `target_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate the target qubit in radians
+    /// - `control_qubits`: array of qubits to phase shift
+    /// - `angle`: amount by which to shift the phase in radians
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`]
-    ///   - if either `control_qubit` or `target_qubit` are outside [0,
-    ///     [`num_qubits()`])
-    ///   - if `control_qubit` and `target_qubit` are equal
+    /// - [`InvalidQuESTInputError`], if
+    ///   - if `control_qubits.len()` is outside `[0, N)`
+    ///   - if any qubit index in `control_qubits` is outside `[0, N)`
+    ///   - if qubits in `control_qubits` are not unique
     ///
     /// # Examples
     ///
@@ -1938,56 +2156,59 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let control_qubit = 1;
-    /// let target_qubit = 0;
-    /// let angle = PI;
+    /// let control_qubits = &[0, 1, 3];
+    /// let angle = 0.5;
     /// qureg
-    ///     .controlled_rotate_y(control_qubit, target_qubit, angle)
+    ///     .multi_controlled_phase_shift(control_qubits, angle)
     ///     .unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_rotate_y(
+    pub fn multi_controlled_phase_shift(
         &mut self,
-        control_qubit: i32,
-        target_qubit: i32,
+        control_qubits: &[i32],
         angle: Qreal,
     ) -> Result<(), QuestError> {
+        let num_control_qubits = control_qubits.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::controlledRotateY(
+            ffi::multiControlledPhaseShift(
                 self.reg,
-                control_qubit,
-                target_qubit,
+                control_qubits.as_ptr(),
+                num_control_qubits,
                 angle,
             );
         })
     }
 
-    /// Applies a controlled rotation by a given angle around the Z-axis of the
-    /// Bloch-sphere.
+    /// Apply the (two-qubit) controlled phase flip gate.
     ///
-    /// The target qubit is rotated in states where the control qubit has value
-    /// `1`.
+    /// Also known as the controlled pauliZ gate. For each state, if both input
+    /// qubits have value one, multiply the amplitude of that state by `-1`.
+    /// This applies the two-qubit unitary:
+    ///
+    /// ```text
+    ///   [ 1  0  0   0 ]
+    ///   [ 0  1  0   0 ]
+    ///   [ 0  0  1   0 ]
+    ///   [ 0  0  0  -1 ]
+    /// ```
     ///
     /// # Parameters
     ///
-    /// - `control_qubit`: qubit which has value `1` in the rotated states
-    /// - `target_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate the target qubit in radians
+    /// - `id_qubit1`: first qubit in the state to operate on
+    /// - `id_qubit2`: second qubit in the state to operate on
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`]
-    ///   - if either `control_qubit` or `target_qubit` are outside [0,
-    ///     [`num_qubits()`])
-    ///   - if `control_qubit` and `target_qubit` are equal
+    /// - [`InvalidQuESTInputError`], if
+    ///   - if `id_qubit1` or `id_qubit2` are outside `[0, N)`
+    ///   - if `id_qubit1` and `id_qubit2` are equal
     ///
     /// # Examples
     ///
@@ -1995,59 +2216,52 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
     ///
-    /// let control_qubit = 1;
-    /// let target_qubit = 0;
-    /// let angle = PI;
-    /// qureg
-    ///     .controlled_rotate_z(control_qubit, target_qubit, angle)
-    ///     .unwrap();
+    /// qureg.controlled_phase_flip(0, 1);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_rotate_z(
+    pub fn controlled_phase_flip(
         &mut self,
-        control_qubit: i32,
-        target_qubit: i32,
-        angle: Qreal,
+        id_qubit1: i32,
+        id_qubit2: i32,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledRotateZ(
-                self.reg,
-                control_qubit,
-                target_qubit,
-                angle,
-            );
+            ffi::controlledPhaseFlip(self.reg, id_qubit1, id_qubit2);
         })
     }
 
-    /// Applies a controlled rotation by  around a given vector of the
-    /// Bloch-sphere.
+    /// Apply the (multiple-qubit) controlled phase flip gate.
     ///
-    /// The vector must not be zero (else an error is thrown), but needn't be
-    /// unit magnitude.
+    /// Also known as the multiple-qubit controlled pauliZ gate. For each state,
+    /// if all control qubits have value one, multiply the amplitude of that
+    /// state by `-1`. This applies the many-qubit unitary:
+    ///
+    /// ```text
+    ///   [  1  0  0  0   0  ]
+    ///   [  0  1  0  0   0  ]
+    ///   [  0  0   ...   0  ]
+    ///   [  0  0  0  1   0  ]
+    ///   [  0  0  0  0  -1  ]
+    /// ```
+    /// on the control qubits.
     ///
     /// # Parameters
     ///
-    /// - `control_qubit`: qubit which has value `1` in the rotated states
-    /// - `target_qubit`: qubit to rotate
-    /// - `angle`: angle by which to rotate in radians
-    /// - `axis`: vector around which to rotate (can be non-unit; will be
-    ///   normalized)
+    /// - `control_qubits`: array of input qubits
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`]
-    ///   - if either `control_qubit` or `target_qubit` are outside [0,
-    ///     [`num_qubits()`])
-    ///   - if `control_qubit` and `target_qubit` are equal
-    ///   - if `axis` is the zero vector
+    /// - [`InvalidQuESTInputError`], if
+    ///   - if `control_qubits.len()` is outside `[0, N)`
+    ///   - if any qubit index in `control_qubits` is outside `[0, N)`
+    ///   - if qubits in `control_qubits` are not unique
     ///
     /// # Examples
     ///
@@ -2055,75 +2269,49 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
     ///
-    /// let control_qubit = 1;
-    /// let target_qubit = 0;
-    /// let angle = PI;
-    /// let vector = Vector::new(0., 0., 1.);
-    /// qureg
-    ///     .controlled_rotate_around_axis(
-    ///         control_qubit,
-    ///         target_qubit,
-    ///         angle,
-    ///         &vector,
-    ///     )
-    ///     .unwrap();
+    /// let control_qubits = &[0, 1, 3];
+    /// qureg.multi_controlled_phase_flip(control_qubits);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`Vector`]: crate::Vector
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_rotate_around_axis(
+    pub fn multi_controlled_phase_flip(
         &mut self,
-        control_qubit: i32,
-        target_qubit: i32,
-        angle: Qreal,
-        axis: &Vector,
+        control_qubits: &[i32],
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledRotateAroundAxis(
+            ffi::multiControlledPhaseFlip(
                 self.reg,
-                control_qubit,
-                target_qubit,
-                angle,
-                axis.0,
+                control_qubits.as_ptr(),
+                control_qubits.len() as i32,
             );
         })
     }
 
-    /// Apply a controlled unitary parameterized by
-    /// two given complex scalars.
+    /// Apply the single-qubit S gate.
     ///
-    ///  Given valid complex numbers `alpha` and `beta`, applies the two-qubit
-    /// unitary:
+    /// This is a rotation of `PI/2` around the Z-axis on the Bloch sphere, or
+    /// the unitary:
     ///
     /// ```text
-    /// [ alpha -beta.conj() ]
-    /// [ beta  alpha.conj() ]
+    ///   [ 1  0 ]
+    ///   [ 0  i ]
     /// ```
     ///
-    /// Valid `alpha`, `beta` satisfy `|alpha|^2 + |beta|^2 = 1`.
-    /// The target unitary is general up to a global phase factor.  
-    ///
     /// # Parameters
     ///
-    /// - `control_qubit`: applies unitary if this qubit is `1`
-    /// - `target_qubit`: qubit to operate on
-    /// - `alpha`: complex unitary parameter (row 1, column 1)
-    /// - `beta`: complex unitary parameter (row 2, column 1)
+    /// - `target_qubit`: qubit to operate upon
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if either `target_qubit` or `control_qubit` is outside [0,
-    ///     [`qureg.num_qubits()`]).
-    ///   - if `control_qubits` and `target_qubit` are equal
-    ///   - if  `alpha`, `beta` don't satisfy: `|alpha|^2 + |beta|^2 = 1`.
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
@@ -2132,11 +2320,13 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.pauli_x(0).unwrap();
     ///
-    /// let norm = SQRT_2.recip();
-    /// let alpha = Qcomplex::new(0., norm);
-    /// let beta = Qcomplex::new(0., norm);
-    /// qureg.controlled_compact_unitary(0, 1, alpha, beta).unwrap();
+    /// qureg.s_gate(0).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(1).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
@@ -2145,42 +2335,34 @@ impl<'a> Qureg<'a> {
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_compact_unitary(
+    pub fn s_gate(
         &mut self,
-        control_qubit: i32,
         target_qubit: i32,
-        alpha: Qcomplex,
-        beta: Qcomplex,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledCompactUnitary(
-                self.reg,
-                control_qubit,
-                target_qubit,
-                alpha.into(),
-                beta.into(),
-            );
+            ffi::sGate(self.reg, target_qubit);
         })
     }
 
-    /// Apply a general controlled unitary.
+    /// Apply the single-qubit T gate.
     ///
-    /// The unitary can include a global phase factor and is applied
-    /// to the target qubit if the control qubit has value `1`.
+    /// This is a rotation of `PI/4` around the Z-axis on the Bloch sphere, or
+    /// the unitary:
     ///
-    /// # Parameters
+    /// ```text
+    ///   [ 1       0       ]
+    ///   [ 0  e^(i PI / 4) ]
+    /// ```
     ///
-    /// - `control_qubit`: applies unitary if this qubit is `1`
-    /// - `target_qubit`: qubit to operate on
-    /// - `u`: single-qubit unitary matrix to apply
+    /// # Parameters
+    ///
+    /// - `target_qubit`: qubit to operate upon
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if either `target_qubit` or `control_qubit` is outside [0,
-    ///    [`qureg.num_qubits()`]).
-    ///   - if `control_qubits` and `target_qubit` are equal
-    ///   - if `u` is not unitary
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///
     ///
     /// # Examples
     ///
@@ -2189,53 +2371,42 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.pauli_x(0).unwrap();
     ///
-    /// let norm = SQRT_2.recip();
-    /// let mtr = &ComplexMatrix2::new(
-    ///     [[norm, norm], [norm, -norm]],
-    ///     [[0., 0.], [0., 0.]],
-    /// );
-    /// qureg.controlled_unitary(0, 1, mtr).unwrap();
+    /// qureg.t_gate(0).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(1).unwrap();
+    /// assert!((amp - SQRT_2 / 2.).abs() < EPSILON);
     /// ```
     ///
-    /// See [`QuEST` `other_qureg.API`]nformation.
+    /// See [QuEST API] for more information.
     ///
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_unitary(
+    pub fn t_gate(
         &mut self,
-        control_qubit: i32,
         target_qubit: i32,
-        u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledUnitary(self.reg, control_qubit, target_qubit, u.0);
+            ffi::tGate(self.reg, target_qubit);
         })
     }
 
-    /// Apply a general multiple-control single-target unitary.
-    ///
-    /// The unitary can include a global phase factor. Any number of control
-    /// qubits can be specified, and if all have value `1`, the given
-    /// unitary is applied to the target qubit.
+    /// Overwrite the amplitudes of `target_qureg` with those from `copy_qureg`.
     ///
     /// # Parameters
     ///
-    /// - `control_qubits`: applies unitary if all qubits in this slice are
-    ///   equal to `1`
-    /// - `target_qubit`: qubit to operate on
-    /// - `u`: single-qubit unitary matrix to apply
+    /// - `copy_qureg`: the `Qureg` to have its quantum state clone into `self`
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubit` or any of `control_qubits` is outside [0,
-    ///     [`qureg.num_qubits()`]).
-    ///   - if any qubit in `control_qubits` is repeated
-    ///   - if `control_qubits` contains `target_qubit`
-    ///   - if `u` is not unitary
+    ///   - if `self` is a state-vector while `copy_qureg` is a density matrix
+    ///     (and vice versa)
+    ///   - if `self` and `copy_qureg` have different dimensions
     ///
     /// # Examples
     ///
@@ -2244,50 +2415,38 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let copy_qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let norm = SQRT_2.recip();
-    /// let mtr = &ComplexMatrix2::new(
-    ///     [[norm, norm], [norm, -norm]],
-    ///     [[0., 0.], [0., 0.]],
-    /// );
-    /// qureg.multi_controlled_unitary(&[1, 2], 0, mtr).unwrap();
+    /// qureg.clone_qureg(&copy_qureg);
     /// ```
     ///
-    /// See [QuEST API] `fother_qureg.or` ation.
+    /// See [QuEST API] for more information.
     ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_unitary(
+    pub fn clone_qureg(
         &mut self,
-        control_qubits: &[i32],
-        target_qubit: i32,
-        u: &ComplexMatrix2,
+        copy_qureg: &Qureg<K>,
     ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::multiControlledUnitary(
-                self.reg,
-                control_qubits.as_ptr(),
-                num_control_qubits,
-                target_qubit,
-                u.0,
-            );
+            ffi::cloneQureg(self.reg, copy_qureg.reg);
         })
     }
 
-    /// Apply the single-qubit Pauli-X gate.
-    ///
-    /// # Parameters
+    /// Copy the state-vector (or density matrix) into GPU memory.
     ///
-    ///  - `target_qubit`: qubit to operate on
+    /// In GPU mode, this copies the state-vector (or density matrix) from RAM
+    /// to VRAM / GPU-memory, which is the version operated upon by other calls
+    /// to the API.
     ///
-    /// # Errors
+    /// In CPU mode, this function has no effect.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if either `control_qubit` or `target_qubit` is outside [0,
-    ///     [`qureg.num_qubits()`])
+    /// In conjunction with [`copy_state_from_gpu()`][api-copy-state-from-gpu]
+    /// (which should be called first), this allows a user to directly modify
+    /// the state-vector in a hardware agnostic way. Note though that users
+    /// should instead use [`set_amps()`][api-set-amps] if possible.
     ///
     /// # Examples
     ///
@@ -2297,38 +2456,33 @@ impl<'a> Qureg<'a> {
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.pauli_x(0).unwrap();
-    ///
-    /// let amp = qureg.get_real_amp(1).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// qureg.copy_state_to_gpu();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [api-copy-state-from-gpu]: crate::Qureg::copy_state_from_gpu()
+    /// [api-set-amps]: crate::Qureg::set_amps()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn pauli_x(
-        &mut self,
-        target_qubit: i32,
-    ) -> Result<(), QuestError> {
+    pub fn copy_state_to_gpu(&mut self) {
         catch_quest_exception(|| unsafe {
-            ffi::pauliX(self.reg, target_qubit);
+            ffi::copyStateToGPU(self.reg);
         })
+        .expect("copy_state_to_gpu should always succeed");
     }
 
-    /// Apply the single-qubit Pauli-Y gate.
-    ///
-    /// # Parameters
+    /// Copy the state-vector (or density matrix) from GPU memory.
     ///
-    ///  - `target_qubit`: qubit to operate on
+    /// In GPU mode, this copies the state-vector (or density matrix) from GPU
+    /// memory to RAM , where it can be accessed/modified  by the user.
     ///
-    /// # Errors
+    /// In CPU mode, this function has no effect.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if either `control_qubit` or `target_qubit` is outside [0,
-    ///     [`qureg.num_qubits()`])
+    /// In conjunction with [`copy_state_to_gpu()`][api-copy-state-to-gpu] ,
+    /// this allows a user to directly modify the state-vector in a hardware
+    /// agnostic way. Note though that users should instead use
+    /// [`set_amps()`][api-set-amps] if possible.
     ///
     /// # Examples
     ///
@@ -2338,143 +2492,163 @@ impl<'a> Qureg<'a> {
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.pauli_y(0).unwrap();
-    ///
-    /// let amp = qureg.get_imag_amp(1).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// qureg.copy_state_from_gpu();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [api-copy-state-to-gpu]: crate::Qureg::copy_state_to_gpu()
+    /// [api-set-amps]: crate::Qureg::set_amps()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn pauli_y(
-        &mut self,
-        target_qubit: i32,
-    ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::pauliY(self.reg, target_qubit);
-        })
+    pub fn copy_state_from_gpu(&mut self) {
+        catch_quest_exception(|| unsafe { ffi::copyStateFromGPU(self.reg) })
+            .expect("copy_state_from_gpu should always succeed");
     }
 
-    /// Apply the single-qubit Pauli-Z gate.
+    /// Copy a part the state-vector (or density matrix) into GPU memory.
     ///
-    /// # Parameters
+    /// In GPU mode, this copies a substate of the state-vector (or density
+    /// matrix) from RAM to VRAM / GPU-memory.
     ///
-    ///  - `target_qubit`: qubit to operate on
+    /// In CPU mode, this function has no effect.
     ///
-    /// # Errors
+    /// In conjunction with
+    /// [`copy_substate_from_gpu()`][api-copy-substate-from-gpu], this allows a
+    /// user to directly modify a subset of the amplitudes the state-vector
+    /// in a hardware agnostic way, without having to load the entire state
+    /// via [`copy_state_to_gpu()`][api-copy-state-to-gpu].
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if either `control_qubit` or `target_qubit` is outside [0,
-    ///     [`qureg.num_qubits()`])
+    /// Note though that users should instead use [`set_amps()`][api-set-amps]
+    /// if possible.
     ///
-    /// # Examples
+    /// # Parameters
     ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// - `start_ind` the index of the first amplitude to copy
+    /// - `num_amps` the number of contiguous amplitudes to copy (starting with
+    ///   `start_ind`)
     ///
-    /// qureg.pauli_z(0).unwrap();
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `start_ind` is an invalid amplitude index
+    ///   - if `num_amps` is greater than the remaining amplitudes in the state,
+    ///     from `start_ind`
     ///
-    /// let amp = qureg.get_real_amp(0).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
-    /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [api-copy-substate-from-gpu]: crate::Qureg::copy_substate_from_gpu()
+    /// [api-copy-state-to-gpu]: crate::Qureg::copy_state_to_gpu()
+    /// [api-set-amps]: crate::Qureg::set_amps()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn pauli_z(
+    pub fn copy_substate_to_gpu(
         &mut self,
-        target_qubit: i32,
+        start_ind: i64,
+        num_amps: i64,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::pauliZ(self.reg, target_qubit);
+            ffi::copySubstateToGPU(self.reg, start_ind, num_amps);
         })
     }
 
-    /// Apply the single-qubit Hadamard gate.
+    /// Copy a part the state-vector (or density matrix) from GPU memory.
     ///
-    /// This function applies the following unitary on `qubit`:
+    /// In GPU mode, this copies a substate of the state-vector (or density
+    /// matrix) from  to VRAM / GPU-memory to RAM, which is the version
+    /// operated upon by other calls to the API.
     ///
-    /// ```text
-    /// SQRT_2.recip() *
-    ///     [ 1  1 ]
-    ///     [ 1 -1 ]
-    /// ```
+    /// In CPU mode, this function has no effect.
+    ///
+    /// In conjunction with
+    /// [`copy_substate_to_gpu()`][api-copy-substate-to-gpu], this allows a user
+    /// to directly modify a subset of the amplitudes the state-vector in a
+    /// hardware agnostic way, without having to load the entire state via
+    /// [`copy_state_from_gpu()`][api-copy-state-from-gpu].
+    ///
+    /// Note though that users should instead use [`set_amps()`][api-set-amps]
+    /// if possible.
     ///
     /// # Parameters
     ///
-    ///  - `target_qubit`: qubit to operate on
+    /// - `start_ind` the index of the first amplitude to copy
+    /// - `num_amps` the number of contiguous amplitudes to copy (starting with
+    ///   `start_ind`)
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if either `control_qubit` or `target_qubit` is outside [0,
-    ///     [`qureg.num_qubits()`])
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// qureg.hadamard(0).unwrap();
+    ///   - if `start_ind` is an invalid amplitude index
+    ///   - if `num_amps` is greater than the remaining amplitudes in the state,
+    ///     from `start_ind`
     ///
-    /// let amp = qureg.get_real_amp(0).unwrap();
-    /// assert!((amp - SQRT_2.recip()).abs() < EPSILON);
-    /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [api-copy-substate-to-gpu]: crate::Qureg::copy_substate_to_gpu()
+    /// [api-copy-state-from-gpu]: crate::Qureg::copy_state_from_gpu()
+    /// [api-set-amps]: crate::Qureg::set_amps()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn hadamard(
+    pub fn copy_substate_from_gpu(
         &mut self,
-        target_qubit: i32,
+        start_ind: i64,
+        num_amps: i64,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::hadamard(self.reg, target_qubit);
+            ffi::copySubstateToGPU(self.reg, start_ind, num_amps);
         })
     }
 
-    /// Apply the controlled not (single control, single target) gate.
+    /// Get all probability amplitudes of the register.
     ///
-    /// The gate is also known as the c-X, c-sigma-X, c-Pauli-X and c-bit-flip
-    /// gate. This applies pauliX to the target qubit if the control qubit
-    /// has value 1. This effects the two-qubit unitary:
+    /// For a state-vector, the returned vector has
+    /// [`num_amps_total()`][api-num-amps-total] elements and the amplitude of
+    /// the computational basis state `|i>` is found at index `i`. For a
+    /// density matrix, the elements are stored in column-major order, i.e. the
+    /// amplitude at `(row, col)` is found at index `row + col * 2^N`, where
+    /// `N` is the number of qubits.
     ///
-    /// ```text
-    ///  [ 1  0  0  0 ]
-    ///  [ 0  1  0  0 ]
-    ///  [ 0  0  0  1 ]
-    ///  [ 0  0  1  0 ]
+    /// In CPU mode, the amplitudes are copied straight from the local chunk
+    /// of memory. In GPU or distributed mode, each amplitude is queried
+    /// separately, which is considerably slower.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let amps = qureg.amplitudes();
+    /// assert_eq!(amps.len(), 4);
+    /// assert!(amps.iter().all(|amp| (amp.re - 0.5).abs() < EPSILON));
     /// ```
     ///
-    /// on the control and target qubits.
+    /// [api-num-amps-total]: crate::Qureg::num_amps_total()
+    #[must_use]
+    pub fn amplitudes(&self) -> Vec<Qcomplex> {
+        self.amplitudes_range(0..self.num_amps_total())
+    }
+
+    /// Get a contiguous range of probability amplitudes of the register.
+    ///
+    /// The indexing convention is the same as in
+    /// [`amplitudes()`][api-amplitudes].
     ///
     /// # Parameters
     ///
-    /// - `control_qubit`: "nots" the target if this qubit is `1`
-    /// - `target_qubit`: qubit to "not"
+    /// - `range`: range of indices of amplitudes to read
     ///
-    /// # Errors
+    /// # Panics
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if either `control_qubit` or `target_qubit` is outside [0,
-    ///     [`num_qubits()`])
-    ///   - if `control_qubit` and `target_qubit` are equal
+    /// This function will panic if `range` is not contained in
+    /// `0..num_amps_total()`.
     ///
     /// # Examples
     ///
@@ -2482,59 +2656,110 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.pauli_x(1).unwrap();
-    ///
-    /// qureg.controlled_not(1, 0).unwrap();
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(5).unwrap();
     ///
-    /// let amp = qureg.get_real_amp(3).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// let amps = qureg.amplitudes_range(4..6);
+    /// assert_eq!(amps[1].re, 1.);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_not(
-        &mut self,
-        control_qubit: i32,
-        target_qubit: i32,
-    ) -> Result<(), QuestError> {
+    /// [api-amplitudes]: crate::Qureg::amplitudes()
+    #[must_use]
+    pub fn amplitudes_range(
+        &self,
+        range: Range<i64>,
+    ) -> Vec<Qcomplex> {
+        assert!(
+            0 <= range.start
+                && range.start <= range.end
+                && range.end <= self.num_amps_total(),
+            "amplitude range {range:?} out of bounds"
+        );
+
+        if cfg!(feature = "gpu") || self.reg.numChunks > 1 {
+            return range.map(|index| self.amplitude_at(index)).collect();
+        }
+
+        let (reals, imags) = self.amplitudes_view();
+        let range = range.start as usize..range.end as usize;
+        reals[range.clone()]
+            .iter()
+            .zip(&imags[range])
+            .map(|(&re, &im)| Qcomplex::new(re, im))
+            .collect()
+    }
+
+    fn amplitude_at(
+        &self,
+        index: i64,
+    ) -> Qcomplex {
+        let dim = 1 << self.num_qubits();
         catch_quest_exception(|| unsafe {
-            ffi::controlledNot(self.reg, control_qubit, target_qubit);
+            if self.is_density_matrix() {
+                ffi::getDensityAmp(self.reg, index % dim, index / dim)
+            } else {
+                ffi::getAmp(self.reg, index)
+            }
         })
+        .map(Into::into)
+        .expect("amplitude index should be valid")
     }
 
-    /// Apply a NOT (or Pauli X) gate with multiple control and target qubits.
+    /// Borrow the local chunk of the state-vector (or density matrix).
     ///
-    /// This applies pauliX to qubits `targs` on every basis state for which the
-    /// control qubits `ctrls` are all in the `|1>` state. The ordering within
-    /// each of `ctrls` and `targs` has no effect on the operation.
+    /// Returns a pair of slices `(reals, imags)` holding the real and
+    /// imaginary parts of the amplitudes stored in RAM on this node. No data is
+    /// copied. The indexing convention is the same as in
+    /// [`amplitudes()`][api-amplitudes], offset by the index of the first
+    /// amplitude in the chunk.
     ///
-    /// This function is equivalent, but significantly faster (approximately
-    /// `targs.len()` times) than applying controlled NOTs on each qubit in
-    /// `targs` in turn.
+    /// In GPU mode, the amplitudes in RAM are only up to date after a call to
+    /// [`copy_state_from_gpu()`][api-copy-state-from-gpu].
     ///
-    /// In distributed mode, this operation requires at most a single round of)
-    /// pair-wise communication between nodes, and hence is as efficient as
-    /// [`pauli_x()`][api-pauli-x].
+    /// The slices can be passed directly to
+    /// [`init_state_from_amps()`][api-init-state-from-amps] of another
+    /// register of the same size.
     ///
-    /// # Parameters
+    /// # Examples
     ///
-    ///  - `ctrls`: a list of the control qubit indices
-    ///  - `targs`: a list of the qubits to be targeted by the X gates
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(2).unwrap();
     ///
-    /// # Errors
+    /// let (reals, imags) = qureg.amplitudes_view();
+    /// assert_eq!(reals, &[0., 0., 1., 0.]);
+    /// assert_eq!(imags, &[0., 0., 0., 0.]);
+    /// ```
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit in `ctrls` and `targs` is invalid, i.e. outside [0,
-    ///     [`qureg.num_qubits()`]).
-    ///   - if the length of `targs` or `ctrls` is larger than
-    ///     [`qureg.num_qubits()`]
-    ///   - if `ctrls` or `targs` contain any repetitions
-    ///   - if any qubit in `ctrls` is also in `targs` (and vice versa)
+    /// [api-amplitudes]: crate::Qureg::amplitudes()
+    /// [api-copy-state-from-gpu]: crate::Qureg::copy_state_from_gpu()
+    /// [api-init-state-from-amps]: crate::Qureg::init_state_from_amps()
+    #[must_use]
+    pub fn amplitudes_view(&self) -> (&[Qreal], &[Qreal]) {
+        let len = self.reg.numAmpsPerChunk as usize;
+        // SAFETY: QuEST allocates `numAmpsPerChunk` amplitudes in RAM for
+        // each of `stateVec.real` and `stateVec.imag`.  The memory lives as
+        // long as `self` and can only be modified through `&mut self`.
+        unsafe {
+            (
+                std::slice::from_raw_parts(self.reg.stateVec.real.0, len),
+                std::slice::from_raw_parts(self.reg.stateVec.imag.0, len),
+            )
+        }
+    }
+
+    /// Mutably borrow the local chunk of the state-vector (or density
+    /// matrix).
+    ///
+    /// This is the mutable counterpart of
+    /// [`amplitudes_view()`][api-amplitudes-view]. The resulting state may not
+    /// necessarily be normalized.
+    ///
+    /// In GPU mode, the modified amplitudes must be copied back to the GPU
+    /// with [`copy_state_to_gpu()`][api-copy-state-to-gpu].
     ///
     /// # Examples
     ///
@@ -2542,70 +2767,50 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.pauli_x(0).unwrap();
-    /// qureg.pauli_x(1).unwrap();
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
     ///
-    /// let ctrls = &[0, 1];
-    /// let targs = &[2, 3];
-    /// qureg
-    ///     .multi_controlled_multi_qubit_not(ctrls, targs)
-    ///     .unwrap();
+    /// let (reals, _) = qureg.amplitudes_view_mut();
+    /// reals.swap(0, 3);
+    /// qureg.copy_state_to_gpu();
     ///
-    /// let amp = qureg.get_real_amp(15).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// assert_eq!(qureg.get_real_amp(3).unwrap(), 1.);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [api-pauli-x]: crate::Qureg::pauli_x()
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_multi_qubit_not(
-        &mut self,
-        ctrls: &[i32],
-        targs: &[i32],
-    ) -> Result<(), QuestError> {
-        let num_ctrls = ctrls.len() as i32;
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
-            ffi::multiControlledMultiQubitNot(
-                self.reg,
-                ctrls.as_ptr(),
-                num_ctrls,
-                targs.as_ptr(),
-                num_targs,
-            );
-        })
+    /// [api-amplitudes-view]: crate::Qureg::amplitudes_view()
+    /// [api-copy-state-to-gpu]: crate::Qureg::copy_state_to_gpu()
+    #[must_use]
+    pub fn amplitudes_view_mut(&mut self) -> (&mut [Qreal], &mut [Qreal]) {
+        let len = self.reg.numAmpsPerChunk as usize;
+        // SAFETY: See `amplitudes_view()`.  The real and imaginary parts are
+        // stored in separate allocations, so the slices do not overlap.
+        unsafe {
+            (
+                std::slice::from_raw_parts_mut(self.reg.stateVec.real.0, len),
+                std::slice::from_raw_parts_mut(self.reg.stateVec.imag.0, len),
+            )
+        }
     }
 
-    /// Apply a NOT (or Pauli X) gate with multiple target qubits.
-    ///
-    /// This has the same  effect as (but is much faster than) applying each
-    /// single-qubit NOT gate in turn.
-    ///
-    /// The ordering within `targs` has no effect on the operation.
-    ///
-    /// This function is equivalent, but significantly faster (approximately
-    /// `targs.len()` times) than applying NOT on each qubit in `targs` in turn.
-    ///
-    /// In distributed mode, this operation requires at most a single round of)
-    /// pair-wise communication between nodes, and hence is as efficient as
-    /// [`pauli_x()`][api-pauli-x].
+    /// A debugging function which calculates the total probability of the
+    /// qubits.
     ///
-    /// # Parameters
+    /// This function should always be 1 for correctly normalized states
+    /// (hence returning a real number).
+    /// For state-vectors, this is the norm of the entire state-vector
+    /// (the sum of the absolute-value-squared of every amplitude).
+    /// For density matrices, it is the trace.
+    /// For un-normalized density matrices (those directly modified or
+    /// initialized by the user),  this function returns the real component
+    /// of the trace.
     ///
-    ///  - `targs`: a list of the qubits to be targeted by the X gates
+    /// Note this calculation utilizes Kahan summation for greater accuracy,
+    /// and hence is not parallelized and so will be slower than other
+    /// functions.
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit in `targs` is invalid, i.e. outside [0,
-    ///     [`qureg.num_qubits()`]).
-    ///   - if the length of `targs` is larger than [`qureg.num_qubits()`]
-    ///   - if `targs` contains any repetitions
+    /// The total probability of the qubits in this `Qureg` being in any state.
     ///
     /// # Examples
     ///
@@ -2614,58 +2819,44 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// let targs = &[0, 1];
-    /// qureg.multi_qubit_not(targs).unwrap();
-    ///
-    /// let amp = qureg.get_real_amp(3).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// let amp = qureg.calc_total_prob();
+    /// assert!((amp - 1.).abs() < EPSILON)
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [api-pauli-x]: crate::Qureg::pauli_x()
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_qubit_not(
-        &mut self,
-        targs: &[i32],
-    ) -> Result<(), QuestError> {
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
-            let targs_ptr = targs.as_ptr();
-            ffi::multiQubitNot(self.reg, targs_ptr, num_targs);
-        })
+    #[must_use]
+    pub fn calc_total_prob(&self) -> Qreal {
+        catch_quest_exception(|| unsafe { ffi::calcTotalProb(self.reg) })
+            .expect("calc_total_prop should always succeed")
     }
 
-    /// Apply the controlled pauli Y (single control, single target) gate.
+    /// Apply a single-qubit unitary parameterized by two given complex scalars.
     ///
-    /// The gate is also known as the c-Y and c-sigma-Y gate.
-    /// This applies pauli Y to the target qubit, if the control qubit has value
-    /// 1. This effects the two-qubit unitary:
+    /// Given valid complex numbers `alpha` and `beta`, applies the unitary:
     ///
     /// ```text
-    ///  [ 1  0  0   0 ]
-    ///  [ 0  1  0   0 ]
-    ///  [ 0  0  0  -i ]
-    ///  [ 0  0  i   0 ]
+    /// [ alpha -beta.conj() ]
+    /// [ beta  alpha.conj() ]
     /// ```
     ///
-    /// on the control and target qubits.
+    /// Valid `alpha`, `beta` satisfy `|alpha|^2 + |beta|^2 = 1`.
+    /// The target unitary is general up to a global phase factor.
     ///
     /// # Parameters
     ///
-    /// - `control_qubit`: applies pauli Y the target if this qubit is `1`
-    /// - `target_qubit`: qubit to modify
+    /// - `target_qubit`: qubit to operate on
+    /// - `alpha`: complex unitary parameter (row 1, column 1)
+    /// - `beta`: complex unitary parameter (row 2, column 1)
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if either `control_qubit` or `target_qubit` is outside [0,
-    ///     [`num_qubits()`])
-    ///   - if `control_qubit` and `target_qubit` are equal
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///   - if  `alpha`, `beta` don't satisfy: `|alpha|^2 + |beta|^2 = 1`.
     ///
     /// # Examples
     ///
@@ -2674,12 +2865,23 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.pauli_x(1).unwrap();
+    /// qureg.init_zero_state();
     ///
-    /// qureg.controlled_pauli_y(1, 0).unwrap();
+    /// let norm = SQRT_2.recip();
+    /// let alpha = Qcomplex::new(0., norm);
+    /// let beta = Qcomplex::new(0., norm);
+    /// qureg.compact_unitary(0, alpha, beta).unwrap();
     ///
-    /// let amp = qureg.get_imag_amp(3).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// let other_qureg = {
+    ///     let mut other_qureg =
+    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///     other_qureg.init_zero_state();
+    ///     other_qureg.hadamard(0).unwrap();
+    ///     other_qureg
+    /// };
+    ///
+    /// let fidelity = qureg.calc_fidelity(&other_qureg).unwrap();
+    /// assert!((fidelity - 1.).abs() < 10. * EPSILON,);
     /// ```
     ///
     /// See [QuEST API] for more information.
@@ -2688,48 +2890,37 @@ impl<'a> Qureg<'a> {
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_pauli_y(
+    pub fn compact_unitary(
         &mut self,
-        control_qubit: i32,
         target_qubit: i32,
+        alpha: Qcomplex,
+        beta: Qcomplex,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledPauliY(self.reg, control_qubit, target_qubit);
+            ffi::compactUnitary(
+                self.reg,
+                target_qubit,
+                alpha.into(),
+                beta.into(),
+            );
         })
     }
 
-    /// Gives the probability of a qubit being measured in the given outcome.
+    /// Apply a general single-qubit unitary (including a global phase factor).
     ///
-    /// This performs no actual measurement and does not change the state of the
-    /// qubits.
-    ///
-    /// - For state-vectors, this function works by summing the
-    ///   absolute-value-squared of every amplitude in the state-vector for
-    ///   which `measure_qubit = 0`. If `outcome = 1`, it returns `1` minus this
-    ///   value. Hence for unnormalized state-vectors, this result will differ
-    ///   from the absolute-value-squared of every amplitude where
-    ///   `measure_qubit = outcome`.
-    ///
-    /// - For density matrices, this function sums the diagonal values (should
-    ///   be real) corresponding to `measure_qubit = 0` (returning 1 minus this
-    ///   if `outcome = 1`).
+    /// The passed 2x2 `ComplexMatrix` must be unitary, otherwise an error is
+    /// thrown.
     ///
     /// # Parameters
     ///
-    /// - `measure_qubit`: qubit to study
-    /// - `outcome`: for which to find the probability of the qubit being
-    ///   measured in
-    ///
-    /// # Returns
-    ///
-    /// Returns probability of qubit `measure_qubit` being measured in the given
-    /// outcome.
+    /// - `target_qubit`: qubit to operate on
+    /// - `u`: single-qubit unitary matrix to apply
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `measure_qubit` is outside [0, [`num_qubits()`])
-    ///   - if `outcome` is not in {0, 1}
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///   - if `u` is not unitary
     ///
     /// # Examples
     ///
@@ -2737,12 +2928,24 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
     ///
-    /// let prob = qureg.calc_prob_of_outcome(0, 0).unwrap();
-    /// assert!((prob - 1.).abs() < EPSILON);
-    /// let prob = qureg.calc_prob_of_outcome(0, 1).unwrap();
-    /// assert!(prob.abs() < EPSILON);
+    /// let norm = SQRT_2.recip();
+    /// let mtr = ComplexMatrix2::new(
+    ///     [[norm, norm], [norm, -norm]],
+    ///     [[0., 0.], [0., 0.]],
+    /// );
+    /// qureg.unitary(0, &mtr).unwrap();
+    ///
+    /// let other_qureg = {
+    ///     let mut other_qureg =
+    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///     other_qureg.hadamard(0).unwrap();
+    ///     other_qureg
+    /// };
+    /// let fidelity = qureg.calc_fidelity(&other_qureg).unwrap();
+    /// assert!((fidelity - 1.).abs() < 10. * EPSILON,);
     /// ```
     ///
     /// See [QuEST API] for more information.
@@ -2750,132 +2953,82 @@ impl<'a> Qureg<'a> {
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn calc_prob_of_outcome(
-        &self,
-        measure_qubit: i32,
-        outcome: i32,
-    ) -> Result<Qreal, QuestError> {
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn unitary(
+        &mut self,
+        target_qubit: i32,
+        u: &ComplexMatrix2,
+    ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::calcProbOfOutcome(self.reg, measure_qubit, outcome)
+            ffi::unitary(self.reg, target_qubit, u.0);
         })
     }
 
-    /// Calculate probabilities of every outcome of the sub-register.
-    ///
-    /// This function populates `outcome_probs` with the probabilities of every
-    /// outcome of the sub-register contained in `qubits`.
-    ///
-    /// This performs no actual measurement and does not modify `qureg`.
-    ///
-    /// - `outcome_probs` must be a pre-allocated array of length
-    ///   `2^qubits.len()`. In distributed mode, every node receives the full
-    ///   list of outcome probabilities.
-    ///
-    /// - Note that the indices in `qubits` need not be adjacent nor ordered.
-    ///   The order of `qubits` determines the order of `outcome_probs`, whereby
-    ///   `qubits` are treated as *increasing* significance.
+    /// Rotate a single qubit by a given angle around the X-axis of the
+    /// Bloch-sphere.
     ///
-    /// - Since all probability amplitudes of a state-vector are ultimately
-    ///   involved in the output probabilities, this function works as expected
-    ///   for unnormalized states. This is similarly true for density matrices,
-    ///   where all  diagonal elements are involved, although only the real
-    ///   values of the diagonal elements will be consulted.
+    /// For angle `theta`, this applies
+    /// ```text
+    /// [    cos(theta/2)   -i sin(theta/2) ]
+    /// [ -i sin(theta/2)      cos(theta/2) ]
+    /// ```
     ///
     /// # Parameters
     ///
-    /// - `outcome_probs`: a pre-allocated array of length `1 << n`, where `n =
-    ///   qubits.len()`  which will be modified to contain all outcome
-    ///   probabilities
-    /// - `qubits`: a list of qubits to study
+    /// - `rot_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate in radians
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if any index in `qubits` is invalid, i.e. outside [0,
-    ///     [`num_qubits()`])
-    ///   - if `qubits` contains any repetitions
-    /// - [`ArrayLengthError`],
-    ///   - if `outcome_probs.len() < 1 << qubits.len()`
+    ///   - if `rot_qubit` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let qureg =
+    /// let mut qureg =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let theta = PI;
     ///
-    /// let qubits = &[1, 2];
-    /// let outcome_probs = &mut vec![0.; 4];
-    /// qureg
-    ///     .calc_prob_of_all_outcomes(outcome_probs, qubits)
-    ///     .unwrap();
-    /// assert_eq!(outcome_probs, &vec![1., 0., 0., 0.]);
+    /// qureg.rotate_x(0, theta).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::cast_sign_loss)]
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn calc_prob_of_all_outcomes(
-        &self,
-        outcome_probs: &mut [Qreal],
-        qubits: &[i32],
+    pub fn rotate_x(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
     ) -> Result<(), QuestError> {
-        if outcome_probs.len() < 1 << qubits.len() {
-            return Err(QuestError::ArrayLengthError);
-        }
-        let num_qubits = qubits.len() as i32;
-        let outcome_probs_ptr = outcome_probs.as_mut_ptr();
         catch_quest_exception(|| unsafe {
-            ffi::calcProbOfAllOutcomes(
-                outcome_probs_ptr,
-                self.reg,
-                qubits.as_ptr(),
-                num_qubits,
-            );
+            ffi::rotateX(self.reg, rot_qubit, angle);
         })
     }
 
-    /// Updates `qureg` to be consistent with measuring qubit in the given
-    /// outcome.
-    ///
-    /// Returns the probability of such a measurement outcome. This is
-    /// effectively performing a renormalizing projection, or a measurement
-    /// with a forced outcome.  This is an irreversible change to the state,
-    /// whereby computational states inconsistent with the outcome are
-    /// given zero amplitude and the `Qureg` is renormalized.  The given
-    /// outcome must not have a near zero probability, else it cannot
-    /// be collapsed into.
-    ///
-    /// Note that the collapse probably used for renormalization is calculated
-    /// for  `outcome = 0`, and assumed `1` minus this probability if
-    /// `outcome = 1`.  Hence this routine will not correctly project
-    /// un-normalised `Qureg`s onto  `outcome = 1`.
+    /// Rotate a single qubit by a given angle around the Y-axis of the
+    /// Bloch-sphere.
     ///
-    /// To avoid renormalization after projection, or force projection into
-    /// non-physical states with very small probability, use
-    /// [`apply_projector()`].  
+    /// For angle `theta`, this applies
+    /// ```text
+    /// [  cos(theta/2)   -sin(theta/2) ]
+    /// [ -sin(theta/2)    cos(theta/2) ]
+    /// ```
     ///
     /// # Parameters
     ///
-    /// - `measure_qubit`: qubit to measure
-    /// - `outcome`: to force the measure qubit to enter
-    ///
-    /// # Returns
-    ///
-    /// Probability of the (forced) measurement outcome.
+    /// - `rot_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate in radians
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `measure_qubit` is outside `[0, N)`
-    ///   - if `outcome` is not in `{0, 1}`
-    ///   - if the probability of `outcome` is zero (within machine epsilon)
+    ///   - if `rot_qubit` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
@@ -2883,54 +3036,46 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
-    ///
-    /// qureg.collapse_to_outcome(0, 0).unwrap();
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let theta = PI;
     ///
-    /// // QuEST throws an exception if probability of outcome is 0.
-    /// qureg.init_zero_state();
-    /// qureg.collapse_to_outcome(0, 1).unwrap_err();
+    /// qureg.rotate_y(0, theta).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`apply_projector()`]: Qureg::apply_projector()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn collapse_to_outcome(
+    pub fn rotate_y(
         &mut self,
-        measure_qubit: i32,
-        outcome: i32,
-    ) -> Result<Qreal, QuestError> {
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::collapseToOutcome(self.reg, measure_qubit, outcome)
+            ffi::rotateY(self.reg, rot_qubit, angle);
         })
     }
 
-    /// Measures a single qubit, collapsing it randomly to `0` or `1`.
-    ///
-    /// Outcome probabilities are weighted by the state vector, which is
-    /// irreversibly  changed after collapse to be consistent with the
-    /// outcome.
+    /// Rotate a single qubit by a given angle around the Z-axis of the
+    /// Bloch-sphere.
     ///
-    /// The random number generator is seeded by [`seed_quest_default()`]
-    /// within  [`QuestEnv::new()`], unless later overridden by
-    /// [`seed_quest()`].
+    /// For angle `theta`, this applies
+    /// ```text
+    /// [ exp(-i theta/2)         0     ]
+    /// [       0          exp(theta/2) ]
+    /// ```
     ///
     /// # Parameters
     ///
-    /// - `measure_qubit`: index of a qubit to measure
-    ///
-    /// # Returns
-    ///
-    /// The measurement outcome, `0` or `1`.
+    /// - `rot_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate in radians
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `measure_qubit` is outside `[0, N)`
+    ///   - if `rot_qubit` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
@@ -2938,59 +3083,46 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// // Prepare an entangled state `|00> + |11>`
-    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
-    ///
-    /// // Qubits are entangled now
-    /// let outcome1 = qureg.measure(0).unwrap();
-    /// let outcome2 = qureg.measure(1).unwrap();
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let theta = PI;
     ///
-    /// assert_eq!(outcome1, outcome2);
+    /// qureg.rotate_z(0, theta).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`seed_quest_default()`]: crate::seed_quest_default()
-    /// [`QuestEnv::new()`]: QuestEnv::new()
-    /// [`seed_quest()`]: crate::seed_quest()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn measure(
+    pub fn rotate_z(
         &mut self,
-        measure_qubit: i32,
-    ) -> Result<i32, QuestError> {
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::measure(self.reg, measure_qubit)
+            ffi::rotateZ(self.reg, rot_qubit, angle);
         })
     }
 
-    /// Measures a single qubit, collapsing it randomly to 0 or 1
-    ///
-    /// Additionally, the function gives the probability of that outcome.
-    /// Outcome probabilities are weighted by the state vector, which is
-    /// irreversibly changed after collapse to be consistent with the outcome.
+    /// Rotate a single qubit by a given angle around a given axis.
     ///
-    /// The random number generator is seeded by [`seed_quest_default()`]
-    /// within  [`QuestEnv::new()`], unless later overridden by
-    /// [`seed_quest()`].
+    /// The axis of rotation is given by a [`Vector`] on the Bloch-sphere.      
+    /// The vector must not be zero (or else an error is thrown), but needn't be
+    /// unit magnitude, since the normalization will be computed by by `QuEST`.
     ///
     /// # Parameters
     ///
-    /// - `measure_qubit`: index of a qubit to measure
-    /// - `outcome_prob`: a mutable reference to a `Qreal` which is set to the
-    ///   probability of the occurred outcome
-    ///
-    /// # Returns
-    ///
-    /// The measurement outcome, `0` or `1`.
+    /// - `rot_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate in radians
+    /// - `axis`: vector around which to rotate (can be non-unit; will be
+    ///   normalized)
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `measure_qubit` is outside `[0, N)`
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `rot_qubit` is outside [0, [`num_qubits()`])
+    ///   - if `axis` is the zero vector
     ///
     /// # Examples
     ///
@@ -2998,47 +3130,49 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// // Prepare an entangled state `|00> + |11>`
-    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
-    ///
-    /// // Qubits are entangled now
-    /// let prob = &mut -1.;
-    /// let outcome1 = qureg.measure_with_stats(0, prob).unwrap();
-    /// assert!((*prob - 0.5).abs() < EPSILON);
-    ///
-    /// let outcome2 = qureg.measure_with_stats(1, prob).unwrap();
-    /// assert!((*prob - 1.).abs() < EPSILON);
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// assert_eq!(outcome1, outcome2);
+    /// let angle = 2.0 * PI;
+    /// let axis = &Vector::new(0., 0., 1.);
+    /// qureg.rotate_around_axis(0, angle, axis).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`seed_quest_default()`]: crate::seed_quest_default()
-    /// [`QuestEnv::new()`]: QuestEnv::new()
-    /// [`seed_quest()`]: crate::seed_quest()
+    /// [`Vector`]: crate::Vector
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn measure_with_stats(
+    pub fn rotate_around_axis(
         &mut self,
-        measure_qubit: i32,
-        outcome_prob: &mut Qreal,
-    ) -> Result<i32, QuestError> {
-        let outcome_prob_ptr = outcome_prob as *mut _;
+        rot_qubit: i32,
+        angle: Qreal,
+        axis: &Vector,
+    ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::measureWithStats(self.reg, measure_qubit, outcome_prob_ptr)
+            ffi::rotateAroundAxis(self.reg, rot_qubit, angle, axis.0);
         })
     }
 
-    /// Enable QASM recording.
+    /// Applies a controlled rotation by a given angle around the X-axis of the
+    /// Bloch-sphere.
     ///
-    /// Gates applied to qureg will here-after be added to a growing log of QASM
-    /// instructions, progressively consuming more memory until disabled with
-    /// [`stop_recording_qasm()`]. The QASM log is bound to this qureg instance.
+    /// The target qubit is rotated in states where the control qubit has value
+    /// `1`.
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubit`: qubit which has value `1` in the rotated states
+    /// - `target_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate the target qubit in radians
+    ///
+    /// # Errors
     ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if either `control_qubit` or `target_qubit` are outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
     ///
     /// # Examples
     ///
@@ -3046,33 +3180,56 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// qureg.start_recording_qasm();
-    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
-    /// qureg.stop_recording_qasm();
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.print_recorded_qasm();
+    /// let control_qubit = 1;
+    /// let target_qubit = 0;
+    /// let angle = PI;
+    /// qureg
+    ///     .controlled_rotate_x(control_qubit, target_qubit, angle)
+    ///     .unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`stop_recording_qasm()`]: Qureg::stop_recording_qasm()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn start_recording_qasm(&mut self) {
+    pub fn controlled_rotate_x(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::startRecordingQASM(self.reg);
+            ffi::controlledRotateX(
+                self.reg,
+                control_qubit,
+                target_qubit,
+                angle,
+            );
         })
-        .expect("start_recording_qasm should always succeed");
     }
 
-    /// Disable QASM recording.
+    /// Applies a controlled rotation by a given angle around the Y-axis of the
+    /// Bloch-sphere.
     ///
-    /// The recorded QASM will be maintained in qureg and continue to be
-    /// appended to if [`start_recording_qasm()`] is recalled.
+    /// The target qubit is rotated in states where the control qubit has value
+    /// `1`.
     ///
-    /// Has no effect if this `Qureg` was not already recording operations.
+    /// # Parameters
+    ///
+    /// - `control_qubit`: qubit which has value `1` in the rotated states
+    /// - `target_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate the target qubit in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if either `control_qubit` or `target_qubit` are outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
     ///
     /// # Examples
     ///
@@ -3080,63 +3237,56 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// qureg.start_recording_qasm();
-    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
-    /// qureg.stop_recording_qasm();
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.print_recorded_qasm();
+    /// let control_qubit = 1;
+    /// let target_qubit = 0;
+    /// let angle = PI;
+    /// qureg
+    ///     .controlled_rotate_y(control_qubit, target_qubit, angle)
+    ///     .unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`start_recording_qasm()`]: Qureg::start_recording_qasm()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn stop_recording_qasm(&mut self) {
+    pub fn controlled_rotate_y(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::stopRecordingQASM(self.reg);
+            ffi::controlledRotateY(
+                self.reg,
+                control_qubit,
+                target_qubit,
+                angle,
+            );
         })
-        .expect("stop_recording_qasm should always succeed");
     }
 
-    /// Clear all QASM so far recorded.
-    ///
-    /// This does not start or stop recording.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.start_recording_qasm();
-    /// qureg.hadamard(0).unwrap();
+    /// Applies a controlled rotation by a given angle around the Z-axis of the
+    /// Bloch-sphere.
     ///
-    /// qureg.clear_recorded_qasm();
+    /// The target qubit is rotated in states where the control qubit has value
+    /// `1`.
     ///
-    /// qureg.controlled_not(0, 1).unwrap();
-    /// qureg.stop_recording_qasm();
-    /// qureg.print_recorded_qasm();
-    /// ```
+    /// # Parameters
     ///
-    /// See [QuEST API] for more information.
+    /// - `control_qubit`: qubit which has value `1` in the rotated states
+    /// - `target_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate the target qubit in radians
     ///
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn clear_recorded_qasm(&mut self) {
-        catch_quest_exception(|| unsafe {
-            ffi::clearRecordedQASM(self.reg);
-        })
-        .expect("clear_recorded_qasm should always succeed");
-    }
-
-    /// Print recorded QASM to stdout.
+    /// # Errors
     ///
-    /// This does not clear the QASM log, nor does it start or stop QASM
-    /// recording.
+    /// - [`InvalidQuESTInputError`]
+    ///   - if either `control_qubit` or `target_qubit` are outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
     ///
     /// # Examples
     ///
@@ -3144,36 +3294,59 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// qureg.start_recording_qasm();
-    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
-    /// qureg.stop_recording_qasm();
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.print_recorded_qasm();
+    /// let control_qubit = 1;
+    /// let target_qubit = 0;
+    /// let angle = PI;
+    /// qureg
+    ///     .controlled_rotate_z(control_qubit, target_qubit, angle)
+    ///     .unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn print_recorded_qasm(&mut self) {
+    pub fn controlled_rotate_z(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::printRecordedQASM(self.reg);
+            ffi::controlledRotateZ(
+                self.reg,
+                control_qubit,
+                target_qubit,
+                angle,
+            );
         })
-        .expect("print_recorded_qasm should always succeed");
     }
 
-    /// Writes recorded QASM to a file, throwing an error if inaccessible.
+    /// Applies a controlled rotation by  around a given vector of the
+    /// Bloch-sphere.
+    ///
+    /// The vector must not be zero (else an error is thrown), but needn't be
+    /// unit magnitude.
     ///
     /// # Parameters
     ///
-    /// - `filename`: the filename of the file to contain the recorded QASM
+    /// - `control_qubit`: qubit which has value `1` in the rotated states
+    /// - `target_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate in radians
+    /// - `axis`: vector around which to rotate (can be non-unit; will be
+    ///   normalized)
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `filename` cannot be written to
+    /// - [`InvalidQuESTInputError`]
+    ///   - if either `control_qubit` or `target_qubit` are outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
+    ///   - if `axis` is the zero vector
     ///
     /// # Examples
     ///
@@ -3181,328 +3354,334 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// qureg.start_recording_qasm();
-    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
-    /// qureg.stop_recording_qasm();
-    ///
-    /// qureg.write_recorded_qasm_to_file("/dev/null").unwrap();
-    /// ```
-    ///
-    /// See [QuEST API] for more information.
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    /// let control_qubit = 1;
+    /// let target_qubit = 0;
+    /// let angle = PI;
+    /// let vector = Vector::new(0., 0., 1.);
+    /// qureg
+    ///     .controlled_rotate_around_axis(
+    ///         control_qubit,
+    ///         target_qubit,
+    ///         angle,
+    ///         &vector,
+    ///     )
+    ///     .unwrap();
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`Vector`]: crate::Vector
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn write_recorded_qasm_to_file(
+    pub fn controlled_rotate_around_axis(
         &mut self,
-        filename: &str,
+        control_qubit: i32,
+        target_qubit: i32,
+        angle: Qreal,
+        axis: &Vector,
     ) -> Result<(), QuestError> {
-        unsafe {
-            let filename_cstr =
-                CString::new(filename).map_err(QuestError::NulError)?;
-            catch_quest_exception(|| {
-                ffi::writeRecordedQASMToFile(
-                    self.reg,
-                    (*filename_cstr).as_ptr(),
-                );
-            })
-        }
+        catch_quest_exception(|| unsafe {
+            ffi::controlledRotateAroundAxis(
+                self.reg,
+                control_qubit,
+                target_qubit,
+                angle,
+                axis.0,
+            );
+        })
     }
 
-    /// Mixes a density matrix to induce single-qubit dephasing noise.
-    ///
-    /// With probability `prob`, applies Pauli Z to `target_qubit` in `qureg`.
+    /// Apply a controlled unitary parameterized by
+    /// two given complex scalars.
     ///
-    /// This transforms `qureg = rho` into the mixed state:
+    ///  Given valid complex numbers `alpha` and `beta`, applies the two-qubit
+    /// unitary:
     ///
     /// ```text
-    /// (1 - prob) * rho  +  prob * Z_q rho Z_q,
+    /// [ alpha -beta.conj() ]
+    /// [ beta  alpha.conj() ]
     /// ```
     ///
-    /// where `q = target_qubit`. The coefficient `prob` cannot exceed `1/2`,
-    /// which maximally mixes `target_qubit`.
+    /// Valid `alpha`, `beta` satisfy `|alpha|^2 + |beta|^2 = 1`.
+    /// The target unitary is general up to a global phase factor.  
     ///
     /// # Parameters
     ///
-    /// - `target_qubit`: qubit upon which to induce dephasing noise
-    /// - `prob`: the probability of the phase error occurring
+    /// - `control_qubit`: applies unitary if this qubit is `1`
+    /// - `target_qubit`: qubit to operate on
+    /// - `alpha`: complex unitary parameter (row 1, column 1)
+    /// - `beta`: complex unitary parameter (row 2, column 1)
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
-    ///   - if `prob` is not in `[0, 1/2]`
+    ///   - if either `target_qubit` or `control_qubit` is outside [0,
+    ///     [`qureg.num_qubits()`]).
+    ///   - if `control_qubits` and `target_qubit` are equal
+    ///   - if  `alpha`, `beta` don't satisfy: `|alpha|^2 + |beta|^2 = 1`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
-    ///
-    /// qureg.mix_dephasing(0, 0.5).unwrap();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let amp = qureg.get_density_amp(0, 0).unwrap();
-    /// assert!((amp.re - 0.25).abs() < EPSILON);
-    /// let amp = qureg.get_density_amp(0, 1).unwrap();
-    /// assert!(amp.re.abs() < EPSILON);
+    /// let norm = SQRT_2.recip();
+    /// let alpha = Qcomplex::new(0., norm);
+    /// let beta = Qcomplex::new(0., norm);
+    /// qureg.controlled_compact_unitary(0, 1, alpha, beta).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_dephasing(
+    pub fn controlled_compact_unitary(
         &mut self,
+        control_qubit: i32,
         target_qubit: i32,
-        prob: Qreal,
+        alpha: Qcomplex,
+        beta: Qcomplex,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::mixDephasing(self.reg, target_qubit, prob);
+            ffi::controlledCompactUnitary(
+                self.reg,
+                control_qubit,
+                target_qubit,
+                alpha.into(),
+                beta.into(),
+            );
         })
     }
 
-    /// Mixes a density matrix `qureg` to induce two-qubit dephasing noise.
-    ///
-    /// With probability `prob`, applies Pauli Z to either or both in `qureg`.
-    ///
-    /// This transforms `qureg = rho` into the mixed state:
-    ///
-    /// ```text
-    /// (1 - prob) * rho  +  prob * 1/3 * (
-    ///         Z_a  rho  Z_a +
-    ///         Z_b  rho  Z_b +
-    ///         Z_a Z_b rho Z_a Z_b
-    ///    )
-    /// ```
+    /// Apply a general controlled unitary.
     ///
-    /// where `a = qubit1`, `b=qubit2`. The coefficient `prob` cannot exceed
-    /// `3/4`, at which maximal mixing occurs.
+    /// The unitary can include a global phase factor and is applied
+    /// to the target qubit if the control qubit has value `1`.
     ///
     /// # Parameters
     ///
-    /// - `qubit1`: qubit upon which to induce dephasing noise
-    /// - `qubit2`: qubit upon which to induce dephasing noise
-    /// - `prob`: the probability of the phase error occurring
+    /// - `control_qubit`: applies unitary if this qubit is `1`
+    /// - `target_qubit`: qubit to operate on
+    /// - `u`: single-qubit unitary matrix to apply
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
-    ///   - if `qubit1` or `qubit2` are outside [0, [`num_qubits()`]).
-    ///   - if `qubit1 = qubit2`
-    ///   - if `prob` is not in `[0, 3/4]`
+    ///   - if either `target_qubit` or `control_qubit` is outside [0,
+    ///    [`qureg.num_qubits()`]).
+    ///   - if `control_qubits` and `target_qubit` are equal
+    ///   - if `u` is not unitary
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(3, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
-    ///
-    /// qureg.mix_two_qubit_dephasing(0, 1, 0.75).unwrap();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let amp = qureg.get_density_amp(0, 0).unwrap();
-    /// assert!((amp.re - 0.125).abs() < EPSILON);
-    /// let amp = qureg.get_density_amp(0, 1).unwrap();
-    /// assert!(amp.re.abs() < EPSILON);
+    /// let norm = SQRT_2.recip();
+    /// let mtr = &ComplexMatrix2::new(
+    ///     [[norm, norm], [norm, -norm]],
+    ///     [[0., 0.], [0., 0.]],
+    /// );
+    /// qureg.controlled_unitary(0, 1, mtr).unwrap();
     /// ```
     ///
-    /// See [QuEST API] for more information.
+    /// See [`QuEST` `other_qureg.API`]nformation.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_two_qubit_dephasing(
+    pub fn controlled_unitary(
         &mut self,
-        qubit1: i32,
-        qubit2: i32,
-        prob: Qreal,
+        control_qubit: i32,
+        target_qubit: i32,
+        u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::mixTwoQubitDephasing(self.reg, qubit1, qubit2, prob);
+            ffi::controlledUnitary(self.reg, control_qubit, target_qubit, u.0);
         })
     }
 
-    /// Mixes a density matrix to induce single-qubit homogeneous
-    /// depolarising noise.
-    /// This is equivalent to, with probability `prob`, uniformly randomly
-    /// applying either Pauli X, Y, or Z to `target_qubit`.
-    ///
-    /// This transforms `qureg = rho` into the mixed state:
-    ///
-    /// ```text
-    /// (1 - prob) * rho  +  prob * 1/3 * (
-    ///      X_q rho X_q +
-    ///      Y_q rho Y_q +
-    ///      Z_q rho Z_q
-    /// )
-    /// ```
+    /// Apply a general multiple-control single-target unitary.
     ///
-    /// where `q = target_qubit`. The coefficient `prob` cannot exceed `3/4`, at
-    /// which maximal mixing occurs.
+    /// The unitary can include a global phase factor. Any number of control
+    /// qubits can be specified, and if all have value `1`, the given
+    /// unitary is applied to the target qubit.
     ///
     /// # Parameters
     ///
-    /// - `target_qubit`: qubit upon which to induce depolarizing noise
-    /// - `prob`: the probability of the depolarizing error occurring
+    /// - `control_qubits`: applies unitary if all qubits in this slice are
+    ///   equal to `1`
+    /// - `target_qubit`: qubit to operate on
+    /// - `u`: single-qubit unitary matrix to apply
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
-    ///   - if `prob` is not in `[0, 3/4]`
+    ///   - if `target_qubit` or any of `control_qubits` is outside [0,
+    ///     [`qureg.num_qubits()`]).
+    ///   - if any qubit in `control_qubits` is repeated
+    ///   - if `control_qubits` contains `target_qubit`
+    ///   - if `u` is not unitary
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    ///
-    /// qureg.mix_depolarising(0, 0.75).unwrap();
-    /// let amp = qureg.get_density_amp(0, 0).unwrap();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// assert!((amp.re - 0.5) < EPSILON);
+    /// let norm = SQRT_2.recip();
+    /// let mtr = &ComplexMatrix2::new(
+    ///     [[norm, norm], [norm, -norm]],
+    ///     [[0., 0.], [0., 0.]],
+    /// );
+    /// qureg.multi_controlled_unitary(&[1, 2], 0, mtr).unwrap();
     /// ```
     ///
-    /// See [QuEST API] for more information.
+    /// See [QuEST API] `fother_qureg.or` ation.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_depolarising(
+    pub fn multi_controlled_unitary(
         &mut self,
+        control_qubits: &[i32],
         target_qubit: i32,
-        prob: Qreal,
+        u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
+        let num_control_qubits = control_qubits.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::mixDepolarising(self.reg, target_qubit, prob);
+            ffi::multiControlledUnitary(
+                self.reg,
+                control_qubits.as_ptr(),
+                num_control_qubits,
+                target_qubit,
+                u.0,
+            );
         })
     }
 
-    ///  Mixes a density matrix to induce single-qubit amplitude damping.
+    /// Apply the single-qubit Pauli-X gate.
     ///
-    /// With probability `prob`, applies damping (transition from `1` to `0`
-    /// state). This transforms `qureg = rho` into the mixed state:
+    /// # Parameters
     ///
-    /// ```text
-    ///  K_0 rho K_0^\dagger + K_1 rho K_1^\dagger
-    /// ```
+    ///  - `target_qubit`: qubit to operate on
     ///
-    /// where `q = target_qubit` and `K_0` and `$K_1` are Kraus operators:
+    /// # Errors
     ///
-    /// ```text
-    ///      K_0 =  [ 1       0       ]   K_1 = [ 0  sqrt(prob) ]
-    ///             [ 0  sqrt(1-prob) ]         [ 0      0      ]
-    /// ```
+    /// - [`InvalidQuESTInputError`],
+    ///   - if either `control_qubit` or `target_qubit` is outside [0,
+    ///     [`qureg.num_qubits()`])
     ///
-    /// The coefficient `prob` cannot exceed 1, at which total damping/decay
-    /// occurs.
+    /// # Examples
     ///
-    /// Note that unlike [`mix_dephasing()`] and [`mix_depolarising()`], this
-    /// function can increase the purity of a mixed state (by, as `prob` becomes
-    /// `1`, gaining certainty that the qubit is in the 0 state).
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.pauli_x(0).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn pauli_x(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        catch_quest_exception(|| unsafe {
+            ffi::pauliX(self.reg, target_qubit);
+        })
+    }
+
+    /// Apply the single-qubit Pauli-Y gate.
     ///
     /// # Parameters
     ///
-    /// - `target_qubit`: qubit upon which to induce amplitude damping
-    /// - `prob`: the probability of the damping
+    ///  - `target_qubit`: qubit to operate on
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
-    ///   - if `prob` is not in `[0, 1]`
+    ///   - if either `control_qubit` or `target_qubit` is outside [0,
+    ///     [`qureg.num_qubits()`])
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.mix_damping(0, 1.).unwrap();
+    /// qureg.pauli_y(0).unwrap();
     ///
-    /// let amp = qureg.get_density_amp(0, 0).unwrap();
-    /// assert!((amp.re - 1.) < EPSILON);
+    /// let amp = qureg.get_imag_amp(1).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`mix_dephasing()`]: crate::Qureg::mix_dephasing()
-    /// [`mix_depolarising()`]: crate::Qureg::mix_depolarising()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_damping(
+    pub fn pauli_y(
         &mut self,
         target_qubit: i32,
-        prob: Qreal,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::mixDamping(self.reg, target_qubit, prob);
+            ffi::pauliY(self.reg, target_qubit);
         })
     }
 
-    /// Mixes a density matrix to induce two-qubit homogeneous depolarising
-    /// noise.
-    ///
-    /// With probability `prob`, applies to `qubit1` and `qubit2` any operator
-    /// of the set:
-    ///
-    /// ```text
-    /// { IX, IY, IZ, XI, YI, ZI, XX, XY, XZ, YX, YY, YZ, ZX, ZY, ZZ }.
-    /// ```
-    ///
-    /// Note this is the set of all two-qubit Pauli gates excluding `II`.
+    /// Apply the single-qubit Pauli-Z gate.
     ///
     /// # Parameters
     ///
-    /// - `qubit1`: qubit upon which to induce depolarizing noise
-    /// - `qubit2`: qubit upon which to induce depolarizing noise
-    /// - `prob`: the probability of the phase error occurring
+    ///  - `target_qubit`: qubit to operate on
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
-    ///   - if `qubit1` or `qubit2` are outside [0, [`num_qubits()`]).
-    ///   - if `qubit1 = qubit2`
-    ///   - if `prob` is not in `[0, 15/16]`
+    ///   - if either `control_qubit` or `target_qubit` is outside [0,
+    ///     [`qureg.num_qubits()`])
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(3, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// qureg.init_plus_state();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.mix_two_qubit_depolarising(0, 1, 15. / 16.).unwrap();
+    /// qureg.pauli_z(0).unwrap();
     ///
-    /// let amp = qureg.get_density_amp(0, 0).unwrap();
-    /// assert!((amp.re - 0.125).abs() < EPSILON);
-    /// let amp = qureg.get_density_amp(0, 1).unwrap();
-    /// assert!(amp.re.abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(0).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
@@ -3511,56 +3690,47 @@ impl<'a> Qureg<'a> {
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_two_qubit_depolarising(
+    pub fn pauli_z(
         &mut self,
-        qubit1: i32,
-        qubit2: i32,
-        prob: Qreal,
+        target_qubit: i32,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::mixTwoQubitDepolarising(self.reg, qubit1, qubit2, prob);
+            ffi::pauliZ(self.reg, target_qubit);
         })
     }
 
-    /// Mixes a density matrix to induce general single-qubit Pauli noise.
+    /// Apply the single-qubit Hadamard gate.
     ///
-    /// With probabilities `prob_x`, `prob_y` and `prob_z`, applies Pauli X, Y,
-    /// and Z respectively to `target_qubit`.
+    /// This function applies the following unitary on `qubit`:
     ///
-    /// This function operates by first converting the given Pauli
-    /// probabilities into a single-qubit Kraus map (four 2x2 operators).
+    /// ```text
+    /// SQRT_2.recip() *
+    ///     [ 1  1 ]
+    ///     [ 1 -1 ]
+    /// ```
     ///
     /// # Parameters
     ///
-    /// - `target_qubit`: qubit to decohere
-    /// - `prob_x`: the probability of inducing an X error
-    /// - `prob_y`: the probability of inducing an Y error
-    /// - `prob_z`: the probability of inducing an Z error
+    ///  - `target_qubit`: qubit to operate on
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
-    ///   - if `target_qubit` is outside [0, [`num_qubits()`])
-    ///   - if any of `prob_x`, `prob_y`, `prob_z` are not in `[0, 1]`
-    ///   - if any of p in `{prob_x, prob_y or prob_z}` don't satisfy `p <= (1 -
-    ///     prob_x - prob_y - prob_z)`
+    ///   - if either `control_qubit` or `target_qubit` is outside [0,
+    ///     [`qureg.num_qubits()`])
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    ///
-    /// let (prob_x, prob_y, prob_z) = (0.25, 0.25, 0.25);
-    /// qureg.mix_pauli(0, prob_x, prob_y, prob_z).unwrap();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let mut outcome_prob = -1.;
-    /// let _ = qureg.measure_with_stats(0, &mut outcome_prob).unwrap();
+    /// qureg.hadamard(0).unwrap();
     ///
-    /// assert!((outcome_prob - 0.5).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(0).unwrap();
+    /// assert!((amp - SQRT_2.recip()).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
@@ -3569,207 +3739,232 @@ impl<'a> Qureg<'a> {
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_pauli(
+    pub fn hadamard(
         &mut self,
         target_qubit: i32,
-        prob_x: Qreal,
-        prob_y: Qreal,
-        prob_z: Qreal,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::mixPauli(self.reg, target_qubit, prob_x, prob_y, prob_z);
+            ffi::hadamard(self.reg, target_qubit);
         })
     }
 
-    /// Modifies register with `other_qureg`.
+    /// Apply the controlled not (single control, single target) gate.
     ///
-    /// The state becomes `(1-prob) * self +  prob * other_qureg`.  The
-    /// probability `prob` must be in `[0,1]`.
+    /// The gate is also known as the c-X, c-sigma-X, c-Pauli-X and c-bit-flip
+    /// gate. This applies pauliX to the target qubit if the control qubit
+    /// has value 1. This effects the two-qubit unitary:
+    ///
+    /// ```text
+    ///  [ 1  0  0  0 ]
+    ///  [ 0  1  0  0 ]
+    ///  [ 0  0  0  1 ]
+    ///  [ 0  0  1  0 ]
+    /// ```
+    ///
+    /// on the control and target qubits.
     ///
     /// # Parameters
     ///
-    /// - `prob`: the probability of `other_qureg` in the modified register
-    /// - `other_qureg`: a density matrix to be mixed into
+    /// - `control_qubit`: "nots" the target if this qubit is `1`
+    /// - `target_qubit`: qubit to "not"
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if either `self` or `other_qureg` are not density matrices
-    ///   - if the dimensions of `self` and `other_qureg` do not match
-    ///   - if `prob` is not in `[0, 1]`
+    ///   - if either `control_qubit` or `target_qubit` is outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut combine_qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// let other_qureg = {
-    ///     let mut other_qureg = Qureg::try_new_density(2, &env)
-    ///         .expect("cannot allocate memory for Qureg");
-    ///     other_qureg.init_classical_state(3).unwrap();
-    ///     other_qureg
-    /// };
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.pauli_x(1).unwrap();
     ///
-    /// combine_qureg.mix_density_matrix(0.5, &other_qureg).unwrap();
+    /// qureg.controlled_not(1, 0).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_density_matrix(
+    pub fn controlled_not(
         &mut self,
-        prob: Qreal,
-        other_qureg: &Qureg<'_>,
+        control_qubit: i32,
+        target_qubit: i32,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::mixDensityMatrix(self.reg, prob, other_qureg.reg);
+            ffi::controlledNot(self.reg, control_qubit, target_qubit);
         })
     }
 
-    /// Calculate the purity of a density matrix.
+    /// Apply a NOT (or Pauli X) gate with multiple control and target qubits.
     ///
-    /// The purity of a density matrix is calculated by taking the trace of the
-    /// density matrix squared. Returns `Tr (\rho^2)`.
-    /// For a pure state, this =1.
-    /// For a mixed state, the purity is less than 1 and is lower bounded by
-    /// `1/2^n`, where n is the number of qubits. The minimum purity is achieved
-    /// for the maximally mixed state `identity/2^n`.
+    /// This applies pauliX to qubits `targs` on every basis state for which the
+    /// control qubits `ctrls` are all in the `|1>` state. The ordering within
+    /// each of `ctrls` and `targs` has no effect on the operation.
     ///
-    /// This function does not accept state-vectors, which clearly have purity
-    /// 1.
+    /// This function is equivalent, but significantly faster (approximately
+    /// `targs.len()` times) than applying controlled NOTs on each qubit in
+    /// `targs` in turn.
     ///
-    /// Note this function will give incorrect results for non-Hermitian Quregs
-    /// (i.e. invalid density matrices), which will disagree with
-    /// `Tr(\rho^2)`. Instead, this function returns `\sum_{ij}
-    /// |\rho_{ij}|^2`.
+    /// In distributed mode, this operation requires at most a single round of)
+    /// pair-wise communication between nodes, and hence is as efficient as
+    /// [`pauli_x()`][api-pauli-x].
     ///
     /// # Parameters
     ///
+    ///  - `ctrls`: a list of the control qubit indices
+    ///  - `targs`: a list of the qubits to be targeted by the X gates
     ///
     /// # Errors
     ///
-    /// Returns [`InvalidQuESTInputError`],
-    ///
-    /// - if the argument `qureg` is not a density matrix
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `ctrls` and `targs` is invalid, i.e. outside [0,
+    ///     [`qureg.num_qubits()`]).
+    ///   - if the length of `targs` or `ctrls` is larger than
+    ///     [`qureg.num_qubits()`]
+    ///   - if `ctrls` or `targs` contain any repetitions
+    ///   - if any qubit in `ctrls` is also in `targs` (and vice versa)
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
+    /// let mut qureg =
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.pauli_x(0).unwrap();
+    /// qureg.pauli_x(1).unwrap();
     ///
-    /// let purity = qureg.calc_purity().unwrap();
-    /// assert!((purity - 1.).abs() < EPSILON);
+    /// let ctrls = &[0, 1];
+    /// let targs = &[2, 3];
+    /// qureg
+    ///     .multi_controlled_multi_qubit_not(ctrls, targs)
+    ///     .unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [api-pauli-x]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn calc_purity(&self) -> Result<Qreal, QuestError> {
-        catch_quest_exception(|| unsafe { ffi::calcPurity(self.reg) })
-    }
-
-    /// Calculates the fidelity of `qureg` (a state-vector or density matrix).
-    ///
-    /// Calculates the fidelity against a reference pure state (necessarily a
-    /// state-vector).
-    ///
-    /// - If `qureg` is a state-vector, this function computes
-    ///
-    /// ```latex
-    ///  |\langle \text{qureg} | \text{pure_state} \rangle|^2
-    /// ```
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_controlled_multi_qubit_not(
+        &mut self,
+        ctrls: &[i32],
+        targs: &[i32],
+    ) -> Result<(), QuestError> {
+        let num_ctrls = ctrls.len() as i32;
+        let num_targs = targs.len() as i32;
+        catch_quest_exception(|| unsafe {
+            ffi::multiControlledMultiQubitNot(
+                self.reg,
+                ctrls.as_ptr(),
+                num_ctrls,
+                targs.as_ptr(),
+                num_targs,
+            );
+        })
+    }
+
+    /// Apply a NOT (or Pauli X) gate with multiple target qubits.
     ///
-    /// - If `qureg` is a density matrix, this function computes
+    /// This has the same  effect as (but is much faster than) applying each
+    /// single-qubit NOT gate in turn.
     ///
-    /// ```latex
-    ///  \langle \text{pure_state} | \text{qureg} | \text{pure_state} \rangle
-    /// ```
+    /// The ordering within `targs` has no effect on the operation.
     ///
-    /// In either case, the returned fidelity lies in `[0, 1]` (assuming both
-    /// input states have valid normalisation). If any of the input `Qureg`s
-    /// are not normalised, this function will return the real component of
-    /// the correct linear algebra calculation.
+    /// This function is equivalent, but significantly faster (approximately
+    /// `targs.len()` times) than applying NOT on each qubit in `targs` in turn.
     ///
-    /// The number of qubits represented in `qureg` and `pure_state` must match.
+    /// In distributed mode, this operation requires at most a single round of)
+    /// pair-wise communication between nodes, and hence is as efficient as
+    /// [`pauli_x()`][api-pauli-x].
     ///
     /// # Parameters
     ///
-    /// - `pure_state`: a state vector
-    ///
-    /// Returns the fidelity between the input registers
+    ///  - `targs`: a list of the qubits to be targeted by the X gates
     ///
     /// # Errors
     ///
-    /// Returns [`InvalidQuESTInputError`],
-    ///
-    /// - if the second argument `pure_state` is not a state-vector
-    /// - if the number of qubits `qureg` and `pure_state` do not match
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `targs` is invalid, i.e. outside [0,
+    ///     [`qureg.num_qubits()`]).
+    ///   - if the length of `targs` is larger than [`qureg.num_qubits()`]
+    ///   - if `targs` contains any repetitions
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// let pure_state = {
-    ///     let mut new_state =
-    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///     new_state.init_plus_state();
-    ///     new_state
-    /// };
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let fidelity = qureg.calc_fidelity(&pure_state).unwrap();
-    /// assert!((fidelity - 0.25).abs() < EPSILON);
+    /// let targs = &[0, 1];
+    /// qureg.multi_qubit_not(targs).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [api-pauli-x]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn calc_fidelity(
-        &self,
-        pure_state: &Qureg<'_>,
-    ) -> Result<Qreal, QuestError> {
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_qubit_not(
+        &mut self,
+        targs: &[i32],
+    ) -> Result<(), QuestError> {
+        let num_targs = targs.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::calcFidelity(self.reg, pure_state.reg)
+            let targs_ptr = targs.as_ptr();
+            ffi::multiQubitNot(self.reg, targs_ptr, num_targs);
         })
     }
 
-    /// Performs a SWAP gate between `qubit1` and `qubit2`.
+    /// Apply the controlled pauli Y (single control, single target) gate.
     ///
-    /// This effects
+    /// The gate is also known as the c-Y and c-sigma-Y gate.
+    /// This applies pauli Y to the target qubit, if the control qubit has value
+    /// 1. This effects the two-qubit unitary:
     ///
     /// ```text
-    /// [1 0 0 0]
-    /// [0 0 1 0]
-    /// [0 1 0 0]
-    /// [0 0 0 1]
+    ///  [ 1  0  0   0 ]
+    ///  [ 0  1  0   0 ]
+    ///  [ 0  0  0  -i ]
+    ///  [ 0  0  i   0 ]
     /// ```
     ///
-    /// on the designated qubits, though is performed internally by three CNOT
-    /// gates.
-    ///
+    /// on the control and target qubits.
     ///
     /// # Parameters
     ///
-    /// - `qubit1`: qubit to swap
-    /// - `qubit2`: other qubit to swap
+    /// - `control_qubit`: applies pauli Y the target if this qubit is `1`
+    /// - `target_qubit`: qubit to modify
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if either `qubit1` or `qubit2` is outside [0,
-    ///     [`qureg.num_qubits()`]).
-    ///   - if `qubit1` and `qubit2` are equal
+    ///   - if either `control_qubit` or `target_qubit` is outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
     ///
     /// # Examples
     ///
@@ -3778,57 +3973,62 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.pauli_x(1).unwrap();
     ///
-    /// // init state |10>
-    /// qureg.init_classical_state(1).unwrap();
-    /// // swap to |01>
-    /// qureg.swap_gate(0, 1).unwrap();
+    /// qureg.controlled_pauli_y(1, 0).unwrap();
     ///
-    /// let outcome = qureg.measure(0).unwrap();
-    /// assert_eq!(outcome, 0);
+    /// let amp = qureg.get_imag_amp(3).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn swap_gate(
+    pub fn controlled_pauli_y(
         &mut self,
-        qubit1: i32,
-        qubit2: i32,
+        control_qubit: i32,
+        target_qubit: i32,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::swapGate(self.reg, qubit1, qubit2);
+            ffi::controlledPauliY(self.reg, control_qubit, target_qubit);
         })
     }
 
-    /// Performs a sqrt SWAP gate between `qubit1` and `qubit2`.
+    /// Gives the probability of a qubit being measured in the given outcome.
     ///
-    /// This effects
+    /// This performs no actual measurement and does not change the state of the
+    /// qubits.
     ///
-    /// ```text
-    /// [ 1     0        0     0 ]
-    /// [ 0  (1+i)/2  (1-i)/2  0 ]
-    /// [ 0  (1-i)/2  (1+i)/2  0 ]
-    /// [ 0     0        0     1 ]
-    /// ```
+    /// - For state-vectors, this function works by summing the
+    ///   absolute-value-squared of every amplitude in the state-vector for
+    ///   which `measure_qubit = 0`. If `outcome = 1`, it returns `1` minus this
+    ///   value. Hence for unnormalized state-vectors, this result will differ
+    ///   from the absolute-value-squared of every amplitude where
+    ///   `measure_qubit = outcome`.
     ///
-    /// on the designated qubits, though is performed internally by three CNOT
-    /// gates.
+    /// - For density matrices, this function sums the diagonal values (should
+    ///   be real) corresponding to `measure_qubit = 0` (returning 1 minus this
+    ///   if `outcome = 1`).
     ///
     /// # Parameters
     ///
-    /// - `qubit1`: qubit to sqrt swap
-    /// - `qubit2`: other qubit to sqrt swap
+    /// - `measure_qubit`: qubit to study
+    /// - `outcome`: for which to find the probability of the qubit being
+    ///   measured in
+    ///
+    /// # Returns
+    ///
+    /// Returns probability of qubit `measure_qubit` being measured in the given
+    /// outcome.
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if either `qubit1` or `qubit2` is outside [0,
-    ///     [`qureg.num_qubits()`]).
-    ///   - if `qubit1` and `qubit2` are equal
+    ///   - if `measure_qubit` is outside [0, [`num_qubits()`])
+    ///   - if `outcome` is not in {0, 1}
     ///
     /// # Examples
     ///
@@ -3836,289 +4036,332 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// // init state |10>
-    /// qureg.init_classical_state(1).unwrap();
-    /// qureg.sqrt_swap_gate(0, 1).unwrap();
-    /// qureg.sqrt_swap_gate(0, 1).unwrap();
-    /// let outcome = qureg.measure(0).unwrap();
-    /// assert_eq!(outcome, 0);
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let prob = qureg.calc_prob_of_outcome(0, 0).unwrap();
+    /// assert!((prob - 1.).abs() < EPSILON);
+    /// let prob = qureg.calc_prob_of_outcome(0, 1).unwrap();
+    /// assert!(prob.abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn sqrt_swap_gate(
-        &mut self,
-        qb1: i32,
-        qb2: i32,
-    ) -> Result<(), QuestError> {
+    pub fn calc_prob_of_outcome(
+        &self,
+        measure_qubit: i32,
+        outcome: i32,
+    ) -> Result<Qreal, QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::sqrtSwapGate(self.reg, qb1, qb2);
+            ffi::calcProbOfOutcome(self.reg, measure_qubit, outcome)
         })
     }
 
-    /// Apply a general single-qubit unitary with multiple control qubits.
+    /// Calculate probabilities of every outcome of the sub-register.
     ///
-    /// The operation is conditioned upon a specific bit sequence:
-    /// `control_state`.
+    /// This function populates `outcome_probs` with the probabilities of every
+    /// outcome of the sub-register contained in `qubits`.
     ///
-    /// Any number of control qubits can be specified, along with their
-    /// classical state (`0` or `1`) to condition upon. Only amplitudes of
-    /// computational basis states  for which `control_qubits` have
-    /// corresponding bit values `control_state` are modified  by `u`.
-    ///  
-    /// This function is equivalent (albeit faster) to applying [`pauli_x()`] on
-    /// each of the control qubits which are conditioned on outcome `0`,
-    /// calling [`multi_controlled_unitary()`], then re-appplying
-    /// `pauli_x()` on the same qubits.
+    /// This performs no actual measurement and does not modify `qureg`.
     ///
-    ///  # Parameters
+    /// - `outcome_probs` must be a pre-allocated array of length
+    ///   `2^qubits.len()`. In distributed mode, every node receives the full
+    ///   list of outcome probabilities.
     ///
-    /// - `control_qubits`: the indices of the control qubits
-    /// - `control_state`: the bit values (`0` or `1`) of each control qubit,
-    ///   upon which to condition
-    /// - `target_qubit`: qubit to operate the unitary upon
-    /// - `u`: single-qubit unitary matrix to apply
+    /// - Note that the indices in `qubits` need not be adjacent nor ordered.
+    ///   The order of `qubits` determines the order of `outcome_probs`, whereby
+    ///   `qubits` are treated as *increasing* significance.
+    ///
+    /// - Since all probability amplitudes of a state-vector are ultimately
+    ///   involved in the output probabilities, this function works as expected
+    ///   for unnormalized states. This is similarly true for density matrices,
+    ///   where all  diagonal elements are involved, although only the real
+    ///   values of the diagonal elements will be consulted.
+    ///
+    /// # Parameters
+    ///
+    /// - `outcome_probs`: a pre-allocated array of length `1 << n`, where `n =
+    ///   qubits.len()`  which will be modified to contain all outcome
+    ///   probabilities
+    /// - `qubits`: a list of qubits to study
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit index (`target_qubit` or one in `control_qubits`) is
-    ///     outside [0, [`num_qubits()`]),
-    ///   - if any qubit in `control_qubits` is repeated
-    ///   - if `control_qubits` contains `target_qubit`
-    ///   - if any element of `control_state` is not a bit (`0` or `1`)
-    ///   - if `u` is not unitary
+    ///   - if any index in `qubits` is invalid, i.e. outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `qubits` contains any repetitions
+    /// - [`ArrayLengthError`],
+    ///   - if `outcome_probs.len() < 1 << qubits.len()`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
+    /// let qureg =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let control_qubits = &[1, 2];
-    /// let control_state = &[0, 0];
-    /// let target_qubit = 0;
-    /// let u = &ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    /// let qubits = &[1, 2];
+    /// let outcome_probs = &mut vec![0.; 4];
     /// qureg
-    ///     .multi_state_controlled_unitary(
-    ///         control_qubits,
-    ///         control_state,
-    ///         target_qubit,
-    ///         u,
-    ///     )
+    ///     .calc_prob_of_all_outcomes(outcome_probs, qubits)
     ///     .unwrap();
-    ///
-    /// let amp = qureg.get_real_amp(1).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// assert_eq!(outcome_probs, &vec![1., 0., 0., 0.]);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`pauli_x()`]: Qureg::pauli_x()
-    /// [`multi_controlled_unitary()`]: Qureg::multi_controlled_unitary()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::cast_sign_loss)]
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_state_controlled_unitary(
-        &mut self,
-        control_qubits: &[i32],
-        control_state: &[i32],
-        target_qubit: i32,
-        u: &ComplexMatrix2,
+    pub fn calc_prob_of_all_outcomes(
+        &self,
+        outcome_probs: &mut [Qreal],
+        qubits: &[i32],
     ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
+        if outcome_probs.len() < 1 << qubits.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_qubits = qubits.len() as i32;
+        let outcome_probs_ptr = outcome_probs.as_mut_ptr();
         catch_quest_exception(|| unsafe {
-            ffi::multiStateControlledUnitary(
+            ffi::calcProbOfAllOutcomes(
+                outcome_probs_ptr,
                 self.reg,
-                control_qubits.as_ptr(),
-                control_state.as_ptr(),
-                num_control_qubits,
-                target_qubit,
-                u.0,
+                qubits.as_ptr(),
+                num_qubits,
             );
         })
     }
 
-    /// Apply a multi-qubit Z rotation on selected qubits.
+    /// Sample measurement outcomes without modifying the register.
     ///
-    /// This is the unitary
+    /// Draws `shots` independent outcomes of measuring `qubits` in the
+    /// computational basis, and returns them as a histogram keyed by
+    /// bitstrings. The order of `qubits` determines the order of bits, whereby
+    /// `qubits` are treated as *increasing* significance, as in
+    /// [`calc_prob_of_all_outcomes()`][api-calc-prob-of-all-outcomes].
     ///
-    /// ```latex
-    ///    \exp \left( - i \, \frac{\theta}{2} \; \bigotimes_{j}^{\text{numQubits}} Z_j\right)
+    /// Outcomes are drawn with [`rand::thread_rng()`]. Use
+    /// [`sample_with_rng()`][api-sample-with-rng] for reproducible results.
+    ///
+    /// # Parameters
+    ///
+    /// - `shots`: number of outcomes to draw
+    /// - `qubits`: a list of qubits to measure
+    ///
+    /// # Errors
+    ///
+    /// - [`NumQubitsError`], if `qubits` is longer than [`num_qubits()`]
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any index in `qubits` is invalid, i.e. outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `qubits` contains any repetitions
+    /// - [`ProbabilityError`], if the probabilities of the outcomes are not
+    ///   finite or sum to zero, e.g. after
+    ///   [`init_blank_state()`][api-init-blank-state]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.pauli_x(2).unwrap();
+    /// qureg.hadamard(0).unwrap();
+    ///
+    /// let counts = qureg.sample(1000, &[0, 2]).unwrap();
+    /// assert_eq!(counts.shots(), 1000);
+    /// assert_eq!(counts.get("10") + counts.get("11"), 1000);
     /// ```
     ///
-    /// where the Pauli Z gates operate the qubits listed in `qubits`, and cause
-    /// rotations of `theta = angle`.
+    /// [api-calc-prob-of-all-outcomes]: crate::Qureg::calc_prob_of_all_outcomes()
+    /// [api-sample-with-rng]: crate::Qureg::sample_with_rng()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [api-init-blank-state]: crate::Qureg::init_blank_state()
+    /// [`NumQubitsError`]: crate::QuestError::NumQubitsError
+    /// [`ProbabilityError`]: crate::QuestError::ProbabilityError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn sample(
+        &self,
+        shots: usize,
+        qubits: &[i32],
+    ) -> Result<Counts, QuestError> {
+        self.sample_with_rng(shots, qubits, &mut rand::thread_rng())
+    }
+
+    /// Sample measurement outcomes using a given random number generator.
     ///
-    /// All qubits not appearing in `qubits` are assumed to receive the
-    /// identity operator.
+    /// See [`sample()`][api-sample] for details.
     ///
-    /// This has the effect of premultiplying every amplitude with `exp(+/- i
-    /// \theta/2)` where the sign is determined by the parity of the target
-    /// qubits for that amplitude.
+    /// `QuEST`'s own generator is not used, so sampling does not affect the
+    /// outcomes of subsequent calls to [`measure()`][api-measure]. To
+    /// reproduce a whole simulation, seed both generators, e.g. with
+    /// [`seed_quest()`][api-seed-quest] and
+    /// [`SeedableRng::seed_from_u64()`][rand-seed-from-u64].
     ///
-    ///  # Parameters
+    /// # Parameters
     ///
-    /// - `qubits`: a list of the indices of the target qubits
-    /// - `angle`: the angle by which the multi-qubit state is rotated around
-    ///   the Z axis
+    /// - `shots`: number of outcomes to draw
+    /// - `qubits`: a list of qubits to measure
+    /// - `rng`: random number generator
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit index in `qubits` is outside [0, [`num_qubits()`]),
-    ///   - if any qubit in `qubits` is repeated
-    ///   - if `control_qubits` contains `target_qubit`
-    ///   - if any element of `control_state` is not a bit (`0` or `1`)
-    ///   - if `u` is not unitary
+    /// See [`sample()`][api-sample].
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
+    /// use rand::{
+    ///     rngs::StdRng,
+    ///     SeedableRng,
+    /// };
+    ///
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     /// qureg.init_plus_state();
     ///
-    /// let qubits = &[0, 1];
-    /// let angle = PI;
-    /// qureg.multi_rotate_z(qubits, angle).unwrap();
-    ///
-    /// let amp = qureg.get_imag_amp(0).unwrap();
-    /// assert!((amp + 0.5).abs() < EPSILON);
-    /// let amp = qureg.get_imag_amp(1).unwrap();
-    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// let counts1 = qureg
+    ///     .sample_with_rng(100, &[0, 1], &mut StdRng::seed_from_u64(42))
+    ///     .unwrap();
+    /// let counts2 = qureg
+    ///     .sample_with_rng(100, &[0, 1], &mut StdRng::seed_from_u64(42))
+    ///     .unwrap();
+    /// assert_eq!(counts1, counts2);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_rotate_z(
-        &mut self,
+    /// [api-sample]: crate::Qureg::sample()
+    /// [api-measure]: crate::Qureg::measure()
+    /// [api-seed-quest]: crate::seed_quest()
+    /// [rand-seed-from-u64]: rand::SeedableRng::seed_from_u64()
+    pub fn sample_with_rng<R: Rng + ?Sized>(
+        &self,
+        shots: usize,
         qubits: &[i32],
-        angle: Qreal,
-    ) -> Result<(), QuestError> {
-        let num_qubits = qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
-            ffi::multiRotateZ(self.reg, qubits.as_ptr(), num_qubits, angle);
-        })
+        rng: &mut R,
+    ) -> Result<Counts, QuestError> {
+        // Check before allocating space for all outcomes
+        if qubits.len() > self.num_qubits() as usize {
+            return Err(QuestError::NumQubitsError);
+        }
+        let outcome_probs = &mut vec![0.; 1 << qubits.len()];
+        self.calc_prob_of_all_outcomes(outcome_probs, qubits)?;
+
+        let total_prob: Qreal = outcome_probs.iter().sum();
+        if !total_prob.is_finite() || total_prob <= 0. {
+            return Err(QuestError::ProbabilityError);
+        }
+        // Rounding errors can make tiny probabilities negative
+        let dist = WeightedIndex::new(outcome_probs.iter().map(|p| p.max(0.)))
+            .map_err(|_| QuestError::ProbabilityError)?;
+        let mut hist = vec![0; outcome_probs.len()];
+        for outcome in dist.sample_iter(rng).take(shots) {
+            hist[outcome] += 1;
+        }
+        Ok(Counts::from_histogram(qubits.len(), &hist))
     }
 
-    /// Apply a multi-qubit multi-Pauli rotation.
+    /// Updates `qureg` to be consistent with measuring qubit in the given
+    /// outcome.
     ///
-    /// This is the unitary
+    /// Returns the probability of such a measurement outcome. This is
+    /// effectively performing a renormalizing projection, or a measurement
+    /// with a forced outcome.  This is an irreversible change to the state,
+    /// whereby computational states inconsistent with the outcome are
+    /// given zero amplitude and the `Qureg` is renormalized.  The given
+    /// outcome must not have a near zero probability, else it cannot
+    /// be collapsed into.
     ///
-    /// ```latex
-    ///    \exp \left( - i \, \frac{\theta}{2} \; \bigotimes_{j}^{\text{numTargets}} \hat{\sigma}_j\right)
-    /// ```
+    /// Note that the collapse probably used for renormalization is calculated
+    /// for  `outcome = 0`, and assumed `1` minus this probability if
+    /// `outcome = 1`.  Hence this routine will not correctly project
+    /// un-normalised `Qureg`s onto  `outcome = 1`.
     ///
-    ///  where  `theta = angle` and `$\hat{\sigma}_j \in \{X, Y, Z\}$` is a
-    /// Pauli operator [`PauliOpType`] operating upon the corresponding qubit
-    /// `target_qubits`.
+    /// To avoid renormalization after projection, or force projection into
+    /// non-physical states with very small probability, use
+    /// [`apply_projector()`].  
     ///
-    /// This function effects the Pauli gadget by first rotating the qubits
-    /// which are nominated to receive `X` or `Y` Paulis into alternate
-    /// basis, performing [`multi_rotate_z()`] on all target qubits, then
-    /// restoring the original basis.
+    /// # Parameters
     ///
-    ///  # Parameters
+    /// - `measure_qubit`: qubit to measure
+    /// - `outcome`: to force the measure qubit to enter
     ///
-    /// - `target_qubits`: a list of the indices of the target qubits
-    /// - `target_paulis`: a list of the Pauli operators `PauliOpType`
-    /// - `angle`: the angle by which the multi-qubit state is rotated
+    /// # Returns
+    ///
+    /// Probability of the (forced) measurement outcome.
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit index in `target_qubits` is outside [0,
-    ///     [`num_qubits()`]),
-    ///   - if any qubit in `target_qubits` is repeated
+    ///   - if `measure_qubit` is outside `[0, N)`
+    ///   - if `outcome` is not in `{0, 1}`
+    ///   - if the probability of `outcome` is zero (within machine epsilon)
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
-    /// use PauliOpType::PAULI_X;
-    ///
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    ///
-    /// let target_qubits = &[1, 2];
-    /// let target_paulis = &[PAULI_X, PAULI_X];
-    /// let angle = PI;
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// qureg
-    ///     .multi_rotate_pauli(target_qubits, target_paulis, angle)
-    ///     .unwrap();
+    /// qureg.collapse_to_outcome(0, 0).unwrap();
     ///
-    /// let amp = qureg.get_imag_amp(6).unwrap();
-    /// assert!((amp + 1.).abs() < 2. * EPSILON);
+    /// // QuEST throws an exception if probability of outcome is 0.
+    /// qureg.init_zero_state();
+    /// qureg.collapse_to_outcome(0, 1).unwrap_err();
     /// ```
     ///
-    ///
     /// See [QuEST API] for more information.
     ///
-    /// [`PauliOpType`]: crate::PauliOpType
-    /// [`multi_rotate_z()`]: Qureg::multi_rotate_z()
+    /// [`apply_projector()`]: Qureg::apply_projector()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_rotate_pauli(
+    pub fn collapse_to_outcome(
         &mut self,
-        target_qubits: &[i32],
-        target_paulis: &[PauliOpType],
-        angle: Qreal,
-    ) -> Result<(), QuestError> {
-        let num_targets = target_qubits.len() as i32;
+        measure_qubit: i32,
+        outcome: i32,
+    ) -> Result<Qreal, QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::multiRotatePauli(
-                self.reg,
-                target_qubits.as_ptr(),
-                target_paulis.as_ptr(),
-                num_targets,
-                angle,
-            );
+            ffi::collapseToOutcome(self.reg, measure_qubit, outcome)
         })
     }
 
-    /// Apply a multi-controlled multi-target Z rotation.
+    /// Measures a single qubit, collapsing it randomly to `0` or `1`.
     ///
-    /// All qubits not appearing in `target_qubits` and `control_qubits` are
-    /// assumed to receive the identity operator.
+    /// Outcome probabilities are weighted by the state vector, which is
+    /// irreversibly  changed after collapse to be consistent with the
+    /// outcome.
     ///
-    /// This has the effect of premultiplying all amplitudes (for which the
-    /// control qubits are `1`)  with `$\exp(\pm i \theta/2)$`, where the
-    /// sign is determined by the parity of  the target qubits for that
-    /// amplitude.
+    /// The random number generator is seeded by [`seed_quest_default()`]
+    /// within  [`QuestEnv::new()`], unless later overridden by
+    /// [`seed_quest()`].
     ///
-    ///  # Parameters
+    /// # Parameters
     ///
-    /// - `control_qubits`: list of the indices of qubits to control upon
-    /// - `target_qubits`: a list of the indices of the target qubits
-    /// - `angle`: the angle by which the multi-qubit state is rotated
+    /// - `measure_qubit`: index of a qubit to measure
+    ///
+    /// # Returns
+    ///
+    /// The measurement outcome, `0` or `1`.
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit index in `target_qubits` or `control_qubits` is outside
-    ///     [0, [`num_qubits()`]),
-    ///   - if `control_qubits` or `target_qubits` contain any repetitions
-    ///   - if any qubit in `control_qubits` is also in `target_qubits` (and
-    ///     vice versa)
+    ///   - if `measure_qubit` is outside `[0, N)`
     ///
     /// # Examples
     ///
@@ -4126,615 +4369,459 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// // Initialize `|1111>`
-    /// (0..4).try_for_each(|i| qureg.pauli_x(i)).unwrap();
+    /// // Prepare an entangled state `|00> + |11>`
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
     ///
-    /// let control_qubits = &[0, 1];
-    /// let target_qubits = &[2, 3];
-    /// let angle = 2. * PI;
-    /// qureg
-    ///     .multi_controlled_multi_rotate_z(control_qubits, target_qubits, angle)
-    ///     .unwrap();
+    /// // Qubits are entangled now
+    /// let outcome1 = qureg.measure(0).unwrap();
+    /// let outcome2 = qureg.measure(1).unwrap();
     ///
-    /// // the state is now `-1. * |1111>`
-    /// let amp = qureg.get_real_amp(15).unwrap();
-    /// assert!((amp + 1.).abs() < EPSILON);
+    /// assert_eq!(outcome1, outcome2);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`seed_quest_default()`]: crate::seed_quest_default()
+    /// [`QuestEnv::new()`]: QuestEnv::new()
+    /// [`seed_quest()`]: crate::seed_quest()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_multi_rotate_z(
+    pub fn measure(
         &mut self,
-        control_qubits: &[i32],
-        target_qubits: &[i32],
-        angle: Qreal,
-    ) -> Result<(), QuestError> {
-        let num_controls = control_qubits.len() as i32;
-        let num_targets = target_qubits.len() as i32;
+        measure_qubit: i32,
+    ) -> Result<i32, QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::multiControlledMultiRotateZ(
-                self.reg,
-                control_qubits.as_ptr(),
-                num_controls,
-                target_qubits.as_ptr(),
-                num_targets,
-                angle,
-            );
+            ffi::measure(self.reg, measure_qubit)
         })
     }
 
-    /// Apply a multi-controlled multi-target multi-Pauli rotation.
+    /// Measures a single qubit, collapsing it randomly to 0 or 1
     ///
-    /// All qubits not appearing in `target_qubits` and `control_qubits` are
-    /// assumed to receive the identity operator.
+    /// Additionally, the function gives the probability of that outcome.
+    /// Outcome probabilities are weighted by the state vector, which is
+    /// irreversibly changed after collapse to be consistent with the outcome.
     ///
-    /// This function effects the controlled Pauli gadget by first (controlled)
-    /// rotating the qubits which are targeted with either `X` or `Y` into
-    /// alternate basis, performing [`multi_controlled_multi_rotate_z()`] on all
-    /// target qubits, then restoring the original basis.
+    /// The random number generator is seeded by [`seed_quest_default()`]
+    /// within  [`QuestEnv::new()`], unless later overridden by
+    /// [`seed_quest()`].
     ///
     /// # Parameters
     ///
-    /// - `control_qubits`: list of the indices of qubits to control upon
-    /// - `target_qubits`: a list of the indices of the target qubits
-    /// - `target_paulis`: a list of the Pauli operators [`PauliOpType`]
-    /// - `angle`: the angle by which the multi-qubit state is rotated
+    /// - `measure_qubit`: index of a qubit to measure
+    /// - `outcome_prob`: a mutable reference to a `Qreal` which is set to the
+    ///   probability of the occurred outcome
+    ///
+    /// # Returns
+    ///
+    /// The measurement outcome, `0` or `1`.
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit index in `target_qubits` or `control_qubits` is outside
-    ///     [0, [`num_qubits()`]),
-    ///   - if `control_qubits` or `target_qubits` contain any repetitions
-    ///   - if any qubit in `control_qubits` is also in `target_qubits` (and
-    ///     vice versa)
+    ///   - if `measure_qubit` is outside `[0, N)`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
-    /// use PauliOpType::PAULI_Z;
-    ///
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// // Initialize `|1111>`
-    /// (0..4).try_for_each(|i| qureg.pauli_x(i)).unwrap();
+    /// // Prepare an entangled state `|00> + |11>`
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
     ///
-    /// let control_qubits = &[0, 1];
-    /// let target_qubits = &[2, 3];
-    /// let target_paulis = &[PAULI_Z, PAULI_Z];
-    /// let angle = 2. * PI;
-    /// qureg
-    ///     .multi_controlled_multi_rotate_pauli(
-    ///         control_qubits,
-    ///         target_qubits,
-    ///         target_paulis,
-    ///         angle,
-    ///     )
-    ///     .unwrap();
+    /// // Qubits are entangled now
+    /// let prob = &mut -1.;
+    /// let outcome1 = qureg.measure_with_stats(0, prob).unwrap();
+    /// assert!((*prob - 0.5).abs() < EPSILON);
     ///
-    /// // the state is now `-1. * |1111>`
-    /// let amp = qureg.get_real_amp(15).unwrap();
-    /// assert!((amp + 1.).abs() < EPSILON);
+    /// let outcome2 = qureg.measure_with_stats(1, prob).unwrap();
+    /// assert!((*prob - 1.).abs() < EPSILON);
+    ///
+    /// assert_eq!(outcome1, outcome2);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`PauliOpType`]: crate::PauliOpType
-    /// [`multi_controlled_multi_rotate_z()`]: Qureg::multi_controlled_multi_rotate_z()
+    /// [`seed_quest_default()`]: crate::seed_quest_default()
+    /// [`QuestEnv::new()`]: QuestEnv::new()
+    /// [`seed_quest()`]: crate::seed_quest()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_multi_rotate_pauli(
+    pub fn measure_with_stats(
         &mut self,
-        control_qubits: &[i32],
-        target_qubits: &[i32],
-        target_paulis: &[PauliOpType],
-        angle: Qreal,
-    ) -> Result<(), QuestError> {
-        let num_controls = control_qubits.len() as i32;
-        let num_targets = target_qubits.len() as i32;
+        measure_qubit: i32,
+        outcome_prob: &mut Qreal,
+    ) -> Result<i32, QuestError> {
+        let outcome_prob_ptr = outcome_prob as *mut _;
         catch_quest_exception(|| unsafe {
-            ffi::multiControlledMultiRotatePauli(
-                self.reg,
-                control_qubits.as_ptr(),
-                num_controls,
-                target_qubits.as_ptr(),
-                target_paulis.as_ptr(),
-                num_targets,
-                angle,
-            );
+            ffi::measureWithStats(self.reg, measure_qubit, outcome_prob_ptr)
         })
     }
 
-    /// Computes the expected value of a product of Pauli operators.
+    /// Enable QASM recording.
     ///
-    /// Letting `$\sigma = \otimes_j \hat{\sigma}_j$` be the operators
-    /// indicated by `pauli_codes` and acting on qubits `target_qubits`,
-    /// this function computes `$ \langle \psi | \sigma | \psi \rangle $`
-    /// if `$qureg = \psi$` is a state-vector, and computes `$ \text{Tr}(\sigma
-    /// \rho) $`  if `$qureg = \rho $` is a density matrix.
-    ///  
-    /// The slice `pauli_codes` specifies which Pauli operators to enact on the
-    /// corresponding qubits in `target_qubits`. The target qubits must be
-    /// unique, and at most `self.num_qubits()` may be specified.
+    /// Gates applied to qureg will here-after be added to a growing log of QASM
+    /// instructions, progressively consuming more memory until disabled with
+    /// [`stop_recording_qasm()`]. The QASM log is bound to this qureg instance.
     ///
-    /// For example, on a 7-qubit state-vector,  
+    ///
+    /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
-    /// # use PauliOpType::{
-    /// #     PAULI_X,
-    /// #     PAULI_Z,
-    /// #     PAULI_Y,
-    /// #     PAULI_I,
-    /// # };
-    /// # let env = QuestEnv::new();
-    /// # let qureg = {
-    /// #     let mut qureg = Qureg::try_new(2, &env).expect(
-    /// #         "cannot allocate memory for Qureg",
-    /// #     );
-    /// #     qureg.init_plus_state();
-    /// #     qureg
-    /// # };
-    /// # let mut workspace = Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.calc_expec_pauli_prod(&[4,5,6], &[PAULI_X, PAULI_I, PAULI_Z], &mut workspace);
-    /// ```
-    ///
-    /// will compute `$ \langle \psi | I I I I X I Z | \psi \rangle $` (where in
-    /// this notation, the left-most operator applies to the
-    /// least-significant qubit, i.e. that with index `0`).
-    ///
-    /// `workspace` must be a register with the same type (state-vector vs
-    /// density matrix) and dimensions (number of represented qubits) as `self`,
-    /// and is used as working space. When this function returns, `self` will
-    /// be unchanged and `workspace` will be set to `$ \sigma
-    /// | \psi \rangle $` (if `self` is a state-vector)  or $ \sigma
-    /// \rho $` (if `self` is a density matrix).
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// NOTE that this last quantity is NOT the result of applying  the paulis
-    /// as unitaries, `$ \sigma^\dagger \rho \sigma $`, but is instead the
-    /// result of their direct multiplication with the density matrix. It
-    /// is therefore itself not a valid density matrix.
+    /// qureg.start_recording_qasm();
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    /// qureg.stop_recording_qasm();
     ///
-    /// This function works by cloning the `self` state into `workspace`,
-    /// applying the specified  Pauli operators to `workspace` then
-    /// computing its inner product with `self` (for state-vectors)
-    /// or its trace (for density matrices). It therefore should scale linearly
-    /// in time with the number of specified non-identity Pauli operators,
-    /// which is bounded by the number of represented qubits.
+    /// qureg.print_recorded_qasm();
+    /// ```
     ///
-    /// # Parameters
+    /// See [QuEST API] for more information.
     ///
-    /// - `target_qubits`: a list of the indices of the target qubits
-    /// - `pauli_codes`: a list of the Pauli codes to apply to the corresponding
-    ///   qubits in `target_qubits`
-    /// - `workspace`: a working-space qureg with the same dimensions as `self`,
-    ///   which is modified to be the result of multiplying the state with the
-    ///   pauli operators
+    /// [`stop_recording_qasm()`]: Qureg::stop_recording_qasm()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn start_recording_qasm(&mut self) {
+        catch_quest_exception(|| unsafe {
+            ffi::startRecordingQASM(self.reg);
+        })
+        .expect("start_recording_qasm should always succeed");
+    }
+
+    /// Disable QASM recording.
     ///
-    /// # Errors
+    /// The recorded QASM will be maintained in qureg and continue to be
+    /// appended to if [`start_recording_qasm()`] is recalled.
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubits.len() > self.num_qubits()`
-    ///   - if any qubit index in `target_qubits` is outside [0,
-    ///     [`num_qubits()`]),
-    ///   - if `target_qubits` contain any repetitions
-    ///   - if `workspace` is not of the same dimension as `self`
+    /// Has no effect if this `Qureg` was not already recording operations.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
-    /// use PauliOpType::PAULI_X;
-    ///
     /// let env = QuestEnv::new();
-    /// let qureg = {
-    ///     let mut qureg = Qureg::try_new(2, &env).expect(
-    ///         "cannot allocate memory for
-    /// Qureg",
-    ///     );
-    ///     qureg.init_plus_state();
-    ///     qureg
-    /// };
-    /// let mut workspace =
+    /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let target_qubits = &[0, 1];
-    /// let pauli_codes = &[PAULI_X, PAULI_X];
+    /// qureg.start_recording_qasm();
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    /// qureg.stop_recording_qasm();
     ///
-    /// let product = qureg
-    ///     .calc_expec_pauli_prod(target_qubits, pauli_codes, &mut workspace)
-    ///     .unwrap();
+    /// qureg.print_recorded_qasm();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`start_recording_qasm()`]: Qureg::start_recording_qasm()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn calc_expec_pauli_prod(
-        &self,
-        target_qubits: &[i32],
-        pauli_codes: &[PauliOpType],
-        workspace: &mut Qureg<'_>,
-    ) -> Result<Qreal, QuestError> {
-        let num_targets = target_qubits.len() as i32;
+    pub fn stop_recording_qasm(&mut self) {
         catch_quest_exception(|| unsafe {
-            ffi::calcExpecPauliProd(
-                self.reg,
-                target_qubits.as_ptr(),
-                pauli_codes.as_ptr(),
-                num_targets,
-                workspace.reg,
-            )
+            ffi::stopRecordingQASM(self.reg);
         })
+        .expect("stop_recording_qasm should always succeed");
     }
 
-    /// Computes the expected value of a sum of products of Pauli operators.
+    /// Clear all QASM so far recorded.
     ///
-    /// Let
+    /// This does not start or stop recording.
     ///
-    /// ```latex
-    /// H = \sum_i c_i \otimes_j^{N} \hat{\sigma}_{i,j}
-    /// ```
+    /// # Examples
     ///
-    /// be the operators indicated by `all_pauli_codes` (where `$ c_i \in $`
-    /// `term_coeffs`  and `N = self.num_qubits()`).
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.start_recording_qasm();
+    /// qureg.hadamard(0).unwrap();
     ///
-    /// This function computes `$ \langle \psi | H | \psi \rangle $` if `self =
-    /// \psi $` is a state-vector, and computes `$ \text{Tr}(H \rho)
-    /// =\text{Trace}(\rho H) $`  if `self = \rho $` is a density matrix.
+    /// qureg.clear_recorded_qasm();
     ///
-    /// `all_pauli_codes` is an array which specifies which Pauli operators to
-    /// apply. For each sum term, a Pauli operator must be specified for
-    /// EVERY qubit in `self`; each set of `term_coeffs.len()` operators will be
-    /// grouped into a product.  `term_coeffs` is an arrray containing the term
-    /// coefficients.
+    /// qureg.controlled_not(0, 1).unwrap();
+    /// qureg.stop_recording_qasm();
+    /// qureg.print_recorded_qasm();
+    /// ```
     ///
-    /// For example, on a 3-qubit state-vector,  
+    /// See [QuEST API] for more information.
+    ///
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn clear_recorded_qasm(&mut self) {
+        catch_quest_exception(|| unsafe {
+            ffi::clearRecordedQASM(self.reg);
+        })
+        .expect("clear_recorded_qasm should always succeed");
+    }
+
+    /// Print recorded QASM to stdout.
+    ///
+    /// This does not clear the QASM log, nor does it start or stop QASM
+    /// recording.
+    ///
+    /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
-    /// # use PauliOpType::{
-    /// #     PAULI_X,
-    /// #     PAULI_Z,
-    /// #     PAULI_Y,
-    /// #     PAULI_I,
-    /// # };
-    /// # let env = QuestEnv::new();
-    /// # let qureg = {
-    /// #     let mut qureg = Qureg::try_new(2, &env).expect(
-    /// #         "cannot allocate memory for Qureg",
-    /// #     );
-    /// #     qureg.init_plus_state();
-    /// #     qureg
-    /// # };
-    /// # let mut workspace =
-    /// #     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    /// let paulis = [PAULI_X, PAULI_I, PAULI_I,  PAULI_X, PAULI_Y, PAULI_Z];
-    /// let coeffs = [1.5, -3.6];
-    /// qureg.calc_expec_pauli_sum(&paulis, &coeffs, &mut workspace);
-    /// ```
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// will compute `$ \langle \psi | (1.5 X I I - 3.6 X Y Z) | \psi \rangle$`
-    /// (where in this notation, the left-most operator  applies to the
-    /// least-significant qubit, i.e. that with index `0`).  
-    /// `workspace` must be a register with the same type (state-vector vs
-    /// density matrix) and dimensions (number of represented qubits) as
-    /// `self`, and is used as working space. When this function returns,
-    /// `self`  will be unchanged and `workspace` will be set to `self`
-    /// pre-multiplied with the final Pauli product.
+    /// qureg.start_recording_qasm();
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    /// qureg.stop_recording_qasm();
     ///
-    /// NOTE that if `self` is a density matrix, `workspace` will become `$
-    /// \hat{\sigma} \rho $` which is itself not a density matrix (it is
-    /// distinct from `$ \hat{\sigma} \rho \hat{\sigma}^\dagger $`).
+    /// qureg.print_recorded_qasm();
+    /// ```
     ///
-    /// This function works by cloning the `self` state into `workspace`,
-    /// applying each of the specified  Pauli products to `workspace` (one
-    /// Pauli operation at a time), then computing its inner product with `self`
-    /// (for state-vectors) or its trace (for density matrices) multiplied
-    /// with the corresponding coefficient, and summing these contributions.
-    /// It therefore should scale linearly in time with the total number of
-    /// non-identity specified Pauli operators.
+    /// See [QuEST API] for more information.
+    ///
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn print_recorded_qasm(&mut self) {
+        catch_quest_exception(|| unsafe {
+            ffi::printRecordedQASM(self.reg);
+        })
+        .expect("print_recorded_qasm should always succeed");
+    }
+
+    /// Writes recorded QASM to a file, throwing an error if inaccessible.
     ///
     /// # Parameters
     ///
-    /// - `all_pauli_codes`: a list of the Pauli codes of all Paulis involved in
-    ///   the products of terms. A Pauli must be specified for each qubit  in
-    ///   the register, in every term of the sum.
-    /// - `term_coeffs`: The coefficients of each term in the sum of Pauli
-    ///   products
-    /// - `workspace`: a working-space qureg with the same dimensions as `self`,
-    ///   which is modified to be the result of multiplying the state with the
-    ///   pauli operators
+    /// - `filename`: the filename of the file to contain the recorded QASM
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `workspace` is not of the same dimension as `self`
+    ///   - if `filename` cannot be written to
     ///
     /// # Examples
+    ///
     /// ```rust
     /// # use quest_bind::*;
-    /// use PauliOpType::{
-    ///     PAULI_X,
-    ///     PAULI_Z,
-    /// };
-    ///
     /// let env = QuestEnv::new();
-    /// let qureg = {
-    ///     let mut qureg = Qureg::try_new(2, &env).expect(
-    ///         "cannot allocate memory for
-    /// Qureg",
-    ///     );
-    ///     qureg.init_plus_state();
-    ///     qureg
-    /// };
-    /// let mut workspace =
+    /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let all_pauli_codes = &[PAULI_X, PAULI_Z, PAULI_Z, PAULI_X];
-    /// let term_coeffs = &[0.5, 0.5];
+    /// qureg.start_recording_qasm();
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    /// qureg.stop_recording_qasm();
     ///
-    /// let sum = qureg
-    ///     .calc_expec_pauli_sum(all_pauli_codes, term_coeffs, &mut workspace)
-    ///     .unwrap();
+    /// qureg.write_recorded_qasm_to_file("/dev/null").unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn calc_expec_pauli_sum(
-        &self,
-        all_pauli_codes: &[PauliOpType],
-        term_coeffs: &[Qreal],
-        workspace: &mut Qureg<'_>,
-    ) -> Result<Qreal, QuestError> {
-        let num_sum_terms = term_coeffs.len() as i32;
-        catch_quest_exception(|| unsafe {
-            ffi::calcExpecPauliSum(
-                self.reg,
-                all_pauli_codes.as_ptr(),
-                term_coeffs.as_ptr(),
-                num_sum_terms,
-                workspace.reg,
-            )
-        })
+    pub fn write_recorded_qasm_to_file(
+        &mut self,
+        filename: &str,
+    ) -> Result<(), QuestError> {
+        unsafe {
+            let filename_cstr =
+                CString::new(filename).map_err(QuestError::NulError)?;
+            catch_quest_exception(|| {
+                ffi::writeRecordedQASMToFile(
+                    self.reg,
+                    (*filename_cstr).as_ptr(),
+                );
+            })
+        }
     }
 
-    /// Computes the expected value under Hermitian operator.
-    ///
-    /// Represent `hamil` as `$ H = \sum_i c_i \otimes_j^{N}
-    /// \hat{\sigma}_{i,j} $`  and `N = hamil.num_qubits()`.  This function
-    /// computes:
-    ///
-    /// ```latex
-    ///  \langle \psi | H | \psi \rangle
-    /// ```
+    /// Get recorded QASM as a string.
     ///
-    ///  if `self =  \psi` is a state-vector, and computes
+    /// This does not clear the QASM log, nor does it start or stop QASM
+    /// recording.
     ///
-    /// ```latex
-    /// \text{Trace}(H \rho) =\text{Trace}(\rho H)
-    /// ```
+    /// # Errors
     ///
-    /// if `self = \rho`  is a density matrix.
+    /// Returns [`Utf8Error`] if the recorded QASM is not valid UTF-8.
     ///
-    /// This function is merely an encapsulation of `calc_expec_pauli_sum()` -
-    /// refer to the doc there for an elaboration.
+    /// # Examples
     ///
-    /// `workspace` must be a register with the same type (state-vector vs
-    /// density matrix) and dimensions (number of represented qubits) as
-    /// `self` and `hamil`, and is used as working space.  When this
-    /// function returns, `self`  will be unchanged and `workspace` will be
-    /// set to `self` pre-multiplied with the final Pauli product in
-    /// `hamil`.
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// Note that if `self` is a density matrix, `workspace` will
-    /// become `$ \hat{\sigma} \rho $`  which is itself not a density
-    /// matrix (it is distinct from `$\hat{\sigma} \rho \hat{\sigma}^\dagger$`).
+    /// qureg.start_recording_qasm();
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    /// qureg.stop_recording_qasm();
     ///
-    /// This function works by cloning the `self` state into `workspace`,
-    /// applying each of the specified  Pauli products in `hamil` to
-    /// `workspace` (one Pauli operation at a time), then computing its inner
-    /// product with `self` (for state-vectors) or its trace (for density
-    /// matrices) multiplied with the corresponding coefficient, and summing
-    /// these contributions.  It therefore should scale linearly in time
-    /// with the total number of non-identity specified Pauli operators.
+    /// let qasm = qureg.recorded_qasm().unwrap();
+    /// assert!(qasm.starts_with("OPENQASM 2.0;"));
+    /// assert!(qasm.contains("h q[0];"));
+    /// assert!(qasm.contains("cx q[0],q[1];"));
+    /// ```
     ///
-    /// # Parameters
+    /// [`Utf8Error`]: crate::QuestError::Utf8Error
+    pub fn recorded_qasm(&self) -> Result<String, QuestError> {
+        String::from_utf8(self.recorded_qasm_bytes().to_vec())
+            .map_err(QuestError::Utf8Error)
+    }
+
+    /// Write recorded QASM into `writer`.
     ///
-    /// - `hamil`: a [`PauliHamil`]
-    /// - `workspace`: a working-space `Qureg` with the same dimensions as
-    ///   `self`, which is modified to be the result of multiplying the state
-    ///   with the final specified Pauli product
+    /// This does not clear the QASM log, nor does it start or stop QASM
+    /// recording.
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `workspace` is not of the same dimension as `self` and `hamil`
+    /// Returns any error returned by `writer`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
-    /// use PauliOpType::{
-    ///     PAULI_X,
-    ///     PAULI_Z,
-    /// };
-    ///
     /// let env = QuestEnv::new();
-    /// let qureg = {
-    ///     let mut qureg =
-    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
-    ///     qureg.init_plus_state();
-    ///     qureg
-    /// };
-    /// let mut workspace =
+    /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let hamil = &mut PauliHamil::try_new(2, 2).unwrap();
-    /// init_pauli_hamil(hamil, &[0.5, 0.5], &[PAULI_X, PAULI_X, PAULI_X, PAULI_Z])
-    ///     .unwrap();
+    /// qureg.start_recording_qasm();
+    /// qureg.hadamard(0).unwrap();
+    /// qureg.stop_recording_qasm();
     ///
-    /// let expec_val =
-    ///     qureg.calc_expec_pauli_hamil(hamil, &mut workspace).unwrap();
+    /// let mut buf = Vec::new();
+    /// qureg.write_recorded_qasm(&mut buf).unwrap();
+    ///
+    /// assert_eq!(buf, qureg.recorded_qasm().unwrap().as_bytes());
     /// ```
+    pub fn write_recorded_qasm<W: std::io::Write>(
+        &self,
+        mut writer: W,
+    ) -> std::io::Result<()> {
+        writer.write_all(self.recorded_qasm_bytes())
+    }
+
+    /// Contents of the QASM logger buffer.
+    #[allow(clippy::cast_sign_loss)]
+    fn recorded_qasm_bytes(&self) -> &[u8] {
+        let logger = self.reg.qasmLog.get();
+        if logger.is_null() {
+            return &[];
+        }
+        // SAFETY: The logger is allocated together with the register, and
+        // its first `bufferFill` chars are initialized by QuEST.  The buffer
+        // is modified only through &mut self.
+        unsafe {
+            let buffer = (*logger).buffer.get();
+            let fill = (*logger).bufferFill;
+            if buffer.is_null() || fill <= 0 {
+                return &[];
+            }
+            std::slice::from_raw_parts(buffer.cast::<u8>(), fill as usize)
+        }
+    }
+
+    /// Calculates the fidelity of `qureg` (a state-vector or density matrix).
     ///
-    /// See [QuEST API] for more information.
+    /// Calculates the fidelity against a reference pure state (necessarily a
+    /// state-vector).
     ///
-    /// [`PauliHamil`]: crate::PauliHamil
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn calc_expec_pauli_hamil(
-        &self,
-        hamil: &PauliHamil,
-        workspace: &mut Qureg<'_>,
-    ) -> Result<Qreal, QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::calcExpecPauliHamil(self.reg, hamil.0, workspace.reg)
-        })
-    }
-
-    ///  Apply a general two-qubit unitary (including a global phase factor).
+    /// - If `qureg` is a state-vector, this function computes
     ///
-    /// `target_qubit1` is treated as the least significant qubit in `u`,
-    /// such that a row in `u` is dotted with the vector
+    /// ```latex
+    ///  |\langle \text{qureg} | \text{pure_state} \rangle|^2
+    /// ```
+    ///
+    /// - If `qureg` is a density matrix, this function computes
     ///
     /// ```latex
-    ///  |\text{targetQubit2} \;\; \text{targetQubit1}\rangle : \{|00\rangle, |01\rangle, |10\rangle, |11\rangle \}
+    ///  \langle \text{pure_state} | \text{qureg} | \text{pure_state} \rangle
     /// ```
     ///
-    /// The passed `ComplexMatrix4` must be unitary, otherwise an error is
-    /// thrown.  Use [`Qureg::apply_matrix4()`] to left-multiply a non-unitary
-    /// `ComplexMatrix4`.
+    /// In either case, the returned fidelity lies in `[0, 1]` (assuming both
+    /// input states have valid normalisation). If any of the input `Qureg`s
+    /// are not normalised, this function will return the real component of
+    /// the correct linear algebra calculation.
     ///
-    /// Note that in distributed mode, this routine requires that each node
-    /// contains at least 4 amplitudes.  This means an q-qubit register
-    /// (state vector or density matrix) can be distributed by at most
-    /// `2^(q/4)` nodes.
+    /// The number of qubits represented in `qureg` and `pure_state` must match.
     ///
     /// # Parameters
     ///
-    /// - `target_qubit1`: first qubit to operate on, treated as least
-    ///   significant in `u`
-    /// - `target_qubit2`: first qubit to operate on, treated as most
-    ///   significant in `u`
-    /// - `u`: unitary matrix to apply
+    /// - `pure_state`: a state vector
+    ///
+    /// Returns the fidelity between the input registers
     ///
     /// # Errors
     ///
-    /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubit1` or `target_qubit2` are outside `[0,
-    ///     self.num_qubits())`
-    ///   - if `target_qubit1` equals `target_qubit2`
-    ///   - if matrix `u` is not unitary
-    ///   - if each node cannot fit 4 amplitudes in distributed mode
+    /// Returns [`InvalidQuESTInputError`],
+    ///
+    /// - if the second argument `pure_state` is not a state-vector
+    /// - if the number of qubits `qureg` and `pure_state` do not match
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.pauli_x(0).unwrap();
-    ///
-    /// let target_qubit1 = 1;
-    /// let target_qubit2 = 2;
-    /// let u = &ComplexMatrix4::new(
-    ///     [
-    ///         [0., 0., 0., 1.],
-    ///         [0., 1., 0., 0.],
-    ///         [0., 0., 1., 0.],
-    ///         [1., 0., 0., 0.],
-    ///     ],
-    ///     [
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///     ],
-    /// );
-    ///
-    /// qureg
-    ///     .two_qubit_unitary(target_qubit1, target_qubit2, u)
-    ///     .unwrap();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// let pure_state = {
+    ///     let mut new_state =
+    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///     new_state.init_plus_state();
+    ///     new_state
+    /// };
     ///
-    /// let amp = qureg.get_real_amp(7).unwrap();
-    /// assert!((amp - 1.) < EPSILON);
+    /// let fidelity = qureg.calc_fidelity(&pure_state).unwrap();
+    /// assert!((fidelity - 0.25).abs() < EPSILON);
     /// ```
     ///
-    /// See [`QuEST` Aqureg.PI] for more information.
+    /// See [QuEST API] for more information.
     ///
-    /// [`Qureg::apply_matrix4()`]: crate::Qureg::apply_matrix4()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn two_qubit_unitary(
-        &mut self,
-        target_qubit1: i32,
-        target_qubit2: i32,
-        u: &ComplexMatrix4,
-    ) -> Result<(), QuestError> {
+    pub fn calc_fidelity(
+        &self,
+        pure_state: &StateVector,
+    ) -> Result<Qreal, QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::twoQubitUnitary(self.reg, target_qubit1, target_qubit2, u.0);
+            ffi::calcFidelity(self.reg, pure_state.reg)
         })
     }
 
-    /// Apply a general controlled two-qubit unitary.
+    /// Performs a SWAP gate between `qubit1` and `qubit2`.
     ///
-    /// The given unitary is applied to the target amplitudes where the control
-    /// qubit has value 1.
+    /// This effects
     ///
-    /// `target_qubit1` is treated as the least significant qubit in `u`,
-    /// such that a row in `u` is dotted with the vector
+    /// ```text
+    /// [1 0 0 0]
+    /// [0 0 1 0]
+    /// [0 1 0 0]
+    /// [0 0 0 1]
+    /// ```
     ///
-    /// The passed `ComplexMatrix4` must be unitary, otherwise an error is
-    /// thrown.
+    /// on the designated qubits, though is performed internally by three CNOT
+    /// gates.
     ///
-    /// Note that in distributed mode, this routine requires that each node
-    /// contains at least 4 amplitudes.  This means an q-qubit register
-    /// (state vector or density matrix) can be distributed by at most
-    /// `2^(q/4)` nodes.
     ///
     /// # Parameters
     ///
-    /// - `contol_qubit`:  the control qubit which must be in state 1 to effect
-    ///   the given unitary
-    /// - `target_qubit1`: first qubit to operate on, treated as least
-    ///   significant in `u`
-    /// - `target_qubit2`: first qubit to operate on, treated as most
-    ///   significant in `u`
-    /// - `u`: unitary matrix to apply
+    /// - `qubit1`: qubit to swap
+    /// - `qubit2`: other qubit to swap
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `control_qubit`,  `target_qubit1` or `target_qubit2` are outside
-    ///     `[0, self.num_qubits())`
-    ///   - if any of `control_qubit`, `target_qubit1` and `target_qubit2` are
-    ///     equal
-    ///   - if matrix `u` is not unitary
-    ///   - if each node cannot fit 4 amplitudes in distributed mode
-    ///
+    ///   - if either `qubit1` or `qubit2` is outside [0,
+    ///     [`qureg.num_qubits()`]).
+    ///   - if `qubit1` and `qubit2` are equal
     ///
     /// # Examples
     ///
@@ -4742,106 +4829,58 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.pauli_x(0).unwrap();
-    ///
-    /// let control_qubit = 0;
-    /// let target_qubit1 = 1;
-    /// let target_qubit2 = 2;
-    /// let u = &ComplexMatrix4::new(
-    ///     [
-    ///         [0., 0., 0., 1.],
-    ///         [0., 1., 0., 0.],
-    ///         [0., 0., 1., 0.],
-    ///         [1., 0., 0., 0.],
-    ///     ],
-    ///     [
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///     ],
-    /// );
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg
-    ///     .controlled_two_qubit_unitary(
-    ///         control_qubit,
-    ///         target_qubit1,
-    ///         target_qubit2,
-    ///         u,
-    ///     )
-    ///     .unwrap();
+    /// // init state |10>
+    /// qureg.init_classical_state(1).unwrap();
+    /// // swap to |01>
+    /// qureg.swap_gate(0, 1).unwrap();
     ///
-    /// let amp = qureg.get_real_amp(7).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// let outcome = qureg.measure(0).unwrap();
+    /// assert_eq!(outcome, 0);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`Qureg::apply_matrix4()`]: crate::Qureg::apply_matrix4()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_two_qubit_unitary(
+    pub fn swap_gate(
         &mut self,
-        control_qubit: i32,
-        target_qubit1: i32,
-        target_qubit2: i32,
-        u: &ComplexMatrix4,
+        qubit1: i32,
+        qubit2: i32,
     ) -> Result<(), QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::controlledTwoQubitUnitary(
-                self.reg,
-                control_qubit,
-                target_qubit1,
-                target_qubit2,
-                u.0,
-            );
+            ffi::swapGate(self.reg, qubit1, qubit2);
         })
     }
 
-    /// Apply a general multi-qubit unitary with any number of target qubits.
-    ///
-    /// Any number of control qubits can be specified, and if all have value 1,
-    /// the given unitary is applied to the target qubit.
+    /// Performs a sqrt SWAP gate between `qubit1` and `qubit2`.
     ///
-    /// `target_qubit1` is treated as the least significant qubit in `u`,
-    /// such that a row in `u` is dotted with the vector
+    /// This effects
     ///
-    /// ```latex
-    ///  |\text{targetQubit2} \;\; \text{targetQubit1}\rangle : \{|00\rangle, |01\rangle, |10\rangle, |11\rangle \}
+    /// ```text
+    /// [ 1     0        0     0 ]
+    /// [ 0  (1+i)/2  (1-i)/2  0 ]
+    /// [ 0  (1-i)/2  (1+i)/2  0 ]
+    /// [ 0     0        0     1 ]
     /// ```
     ///
-    /// The passed `ComplexMatrix4` must be unitary, otherwise an error is
-    /// thrown.  Use [`Qureg::apply_matrix4()`] to left-multiply a non-unitary
-    /// `ComplexMatrix4`.
-    ///
-    /// Note that in distributed mode, this routine requires that each node
-    /// contains at least 4 amplitudes.  This means an q-qubit register
-    /// (state vector or density matrix) can be distributed by at most
+    /// on the designated qubits, though is performed internally by three CNOT
+    /// gates.
     ///
     /// # Parameters
     ///
-    /// - `contol_qubits`:  the control qubits which all must be in state 1 to
-    ///   effect the given unitary
-    /// - `target_qubit1`: first qubit to operate on, treated as least
-    ///   significant in `u`
-    /// - `target_qubit2`: first qubit to operate on, treated as most
-    ///   significant in `u`
-    /// - `u`: unitary matrix to apply
+    /// - `qubit1`: qubit to sqrt swap
+    /// - `qubit2`: other qubit to sqrt swap
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubit1` or `target_qubit2` are outside `[0,
-    ///     self.num_qubits())`
-    ///   - if `target_qubit1` equals `target_qubit2`
-    ///   - if any qubit in `control_qubits` is outside `[0, self.num_qubits())`
-    ///   - if `control_qubits` are not unique
-    ///   - if either `target_qubit1` or `target_qubit2` are in `control_qubit``
-    ///   - if matrix `u` is not unitary
-    ///   - if each node cannot fit 4 amplitudes in distributed mode
+    ///   - if either `qubit1` or `qubit2` is outside [0,
+    ///     [`qureg.num_qubits()`]).
+    ///   - if `qubit1` and `qubit2` are equal
     ///
     /// # Examples
     ///
@@ -4849,192 +4888,1085 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
-    /// qureg.pauli_x(0).unwrap();
-    /// qureg.pauli_x(1).unwrap();
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// // init state |10>
+    /// qureg.init_classical_state(1).unwrap();
+    /// qureg.sqrt_swap_gate(0, 1).unwrap();
+    /// qureg.sqrt_swap_gate(0, 1).unwrap();
+    /// let outcome = qureg.measure(0).unwrap();
+    /// assert_eq!(outcome, 0);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn sqrt_swap_gate(
+        &mut self,
+        qb1: i32,
+        qb2: i32,
+    ) -> Result<(), QuestError> {
+        catch_quest_exception(|| unsafe {
+            ffi::sqrtSwapGate(self.reg, qb1, qb2);
+        })
+    }
+
+    /// Apply the square root of the Pauli X gate to `target_qubit`.
+    ///
+    /// See [`gates::sqrt_x()`] for the matrix.  Applying the gate twice is
+    /// equivalent to [`pauli_x()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit`: qubit to operate on
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.sqrt_x(0).unwrap();
+    /// qureg.sqrt_x(0).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`gates::sqrt_x()`]: crate::gates::sqrt_x()
+    /// [`pauli_x()`]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn sqrt_x(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::sqrt_x())
+    }
+
+    /// Apply a general single-qubit gate in the `U3` parametrization.
+    ///
+    /// See [`gates::u3()`] for the matrix.  Unlike [`compact_unitary()`],
+    /// this gate keeps the global phase factor `exp(i*(phi+lambda)/2)`.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit`: qubit to operate on
+    /// - `theta`, `phi`, `lambda`: Euler angles in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // U3(pi/2, 0, pi) is the Hadamard gate
+    /// qureg.u3(0, PI / 2., 0., PI).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp - SQRT_2.recip()).abs() < 10. * EPSILON);
+    /// ```
+    ///
+    /// [`gates::u3()`]: crate::gates::u3()
+    /// [`compact_unitary()`]: crate::Qureg::compact_unitary()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn u3(
+        &mut self,
+        target_qubit: i32,
+        theta: Qreal,
+        phi: Qreal,
+        lambda: Qreal,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::u3(theta, phi, lambda))
+    }
+
+    /// Performs an iSWAP gate between `qubit1` and `qubit2`.
+    ///
+    /// See [`gates::iswap()`] for the matrix.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubit1`: qubit to swap
+    /// - `qubit2`: other qubit to swap
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if either `qubit1` or `qubit2` is outside [0, [`num_qubits()`]).
+    ///   - if `qubit1` and `qubit2` are equal
+    ///   - if each node cannot fit 4 amplitudes in distributed mode
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // init state |01>
+    /// qureg.init_classical_state(1).unwrap();
+    /// // swap to i|10>
+    /// qureg.iswap(0, 1).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(2).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`gates::iswap()`]: crate::gates::iswap()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn iswap(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+    ) -> Result<(), QuestError> {
+        self.two_qubit_unitary(qubit1, qubit2, &gates::iswap())
+    }
+
+    /// Performs a fermionic simulation gate between `qubit1` and `qubit2`.
+    ///
+    /// See [`gates::fsim()`] for the matrix.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubit1`: first qubit to operate on
+    /// - `qubit2`: second qubit to operate on
+    /// - `theta`: swap angle in radians
+    /// - `phi`: controlled phase angle in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if either `qubit1` or `qubit2` is outside [0, [`num_qubits()`]).
+    ///   - if `qubit1` and `qubit2` are equal
+    ///   - if each node cannot fit 4 amplitudes in distributed mode
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // init state |11>
+    /// qureg.init_classical_state(3).unwrap();
+    /// qureg.fsim(0, 1, PI / 2., PI).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!((amp + 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`gates::fsim()`]: crate::gates::fsim()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn fsim(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+        theta: Qreal,
+        phi: Qreal,
+    ) -> Result<(), QuestError> {
+        self.two_qubit_unitary(qubit1, qubit2, &gates::fsim(theta, phi))
+    }
+
+    /// Performs an XY gate between `qubit1` and `qubit2`.
+    ///
+    /// See [`gates::xy()`] for the matrix.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubit1`: first qubit to operate on
+    /// - `qubit2`: second qubit to operate on
+    /// - `theta`: angle in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if either `qubit1` or `qubit2` is outside [0, [`num_qubits()`]).
+    ///   - if `qubit1` and `qubit2` are equal
+    ///   - if each node cannot fit 4 amplitudes in distributed mode
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // init state |01>
+    /// qureg.init_classical_state(1).unwrap();
+    /// qureg.xy(0, 1, PI).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(2).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`gates::xy()`]: crate::gates::xy()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn xy(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+        theta: Qreal,
+    ) -> Result<(), QuestError> {
+        self.two_qubit_unitary(qubit1, qubit2, &gates::xy(theta))
+    }
+
+    /// Performs a Mølmer–Sørensen gate between `qubit1` and `qubit2`.
+    ///
+    /// See [`gates::ms()`] for the matrix.  The gate is applied natively
+    /// by `QuEST` as the rotation `exp(-i theta/2 XX)`, see
+    /// [`multi_rotate_pauli()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `qubit1`: first qubit to operate on
+    /// - `qubit2`: second qubit to operate on
+    /// - `theta`: angle in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if either `qubit1` or `qubit2` is outside [0, [`num_qubits()`]).
+    ///   - if `qubit1` and `qubit2` are equal
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.ms(0, 1, PI / 2.).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(3).unwrap();
+    /// assert!((amp + SQRT_2.recip()).abs() < 10. * EPSILON);
+    /// ```
+    ///
+    /// [`gates::ms()`]: crate::gates::ms()
+    /// [`multi_rotate_pauli()`]: crate::Qureg::multi_rotate_pauli()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn ms(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+        theta: Qreal,
+    ) -> Result<(), QuestError> {
+        self.multi_rotate_pauli(
+            &[qubit1, qubit2],
+            &[PauliOpType::PAULI_X, PauliOpType::PAULI_X],
+            theta,
+        )
+    }
+
+    /// Apply a general single-qubit unitary with multiple control qubits.
+    ///
+    /// The operation is conditioned upon a specific bit sequence:
+    /// `control_state`.
+    ///
+    /// Any number of control qubits can be specified, along with their
+    /// classical state (`0` or `1`) to condition upon. Only amplitudes of
+    /// computational basis states  for which `control_qubits` have
+    /// corresponding bit values `control_state` are modified  by `u`.
+    ///  
+    /// This function is equivalent (albeit faster) to applying [`pauli_x()`] on
+    /// each of the control qubits which are conditioned on outcome `0`,
+    /// calling [`multi_controlled_unitary()`], then re-appplying
+    /// `pauli_x()` on the same qubits.
+    ///
+    ///  # Parameters
+    ///
+    /// - `control_qubits`: the indices of the control qubits
+    /// - `control_state`: the bit values (`0` or `1`) of each control qubit,
+    ///   upon which to condition
+    /// - `target_qubit`: qubit to operate the unitary upon
+    /// - `u`: single-qubit unitary matrix to apply
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index (`target_qubit` or one in `control_qubits`) is
+    ///     outside [0, [`num_qubits()`]),
+    ///   - if any qubit in `control_qubits` is repeated
+    ///   - if `control_qubits` contains `target_qubit`
+    ///   - if any element of `control_state` is not a bit (`0` or `1`)
+    ///   - if `u` is not unitary
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let control_qubits = &[1, 2];
+    /// let control_state = &[0, 0];
+    /// let target_qubit = 0;
+    /// let u = &ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    /// qureg
+    ///     .multi_state_controlled_unitary(
+    ///         control_qubits,
+    ///         control_state,
+    ///         target_qubit,
+    ///         u,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`pauli_x()`]: Qureg::pauli_x()
+    /// [`multi_controlled_unitary()`]: Qureg::multi_controlled_unitary()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_state_controlled_unitary(
+        &mut self,
+        control_qubits: &[i32],
+        control_state: &[i32],
+        target_qubit: i32,
+        u: &ComplexMatrix2,
+    ) -> Result<(), QuestError> {
+        let num_control_qubits = control_qubits.len() as i32;
+        catch_quest_exception(|| unsafe {
+            ffi::multiStateControlledUnitary(
+                self.reg,
+                control_qubits.as_ptr(),
+                control_state.as_ptr(),
+                num_control_qubits,
+                target_qubit,
+                u.0,
+            );
+        })
+    }
+
+    /// Apply a multi-qubit Z rotation on selected qubits.
+    ///
+    /// This is the unitary
+    ///
+    /// ```latex
+    ///    \exp \left( - i \, \frac{\theta}{2} \; \bigotimes_{j}^{\text{numQubits}} Z_j\right)
+    /// ```
+    ///
+    /// where the Pauli Z gates operate the qubits listed in `qubits`, and cause
+    /// rotations of `theta = angle`.
+    ///
+    /// All qubits not appearing in `qubits` are assumed to receive the
+    /// identity operator.
+    ///
+    /// This has the effect of premultiplying every amplitude with `exp(+/- i
+    /// \theta/2)` where the sign is determined by the parity of the target
+    /// qubits for that amplitude.
+    ///
+    ///  # Parameters
+    ///
+    /// - `qubits`: a list of the indices of the target qubits
+    /// - `angle`: the angle by which the multi-qubit state is rotated around
+    ///   the Z axis
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index in `qubits` is outside [0, [`num_qubits()`]),
+    ///   - if any qubit in `qubits` is repeated
+    ///   - if `control_qubits` contains `target_qubit`
+    ///   - if any element of `control_state` is not a bit (`0` or `1`)
+    ///   - if `u` is not unitary
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let qubits = &[0, 1];
+    /// let angle = PI;
+    /// qureg.multi_rotate_z(qubits, angle).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(0).unwrap();
+    /// assert!((amp + 0.5).abs() < EPSILON);
+    /// let amp = qureg.get_imag_amp(1).unwrap();
+    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_rotate_z(
+        &mut self,
+        qubits: &[i32],
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        let num_qubits = qubits.len() as i32;
+        catch_quest_exception(|| unsafe {
+            ffi::multiRotateZ(self.reg, qubits.as_ptr(), num_qubits, angle);
+        })
+    }
+
+    /// Apply a multi-qubit multi-Pauli rotation.
+    ///
+    /// This is the unitary
+    ///
+    /// ```latex
+    ///    \exp \left( - i \, \frac{\theta}{2} \; \bigotimes_{j}^{\text{numTargets}} \hat{\sigma}_j\right)
+    /// ```
+    ///
+    ///  where  `theta = angle` and `$\hat{\sigma}_j \in \{X, Y, Z\}$` is a
+    /// Pauli operator [`PauliOpType`] operating upon the corresponding qubit
+    /// `target_qubits`.
+    ///
+    /// This function effects the Pauli gadget by first rotating the qubits
+    /// which are nominated to receive `X` or `Y` Paulis into alternate
+    /// basis, performing [`multi_rotate_z()`] on all target qubits, then
+    /// restoring the original basis.
+    ///
+    ///  # Parameters
+    ///
+    /// - `target_qubits`: a list of the indices of the target qubits
+    /// - `target_paulis`: a list of the Pauli operators `PauliOpType`
+    /// - `angle`: the angle by which the multi-qubit state is rotated
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index in `target_qubits` is outside [0,
+    ///     [`num_qubits()`]),
+    ///   - if any qubit in `target_qubits` is repeated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_X;
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let target_qubits = &[1, 2];
+    /// let target_paulis = &[PAULI_X, PAULI_X];
+    /// let angle = PI;
+    ///
+    /// qureg
+    ///     .multi_rotate_pauli(target_qubits, target_paulis, angle)
+    ///     .unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(6).unwrap();
+    /// assert!((amp + 1.).abs() < 2. * EPSILON);
+    /// ```
+    ///
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`PauliOpType`]: crate::PauliOpType
+    /// [`multi_rotate_z()`]: Qureg::multi_rotate_z()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_rotate_pauli(
+        &mut self,
+        target_qubits: &[i32],
+        target_paulis: &[PauliOpType],
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        let num_targets = target_qubits.len() as i32;
+        catch_quest_exception(|| unsafe {
+            ffi::multiRotatePauli(
+                self.reg,
+                target_qubits.as_ptr(),
+                target_paulis.as_ptr(),
+                num_targets,
+                angle,
+            );
+        })
+    }
+
+    /// Apply a multi-controlled multi-target Z rotation.
+    ///
+    /// All qubits not appearing in `target_qubits` and `control_qubits` are
+    /// assumed to receive the identity operator.
+    ///
+    /// This has the effect of premultiplying all amplitudes (for which the
+    /// control qubits are `1`)  with `$\exp(\pm i \theta/2)$`, where the
+    /// sign is determined by the parity of  the target qubits for that
+    /// amplitude.
+    ///
+    ///  # Parameters
+    ///
+    /// - `control_qubits`: list of the indices of qubits to control upon
+    /// - `target_qubits`: a list of the indices of the target qubits
+    /// - `angle`: the angle by which the multi-qubit state is rotated
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index in `target_qubits` or `control_qubits` is outside
+    ///     [0, [`num_qubits()`]),
+    ///   - if `control_qubits` or `target_qubits` contain any repetitions
+    ///   - if any qubit in `control_qubits` is also in `target_qubits` (and
+    ///     vice versa)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // Initialize `|1111>`
+    /// (0..4).try_for_each(|i| qureg.pauli_x(i)).unwrap();
     ///
     /// let control_qubits = &[0, 1];
-    /// let target_qubit1 = 2;
-    /// let target_qubit2 = 3;
-    /// let u = &ComplexMatrix4::new(
-    ///     [
-    ///         [0., 0., 0., 1.],
-    ///         [0., 1., 0., 0.],
-    ///         [0., 0., 1., 0.],
-    ///         [1., 0., 0., 0.],
-    ///     ],
-    ///     [
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///     ],
-    /// );
+    /// let target_qubits = &[2, 3];
+    /// let angle = 2. * PI;
+    /// qureg
+    ///     .multi_controlled_multi_rotate_z(control_qubits, target_qubits, angle)
+    ///     .unwrap();
+    ///
+    /// // the state is now `-1. * |1111>`
+    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// assert!((amp + 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_controlled_multi_rotate_z(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubits: &[i32],
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        let num_controls = control_qubits.len() as i32;
+        let num_targets = target_qubits.len() as i32;
+        catch_quest_exception(|| unsafe {
+            ffi::multiControlledMultiRotateZ(
+                self.reg,
+                control_qubits.as_ptr(),
+                num_controls,
+                target_qubits.as_ptr(),
+                num_targets,
+                angle,
+            );
+        })
+    }
+
+    /// Apply a multi-controlled multi-target multi-Pauli rotation.
+    ///
+    /// All qubits not appearing in `target_qubits` and `control_qubits` are
+    /// assumed to receive the identity operator.
+    ///
+    /// This function effects the controlled Pauli gadget by first (controlled)
+    /// rotating the qubits which are targeted with either `X` or `Y` into
+    /// alternate basis, performing [`multi_controlled_multi_rotate_z()`] on all
+    /// target qubits, then restoring the original basis.
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubits`: list of the indices of qubits to control upon
+    /// - `target_qubits`: a list of the indices of the target qubits
+    /// - `target_paulis`: a list of the Pauli operators [`PauliOpType`]
+    /// - `angle`: the angle by which the multi-qubit state is rotated
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index in `target_qubits` or `control_qubits` is outside
+    ///     [0, [`num_qubits()`]),
+    ///   - if `control_qubits` or `target_qubits` contain any repetitions
+    ///   - if any qubit in `control_qubits` is also in `target_qubits` (and
+    ///     vice versa)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_Z;
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // Initialize `|1111>`
+    /// (0..4).try_for_each(|i| qureg.pauli_x(i)).unwrap();
+    ///
+    /// let control_qubits = &[0, 1];
+    /// let target_qubits = &[2, 3];
+    /// let target_paulis = &[PAULI_Z, PAULI_Z];
+    /// let angle = 2. * PI;
+    /// qureg
+    ///     .multi_controlled_multi_rotate_pauli(
+    ///         control_qubits,
+    ///         target_qubits,
+    ///         target_paulis,
+    ///         angle,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // the state is now `-1. * |1111>`
+    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// assert!((amp + 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`PauliOpType`]: crate::PauliOpType
+    /// [`multi_controlled_multi_rotate_z()`]: Qureg::multi_controlled_multi_rotate_z()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_controlled_multi_rotate_pauli(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubits: &[i32],
+        target_paulis: &[PauliOpType],
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        let num_controls = control_qubits.len() as i32;
+        let num_targets = target_qubits.len() as i32;
+        catch_quest_exception(|| unsafe {
+            ffi::multiControlledMultiRotatePauli(
+                self.reg,
+                control_qubits.as_ptr(),
+                num_controls,
+                target_qubits.as_ptr(),
+                target_paulis.as_ptr(),
+                num_targets,
+                angle,
+            );
+        })
+    }
+
+    /// Computes the expected value of a product of Pauli operators.
+    ///
+    /// Letting `$\sigma = \otimes_j \hat{\sigma}_j$` be the operators
+    /// indicated by `pauli_codes` and acting on qubits `target_qubits`,
+    /// this function computes `$ \langle \psi | \sigma | \psi \rangle $`
+    /// if `$qureg = \psi$` is a state-vector, and computes `$ \text{Tr}(\sigma
+    /// \rho) $`  if `$qureg = \rho $` is a density matrix.
+    ///  
+    /// The slice `pauli_codes` specifies which Pauli operators to enact on the
+    /// corresponding qubits in `target_qubits`. The target qubits must be
+    /// unique, and at most `self.num_qubits()` may be specified.
+    ///
+    /// For example, on a 7-qubit state-vector,  
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// # use PauliOpType::{
+    /// #     PAULI_X,
+    /// #     PAULI_Z,
+    /// #     PAULI_Y,
+    /// #     PAULI_I,
+    /// # };
+    /// # let env = QuestEnv::new();
+    /// # let qureg = {
+    /// #     let mut qureg = Qureg::try_new(2, &env).expect(
+    /// #         "cannot allocate memory for Qureg",
+    /// #     );
+    /// #     qureg.init_plus_state();
+    /// #     qureg
+    /// # };
+    /// # let mut workspace = Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.calc_expec_pauli_prod(&[4,5,6], &[PAULI_X, PAULI_I, PAULI_Z], &mut workspace);
+    /// ```
+    ///
+    /// will compute `$ \langle \psi | I I I I X I Z | \psi \rangle $` (where in
+    /// this notation, the left-most operator applies to the
+    /// least-significant qubit, i.e. that with index `0`).
+    ///
+    /// `workspace` must be a register with the same type (state-vector vs
+    /// density matrix) and dimensions (number of represented qubits) as `self`,
+    /// and is used as working space. When this function returns, `self` will
+    /// be unchanged and `workspace` will be set to `$ \sigma
+    /// | \psi \rangle $` (if `self` is a state-vector)  or $ \sigma
+    /// \rho $` (if `self` is a density matrix).
+    ///
+    /// NOTE that this last quantity is NOT the result of applying  the paulis
+    /// as unitaries, `$ \sigma^\dagger \rho \sigma $`, but is instead the
+    /// result of their direct multiplication with the density matrix. It
+    /// is therefore itself not a valid density matrix.
     ///
-    /// qureg
-    ///     .multi_controlled_two_qubit_unitary(
-    ///         control_qubits,
-    ///         target_qubit1,
-    ///         target_qubit2,
-    ///         u,
-    ///     )
+    /// This function works by cloning the `self` state into `workspace`,
+    /// applying the specified  Pauli operators to `workspace` then
+    /// computing its inner product with `self` (for state-vectors)
+    /// or its trace (for density matrices). It therefore should scale linearly
+    /// in time with the number of specified non-identity Pauli operators,
+    /// which is bounded by the number of represented qubits.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubits`: a list of the indices of the target qubits
+    /// - `pauli_codes`: a list of the Pauli codes to apply to the corresponding
+    ///   qubits in `target_qubits`
+    /// - `workspace`: a working-space qureg with the same dimensions as `self`,
+    ///   which is modified to be the result of multiplying the state with the
+    ///   pauli operators
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubits.len() > self.num_qubits()`
+    ///   - if any qubit index in `target_qubits` is outside [0,
+    ///     [`num_qubits()`]),
+    ///   - if `target_qubits` contain any repetitions
+    ///   - if `workspace` is not of the same dimension as `self`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_X;
+    ///
+    /// let env = QuestEnv::new();
+    /// let qureg = {
+    ///     let mut qureg = Qureg::try_new(2, &env).expect(
+    ///         "cannot allocate memory for
+    /// Qureg",
+    ///     );
+    ///     qureg.init_plus_state();
+    ///     qureg
+    /// };
+    /// let mut workspace =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let target_qubits = &[0, 1];
+    /// let pauli_codes = &[PAULI_X, PAULI_X];
+    ///
+    /// let product = qureg
+    ///     .calc_expec_pauli_prod(target_qubits, pauli_codes, &mut workspace)
     ///     .unwrap();
+    /// ```
     ///
-    /// let amp = qureg.get_real_amp(15).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn calc_expec_pauli_prod(
+        &self,
+        target_qubits: &[i32],
+        pauli_codes: &[PauliOpType],
+        workspace: &mut Qureg<K>,
+    ) -> Result<Qreal, QuestError> {
+        let num_targets = target_qubits.len() as i32;
+        catch_quest_exception(|| unsafe {
+            ffi::calcExpecPauliProd(
+                self.reg,
+                target_qubits.as_ptr(),
+                pauli_codes.as_ptr(),
+                num_targets,
+                workspace.reg,
+            )
+        })
+    }
+
+    /// Computes the expected value of a sum of products of Pauli operators.
+    ///
+    /// Let
+    ///
+    /// ```latex
+    /// H = \sum_i c_i \otimes_j^{N} \hat{\sigma}_{i,j}
+    /// ```
+    ///
+    /// be the operators indicated by `all_pauli_codes` (where `$ c_i \in $`
+    /// `term_coeffs`  and `N = self.num_qubits()`).
+    ///
+    /// This function computes `$ \langle \psi | H | \psi \rangle $` if `self =
+    /// \psi $` is a state-vector, and computes `$ \text{Tr}(H \rho)
+    /// =\text{Trace}(\rho H) $`  if `self = \rho $` is a density matrix.
+    ///
+    /// `all_pauli_codes` is an array which specifies which Pauli operators to
+    /// apply. For each sum term, a Pauli operator must be specified for
+    /// EVERY qubit in `self`; each set of `term_coeffs.len()` operators will be
+    /// grouped into a product.  `term_coeffs` is an arrray containing the term
+    /// coefficients.
+    ///
+    /// For example, on a 3-qubit state-vector,  
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// # use PauliOpType::{
+    /// #     PAULI_X,
+    /// #     PAULI_Z,
+    /// #     PAULI_Y,
+    /// #     PAULI_I,
+    /// # };
+    /// # let env = QuestEnv::new();
+    /// # let qureg = {
+    /// #     let mut qureg = Qureg::try_new(2, &env).expect(
+    /// #         "cannot allocate memory for Qureg",
+    /// #     );
+    /// #     qureg.init_plus_state();
+    /// #     qureg
+    /// # };
+    /// # let mut workspace =
+    /// #     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let paulis = [PAULI_X, PAULI_I, PAULI_I,  PAULI_X, PAULI_Y, PAULI_Z];
+    /// let coeffs = [1.5, -3.6];
+    /// qureg.calc_expec_pauli_sum(&paulis, &coeffs, &mut workspace);
+    /// ```
+    ///
+    /// will compute `$ \langle \psi | (1.5 X I I - 3.6 X Y Z) | \psi \rangle$`
+    /// (where in this notation, the left-most operator  applies to the
+    /// least-significant qubit, i.e. that with index `0`).  
+    /// `workspace` must be a register with the same type (state-vector vs
+    /// density matrix) and dimensions (number of represented qubits) as
+    /// `self`, and is used as working space. When this function returns,
+    /// `self`  will be unchanged and `workspace` will be set to `self`
+    /// pre-multiplied with the final Pauli product.
+    ///
+    /// NOTE that if `self` is a density matrix, `workspace` will become `$
+    /// \hat{\sigma} \rho $` which is itself not a density matrix (it is
+    /// distinct from `$ \hat{\sigma} \rho \hat{\sigma}^\dagger $`).
+    ///
+    /// This function works by cloning the `self` state into `workspace`,
+    /// applying each of the specified  Pauli products to `workspace` (one
+    /// Pauli operation at a time), then computing its inner product with `self`
+    /// (for state-vectors) or its trace (for density matrices) multiplied
+    /// with the corresponding coefficient, and summing these contributions.
+    /// It therefore should scale linearly in time with the total number of
+    /// non-identity specified Pauli operators.
+    ///
+    /// # Parameters
+    ///
+    /// - `all_pauli_codes`: a list of the Pauli codes of all Paulis involved in
+    ///   the products of terms. A Pauli must be specified for each qubit  in
+    ///   the register, in every term of the sum.
+    /// - `term_coeffs`: The coefficients of each term in the sum of Pauli
+    ///   products
+    /// - `workspace`: a working-space qureg with the same dimensions as `self`,
+    ///   which is modified to be the result of multiplying the state with the
+    ///   pauli operators
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `workspace` is not of the same dimension as `self`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::{
+    ///     PAULI_X,
+    ///     PAULI_Z,
+    /// };
+    ///
+    /// let env = QuestEnv::new();
+    /// let qureg = {
+    ///     let mut qureg = Qureg::try_new(2, &env).expect(
+    ///         "cannot allocate memory for
+    /// Qureg",
+    ///     );
+    ///     qureg.init_plus_state();
+    ///     qureg
+    /// };
+    /// let mut workspace =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let all_pauli_codes = &[PAULI_X, PAULI_Z, PAULI_Z, PAULI_X];
+    /// let term_coeffs = &[0.5, 0.5];
+    ///
+    /// let sum = qureg
+    ///     .calc_expec_pauli_sum(all_pauli_codes, term_coeffs, &mut workspace)
+    ///     .unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_two_qubit_unitary(
-        &mut self,
-        control_qubits: &[i32],
-        target_qubit1: i32,
-        target_qubit2: i32,
-        u: &ComplexMatrix4,
-    ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
+    pub fn calc_expec_pauli_sum(
+        &self,
+        all_pauli_codes: &[PauliOpType],
+        term_coeffs: &[Qreal],
+        workspace: &mut Qureg<K>,
+    ) -> Result<Qreal, QuestError> {
+        let num_sum_terms = term_coeffs.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::multiControlledTwoQubitUnitary(
+            ffi::calcExpecPauliSum(
                 self.reg,
-                control_qubits.as_ptr(),
-                num_control_qubits,
-                target_qubit1,
-                target_qubit2,
-                u.0,
-            );
+                all_pauli_codes.as_ptr(),
+                term_coeffs.as_ptr(),
+                num_sum_terms,
+                workspace.reg,
+            )
         })
     }
 
-    /// Apply a general multi-qubit unitary with any number of target qubits.
+    /// Computes the expected value under Hermitian operator.
     ///
-    /// The first target qubit in `targs` is treated as least significant in
-    /// `u`.
+    /// Represent `hamil` as `$ H = \sum_i c_i \otimes_j^{N}
+    /// \hat{\sigma}_{i,j} $`  and `N = hamil.num_qubits()`.  This function
+    /// computes:
     ///
-    /// The passed `ComplexMatrixN` must be unitary and be a compatible size
-    /// with the specified number of target qubits, otherwise an error is
-    /// thrown.
+    /// ```latex
+    ///  \langle \psi | H | \psi \rangle
+    /// ```
     ///
-    /// To left-multiply a non-unitary `ComplexMatrixN`, use
-    /// [`apply_matrix_n()`].
+    ///  if `self =  \psi` is a state-vector, and computes
+    ///
+    /// ```latex
+    /// \text{Trace}(H \rho) =\text{Trace}(\rho H)
+    /// ```
+    ///
+    /// if `self = \rho`  is a density matrix.
+    ///
+    /// This function is merely an encapsulation of `calc_expec_pauli_sum()` -
+    /// refer to the doc there for an elaboration.
+    ///
+    /// `workspace` must be a register with the same type (state-vector vs
+    /// density matrix) and dimensions (number of represented qubits) as
+    /// `self` and `hamil`, and is used as working space.  When this
+    /// function returns, `self`  will be unchanged and `workspace` will be
+    /// set to `self` pre-multiplied with the final Pauli product in
+    /// `hamil`.
+    ///
+    /// Note that if `self` is a density matrix, `workspace` will
+    /// become `$ \hat{\sigma} \rho $`  which is itself not a density
+    /// matrix (it is distinct from `$\hat{\sigma} \rho \hat{\sigma}^\dagger$`).
     ///
-    /// Note that in multithreaded mode, each thread will clone
-    /// `2^(targs.len())` amplitudes, and store these in the runtime stack.
-    /// Using `t` threads, the total memory overhead of this function is
-    /// `t*2^(targs.len())`. For many targets (e.g. 16 qubits), this may
-    /// cause a stack-overflow / seg-fault (e.g. on a 1 MiB stack).
-    ///  
-    /// Note too that in distributed mode, this routine requires that each node
-    /// contains at least `2^(targs.len())` amplitudes in the register. This
-    /// means an q-qubit register (state vector or density matrix)
-    /// can be distributed by at most `2^q / 2^(targs.len())` nodes.
+    /// This function works by cloning the `self` state into `workspace`,
+    /// applying each of the specified  Pauli products in `hamil` to
+    /// `workspace` (one Pauli operation at a time), then computing its inner
+    /// product with `self` (for state-vectors) or its trace (for density
+    /// matrices) multiplied with the corresponding coefficient, and summing
+    /// these contributions.  It therefore should scale linearly in time
+    /// with the total number of non-identity specified Pauli operators.
     ///
     /// # Parameters
     ///
-    /// - `targs`: a list of the target qubits, ordered least significant to
-    ///   most in `u`
-    /// - `u`: unitary matrix to apply
+    /// - `hamil`: a [`PauliHamil`]
+    /// - `workspace`: a working-space `Qureg` with the same dimensions as
+    ///   `self`, which is modified to be the result of multiplying the state
+    ///   with the final specified Pauli product
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if any index in `targs` is outside of `[0, self.num_qubits())`
-    ///   - if `targs` are not unique
-    ///   - if matrix `u` is not unitary
-    ///   - if `u` is not of a compatible size with `targs.len()`
-    ///   - if a node cannot fit the required number of target amplitudes in
-    ///     distributed mode
+    ///   - if `workspace` is not of the same dimension as `self` and `hamil`
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
+    /// use PauliOpType::{
+    ///     PAULI_X,
+    ///     PAULI_Z,
+    /// };
+    ///
     /// let env = QuestEnv::new();
-    /// let mut qureg =
+    /// let qureg = {
+    ///     let mut qureg =
+    ///         Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///     qureg.init_plus_state();
+    ///     qureg
+    /// };
+    /// let mut workspace =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// let u = &mut ComplexMatrixN::try_new(2).unwrap();
-    /// let zero_row = &[0., 0., 0., 0.];
-    /// init_complex_matrix_n(
-    ///     u,
-    ///     &[
-    ///         &[0., 0., 0., 1.],
-    ///         &[0., 1., 0., 0.],
-    ///         &[0., 0., 1., 0.],
-    ///         &[1., 0., 0., 0.],
-    ///     ],
-    ///     &[zero_row, zero_row, zero_row, zero_row],
-    /// )
-    /// .unwrap();
-    ///
-    /// qureg.multi_qubit_unitary(&[0, 1], u).unwrap();
+    /// let hamil = &mut PauliHamil::try_new(2, 2).unwrap();
+    /// init_pauli_hamil(hamil, &[0.5, 0.5], &[PAULI_X, PAULI_X, PAULI_X, PAULI_Z])
+    ///     .unwrap();
     ///
-    /// // Check if the register is now in the state `|11>`
-    /// let amp = qureg.get_real_amp(3).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// let expec_val =
+    ///     qureg.calc_expec_pauli_hamil(hamil, &mut workspace).unwrap();
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+    /// [`PauliHamil`]: crate::PauliHamil
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_qubit_unitary(
-        &mut self,
-        targs: &[i32],
-        u: &ComplexMatrixN,
-    ) -> Result<(), QuestError> {
-        let num_targs = targs.len() as i32;
+    pub fn calc_expec_pauli_hamil(
+        &self,
+        hamil: &PauliHamil,
+        workspace: &mut Qureg<K>,
+    ) -> Result<Qreal, QuestError> {
         catch_quest_exception(|| unsafe {
-            ffi::multiQubitUnitary(self.reg, targs.as_ptr(), num_targs, u.0);
+            ffi::calcExpecPauliHamil(self.reg, hamil.0, workspace.reg)
         })
     }
 
-    /// Apply a general controlled multi-qubit unitary (including a global phase
-    /// factor).
+    ///  Apply a general two-qubit unitary (including a global phase factor).
     ///
-    /// One control and any number of target qubits can be specified.
+    /// `target_qubit1` is treated as the least significant qubit in `u`,
+    /// such that a row in `u` is dotted with the vector
     ///
-    /// The target qubits in `targs` are treated as ordered least significant
-    /// to most significant in `u`.
+    /// ```latex
+    ///  |\text{targetQubit2} \;\; \text{targetQubit1}\rangle : \{|00\rangle, |01\rangle, |10\rangle, |11\rangle \}
+    /// ```
     ///
-    /// The passed `ComplexMatrixN` must be unitary and be a compatible size
-    /// with the specified number of target qubits, otherwise an error is
-    /// thrown.
+    /// The passed `ComplexMatrix4` must be unitary, otherwise an error is
+    /// thrown.  Use [`Qureg::apply_matrix4()`] to left-multiply a non-unitary
+    /// `ComplexMatrix4`.
     ///
-    /// Note that in multithreaded mode, each thread will clone
-    /// `2^(targs.len())` amplitudes, and store these in the runtime stack.
-    /// Using `t` threads, the total memory overhead of this function is
-    /// `t*2^(targs.len())`. For many targets (e.g. 16 qubits), this may
-    /// cause a stack-overflow / seg-fault (e.g. on a 1 MiB stack).
-    ///  
-    /// Note too that in distributed mode, this routine requires that each node
-    /// contains at least `2^(targs.len())` amplitudes in the register. This
-    /// means an q-qubit register (state vector or density matrix)
-    /// can be distributed by at most `2^q / 2^(targs.len())` nodes.
+    /// Note that in distributed mode, this routine requires that each node
+    /// contains at least 4 amplitudes.  This means an q-qubit register
+    /// (state vector or density matrix) can be distributed by at most
+    /// `2^(q/4)` nodes.
     ///
     /// # Parameters
     ///
-    /// - `ctrl`: the control qubit
-    /// - `targs`: a list of the target qubits, ordered least significant to
-    ///   most in `u`
+    /// - `target_qubit1`: first qubit to operate on, treated as least
+    ///   significant in `u`
+    /// - `target_qubit2`: first qubit to operate on, treated as most
+    ///   significant in `u`
     /// - `u`: unitary matrix to apply
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `ctrl` or any index in `targs` is outside of `[0,
+    ///   - if `target_qubit1` or `target_qubit2` are outside `[0,
     ///     self.num_qubits())`
-    ///   - if `targs` are not unique
+    ///   - if `target_qubit1` equals `target_qubit2`
     ///   - if matrix `u` is not unitary
-    ///   - if `u` is not of a compatible size with `targs.len()`
-    ///   - if a node cannot fit the required number of target amplitudes in
-    ///     distributed mode
+    ///   - if each node cannot fit 4 amplitudes in distributed mode
     ///
     /// # Examples
     ///
@@ -5045,99 +5977,85 @@ impl<'a> Qureg<'a> {
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     /// qureg.pauli_x(0).unwrap();
     ///
-    /// let u = &mut ComplexMatrixN::try_new(2).unwrap();
-    /// let zero_row = &[0., 0., 0., 0.];
-    /// init_complex_matrix_n(
-    ///     u,
-    ///     &[
-    ///         &[0., 0., 0., 1.],
-    ///         &[0., 1., 0., 0.],
-    ///         &[0., 0., 1., 0.],
-    ///         &[1., 0., 0., 0.],
+    /// let target_qubit1 = 1;
+    /// let target_qubit2 = 2;
+    /// let u = &ComplexMatrix4::new(
+    ///     [
+    ///         [0., 0., 0., 1.],
+    ///         [0., 1., 0., 0.],
+    ///         [0., 0., 1., 0.],
+    ///         [1., 0., 0., 0.],
     ///     ],
-    ///     &[zero_row, zero_row, zero_row, zero_row],
-    /// )
-    /// .unwrap();
+    ///     [
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///     ],
+    /// );
     ///
-    /// let ctrl = 0;
-    /// let targs = &[1, 2];
     /// qureg
-    ///     .controlled_multi_qubit_unitary(ctrl, targs, u)
+    ///     .two_qubit_unitary(target_qubit1, target_qubit2, u)
     ///     .unwrap();
     ///
-    /// // Check if the register is now in the state `|111>`
     /// let amp = qureg.get_real_amp(7).unwrap();
-    /// assert!((amp - 1.).abs() < EPSILON);
+    /// assert!((amp - 1.) < EPSILON);
     /// ```
     ///
-    /// See [QuEST API] for more information.
+    /// See [`QuEST` Aqureg.PI] for more information.
     ///
-    /// [`Qureg::apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+    /// [`Qureg::apply_matrix4()`]: crate::Qureg::apply_matrix4()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn controlled_multi_qubit_unitary(
+    pub fn two_qubit_unitary(
         &mut self,
-        ctrl: i32,
-        targs: &[i32],
-        u: &ComplexMatrixN,
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u: &ComplexMatrix4,
     ) -> Result<(), QuestError> {
-        let num_targs = targs.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::controlledMultiQubitUnitary(
-                self.reg,
-                ctrl,
-                targs.as_ptr(),
-                num_targs,
-                u.0,
-            );
+            ffi::twoQubitUnitary(self.reg, target_qubit1, target_qubit2, u.0);
         })
     }
 
-    /// Apply a general multi-controlled multi-qubit unitary (including a global
-    /// phase factor).
+    /// Apply a general controlled two-qubit unitary.
     ///
-    /// Any number of control and target qubits can be specified.
+    /// The given unitary is applied to the target amplitudes where the control
+    /// qubit has value 1.
     ///
-    /// The target qubits in `targs` are treated as ordered least significant
-    /// to most significant in `u`.
+    /// `target_qubit1` is treated as the least significant qubit in `u`,
+    /// such that a row in `u` is dotted with the vector
     ///
-    /// The passed `ComplexMatrixN` must be unitary and be a compatible size
-    /// with the specified number of target qubits, otherwise an error is
+    /// The passed `ComplexMatrix4` must be unitary, otherwise an error is
     /// thrown.
     ///
-    /// Note that in multithreaded mode, each thread will clone
-    /// `2^(targs.len())` amplitudes, and store these in the runtime stack.
-    /// Using `t` threads, the total memory overhead of this function is
-    /// `t*2^(targs.len())`. For many targets (e.g. 16 qubits), this may
-    /// cause a stack-overflow / seg-fault (e.g. on a 1 MiB stack).
-    ///  
-    /// Note too that in distributed mode, this routine requires that each node
-    /// contains at least `2^(targs.len())` amplitudes in the register. This
-    /// means an q-qubit register (state vector or density matrix)
-    /// can be distributed by at most `2^q / 2^(targs.len())` nodes.
+    /// Note that in distributed mode, this routine requires that each node
+    /// contains at least 4 amplitudes.  This means an q-qubit register
+    /// (state vector or density matrix) can be distributed by at most
+    /// `2^(q/4)` nodes.
     ///
     /// # Parameters
     ///
-    /// - `ctrls`: a list  of control qubits
-    /// - `targs`: a list of the target qubits, ordered least significant to
-    ///   most in `u`
+    /// - `contol_qubit`:  the control qubit which must be in state 1 to effect
+    ///   the given unitary
+    /// - `target_qubit1`: first qubit to operate on, treated as least
+    ///   significant in `u`
+    /// - `target_qubit2`: first qubit to operate on, treated as most
+    ///   significant in `u`
     /// - `u`: unitary matrix to apply
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if any qubit in `ctrls` or `targs` is outside of `[0,
-    ///     self.num_qubits())`
-    ///   - if `ctrls` or `targs` contain any repetitions
-    ///   - if any qubit in `ctrls` is also in `targs` (and vice versa)
-    ///   - if `targs.len() < 1`
-    ///   - if `ctrls.len() < 1` (use [`multi_qubit_unitary()`] for no controls)
+    ///   - if `control_qubit`,  `target_qubit1` or `target_qubit2` are outside
+    ///     `[0, self.num_qubits())`
+    ///   - if any of `control_qubit`, `target_qubit1` and `target_qubit2` are
+    ///     equal
     ///   - if matrix `u` is not unitary
-    ///   - if `u` is not of a compatible size with `targs.len()`
-    ///   - if a node cannot fit the required number of target amplitudes in
-    ///     distributed mode
+    ///   - if each node cannot fit 4 amplitudes in distributed mode
+    ///
     ///
     /// # Examples
     ///
@@ -5145,113 +6063,121 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     /// qureg.pauli_x(0).unwrap();
-    /// qureg.pauli_x(1).unwrap();
     ///
-    /// let u = &mut ComplexMatrixN::try_new(2).unwrap();
-    /// let zero_row = &[0., 0., 0., 0.];
-    /// init_complex_matrix_n(
-    ///     u,
-    ///     &[
-    ///         &[0., 0., 0., 1.],
-    ///         &[0., 1., 0., 0.],
-    ///         &[0., 0., 1., 0.],
-    ///         &[1., 0., 0., 0.],
+    /// let control_qubit = 0;
+    /// let target_qubit1 = 1;
+    /// let target_qubit2 = 2;
+    /// let u = &ComplexMatrix4::new(
+    ///     [
+    ///         [0., 0., 0., 1.],
+    ///         [0., 1., 0., 0.],
+    ///         [0., 0., 1., 0.],
+    ///         [1., 0., 0., 0.],
     ///     ],
-    ///     &[zero_row, zero_row, zero_row, zero_row],
-    /// )
-    /// .unwrap();
+    ///     [
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///     ],
+    /// );
     ///
-    /// let ctrls = &[0, 1];
-    /// let targs = &[2, 3];
     /// qureg
-    ///     .multi_controlled_multi_qubit_unitary(ctrls, targs, u)
+    ///     .controlled_two_qubit_unitary(
+    ///         control_qubit,
+    ///         target_qubit1,
+    ///         target_qubit2,
+    ///         u,
+    ///     )
     ///     .unwrap();
     ///
-    /// // Check if the register is now in the state `|1111>`
-    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// let amp = qureg.get_real_amp(7).unwrap();
     /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
-    /// [`Qureg::apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+    /// [`Qureg::apply_matrix4()`]: crate::Qureg::apply_matrix4()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`multi_qubit_unitary()`]: crate::Qureg::multi_qubit_unitary()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_multi_qubit_unitary(
+    pub fn controlled_two_qubit_unitary(
         &mut self,
-        ctrls: &[i32],
-        targs: &[i32],
-        u: &ComplexMatrixN,
+        control_qubit: i32,
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u: &ComplexMatrix4,
     ) -> Result<(), QuestError> {
-        let num_ctrls = ctrls.len() as i32;
-        let num_targs = targs.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::multiControlledMultiQubitUnitary(
+            ffi::controlledTwoQubitUnitary(
                 self.reg,
-                ctrls.as_ptr(),
-                num_ctrls,
-                targs.as_ptr(),
-                num_targs,
+                control_qubit,
+                target_qubit1,
+                target_qubit2,
                 u.0,
             );
         })
     }
 
-    /// Apply a general single-qubit Kraus map to a density matrix.
+    /// Apply a general multi-qubit unitary with any number of target qubits.
     ///
-    /// The map is specified by at most four Kraus operators.
+    /// Any number of control qubits can be specified, and if all have value 1,
+    /// the given unitary is applied to the target qubit.
     ///
-    /// # Examples
+    /// `target_qubit1` is treated as the least significant qubit in `u`,
+    /// such that a row in `u` is dotted with the vector
     ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
+    /// ```latex
+    ///  |\text{targetQubit2} \;\; \text{targetQubit1}\rangle : \{|00\rangle, |01\rangle, |10\rangle, |11\rangle \}
+    /// ```
     ///
-    /// let m = &ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
-    /// let target = 1;
-    /// qureg.mix_kraus_map(target, &[m]).unwrap();
+    /// The passed `ComplexMatrix4` must be unitary, otherwise an error is
+    /// thrown.  Use [`Qureg::apply_matrix4()`] to left-multiply a non-unitary
+    /// `ComplexMatrix4`.
     ///
-    /// // Check is the register is now in the state |01>
-    /// let amp = qureg.get_density_amp(2, 2).unwrap();
-    /// assert!((amp.re - 1.).abs() < EPSILON);
-    /// ```
+    /// Note that in distributed mode, this routine requires that each node
+    /// contains at least 4 amplitudes.  This means an q-qubit register
+    /// (state vector or density matrix) can be distributed by at most
     ///
-    /// See [QuEST API] for more information.
+    /// # Parameters
     ///
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_kraus_map(
-        &mut self,
-        target: i32,
-        ops: &[&ComplexMatrix2],
-    ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
-        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
-            ffi::mixKrausMap(self.reg, target, ops_inner.as_ptr(), num_ops);
-        })
-    }
-
-    /// Apply a general two-qubit Kraus map to a density matrix.
+    /// - `contol_qubits`:  the control qubits which all must be in state 1 to
+    ///   effect the given unitary
+    /// - `target_qubit1`: first qubit to operate on, treated as least
+    ///   significant in `u`
+    /// - `target_qubit2`: first qubit to operate on, treated as most
+    ///   significant in `u`
+    /// - `u`: unitary matrix to apply
     ///
-    /// The map is specified by at most sixteen Kraus operators.
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit1` or `target_qubit2` are outside `[0,
+    ///     self.num_qubits())`
+    ///   - if `target_qubit1` equals `target_qubit2`
+    ///   - if any qubit in `control_qubits` is outside `[0, self.num_qubits())`
+    ///   - if `control_qubits` are not unique
+    ///   - if either `target_qubit1` or `target_qubit2` are in `control_qubit``
+    ///   - if matrix `u` is not unitary
+    ///   - if each node cannot fit 4 amplitudes in distributed mode
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(3, &env)
-    ///     .expect("cannot allocate memory for Qureg");
+    /// let mut qureg =
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.pauli_x(0).unwrap();
+    /// qureg.pauli_x(1).unwrap();
     ///
-    /// let m = &ComplexMatrix4::new(
+    /// let control_qubits = &[0, 1];
+    /// let target_qubit1 = 2;
+    /// let target_qubit2 = 3;
+    /// let u = &ComplexMatrix4::new(
     ///     [
     ///         [0., 0., 0., 1.],
     ///         [0., 1., 0., 0.],
@@ -5265,262 +6191,334 @@ impl<'a> Qureg<'a> {
     ///         [0., 0., 0., 0.],
     ///     ],
     /// );
-    /// let target1 = 1;
-    /// let target2 = 2;
+    ///
     /// qureg
-    ///     .mix_two_qubit_kraus_map(target1, target2, &[m])
+    ///     .multi_controlled_two_qubit_unitary(
+    ///         control_qubits,
+    ///         target_qubit1,
+    ///         target_qubit2,
+    ///         u,
+    ///     )
     ///     .unwrap();
     ///
-    /// // Check is the register is now in the state |011>
-    /// let amp = qureg.get_density_amp(6, 6).unwrap();
-    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_two_qubit_kraus_map(
+    pub fn multi_controlled_two_qubit_unitary(
         &mut self,
-        target1: i32,
-        target2: i32,
-        ops: &[&ComplexMatrix4],
+        control_qubits: &[i32],
+        target_qubit1: i32,
+        target_qubit2: i32,
+        u: &ComplexMatrix4,
     ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
-        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
+        let num_control_qubits = control_qubits.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::mixTwoQubitKrausMap(
+            ffi::multiControlledTwoQubitUnitary(
                 self.reg,
-                target1,
-                target2,
-                ops_inner.as_ptr(),
-                num_ops,
+                control_qubits.as_ptr(),
+                num_control_qubits,
+                target_qubit1,
+                target_qubit2,
+                u.0,
             );
         })
     }
 
-    /// Apply a general N-qubit Kraus map to a density matrix.
+    /// Apply a general multi-qubit unitary with any number of target qubits.
     ///
-    /// The map is specified by at most `(2N)^2` Kraus operators.
+    /// The first target qubit in `targs` is treated as least significant in
+    /// `u`.
     ///
-    /// # Examples
+    /// The passed `ComplexMatrixN` must be unitary and be a compatible size
+    /// with the specified number of target qubits, otherwise an error is
+    /// thrown.
     ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(3, &env)
-    ///     .expect("cannot allocate memory for Qureg");
+    /// To left-multiply a non-unitary `ComplexMatrixN`, use
+    /// [`apply_matrix_n()`].
     ///
-    /// let m = &mut ComplexMatrixN::try_new(2).unwrap();
-    /// init_complex_matrix_n(
-    ///     m,
-    ///     &[
-    ///         &[0., 0., 0., 1.],
-    ///         &[0., 1., 0., 0.],
-    ///         &[0., 0., 1., 0.],
-    ///         &[1., 0., 0., 0.],
-    ///     ],
-    ///     &[
-    ///         &[0., 0., 0., 0.],
-    ///         &[0., 0., 0., 0.],
-    ///         &[0., 0., 0., 0.],
-    ///         &[0., 0., 0., 0.],
-    ///     ],
-    /// )
-    /// .unwrap();
-    /// let targets = &[1, 2];
-    /// qureg.mix_multi_qubit_kraus_map(targets, &[m]).unwrap();
+    /// Note that in multithreaded mode, each thread will clone
+    /// `2^(targs.len())` amplitudes, and store these in the runtime stack.
+    /// Using `t` threads, the total memory overhead of this function is
+    /// `t*2^(targs.len())`. For many targets (e.g. 16 qubits), this may
+    /// cause a stack-overflow / seg-fault (e.g. on a 1 MiB stack).
+    ///  
+    /// Note too that in distributed mode, this routine requires that each node
+    /// contains at least `2^(targs.len())` amplitudes in the register. This
+    /// means an q-qubit register (state vector or density matrix)
+    /// can be distributed by at most `2^q / 2^(targs.len())` nodes.
     ///
-    /// // Check if the register is now in the state |011>
-    /// let amp = qureg.get_density_amp(6, 6).unwrap();
-    /// assert!((amp.re - 1.).abs() < EPSILON);
-    /// ```
+    /// # Parameters
     ///
-    /// See [QuEST API] for more information.
+    /// - `targs`: a list of the target qubits, ordered least significant to
+    ///   most in `u`
+    /// - `u`: unitary matrix to apply
     ///
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_multi_qubit_kraus_map(
-        &mut self,
-        targets: &[i32],
-        ops: &[&ComplexMatrixN],
-    ) -> Result<(), QuestError> {
-        let num_targets = targets.len() as i32;
-        let num_ops = ops.len() as i32;
-        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
-            ffi::mixMultiQubitKrausMap(
-                self.reg,
-                targets.as_ptr(),
-                num_targets,
-                ops_inner.as_ptr(),
-                num_ops,
-            );
-        })
-    }
-
-    /// Apply a general non-trace-preserving single-qubit Kraus map.
+    /// # Errors
     ///
-    /// The state must be a density matrix, and the map is specified by at most
-    /// four operators.
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any index in `targs` is outside of `[0, self.num_qubits())`
+    ///   - if `targs` are not unique
+    ///   - if matrix `u` is not unitary
+    ///   - if `u` is not of a compatible size with `targs.len()`
+    ///   - if a node cannot fit the required number of target amplitudes in
+    ///     distributed mode
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(2, &env)
-    ///     .expect("cannot allocate memory for Qureg");
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let u = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// let zero_row = &[0., 0., 0., 0.];
+    /// init_complex_matrix_n(
+    ///     u,
+    ///     &[
+    ///         &[0., 0., 0., 1.],
+    ///         &[0., 1., 0., 0.],
+    ///         &[0., 0., 1., 0.],
+    ///         &[1., 0., 0., 0.],
+    ///     ],
+    ///     &[zero_row, zero_row, zero_row, zero_row],
+    /// )
+    /// .unwrap();
     ///
-    /// let m = &ComplexMatrix2::new([[0., 1.], [0., 0.]], [[0., 0.], [0., 0.]]);
-    /// let target = 1;
-    /// qureg.mix_nontp_kraus_map(target, &[m]).unwrap();
+    /// qureg.multi_qubit_unitary(&[0, 1], u).unwrap();
     ///
-    /// // The register is in an unphysical null state
-    /// let amp = qureg.get_density_amp(2, 2).unwrap();
-    /// assert!(amp.re.abs() < EPSILON);
+    /// // Check if the register is now in the state `|11>`
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_nontp_kraus_map(
+    pub fn multi_qubit_unitary(
         &mut self,
-        target: i32,
-        ops: &[&ComplexMatrix2],
+        targs: &[i32],
+        u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
-        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
+        let num_targs = targs.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::mixNonTPKrausMap(
-                self.reg,
-                target,
-                ops_inner.as_ptr(),
-                num_ops,
-            );
+            ffi::multiQubitUnitary(self.reg, targs.as_ptr(), num_targs, u.0);
         })
     }
 
-    /// Apply a general non-trace-preserving two-qubit Kraus map.
+    /// Apply a general controlled multi-qubit unitary (including a global phase
+    /// factor).
     ///
-    /// The state must be a density matrix, and the map is specified
-    /// by at most 16 operators.
+    /// One control and any number of target qubits can be specified.
+    ///
+    /// The target qubits in `targs` are treated as ordered least significant
+    /// to most significant in `u`.
+    ///
+    /// The passed `ComplexMatrixN` must be unitary and be a compatible size
+    /// with the specified number of target qubits, otherwise an error is
+    /// thrown.
+    ///
+    /// Note that in multithreaded mode, each thread will clone
+    /// `2^(targs.len())` amplitudes, and store these in the runtime stack.
+    /// Using `t` threads, the total memory overhead of this function is
+    /// `t*2^(targs.len())`. For many targets (e.g. 16 qubits), this may
+    /// cause a stack-overflow / seg-fault (e.g. on a 1 MiB stack).
+    ///  
+    /// Note too that in distributed mode, this routine requires that each node
+    /// contains at least `2^(targs.len())` amplitudes in the register. This
+    /// means an q-qubit register (state vector or density matrix)
+    /// can be distributed by at most `2^q / 2^(targs.len())` nodes.
+    ///
+    /// # Parameters
+    ///
+    /// - `ctrl`: the control qubit
+    /// - `targs`: a list of the target qubits, ordered least significant to
+    ///   most in `u`
+    /// - `u`: unitary matrix to apply
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `ctrl` or any index in `targs` is outside of `[0,
+    ///     self.num_qubits())`
+    ///   - if `targs` are not unique
+    ///   - if matrix `u` is not unitary
+    ///   - if `u` is not of a compatible size with `targs.len()`
+    ///   - if a node cannot fit the required number of target amplitudes in
+    ///     distributed mode
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(3, &env)
-    ///     .expect("cannot allocate memory for Qureg");
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.pauli_x(0).unwrap();
     ///
-    /// let m = &ComplexMatrix4::new(
-    ///     [
-    ///         [0., 0., 0., 1.],
-    ///         [0., 1., 0., 0.],
-    ///         [0., 0., 1., 0.],
-    ///         [0., 0., 0., 0.],
-    ///     ],
-    ///     [
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
-    ///         [0., 0., 0., 0.],
+    /// let u = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// let zero_row = &[0., 0., 0., 0.];
+    /// init_complex_matrix_n(
+    ///     u,
+    ///     &[
+    ///         &[0., 0., 0., 1.],
+    ///         &[0., 1., 0., 0.],
+    ///         &[0., 0., 1., 0.],
+    ///         &[1., 0., 0., 0.],
     ///     ],
-    /// );
-    /// let target1 = 1;
-    /// let target2 = 2;
+    ///     &[zero_row, zero_row, zero_row, zero_row],
+    /// )
+    /// .unwrap();
+    ///
+    /// let ctrl = 0;
+    /// let targs = &[1, 2];
     /// qureg
-    ///     .mix_nontp_two_qubit_kraus_map(target1, target2, &[m])
+    ///     .controlled_multi_qubit_unitary(ctrl, targs, u)
     ///     .unwrap();
     ///
-    /// // The register is in an unphysical null state
-    /// let amp = qureg.get_density_amp(6, 6).unwrap();
-    /// assert!(amp.re.abs() < EPSILON);
+    /// // Check if the register is now in the state `|111>`
+    /// let amp = qureg.get_real_amp(7).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`Qureg::apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_nontp_two_qubit_kraus_map(
+    pub fn controlled_multi_qubit_unitary(
         &mut self,
-        target1: i32,
-        target2: i32,
-        ops: &[&ComplexMatrix4],
+        ctrl: i32,
+        targs: &[i32],
+        u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
-        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
+        let num_targs = targs.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::mixNonTPTwoQubitKrausMap(
+            ffi::controlledMultiQubitUnitary(
                 self.reg,
-                target1,
-                target2,
-                ops_inner.as_ptr(),
-                num_ops,
+                ctrl,
+                targs.as_ptr(),
+                num_targs,
+                u.0,
             );
         })
     }
 
-    /// Apply a general N-qubit non-trace-preserving Kraus map.
+    /// Apply a general multi-controlled multi-qubit unitary (including a global
+    /// phase factor).
     ///
-    /// The state must be a density matrix, and the map is specified
-    /// by at most `2^(2N)` operators.
+    /// Any number of control and target qubits can be specified.
+    ///
+    /// The target qubits in `targs` are treated as ordered least significant
+    /// to most significant in `u`.
+    ///
+    /// The passed `ComplexMatrixN` must be unitary and be a compatible size
+    /// with the specified number of target qubits, otherwise an error is
+    /// thrown.
+    ///
+    /// Note that in multithreaded mode, each thread will clone
+    /// `2^(targs.len())` amplitudes, and store these in the runtime stack.
+    /// Using `t` threads, the total memory overhead of this function is
+    /// `t*2^(targs.len())`. For many targets (e.g. 16 qubits), this may
+    /// cause a stack-overflow / seg-fault (e.g. on a 1 MiB stack).
+    ///  
+    /// Note too that in distributed mode, this routine requires that each node
+    /// contains at least `2^(targs.len())` amplitudes in the register. This
+    /// means an q-qubit register (state vector or density matrix)
+    /// can be distributed by at most `2^q / 2^(targs.len())` nodes.
+    ///
+    /// # Parameters
+    ///
+    /// - `ctrls`: a list  of control qubits
+    /// - `targs`: a list of the target qubits, ordered least significant to
+    ///   most in `u`
+    /// - `u`: unitary matrix to apply
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `ctrls` or `targs` is outside of `[0,
+    ///     self.num_qubits())`
+    ///   - if `ctrls` or `targs` contain any repetitions
+    ///   - if any qubit in `ctrls` is also in `targs` (and vice versa)
+    ///   - if `targs.len() < 1`
+    ///   - if `ctrls.len() < 1` (use [`multi_qubit_unitary()`] for no controls)
+    ///   - if matrix `u` is not unitary
+    ///   - if `u` is not of a compatible size with `targs.len()`
+    ///   - if a node cannot fit the required number of target amplitudes in
+    ///     distributed mode
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg = Qureg::try_new_density(3, &env)
-    ///     .expect("cannot allocate memory for Qureg");
-    /// let m = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// let mut qureg =
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.pauli_x(0).unwrap();
+    /// qureg.pauli_x(1).unwrap();
+    ///
+    /// let u = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// let zero_row = &[0., 0., 0., 0.];
     /// init_complex_matrix_n(
-    ///     m,
+    ///     u,
     ///     &[
     ///         &[0., 0., 0., 1.],
     ///         &[0., 1., 0., 0.],
     ///         &[0., 0., 1., 0.],
-    ///         &[0., 0., 0., 0.],
-    ///     ],
-    ///     &[
-    ///         &[0., 0., 0., 0.],
-    ///         &[0., 0., 0., 0.],
-    ///         &[0., 0., 0., 0.],
-    ///         &[0., 0., 0., 0.],
+    ///         &[1., 0., 0., 0.],
     ///     ],
+    ///     &[zero_row, zero_row, zero_row, zero_row],
     /// )
     /// .unwrap();
-    /// let targets = &[1, 2];
+    ///
+    /// let ctrls = &[0, 1];
+    /// let targs = &[2, 3];
     /// qureg
-    ///     .mix_nontp_multi_qubit_kraus_map(targets, &[m])
+    ///     .multi_controlled_multi_qubit_unitary(ctrls, targs, u)
     ///     .unwrap();
     ///
-    /// // The register is in an unphysical null state
-    /// let amp = qureg.get_density_amp(6, 6).unwrap();
-    /// assert!(amp.re.abs() < EPSILON);
+    /// // Check if the register is now in the state `|1111>`
+    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`Qureg::apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`multi_qubit_unitary()`]: crate::Qureg::multi_qubit_unitary()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_nontp_multi_qubit_kraus_map(
+    pub fn multi_controlled_multi_qubit_unitary(
         &mut self,
-        targets: &[i32],
-        ops: &[&ComplexMatrixN],
+        ctrls: &[i32],
+        targs: &[i32],
+        u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_targets = targets.len() as i32;
-        let num_ops = ops.len() as i32;
-        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
+        let num_ctrls = ctrls.len() as i32;
+        let num_targs = targs.len() as i32;
         catch_quest_exception(|| unsafe {
-            ffi::mixNonTPMultiQubitKrausMap(
+            ffi::multiControlledMultiQubitUnitary(
                 self.reg,
-                targets.as_ptr(),
-                num_targets,
-                ops_inner.as_ptr(),
-                num_ops,
+                ctrls.as_ptr(),
+                num_ctrls,
+                targs.as_ptr(),
+                num_targs,
+                u.0,
             );
         })
     }
@@ -6727,10 +7725,22 @@ impl<'a> Qureg<'a> {
     }
 } // Qureg
 
-impl<'a> Drop for Qureg<'a> {
+/// Cloning a register allocates a new one.
+///
+/// # Panics
+///
+/// This function will panic if the new register cannot be created.
+/// See [`Qureg::try_clone()`] for a fallible alternative.
+impl<K: QuregKind> Clone for Qureg<K> {
+    fn clone(&self) -> Self {
+        self.try_clone().expect("cannot allocate memory for Qureg")
+    }
+}
+
+impl<K> Drop for Qureg<K> {
     fn drop(&mut self) {
         catch_quest_exception(|| {
-            unsafe { ffi::destroyQureg(self.reg, self.env.0) };
+            unsafe { ffi::destroyQureg(self.reg, self.env.raw()) };
         })
         .expect("dropping Qureg should always succeed");
     }
@@ -6778,10 +7788,10 @@ impl<'a> Drop for Qureg<'a> {
 ///
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
-pub fn apply_pauli_hamil(
-    in_qureg: &mut Qureg<'_>,
+pub fn apply_pauli_hamil<K: QuregKind>(
+    in_qureg: &mut Qureg<K>,
     hamil: &PauliHamil,
-    out_qureg: &mut Qureg<'_>,
+    out_qureg: &mut Qureg<K>,
 ) -> Result<(), QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::applyPauliHamil(in_qureg.reg, hamil.0, out_qureg.reg);
@@ -6830,11 +7840,11 @@ pub fn apply_pauli_hamil(
 ///
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
-pub fn apply_pauli_sum(
-    in_qureg: &mut Qureg<'_>,
+pub fn apply_pauli_sum<K: QuregKind>(
+    in_qureg: &mut Qureg<K>,
     all_pauli_codes: &[PauliOpType],
     term_coeffs: &[Qreal],
-    out_qureg: &mut Qureg<'_>,
+    out_qureg: &mut Qureg<K>,
 ) -> Result<(), QuestError> {
     let num_sum_terms = term_coeffs.len() as i32;
     catch_quest_exception(|| unsafe {
@@ -6895,8 +7905,8 @@ pub fn apply_pauli_sum(
 /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 pub fn calc_hilbert_schmidt_distance(
-    a: &Qureg<'_>,
-    b: &Qureg<'_>,
+    a: &DensityMatrix,
+    b: &DensityMatrix,
 ) -> Result<Qreal, QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::calcHilbertSchmidtDistance(a.reg, b.reg)
@@ -6957,8 +7967,8 @@ pub fn calc_hilbert_schmidt_distance(
 /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 pub fn calc_inner_product(
-    bra: &Qureg<'_>,
-    ket: &Qureg<'_>,
+    bra: &StateVector,
+    ket: &StateVector,
 ) -> Result<Qcomplex, QuestError> {
     catch_quest_exception(|| unsafe { ffi::calcInnerProduct(bra.reg, ket.reg) })
         .map(Into::into)
@@ -6988,8 +7998,8 @@ pub fn calc_inner_product(
 ///
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 pub fn calc_density_inner_product(
-    rho1: &Qureg<'_>,
-    rho2: &Qureg<'_>,
+    rho1: &DensityMatrix,
+    rho2: &DensityMatrix,
 ) -> Result<Qreal, QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::calcDensityInnerProduct(rho1.reg, rho2.reg)
@@ -7012,13 +8022,13 @@ pub fn calc_density_inner_product(
 ///
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
-pub fn set_weighted_qureg(
+pub fn set_weighted_qureg<K: QuregKind>(
     fac1: Qcomplex,
-    qureg1: &Qureg<'_>,
+    qureg1: &Qureg<K>,
     fac2: Qcomplex,
-    qureg2: &Qureg<'_>,
+    qureg2: &Qureg<K>,
     fac_out: Qcomplex,
-    out: &mut Qureg<'_>,
+    out: &mut Qureg<K>,
 ) -> Result<(), QuestError> {
     catch_quest_exception(|| unsafe {
         ffi::setWeightedQureg(
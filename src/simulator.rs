@@ -0,0 +1,903 @@
+//! Common interface of state-vector simulators.
+
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    gates,
+    ComplexMatrix2,
+    PauliOpType,
+    Qcomplex,
+    Qreal,
+    QuestError,
+    StateVector,
+    REAL_EPS,
+};
+
+/// Core operations of a state-vector simulator.
+///
+/// The trait covers initialization, unitary and controlled gates,
+/// measurement, probabilities and expectation values.  It is implemented by
+/// [`StateVector`], backed by `QuEST`, and by [`DenseStateVector`], a small
+/// reference simulator written in pure Rust.  The Rust backend validates its
+/// input in the same way as `QuEST` does, so that both backends report
+/// errors of the same [`kind()`].
+///
+/// Qubits and amplitudes are indexed in the same way as in [`Qureg`], and
+/// each method behaves as the method of [`Qureg`] with the same name.
+/// Only the first group of methods is required.  The remaining gates are
+/// provided in terms of [`unitary()`] and [`multi_controlled_unitary()`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// fn prepare_bell_state<S: Simulator>(sim: &mut S) -> Result<(), QuestError> {
+///     sim.init_zero_state();
+///     sim.hadamard(0)?;
+///     sim.controlled_not(0, 1)
+/// }
+///
+/// let env = QuestEnv::new();
+/// let qureg = &mut Qureg::try_new(2, &env).unwrap();
+/// let dense = &mut DenseStateVector::try_new(2).unwrap();
+///
+/// prepare_bell_state(qureg).unwrap();
+/// prepare_bell_state(dense).unwrap();
+///
+/// let p = qureg.get_prob_amp(3).unwrap();
+/// let q = dense.get_prob_amp(3).unwrap();
+/// assert!((p - q).abs() < REAL_EPS);
+/// ```
+///
+/// [`StateVector`]: crate::StateVector
+/// [`kind()`]: crate::QuestError::kind()
+/// [`Qureg`]: crate::Qureg
+/// [`unitary()`]: Simulator::unitary()
+/// [`multi_controlled_unitary()`]: Simulator::multi_controlled_unitary()
+pub trait Simulator {
+    /// Number of qubits.  See [`Qureg::num_qubits()`].
+    ///
+    /// [`Qureg::num_qubits()`]: crate::Qureg::num_qubits()
+    fn num_qubits(&self) -> i32;
+
+    /// See [`Qureg::init_zero_state()`].
+    ///
+    /// [`Qureg::init_zero_state()`]: crate::Qureg::init_zero_state()
+    fn init_zero_state(&mut self);
+
+    /// See [`Qureg::init_plus_state()`].
+    ///
+    /// [`Qureg::init_plus_state()`]: crate::Qureg::init_plus_state()
+    fn init_plus_state(&mut self);
+
+    /// See [`Qureg::init_classical_state()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `state_ind` is out of range.
+    ///
+    /// [`Qureg::init_classical_state()`]: crate::Qureg::init_classical_state()
+    fn init_classical_state(
+        &mut self,
+        state_ind: i64,
+    ) -> Result<(), QuestError>;
+
+    /// See [`Qureg::get_amp()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range.
+    ///
+    /// [`Qureg::get_amp()`]: crate::Qureg::get_amp()
+    fn get_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qcomplex, QuestError>;
+
+    /// See [`Qureg::calc_total_prob()`].
+    ///
+    /// [`Qureg::calc_total_prob()`]: crate::Qureg::calc_total_prob()
+    fn calc_total_prob(&self) -> Qreal;
+
+    /// See [`Qureg::calc_prob_of_outcome()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `measure_qubit` is out of range, or `outcome` is
+    /// neither 0 nor 1.
+    ///
+    /// [`Qureg::calc_prob_of_outcome()`]: crate::Qureg::calc_prob_of_outcome()
+    fn calc_prob_of_outcome(
+        &self,
+        measure_qubit: i32,
+        outcome: i32,
+    ) -> Result<Qreal, QuestError>;
+
+    /// See [`Qureg::unitary()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range, or `u` is not
+    /// unitary.
+    ///
+    /// [`Qureg::unitary()`]: crate::Qureg::unitary()
+    fn unitary(
+        &mut self,
+        target_qubit: i32,
+        u: &ComplexMatrix2,
+    ) -> Result<(), QuestError>;
+
+    /// See [`Qureg::multi_controlled_unitary()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any qubit is out of range or repeated,
+    /// `control_qubits` is empty, or `u` is not unitary.
+    ///
+    /// [`Qureg::multi_controlled_unitary()`]: crate::Qureg::multi_controlled_unitary()
+    fn multi_controlled_unitary(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubit: i32,
+        u: &ComplexMatrix2,
+    ) -> Result<(), QuestError>;
+
+    /// See [`Qureg::measure_with_stats()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `measure_qubit` is out of range.
+    ///
+    /// [`Qureg::measure_with_stats()`]: crate::Qureg::measure_with_stats()
+    fn measure_with_stats(
+        &mut self,
+        measure_qubit: i32,
+        outcome_prob: &mut Qreal,
+    ) -> Result<i32, QuestError>;
+
+    /// Expectation value of a product of Pauli operators.
+    ///
+    /// See [`Qureg::calc_expec_pauli_prod()`].  No workspace is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any qubit is out of range or repeated, or the
+    /// lengths of `target_qubits` and `pauli_codes` differ.
+    ///
+    /// [`Qureg::calc_expec_pauli_prod()`]: crate::Qureg::calc_expec_pauli_prod()
+    fn calc_expec_pauli_prod(
+        &self,
+        target_qubits: &[i32],
+        pauli_codes: &[PauliOpType],
+    ) -> Result<Qreal, QuestError>;
+
+    /// Expectation value of a sum of products of Pauli operators.
+    ///
+    /// See [`Qureg::calc_expec_pauli_sum()`].  No workspace is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length of `all_pauli_codes` is not the
+    /// number of qubits times the length of `term_coeffs`.
+    ///
+    /// [`Qureg::calc_expec_pauli_sum()`]: crate::Qureg::calc_expec_pauli_sum()
+    fn calc_expec_pauli_sum(
+        &self,
+        all_pauli_codes: &[PauliOpType],
+        term_coeffs: &[Qreal],
+    ) -> Result<Qreal, QuestError>;
+
+    /// See [`Qureg::get_prob_amp()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range.
+    ///
+    /// [`Qureg::get_prob_amp()`]: crate::Qureg::get_prob_amp()
+    fn get_prob_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qreal, QuestError> {
+        self.get_amp(index).map(|amp| amp.norm_sqr())
+    }
+
+    /// See [`Qureg::measure()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `measure_qubit` is out of range.
+    ///
+    /// [`Qureg::measure()`]: crate::Qureg::measure()
+    fn measure(
+        &mut self,
+        measure_qubit: i32,
+    ) -> Result<i32, QuestError> {
+        self.measure_with_stats(measure_qubit, &mut 0.)
+    }
+
+    /// See [`Qureg::hadamard()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range.
+    ///
+    /// [`Qureg::hadamard()`]: crate::Qureg::hadamard()
+    fn hadamard(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::hadamard())
+    }
+
+    /// See [`Qureg::pauli_x()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range.
+    ///
+    /// [`Qureg::pauli_x()`]: crate::Qureg::pauli_x()
+    fn pauli_x(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::pauli_x())
+    }
+
+    /// See [`Qureg::pauli_y()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range.
+    ///
+    /// [`Qureg::pauli_y()`]: crate::Qureg::pauli_y()
+    fn pauli_y(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::pauli_y())
+    }
+
+    /// See [`Qureg::pauli_z()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range.
+    ///
+    /// [`Qureg::pauli_z()`]: crate::Qureg::pauli_z()
+    fn pauli_z(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::pauli_z())
+    }
+
+    /// See [`Qureg::s_gate()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range.
+    ///
+    /// [`Qureg::s_gate()`]: crate::Qureg::s_gate()
+    fn s_gate(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::s())
+    }
+
+    /// See [`Qureg::t_gate()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range.
+    ///
+    /// [`Qureg::t_gate()`]: crate::Qureg::t_gate()
+    fn t_gate(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::t())
+    }
+
+    /// See [`Qureg::phase_shift()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_qubit` is out of range.
+    ///
+    /// [`Qureg::phase_shift()`]: crate::Qureg::phase_shift()
+    fn phase_shift(
+        &mut self,
+        target_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gates::phase_shift(angle))
+    }
+
+    /// See [`Qureg::rotate_x()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `rot_qubit` is out of range.
+    ///
+    /// [`Qureg::rotate_x()`]: crate::Qureg::rotate_x()
+    fn rotate_x(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        self.unitary(rot_qubit, &gates::rotate_x(angle))
+    }
+
+    /// See [`Qureg::rotate_y()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `rot_qubit` is out of range.
+    ///
+    /// [`Qureg::rotate_y()`]: crate::Qureg::rotate_y()
+    fn rotate_y(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        self.unitary(rot_qubit, &gates::rotate_y(angle))
+    }
+
+    /// See [`Qureg::rotate_z()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `rot_qubit` is out of range.
+    ///
+    /// [`Qureg::rotate_z()`]: crate::Qureg::rotate_z()
+    fn rotate_z(
+        &mut self,
+        rot_qubit: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        self.unitary(rot_qubit, &gates::rotate_z(angle))
+    }
+
+    /// See [`Qureg::controlled_not()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either qubit is out of range, or the qubits are
+    /// equal.
+    ///
+    /// [`Qureg::controlled_not()`]: crate::Qureg::controlled_not()
+    fn controlled_not(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.multi_controlled_unitary(
+            &[control_qubit],
+            target_qubit,
+            &gates::pauli_x(),
+        )
+    }
+
+    /// See [`Qureg::controlled_phase_flip()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either qubit is out of range, or the qubits are
+    /// equal.
+    ///
+    /// [`Qureg::controlled_phase_flip()`]: crate::Qureg::controlled_phase_flip()
+    fn controlled_phase_flip(
+        &mut self,
+        id_qubit1: i32,
+        id_qubit2: i32,
+    ) -> Result<(), QuestError> {
+        self.multi_controlled_unitary(
+            &[id_qubit1],
+            id_qubit2,
+            &gates::pauli_z(),
+        )
+    }
+
+    /// See [`Qureg::controlled_phase_shift()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either qubit is out of range, or the qubits are
+    /// equal.
+    ///
+    /// [`Qureg::controlled_phase_shift()`]: crate::Qureg::controlled_phase_shift()
+    fn controlled_phase_shift(
+        &mut self,
+        id_qubit1: i32,
+        id_qubit2: i32,
+        angle: Qreal,
+    ) -> Result<(), QuestError> {
+        self.multi_controlled_unitary(
+            &[id_qubit1],
+            id_qubit2,
+            &gates::phase_shift(angle),
+        )
+    }
+
+    /// See [`Qureg::controlled_unitary()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either qubit is out of range, the qubits are
+    /// equal, or `u` is not unitary.
+    ///
+    /// [`Qureg::controlled_unitary()`]: crate::Qureg::controlled_unitary()
+    fn controlled_unitary(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+        u: &ComplexMatrix2,
+    ) -> Result<(), QuestError> {
+        self.multi_controlled_unitary(&[control_qubit], target_qubit, u)
+    }
+
+    /// See [`Qureg::swap_gate()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either qubit is out of range, or the qubits are
+    /// equal.
+    ///
+    /// [`Qureg::swap_gate()`]: crate::Qureg::swap_gate()
+    fn swap_gate(
+        &mut self,
+        qubit1: i32,
+        qubit2: i32,
+    ) -> Result<(), QuestError> {
+        self.controlled_not(qubit1, qubit2)?;
+        self.controlled_not(qubit2, qubit1)?;
+        self.controlled_not(qubit1, qubit2)
+    }
+}
+
+/// Forward methods of [`Simulator`] to the methods of [`Qureg`] with the same
+/// names.
+///
+/// [`Qureg`]: crate::Qureg
+macro_rules! forward_to_qureg {
+    ($(
+        fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) -> $ret:ty;
+    )*) => {
+        $(
+            fn $name(
+                &mut self,
+                $($arg: $ty),*
+            ) -> $ret {
+                StateVector::$name(self, $($arg),*)
+            }
+        )*
+    };
+}
+
+impl Simulator for StateVector {
+    forward_to_qureg! {
+        fn unitary(&mut self, target_qubit: i32, u: &ComplexMatrix2)
+            -> Result<(), QuestError>;
+        fn multi_controlled_unitary(
+            &mut self,
+            control_qubits: &[i32],
+            target_qubit: i32,
+            u: &ComplexMatrix2
+        ) -> Result<(), QuestError>;
+        fn measure_with_stats(&mut self, measure_qubit: i32, outcome_prob: &mut Qreal)
+            -> Result<i32, QuestError>;
+        fn measure(&mut self, measure_qubit: i32) -> Result<i32, QuestError>;
+        fn hadamard(&mut self, target_qubit: i32) -> Result<(), QuestError>;
+        fn pauli_x(&mut self, target_qubit: i32) -> Result<(), QuestError>;
+        fn pauli_y(&mut self, target_qubit: i32) -> Result<(), QuestError>;
+        fn pauli_z(&mut self, target_qubit: i32) -> Result<(), QuestError>;
+        fn s_gate(&mut self, target_qubit: i32) -> Result<(), QuestError>;
+        fn t_gate(&mut self, target_qubit: i32) -> Result<(), QuestError>;
+        fn phase_shift(&mut self, target_qubit: i32, angle: Qreal)
+            -> Result<(), QuestError>;
+        fn rotate_x(&mut self, rot_qubit: i32, angle: Qreal)
+            -> Result<(), QuestError>;
+        fn rotate_y(&mut self, rot_qubit: i32, angle: Qreal)
+            -> Result<(), QuestError>;
+        fn rotate_z(&mut self, rot_qubit: i32, angle: Qreal)
+            -> Result<(), QuestError>;
+        fn controlled_not(&mut self, control_qubit: i32, target_qubit: i32)
+            -> Result<(), QuestError>;
+        fn controlled_phase_flip(&mut self, id_qubit1: i32, id_qubit2: i32)
+            -> Result<(), QuestError>;
+        fn controlled_phase_shift(
+            &mut self,
+            id_qubit1: i32,
+            id_qubit2: i32,
+            angle: Qreal
+        ) -> Result<(), QuestError>;
+        fn controlled_unitary(
+            &mut self,
+            control_qubit: i32,
+            target_qubit: i32,
+            u: &ComplexMatrix2
+        ) -> Result<(), QuestError>;
+        fn swap_gate(&mut self, qubit1: i32, qubit2: i32)
+            -> Result<(), QuestError>;
+    }
+
+    fn num_qubits(&self) -> i32 {
+        StateVector::num_qubits(self)
+    }
+
+    fn init_zero_state(&mut self) {
+        StateVector::init_zero_state(self);
+    }
+
+    fn init_plus_state(&mut self) {
+        StateVector::init_plus_state(self);
+    }
+
+    fn init_classical_state(
+        &mut self,
+        state_ind: i64,
+    ) -> Result<(), QuestError> {
+        StateVector::init_classical_state(self, state_ind)
+    }
+
+    fn get_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qcomplex, QuestError> {
+        StateVector::get_amp(self, index)
+    }
+
+    fn get_prob_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qreal, QuestError> {
+        StateVector::get_prob_amp(self, index)
+    }
+
+    fn calc_total_prob(&self) -> Qreal {
+        StateVector::calc_total_prob(self)
+    }
+
+    fn calc_prob_of_outcome(
+        &self,
+        measure_qubit: i32,
+        outcome: i32,
+    ) -> Result<Qreal, QuestError> {
+        StateVector::calc_prob_of_outcome(self, measure_qubit, outcome)
+    }
+
+    fn calc_expec_pauli_prod(
+        &self,
+        target_qubits: &[i32],
+        pauli_codes: &[PauliOpType],
+    ) -> Result<Qreal, QuestError> {
+        let workspace = &mut self.try_clone()?;
+        StateVector::calc_expec_pauli_prod(
+            self,
+            target_qubits,
+            pauli_codes,
+            workspace,
+        )
+    }
+
+    fn calc_expec_pauli_sum(
+        &self,
+        all_pauli_codes: &[PauliOpType],
+        term_coeffs: &[Qreal],
+    ) -> Result<Qreal, QuestError> {
+        let workspace = &mut self.try_clone()?;
+        StateVector::calc_expec_pauli_sum(
+            self,
+            all_pauli_codes,
+            term_coeffs,
+            workspace,
+        )
+    }
+}
+
+/// Dense state-vector simulator written in pure Rust.
+///
+/// The amplitudes are stored in a single vector of length
+/// `2^num_qubits`, in the same order as in [`Qureg`].  Measurement outcomes
+/// are drawn from a random number generator owned by the simulator, which
+/// can be seeded with [`seed()`](DenseStateVector::seed()).
+///
+/// The simulator is meant as a reference implementation of [`Simulator`]
+/// for testing and for tiny circuits.  It makes no attempt at performance.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let sim = &mut DenseStateVector::try_new(2).unwrap();
+/// sim.seed(1);
+///
+/// sim.init_zero_state();
+/// sim.hadamard(0).unwrap();
+/// sim.controlled_not(0, 1).unwrap();
+///
+/// let outcome = sim.measure(0).unwrap();
+/// assert_eq!(sim.measure(1).unwrap(), outcome);
+/// ```
+///
+/// [`Qureg`]: crate::Qureg
+#[derive(Debug, Clone)]
+pub struct DenseStateVector {
+    num_qubits: i32,
+    amps:       Vec<Qcomplex>,
+    rng:        StdRng,
+}
+
+impl DenseStateVector {
+    /// Create a simulator of `num_qubits` qubits in the zero state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NumQubitsError`] if `num_qubits` is not positive, or too
+    /// large for the amplitudes to be indexed or allocated.
+    ///
+    /// [`NumQubitsError`]: crate::QuestError::NumQubitsError
+    pub fn try_new(num_qubits: i32) -> Result<Self, QuestError> {
+        if !(1..i64::BITS as i32 - 1).contains(&num_qubits) {
+            return Err(QuestError::NumQubitsError);
+        }
+        let len = usize::try_from(1_u64 << num_qubits)
+            .map_err(|_| QuestError::NumQubitsError)?;
+        // Fails on capacity overflow, or if the allocator refuses
+        let mut amps = Vec::new();
+        amps.try_reserve_exact(len)
+            .map_err(|_| QuestError::NumQubitsError)?;
+        amps.resize(len, Qcomplex::new(0., 0.));
+        amps[0] = Qcomplex::new(1., 0.);
+        Ok(Self {
+            num_qubits,
+            amps,
+            rng: StdRng::from_entropy(),
+        })
+    }
+
+    /// Seed the random number generator used for measurements.
+    pub fn seed(
+        &mut self,
+        seed: u64,
+    ) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// All amplitudes of the state-vector.
+    #[must_use]
+    pub fn amplitudes(&self) -> &[Qcomplex] {
+        &self.amps
+    }
+
+    fn check_qubit(
+        &self,
+        qubit: i32,
+    ) -> Result<usize, QuestError> {
+        if (0..self.num_qubits).contains(&qubit) {
+            Ok(qubit as usize)
+        } else {
+            Err(QuestError::QubitIndexError)
+        }
+    }
+
+    /// Check that all `qubits` are valid and distinct, and return the bit
+    /// mask of the qubits.
+    fn check_qubits(
+        &self,
+        qubits: &[i32],
+    ) -> Result<usize, QuestError> {
+        qubits.iter().try_fold(0, |mask, &qubit| {
+            let bit = 1 << self.check_qubit(qubit)?;
+            if mask & bit == 0 {
+                Ok(mask | bit)
+            } else {
+                Err(QuestError::RepeatedQubitsError)
+            }
+        })
+    }
+
+    fn check_index(
+        &self,
+        index: i64,
+    ) -> Result<usize, QuestError> {
+        usize::try_from(index)
+            .ok()
+            .filter(|&i| i < self.amps.len())
+            .ok_or(QuestError::IndexError)
+    }
+
+    /// Apply `u` to the target qubit of all amplitudes whose indices have
+    /// all bits of `control_mask` set.
+    fn apply_matrix2(
+        amps: &mut [Qcomplex],
+        control_mask: usize,
+        target: usize,
+        u: &ComplexMatrix2,
+    ) {
+        let elems = u.to_vec();
+        let target_bit = 1 << target;
+        for i in 0..amps.len() {
+            if i & target_bit != 0 || i & control_mask != control_mask {
+                continue;
+            }
+            let j = i | target_bit;
+            let (a, b) = (amps[i], amps[j]);
+            amps[i] = elems[0] * a + elems[1] * b;
+            amps[j] = elems[2] * a + elems[3] * b;
+        }
+    }
+}
+
+impl Simulator for DenseStateVector {
+    fn num_qubits(&self) -> i32 {
+        self.num_qubits
+    }
+
+    fn init_zero_state(&mut self) {
+        self.amps.fill(Qcomplex::new(0., 0.));
+        self.amps[0] = Qcomplex::new(1., 0.);
+    }
+
+    fn init_plus_state(&mut self) {
+        let amp = (self.amps.len() as Qreal).sqrt().recip();
+        self.amps.fill(Qcomplex::new(amp, 0.));
+    }
+
+    fn init_classical_state(
+        &mut self,
+        state_ind: i64,
+    ) -> Result<(), QuestError> {
+        let index = self.check_index(state_ind)?;
+        self.amps.fill(Qcomplex::new(0., 0.));
+        self.amps[index] = Qcomplex::new(1., 0.);
+        Ok(())
+    }
+
+    fn get_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qcomplex, QuestError> {
+        self.check_index(index).map(|i| self.amps[i])
+    }
+
+    fn calc_total_prob(&self) -> Qreal {
+        self.amps.iter().map(Qcomplex::norm_sqr).sum()
+    }
+
+    fn calc_prob_of_outcome(
+        &self,
+        measure_qubit: i32,
+        outcome: i32,
+    ) -> Result<Qreal, QuestError> {
+        let bit = 1 << self.check_qubit(measure_qubit)?;
+        let outcome = match outcome {
+            0 => 0,
+            1 => bit,
+            _ => return Err(QuestError::IndexError),
+        };
+        Ok(self
+            .amps
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i & bit == outcome)
+            .map(|(_, amp)| amp.norm_sqr())
+            .sum())
+    }
+
+    fn unitary(
+        &mut self,
+        target_qubit: i32,
+        u: &ComplexMatrix2,
+    ) -> Result<(), QuestError> {
+        let target = self.check_qubit(target_qubit)?;
+        if !u.is_unitary(REAL_EPS) {
+            return Err(QuestError::NonUnitaryError);
+        }
+        Self::apply_matrix2(&mut self.amps, 0, target, u);
+        Ok(())
+    }
+
+    fn multi_controlled_unitary(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubit: i32,
+        u: &ComplexMatrix2,
+    ) -> Result<(), QuestError> {
+        if control_qubits.is_empty() {
+            return Err(QuestError::NumQubitsError);
+        }
+        let target = self.check_qubit(target_qubit)?;
+        let control_mask = self.check_qubits(control_qubits)?;
+        if control_mask & (1 << target) != 0 {
+            return Err(QuestError::RepeatedQubitsError);
+        }
+        if !u.is_unitary(REAL_EPS) {
+            return Err(QuestError::NonUnitaryError);
+        }
+        Self::apply_matrix2(&mut self.amps, control_mask, target, u);
+        Ok(())
+    }
+
+    fn measure_with_stats(
+        &mut self,
+        measure_qubit: i32,
+        outcome_prob: &mut Qreal,
+    ) -> Result<i32, QuestError> {
+        let prob_zero = self.calc_prob_of_outcome(measure_qubit, 0)?;
+        let outcome = i32::from(self.rng.gen::<Qreal>() >= prob_zero);
+        let prob = if outcome == 0 {
+            prob_zero
+        } else {
+            1. - prob_zero
+        };
+
+        // collapse the state onto the measured outcome
+        let bit = 1 << measure_qubit;
+        let norm = prob.sqrt().recip();
+        for (i, amp) in self.amps.iter_mut().enumerate() {
+            if (i & bit != 0) == (outcome == 1) {
+                *amp *= norm;
+            } else {
+                *amp = Qcomplex::new(0., 0.);
+            }
+        }
+
+        *outcome_prob = prob;
+        Ok(outcome)
+    }
+
+    fn calc_expec_pauli_prod(
+        &self,
+        target_qubits: &[i32],
+        pauli_codes: &[PauliOpType],
+    ) -> Result<Qreal, QuestError> {
+        if target_qubits.len() != pauli_codes.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        self.check_qubits(target_qubits)?;
+
+        let mut amps = self.amps.clone();
+        for (&qubit, &code) in target_qubits.iter().zip(pauli_codes) {
+            let u = match code {
+                PauliOpType::PAULI_I => continue,
+                PauliOpType::PAULI_X => gates::pauli_x(),
+                PauliOpType::PAULI_Y => gates::pauli_y(),
+                PauliOpType::PAULI_Z => gates::pauli_z(),
+            };
+            Self::apply_matrix2(&mut amps, 0, qubit as usize, &u);
+        }
+        Ok(self
+            .amps
+            .iter()
+            .zip(&amps)
+            .map(|(a, b)| (a.conj() * b).re)
+            .sum())
+    }
+
+    fn calc_expec_pauli_sum(
+        &self,
+        all_pauli_codes: &[PauliOpType],
+        term_coeffs: &[Qreal],
+    ) -> Result<Qreal, QuestError> {
+        let num_qubits = self.num_qubits as usize;
+        if all_pauli_codes.len() != term_coeffs.len() * num_qubits {
+            return Err(QuestError::ArrayLengthError);
+        }
+
+        let targets = (0..self.num_qubits).collect::<Vec<_>>();
+        all_pauli_codes
+            .chunks(num_qubits)
+            .zip(term_coeffs)
+            .map(|(codes, coeff)| {
+                self.calc_expec_pauli_prod(&targets, codes)
+                    .map(|expec| coeff * expec)
+            })
+            .sum()
+    }
+}
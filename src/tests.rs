@@ -2,6 +2,7 @@
 
 use num::{
     Complex,
+    One,
     Zero,
 };
 
@@ -36,6 +37,114 @@ fn create_density_qureg_01() -> Result<(), QuestError> {
     Ok(())
 }
 
+#[test]
+fn qureg_try_clone_01() {
+    let env = &QuestEnv::new();
+    let mut qureg = Qureg::try_new(2, env).unwrap();
+    qureg.hadamard(0).unwrap();
+    qureg.controlled_not(0, 1).unwrap();
+
+    let mut clone = qureg.try_clone().unwrap();
+    assert!(!clone.is_density_matrix());
+    assert_eq!(clone.num_qubits(), 2);
+    assert_eq!(clone.amplitudes(), qureg.amplitudes());
+
+    // the clone is independent of the original register
+    clone.pauli_x(0).unwrap();
+    assert!((qureg.get_real_amp(0).unwrap() - SQRT_2.recip()).abs() < EPSILON);
+    assert!(clone.get_real_amp(0).unwrap().abs() < EPSILON);
+}
+
+#[test]
+fn qureg_clone_01() {
+    let env = &QuestEnv::new();
+    let mut qureg = Qureg::try_new_density(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let clone = qureg.clone();
+    assert!(clone.is_density_matrix());
+    assert_eq!(clone.density_matrix(), qureg.density_matrix());
+
+    qureg.init_zero_state();
+    assert!((clone.calc_purity().unwrap() - 1.).abs() < EPSILON);
+    assert!((clone.get_density_amp(0, 3).unwrap().re - 0.25).abs() < EPSILON);
+}
+
+#[test]
+fn qureg_kind_01() {
+    fn prepare_bell_state<K: QuregKind>(qureg: &mut Qureg<K>) {
+        qureg.init_zero_state();
+        qureg.hadamard(0).unwrap();
+        qureg.controlled_not(0, 1).unwrap();
+    }
+
+    let env = &QuestEnv::new();
+    let sv: &mut StateVector = &mut Qureg::try_new(2, env).unwrap();
+    let dm: &mut DensityMatrix = &mut Qureg::try_new_density(2, env).unwrap();
+    assert!(!sv.is_density_matrix());
+    assert!(dm.is_density_matrix());
+
+    prepare_bell_state(sv);
+    prepare_bell_state(dm);
+    assert!((sv.get_prob_amp(3).unwrap() - 0.5).abs() < EPSILON);
+    assert!((dm.get_density_amp(3, 0).unwrap().re - 0.5).abs() < EPSILON);
+    assert!((dm.calc_fidelity(sv).unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn qureg_kind_02() {
+    let env = &QuestEnv::new();
+    let sv = &mut Qureg::try_new(2, env).unwrap();
+    let dm = &mut Qureg::try_new_density(2, env).unwrap();
+
+    // density-matrix-only operations are checked at runtime in circuits
+    let mut circuit = Circuit::new();
+    circuit.hadamard(0).mix_dephasing(0, 0.5);
+
+    circuit.apply(dm).unwrap();
+    assert!((dm.calc_purity().unwrap() - 0.5).abs() < EPSILON);
+    circuit.apply(sv).unwrap_err();
+}
+
+#[test]
+fn quest_env_handle_01() {
+    struct Simulation {
+        qureg: StateVector,
+        op:    DiagonalOp,
+    }
+
+    fn new_simulation(num_qubits: i32) -> Simulation {
+        // the environment handle is dropped here, but the environment lives
+        // as long as the register and the operator
+        let env = QuestEnv::new();
+        Simulation {
+            qureg: Qureg::try_new(num_qubits, &env).unwrap(),
+            op:    DiagonalOp::try_new(num_qubits, &env).unwrap(),
+        }
+    }
+
+    let mut sim = new_simulation(2);
+    sim.qureg.init_plus_state();
+    let clone = sim.qureg.clone();
+    drop(sim.qureg);
+    assert!((clone.get_prob_amp(3).unwrap() - 0.25).abs() < EPSILON);
+    assert_eq!(sim.op.num_qubits(), 2);
+}
+
+#[test]
+fn quest_env_handle_02() {
+    let env = QuestEnv::new();
+    let mut qureg = Qureg::try_new(2, &env).unwrap();
+    drop(env);
+
+    let handle = std::thread::spawn(move || {
+        qureg.init_classical_state(2).unwrap();
+        qureg
+    });
+    let qureg = handle.join().unwrap();
+    assert!((qureg.get_prob_amp(2).unwrap() - 1.).abs() < EPSILON);
+}
+
 #[test]
 fn get_matrix_n_elem_01() {
     let env = &QuestEnv::new();
@@ -151,6 +260,225 @@ fn init_complex_matrix_from_slice_01() {
     assert_eq!(m.row_imag_as_slice(1), &[13., 14.]);
 }
 
+#[test]
+fn complex_matrix_2_ops_01() {
+    let h = ComplexMatrix2::new(
+        [[1. / SQRT_2, 1. / SQRT_2], [1. / SQRT_2, -1. / SQRT_2]],
+        [[0., 0.], [0., 0.]],
+    );
+    let s = ComplexMatrix2::new([[1., 0.], [0., 0.]], [[0., 0.], [0., 1.]]);
+
+    assert!(h.is_unitary(REAL_EPS));
+    assert!(s.is_unitary(REAL_EPS));
+    assert_eq!(s.get_elem(1, 1), Some(Qcomplex::new(0., 1.)));
+    assert_eq!(s.get_elem(2, 0), None);
+    assert_eq!(s.trace(), Qcomplex::new(1., 1.));
+    assert_eq!(s.adjoint().get_elem(1, 1), Some(Qcomplex::new(0., -1.)));
+
+    // S * S^dagger = I
+    let id = s.matmul(&s.adjoint());
+    assert_eq!(
+        id.to_vec(),
+        ComplexMatrix2::from_vec(vec![
+            Qcomplex::one(),
+            Qcomplex::zero(),
+            Qcomplex::zero(),
+            Qcomplex::one(),
+        ])
+        .unwrap()
+        .to_vec()
+    );
+
+    let mut m = h;
+    m.set_elem(0, 1, Qcomplex::new(2., 0.));
+    assert!(!m.is_unitary(REAL_EPS));
+
+    let _ = ComplexMatrix2::from_vec(vec![Qcomplex::zero(); 3]).unwrap_err();
+}
+
+#[test]
+fn complex_matrix_4_ops_01() {
+    let x = ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    let z = ComplexMatrix2::new([[1., 0.], [0., -1.]], [[0., 0.], [0., 0.]]);
+
+    // Z acts on the first qubit, X on the second
+    let m = x.kron(&z);
+    assert!(m.is_unitary(REAL_EPS));
+    assert_eq!(m.trace(), Qcomplex::zero());
+    let elems = m.to_vec();
+    for (i, z) in elems.iter().enumerate() {
+        let expected = match (i / 4, i % 4) {
+            (0, 2) | (2, 0) => 1.,
+            (1, 3) | (3, 1) => -1.,
+            _ => 0.,
+        };
+        assert_eq!(*z, Qcomplex::new(expected, 0.));
+    }
+
+    let id = m.matmul(&m.adjoint());
+    for i in 0..4 {
+        for j in 0..4 {
+            let expected = if i == j { 1. } else { 0. };
+            assert_eq!(id.get_elem(i, j), Some(Qcomplex::new(expected, 0.)));
+        }
+    }
+}
+
+#[test]
+fn complex_matrix_n_ops_01() {
+    let (zero, one, i) = (Qcomplex::zero(), Qcomplex::one(), Qcomplex::i());
+    let y = ComplexMatrixN::from_vec(vec![zero, -i, i, zero]).unwrap();
+    let z = ComplexMatrixN::from_vec(vec![one, zero, zero, -one]).unwrap();
+
+    assert_eq!(y.num_qubits(), 1);
+    assert_eq!(y.get_elem(0, 1), Some(-i));
+    assert_eq!(y.get_elem(1, 0).unwrap(), i);
+    assert!(y.is_unitary(REAL_EPS));
+    assert_eq!(y.adjoint().unwrap().to_vec(), y.to_vec());
+
+    // Y * Z = i X
+    let yz = y.matmul(&z).unwrap();
+    assert_eq!(yz.to_vec(), [zero, i, i, zero]);
+    assert_eq!(yz.trace(), zero);
+
+    let yzz = y.kron(&z).unwrap().kron(&z).unwrap();
+    assert_eq!(yzz.num_qubits(), 3);
+    assert!(yzz.is_unitary(REAL_EPS));
+    assert_eq!(yzz.get_elem(0, 4), Some(-i));
+    assert_eq!(yzz.get_elem(1, 5), Some(i));
+    assert_eq!(yzz.get_elem(8, 0), None);
+
+    let mut m = ComplexMatrixN::try_new(2).unwrap();
+    m.set_elem(3, 0, one);
+    assert_eq!(m.get_elem(3, 0), Some(one));
+    assert!(!m.is_unitary(REAL_EPS));
+
+    let _ = y.matmul(&yzz).unwrap_err();
+    let _ = ComplexMatrixN::from_vec(vec![]).unwrap_err();
+    let _ = ComplexMatrixN::from_vec(vec![one]).unwrap_err();
+    let _ = ComplexMatrixN::from_vec(vec![one; 2]).unwrap_err();
+    let _ = ComplexMatrixN::from_vec(vec![one; 8]).unwrap_err();
+}
+
+#[test]
+fn gates_01() {
+    let mtrs = [
+        gates::identity(),
+        gates::hadamard(),
+        gates::pauli_x(),
+        gates::pauli_y(),
+        gates::pauli_z(),
+        gates::s(),
+        gates::t(),
+        gates::sqrt_x(),
+        gates::phase_shift(0.3),
+        gates::rotate_x(0.3),
+        gates::rotate_y(0.3),
+        gates::rotate_z(0.3),
+        gates::u3(0.1, 0.2, 0.3),
+    ];
+    for m in mtrs {
+        assert!(m.is_unitary(REAL_EPS));
+    }
+
+    let mtrs = [
+        gates::swap(),
+        gates::sqrt_swap(),
+        gates::controlled_z(),
+        gates::controlled_phase_shift(0.3),
+        gates::iswap(),
+        gates::fsim(0.1, 0.2),
+        gates::xy(0.3),
+        gates::ms(0.3),
+    ];
+    for m in mtrs {
+        assert!(m.is_unitary(REAL_EPS));
+    }
+}
+
+#[test]
+fn gates_02() {
+    let assert_close = |a: Vec<Qcomplex>, b: Vec<Qcomplex>| {
+        for (x, y) in a.iter().zip(&b) {
+            assert!((x - y).norm() < 10. * EPSILON, "{a:?} != {b:?}");
+        }
+    };
+
+    let sqrt_x = gates::sqrt_x();
+    assert_close(sqrt_x.matmul(&sqrt_x).to_vec(), gates::pauli_x().to_vec());
+    assert_close(
+        gates::u3(PI / 2., 0., PI).to_vec(),
+        gates::hadamard().to_vec(),
+    );
+    assert_close(gates::t().matmul(&gates::t()).to_vec(), gates::s().to_vec());
+    assert_close(gates::xy(PI).to_vec(), gates::iswap().to_vec());
+    assert_close(gates::fsim(-PI / 2., 0.).to_vec(), gates::iswap().to_vec());
+    let sqrt_swap = gates::sqrt_swap();
+    assert_close(
+        sqrt_swap.matmul(&sqrt_swap).to_vec(),
+        gates::swap().to_vec(),
+    );
+
+    // Molmer-Sorensen gate is the rotation exp(-i theta/2 XX)
+    let xx = gates::pauli_x().kron(&gates::pauli_x()).to_vec();
+    let theta: Qreal = 0.3;
+    let expected = xx
+        .iter()
+        .enumerate()
+        .map(|(i, z)| {
+            let id = if i / 4 == i % 4 { 1. } else { 0. };
+            Qcomplex::new(id * (theta / 2.).cos(), 0.)
+                - Qcomplex::i() * (theta / 2.).sin() * z
+        })
+        .collect();
+    assert_close(gates::ms(theta).to_vec(), expected);
+}
+
+#[test]
+fn gates_03() {
+    let qft = gates::qft(2).unwrap();
+    assert!(qft.is_unitary(REAL_EPS));
+    assert_eq!(qft.get_elem(0, 0), Some(Qcomplex::new(0.5, 0.)));
+    assert_eq!(qft.get_elem(1, 2), Some(Qcomplex::new(-0.5, 0.)));
+
+    let id = gates::identity_n(3).unwrap();
+    assert!(id.is_unitary(0.));
+    assert_eq!(id.trace(), Qcomplex::new(8., 0.));
+
+    let _ = gates::qft(0).unwrap_err();
+}
+
+#[test]
+fn qureg_named_gates_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let other = &mut Qureg::try_new(3, env).unwrap();
+
+    qureg.hadamard(0).unwrap();
+    qureg.sqrt_x(1).unwrap();
+    qureg.u3(2, 0.1, 0.2, 0.3).unwrap();
+    qureg.iswap(0, 1).unwrap();
+    qureg.fsim(1, 2, 0.4, 0.5).unwrap();
+    qureg.xy(0, 2, 0.6).unwrap();
+    qureg.ms(2, 1, 0.7).unwrap();
+
+    other.unitary(0, &gates::hadamard()).unwrap();
+    other.unitary(1, &gates::sqrt_x()).unwrap();
+    other.unitary(2, &gates::u3(0.1, 0.2, 0.3)).unwrap();
+    other.two_qubit_unitary(0, 1, &gates::iswap()).unwrap();
+    other
+        .two_qubit_unitary(1, 2, &gates::fsim(0.4, 0.5))
+        .unwrap();
+    other.two_qubit_unitary(0, 2, &gates::xy(0.6)).unwrap();
+    other.two_qubit_unitary(2, 1, &gates::ms(0.7)).unwrap();
+
+    let inner = calc_inner_product(qureg, other).unwrap();
+    assert!((inner - Qcomplex::new(1., 0.)).norm() < 100. * EPSILON);
+
+    qureg.iswap(0, 0).unwrap_err();
+    qureg.ms(0, 3, 0.1).unwrap_err();
+}
+
 #[test]
 fn create_diagonal_op_01() {
     let env = &QuestEnv::new();
@@ -160,6 +488,179 @@ fn create_diagonal_op_01() {
     let _ = DiagonalOp::try_new(-1, env).unwrap_err();
 }
 
+#[test]
+fn diagonal_op_get_elem_01() {
+    let env = &QuestEnv::new();
+    let mut op = DiagonalOp::try_new(2, env).unwrap();
+    assert_eq!(op.num_qubits(), 2);
+    assert_eq!(op.get_elem(0), Some(Qcomplex::zero()));
+
+    init_diagonal_op(&mut op, &[1., 2., 3., 4.], &[5., 6., 7., 8.]).unwrap();
+    assert_eq!(op.get_elem(0), Some(Qcomplex::new(1., 5.)));
+    assert_eq!(op.get_elem(3), Some(Qcomplex::new(4., 8.)));
+    assert_eq!(op.get_elem(-1), None);
+    assert_eq!(op.get_elem(4), None);
+
+    set_diagonal_op_elems(&mut op, 1, &[9.], &[10.], 1).unwrap();
+    assert_eq!(op.get_elem(1), Some(Qcomplex::new(9., 10.)));
+}
+
+#[test]
+fn diagonal_op_elems_view_01() {
+    let env = &QuestEnv::new();
+    let mut op = DiagonalOp::try_new(2, env).unwrap();
+    init_diagonal_op(&mut op, &[1., 2., 3., 4.], &[5., 6., 7., 8.]).unwrap();
+
+    let (reals, imags) = op.elems_view();
+    assert_eq!(reals, &[1., 2., 3., 4.]);
+    assert_eq!(imags, &[5., 6., 7., 8.]);
+
+    let elems = op.iter().collect::<Vec<_>>();
+    assert_eq!(elems.len(), 4);
+    assert_eq!(elems[2], Qcomplex::new(3., 7.));
+}
+
+#[test]
+fn diagonal_op_modify_elems_01() {
+    let env = &QuestEnv::new();
+    let mut qureg = Qureg::try_new(1, env).unwrap();
+    let mut op = DiagonalOp::try_new(1, env).unwrap();
+    qureg.init_plus_state();
+
+    let len = op
+        .modify_elems(|reals, imags| {
+            reals.copy_from_slice(&[1., -1.]);
+            imags.fill(0.);
+            reals.len()
+        })
+        .unwrap();
+    assert_eq!(len, 2);
+    assert_eq!(op.get_elem(1), Some(Qcomplex::new(-1., 0.)));
+
+    // Z |+> = |->
+    apply_diagonal_op(&mut qureg, &op).unwrap();
+    assert!((qureg.get_real_amp(1).unwrap() + SQRT_2.recip()).abs() < EPSILON);
+}
+
+#[test]
+fn diagonal_op_from_fn_01() {
+    let env = &QuestEnv::new();
+    let op =
+        DiagonalOp::from_fn(3, env, |i| Qcomplex::new(i as Qreal, -i as Qreal))
+            .unwrap();
+
+    assert_eq!(op.num_qubits(), 3);
+    for (i, elem) in op.iter().enumerate() {
+        assert_eq!(elem, Qcomplex::new(i as Qreal, -(i as Qreal)));
+    }
+
+    let _ = DiagonalOp::from_fn(0, env, |_| Qcomplex::zero()).unwrap_err();
+}
+
+#[test]
+fn diagonal_op_from_fn_02() {
+    let env = &QuestEnv::new();
+    let mut qureg = Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let mut calls = Vec::new();
+    let op = DiagonalOp::from_fn(2, env, |i| {
+        calls.push(i);
+        Qcomplex::new(0., 1.)
+    })
+    .unwrap();
+    assert_eq!(calls, &[0, 1, 2, 3]);
+
+    apply_diagonal_op(&mut qureg, &op).unwrap();
+    assert!((qureg.get_imag_amp(3).unwrap() - 0.5).abs() < EPSILON);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn diagonal_op_par_from_fn_01() {
+    let env = &QuestEnv::new();
+    let op = DiagonalOp::par_from_fn(4, env, |i| {
+        Qcomplex::new(i.count_ones() as Qreal, 0.)
+    })
+    .unwrap();
+    let expected = DiagonalOp::from_fn(4, env, |i| {
+        Qcomplex::new(i.count_ones() as Qreal, 0.)
+    })
+    .unwrap();
+
+    assert_eq!(op.elems_view(), expected.elems_view());
+}
+
+#[test]
+fn diagonal_op_arithmetic_01() {
+    let env = &QuestEnv::new();
+    let d1 =
+        DiagonalOp::from_fn(2, env, |i| Qcomplex::new(i as Qreal, 1.)).unwrap();
+    let d2 = DiagonalOp::from_fn(2, env, |_| Qcomplex::new(2., 0.)).unwrap();
+
+    let sum = d1.try_add(&d2).unwrap();
+    assert_eq!(sum.get_elem(3), Some(Qcomplex::new(5., 1.)));
+    let diff = d1.try_sub(&d2).unwrap();
+    assert_eq!(diff.get_elem(3), Some(Qcomplex::new(1., 1.)));
+    let prod = d1.try_mul(&d2).unwrap();
+    assert_eq!(prod.get_elem(3), Some(Qcomplex::new(6., 2.)));
+
+    // operands are left untouched
+    assert_eq!(d1.get_elem(3), Some(Qcomplex::new(3., 1.)));
+    assert_eq!(d2.get_elem(3), Some(Qcomplex::new(2., 0.)));
+
+    let d3 = DiagonalOp::try_new(1, env).unwrap();
+    assert_eq!(d1.try_add(&d3).unwrap_err(), QuestError::ArrayLengthError);
+}
+
+#[test]
+fn diagonal_op_arithmetic_02() {
+    let env = &QuestEnv::new();
+    let mut d1 =
+        DiagonalOp::from_fn(1, env, |i| Qcomplex::new(i as Qreal, 1.)).unwrap();
+    let d2 = d1.try_clone().unwrap();
+
+    d1.add_in_place(&d2).unwrap();
+    assert_eq!(d1.get_elem(1), Some(Qcomplex::new(2., 2.)));
+    d1.mul_in_place(&d2).unwrap();
+    assert_eq!(d1.get_elem(1), Some(Qcomplex::new(0., 4.)));
+    d1.sub_in_place(&d2).unwrap();
+    assert_eq!(d1.get_elem(1), Some(Qcomplex::new(-1., 3.)));
+    d1.scale_in_place(Qcomplex::new(0., 1.)).unwrap();
+    assert_eq!(d1.get_elem(1), Some(Qcomplex::new(-3., -1.)));
+    d1.conj_in_place().unwrap();
+    assert_eq!(d1.get_elem(1), Some(Qcomplex::new(-3., 1.)));
+    d1.powi_in_place(2).unwrap();
+    assert_eq!(d1.get_elem(1), Some(Qcomplex::new(8., -6.)));
+
+    let d3 = DiagonalOp::try_new(2, env).unwrap();
+    d1.mul_in_place(&d3).unwrap_err();
+}
+
+#[test]
+fn diagonal_op_exp_01() {
+    let env = &QuestEnv::new();
+    let mut d =
+        DiagonalOp::from_fn(2, env, |i| Qcomplex::new(i as Qreal, 0.)).unwrap();
+
+    let u = d.exp(PI / 2.).unwrap();
+    for (i, elem) in u.iter().enumerate() {
+        let expected = Qcomplex::new(0., -PI / 2. * i as Qreal).exp();
+        assert!((elem - expected).norm() < EPSILON);
+    }
+
+    let sqrt = d.powf(0.5).unwrap();
+    assert!((sqrt.get_elem(2).unwrap().re - SQRT_2).abs() < EPSILON);
+
+    d.exp_in_place(PI).unwrap();
+    assert!((d.get_elem(1).unwrap().re + 1.).abs() < EPSILON);
+    d.powf_in_place(2.).unwrap();
+    assert!((d.get_elem(1).unwrap().re - 1.).abs() < EPSILON);
+
+    let conj = d.conj().unwrap().try_mul(&d).unwrap();
+    assert!(conj.iter().all(|elem| (elem.re - 1.).abs() < EPSILON));
+}
+
 #[test]
 fn set_diagonal_op_elems_01() {
     let env = &QuestEnv::new();
@@ -229,6 +730,280 @@ fn initialize_pauli_hamil_01() {
     .unwrap();
 }
 
+#[test]
+fn pauli_hamil_from_file_str_01() {
+    use PauliOpType::*;
+    let hamil =
+        PauliHamil::try_new_from_file_str("0.5 1 2\n\n  -1e-3\t3 0  \n")
+            .unwrap();
+
+    assert_eq!(hamil.num_qubits(), 2);
+    assert_eq!(hamil.num_sum_terms(), 2);
+    assert_eq!(hamil.term_coeffs(), &[0.5, -1e-3]);
+    assert_eq!(hamil.pauli_codes(), &[PAULI_X, PAULI_Y, PAULI_Z, PAULI_I]);
+}
+
+#[test]
+fn pauli_hamil_from_file_str_02() {
+    let parse_error =
+        |contents| match PauliHamil::try_new_from_file_str(contents) {
+            Err(QuestError::ParseError {
+                line,
+                column,
+                ..
+            }) => (line, column),
+            _ => panic!("expected parse error"),
+        };
+    assert_eq!(parse_error("0.5 1 2\n1.0 0 4\n"), (2, 7));
+    assert_eq!(parse_error("0.5 1 2\n1.0 0\n"), (2, 6));
+    assert_eq!(parse_error("0.5 1 2\nx 0 0\n"), (2, 1));
+    assert_eq!(parse_error("0.5\n"), (1, 4));
+    assert_eq!(parse_error("\n  \n"), (1, 1));
+
+    let _ = PauliHamil::try_new_from_file_str("").unwrap_err();
+    let _ = PauliHamil::try_new_from_file_str("0.5 1 -1\n").unwrap_err();
+}
+
+#[test]
+fn pauli_hamil_write_to_01() {
+    let contents = "0.1 1 0 3\n-2 2 2 0\n0.3333333333333333 0 0 0\n";
+    let hamil = PauliHamil::try_new_from_file_str(contents).unwrap();
+
+    let mut buf = Vec::new();
+    hamil.write_to(&mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), contents);
+}
+
+#[test]
+fn pauli_hamil_from_file_01() {
+    let path = std::env::temp_dir().join("quest_bind_pauli_hamil_01.txt");
+    let hamil = PauliHamil::try_new_from_file_str("0.5 1 3\n0.25 0 2").unwrap();
+    hamil.write_to_file(&path).unwrap();
+
+    let hamil2 = PauliHamil::try_new_from_file(&path).unwrap();
+    assert_eq!(hamil.term_coeffs(), hamil2.term_coeffs());
+    assert_eq!(hamil.pauli_codes(), hamil2.pauli_codes());
+    std::fs::remove_file(&path).unwrap();
+
+    let err = PauliHamil::try_new_from_file(&path).unwrap_err();
+    assert_eq!(err.kind(), QuestErrorKind::File);
+}
+
+#[test]
+fn diagonal_op_from_file_01() {
+    let env = &QuestEnv::new();
+    let path = std::env::temp_dir().join("quest_bind_diagonal_op_01.txt");
+
+    std::fs::write(&path, "0.5 3 0\n0.5 0 0\n").unwrap();
+    let op = DiagonalOp::try_new_from_file(&path, env).unwrap();
+    assert_eq!(op.op.numQubits, 2);
+
+    std::fs::write(&path, "0.5 1 0\n").unwrap();
+    let _ = DiagonalOp::try_new_from_file(&path, env).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn pauli_hamil_from_terms_01() {
+    use PauliOpType::*;
+    let hamil =
+        PauliHamil::from_terms(&[(0.5, "X0 Z2"), (-1.2, " Y1 "), (2., "")])
+            .unwrap();
+
+    assert_eq!(hamil.num_qubits(), 3);
+    assert_eq!(hamil.num_sum_terms(), 3);
+    assert_eq!(hamil.term_coeffs(), &[0.5, -1.2, 2.]);
+    assert_eq!(
+        hamil.pauli_codes(),
+        &[
+            PAULI_X, PAULI_I, PAULI_Z, PAULI_I, PAULI_Y, PAULI_I, PAULI_I,
+            PAULI_I, PAULI_I
+        ]
+    );
+
+    let hamil = PauliHamil::from_terms(&[(1., "")]).unwrap();
+    assert_eq!(hamil.num_qubits(), 1);
+
+    let _ = PauliHamil::from_terms(&[(1., "X0 X0")]).unwrap_err();
+    let _ = PauliHamil::from_terms(&[(1., "X")]).unwrap_err();
+    let _ = PauliHamil::from_terms(&[(1., "X0 + Y1")]).unwrap_err();
+    let _ = PauliHamil::from_terms::<&str>(&[]).unwrap_err();
+}
+
+#[test]
+fn pauli_hamil_from_str_01() {
+    use PauliOpType::*;
+    let hamil: PauliHamil =
+        "0.5 X0 Z1 - 1.2 Y2 + Z0Z2\n -1e-3 + 4 I3".parse().unwrap();
+
+    assert_eq!(hamil.num_qubits(), 4);
+    assert_eq!(hamil.term_coeffs(), &[0.5, -1.2, 1., -1e-3, 4.]);
+    assert_eq!(
+        &hamil.pauli_codes()[..8],
+        &[
+            PAULI_X, PAULI_Z, PAULI_I, PAULI_I, PAULI_I, PAULI_I, PAULI_Y,
+            PAULI_I
+        ]
+    );
+    assert_eq!(
+        &hamil.pauli_codes()[8..12],
+        &[PAULI_Z, PAULI_I, PAULI_Z, PAULI_I]
+    );
+
+    let hamil: PauliHamil = "-X0".parse().unwrap();
+    assert_eq!(hamil.term_coeffs(), &[-1.]);
+}
+
+#[test]
+fn pauli_hamil_from_str_02() {
+    let parse_error = |s: &str| match s.parse::<PauliHamil>() {
+        Err(QuestError::ParseError {
+            line,
+            column,
+            ..
+        }) => (line, column),
+        _ => panic!("expected parse error"),
+    };
+    assert_eq!(parse_error(""), (1, 1));
+    assert_eq!(parse_error("0.5 X0 +"), (1, 9));
+    assert_eq!(parse_error("0.5 X0 Y"), (1, 9));
+    assert_eq!(parse_error("0.5 X0 Y0"), (1, 8));
+    assert_eq!(parse_error("0.5 X0 0.5"), (1, 8));
+    assert_eq!(parse_error("X0\n+ 1.2.3 Z1"), (2, 6));
+    assert_eq!(parse_error("X0 - A1"), (1, 6));
+    assert_eq!(parse_error("X0 Z64"), (1, 5));
+    assert_eq!(parse_error("X1000000000"), (1, 2));
+    assert_eq!(parse_error("X2147483647"), (1, 2));
+    assert_eq!(parse_error("X99999999999"), (1, 2));
+}
+
+#[test]
+fn pauli_hamil_display_01() {
+    let hamil: PauliHamil = "0.5 X0 Z1 - 1.2 Y2 + 3".parse().unwrap();
+    assert_eq!(hamil.to_string(), "0.5 X0 Z1 - 1.2 Y2 + 3");
+
+    let hamil: PauliHamil = "-0.25 Y1 Z3 + X0".parse().unwrap();
+    assert_eq!(hamil.to_string(), "-0.25 Y1 Z3 + 1 X0");
+
+    let hamil2: PauliHamil = hamil.to_string().parse().unwrap();
+    assert_eq!(hamil.term_coeffs(), hamil2.term_coeffs());
+    assert_eq!(hamil.pauli_codes(), hamil2.pauli_codes());
+}
+
+#[test]
+fn pauli_sum_mul_01() {
+    let p = |s: &str| s.parse::<PauliSum>().unwrap();
+    let i = Qcomplex::new(0., 1.);
+
+    assert_eq!(p("X0") * p("Y0"), p("Z0") * i);
+    assert_eq!(p("Y0") * p("Z0"), p("X0") * i);
+    assert_eq!(p("Z0") * p("X0"), p("Y0") * i);
+    assert_eq!(p("Y0") * p("X0"), p("Z0") * -i);
+    assert_eq!(p("X0") * p("X0"), p("1"));
+    assert_eq!(p("X0 Z2") * p("Y1 Z2"), p("X0 Y1"));
+    assert_eq!(p("X0 Y1") * p("Y0 Z1"), p("Z0 X1") * -1.);
+    assert_eq!(p("2 X0") * p("3 Y1"), p("6 X0 Y1"));
+    assert_eq!((p("X0 + Y0") * p("X0 - Y0")).to_string(), "(0-2i) Z0");
+}
+
+#[test]
+fn pauli_sum_add_01() {
+    let p = |s: &str| s.parse::<PauliSum>().unwrap();
+
+    let sum = p("X0 + 0.5 Z1") + p("Y2 - 0.5 Z1");
+    assert_eq!(sum.to_string(), "1 X0 + 1 Y2");
+    assert!((p("X0") - p("X0")).is_empty());
+    assert_eq!(-p("X0 - Z1"), p("-X0 + Z1"));
+    assert_eq!(&p("X0") + p("Z1"), p("X0 + Z1"));
+    assert_eq!(2. * p("X0"), p("2 X0"));
+    assert_eq!(&p("X0") * 0., PauliSum::new());
+}
+
+#[test]
+fn pauli_sum_from_term_01() {
+    use PauliOpType::*;
+
+    let sum =
+        PauliSum::from_term(1., &[(1, PAULI_Y), (1, PAULI_I), (0, PAULI_Z)])
+            .unwrap();
+    assert_eq!(sum, "Z0 Y1".parse().unwrap());
+    assert_eq!(sum.num_qubits(), 2);
+
+    let sum = PauliSum::from_term(1., &[(0, PAULI_X), (0, PAULI_Z)]).unwrap();
+    assert_eq!(sum.iter().next().unwrap().0, Qcomplex::new(0., -1.));
+    assert_eq!(sum.iter().next().unwrap().1, &[(0, PAULI_Y)]);
+
+    let sum = PauliSum::from_term(1., &[]).unwrap();
+    assert_eq!(sum.num_qubits(), 0);
+    assert_eq!(sum.len(), 1);
+
+    let _ = PauliSum::from_term(1., &[(-1, PAULI_X)]).unwrap_err();
+    let _ = PauliSum::from_term(1., &[(i32::MAX, PAULI_X)]).unwrap_err();
+    assert!("X2147483647".parse::<PauliSum>().is_err());
+}
+
+#[test]
+fn pauli_sum_commutator_01() {
+    let p = |s: &str| s.parse::<PauliSum>().unwrap();
+
+    assert!(p("X0 X1").commutator(&p("Z0 Z1")).is_empty());
+    assert_eq!(
+        p("X0").commutator(&p("Z0 + Z1")),
+        p("-2 Y0") * Qcomplex::new(0., 1.)
+    );
+    assert!(p("X0 + Z1").is_hermitian());
+    assert!(!p("X0").commutator(&p("Y0")).is_hermitian());
+    assert!(
+        (p("X0").commutator(&p("Y0")) * Qcomplex::new(0., 1.)).is_hermitian()
+    );
+}
+
+#[test]
+fn pauli_sum_to_pauli_hamil_01() {
+    use PauliOpType::*;
+    let sum: PauliSum = "0.5 X0 Z2 - Y1 + 0.5 Z2 X0".parse().unwrap();
+
+    let hamil = PauliHamil::try_from(&sum).unwrap();
+    assert_eq!(hamil.num_qubits(), 3);
+    assert_eq!(hamil.term_coeffs(), &[1., -1.]);
+    assert_eq!(
+        hamil.pauli_codes(),
+        &[PAULI_X, PAULI_I, PAULI_Z, PAULI_I, PAULI_Y, PAULI_I]
+    );
+
+    let hamil = sum.to_pauli_hamil(4).unwrap();
+    assert_eq!(hamil.num_qubits(), 4);
+    assert_eq!(PauliSum::from(&hamil), sum.clone() + PauliSum::new());
+
+    let hamil = PauliHamil::try_from(&PauliSum::new()).unwrap();
+    assert_eq!(hamil.term_coeffs(), &[0.]);
+
+    assert_eq!(
+        sum.to_pauli_hamil(2).unwrap_err(),
+        QuestError::QubitIndexError
+    );
+    assert_eq!(
+        (sum * Qcomplex::new(0., 1.)).to_pauli_hamil(3).unwrap_err(),
+        QuestError::NonHermitianError
+    );
+}
+
+#[test]
+fn pauli_sum_apply_trotter_circuit_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let x: PauliSum = "X0".parse().unwrap();
+    let z: PauliSum = "Z0".parse().unwrap();
+    let h = x.commutator(&z) * Qcomplex::new(0., 0.5);
+    let hamil = h.to_pauli_hamil(qureg.num_qubits()).unwrap();
+    qureg.apply_trotter_circuit(&hamil, PI / 2., 1, 1).unwrap();
+
+    // exp(-i pi/2 Y) |0> = |1>
+    assert!((qureg.get_real_amp(1).unwrap() - 1.).abs() < EPSILON);
+}
+
 #[test]
 fn set_amps_01() {
     let env = &QuestEnv::new();
@@ -278,8 +1053,6 @@ fn set_density_amps_01() {
 
     qureg.set_density_amps(1, 3, re, im).unwrap();
 
-    qureg.set_amps(0, re, im).unwrap_err();
-
     qureg.set_density_amps(0, 9, re, im).unwrap_err();
     qureg.set_density_amps(8, 7, re, im).unwrap_err();
     qureg.set_density_amps(0, -1, re, im).unwrap_err();
@@ -463,29 +1236,189 @@ fn get_prob_amp_01() {
 }
 
 #[test]
-fn get_density_amp_01() {
+fn get_density_amp_02() {
     let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
 
-    qureg.get_density_amp(0, 0).unwrap_err();
-    qureg.get_density_amp(1, 0).unwrap_err();
-    qureg.get_density_amp(2, 0).unwrap_err();
-    qureg.get_density_amp(3, 0).unwrap_err();
-    qureg.get_density_amp(-1, 5).unwrap_err();
+    qureg.get_density_amp(0, 0).unwrap();
+    qureg.get_density_amp(1, 0).unwrap();
+    qureg.get_density_amp(2, 0).unwrap();
+    qureg.get_density_amp(3, 0).unwrap();
+    qureg.get_density_amp(-1, 0).unwrap_err();
     qureg.get_density_amp(4, 0).unwrap_err();
 }
 
 #[test]
-fn get_density_amp_02() {
+fn amplitudes_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let amps = qureg.amplitudes();
+    assert_eq!(amps.len(), 4);
+    for amp in amps {
+        assert!((amp.re - 0.5).abs() < EPSILON);
+        assert!(amp.im.abs() < EPSILON);
+    }
+}
+
+#[test]
+fn amplitudes_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_classical_state(1).unwrap();
+
+    let amps = qureg.amplitudes();
+    assert_eq!(amps.len(), 16);
+    for (i, amp) in amps.iter().enumerate() {
+        let expected = if i == 1 + 4 { 1. } else { 0. };
+        assert!((amp.re - expected).abs() < EPSILON);
+        assert!(amp.im.abs() < EPSILON);
+    }
+}
+
+#[test]
+fn amplitudes_range_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(5).unwrap();
+
+    let amps = qureg.amplitudes_range(4..6);
+    assert_eq!(amps.len(), 2);
+    assert!(amps[0].re.abs() < EPSILON);
+    assert!((amps[1].re - 1.).abs() < EPSILON);
+
+    assert!(qureg.amplitudes_range(3..3).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn amplitudes_range_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let _ = qureg.amplitudes_range(4..9);
+}
+
+#[test]
+fn amplitudes_view_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg
+        .init_state_from_amps(&[1., 2., 3., 4.], &[5., 6., 7., 8.])
+        .unwrap();
+
+    let other = &mut Qureg::try_new(2, env).unwrap();
+    let (reals, imags) = qureg.amplitudes_view();
+    assert_eq!(reals, &[1., 2., 3., 4.]);
+    assert_eq!(imags, &[5., 6., 7., 8.]);
+    other.init_state_from_amps(reals, imags).unwrap();
+
+    assert_eq!(other.get_real_amp(2).unwrap(), 3.);
+    assert_eq!(other.get_imag_amp(3).unwrap(), 8.);
+}
+
+#[test]
+fn amplitudes_view_mut_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let (reals, imags) = qureg.amplitudes_view_mut();
+    reals.swap(0, 3);
+    imags[1] = 1.;
+    qureg.copy_state_to_gpu();
+
+    assert_eq!(qureg.get_real_amp(0).unwrap(), 0.);
+    assert_eq!(qureg.get_real_amp(3).unwrap(), 1.);
+    assert_eq!(qureg.get_imag_amp(1).unwrap(), 1.);
+}
+
+#[test]
+fn density_matrix_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let rho = qureg.density_matrix();
+    assert_eq!(rho.len(), 4);
+    for row in rho {
+        assert_eq!(row.len(), 4);
+        for elem in row {
+            assert!((elem.re - 0.25).abs() < EPSILON);
+            assert!(elem.im.abs() < EPSILON);
+        }
+    }
+}
+
+#[test]
+fn density_matrix_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+    qureg.init_zero_state();
+    qureg.rotate_x(0, PI / 2.).unwrap();
+
+    let rho = qureg.density_matrix();
+    assert!((rho[0][0].re - 0.5).abs() < EPSILON);
+    assert!((rho[1][1].re - 0.5).abs() < EPSILON);
+    assert!((rho[0][1].im - 0.5).abs() < EPSILON);
+    assert!((rho[1][0].im + 0.5).abs() < EPSILON);
+}
+
+#[test]
+fn init_density_matrix_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(1, env).unwrap();
+
+    let rho = [
+        [Qcomplex::new(0.75, 0.), Qcomplex::new(0.1, -0.2)],
+        [Qcomplex::new(0.1, 0.2), Qcomplex::new(0.25, 0.)],
+    ];
+    qureg.init_density_matrix(&rho).unwrap();
+
+    let amp = qureg.get_density_amp(1, 0).unwrap();
+    assert!((amp.re - 0.1).abs() < EPSILON);
+    assert!((amp.im - 0.2).abs() < EPSILON);
+    assert_eq!(qureg.density_matrix(), rho.map(Vec::from).to_vec());
+}
+
+#[test]
+fn init_density_matrix_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(1, env).unwrap();
+
+    let zero = Qcomplex::new(0., 0.);
+    let half = Qcomplex::new(0.5, 0.);
+    assert_eq!(
+        qureg.init_density_matrix(&[[half, half]]),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.init_density_matrix(&[vec![half, zero], vec![zero]]),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.init_density_matrix(&[[half, half], [zero, half]]),
+        Err(QuestError::NonHermitianError)
+    );
+    assert_eq!(
+        qureg.init_density_matrix(&[[half, zero], [zero, zero]]),
+        Err(QuestError::TraceError)
+    );
+}
+
+#[test]
+fn try_new_density_from_matrix_01() {
     let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
 
-    qureg.get_density_amp(0, 0).unwrap();
-    qureg.get_density_amp(1, 0).unwrap();
-    qureg.get_density_amp(2, 0).unwrap();
-    qureg.get_density_amp(3, 0).unwrap();
-    qureg.get_density_amp(-1, 0).unwrap_err();
-    qureg.get_density_amp(4, 0).unwrap_err();
+    let zero = Qcomplex::new(0., 0.);
+    let one = Qcomplex::new(1., 0.);
+    let mut rho = vec![vec![zero; 8]; 8];
+    rho[5][5] = one;
+    let qureg = Qureg::try_new_density_from_matrix(&rho, env).unwrap();
+    assert_eq!(qureg.num_qubits(), 3);
+    assert!((qureg.calc_prob_of_outcome(1, 0).unwrap() - 1.).abs() < EPSILON);
+
+    let _ = Qureg::try_new_density_from_matrix(&rho[..6], env).unwrap_err();
 }
 
 #[test]
@@ -1057,6 +1990,77 @@ fn calc_prob_of_all_outcomes_02() {
         .unwrap_err();
 }
 
+#[test]
+fn sample_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(6).unwrap();
+
+    let counts = qureg.sample(100, &[0, 1, 2]).unwrap();
+    assert_eq!(counts.num_bits(), 3);
+    assert_eq!(counts.shots(), 100);
+    assert_eq!(counts.get("110"), 100);
+
+    let counts = qureg.sample(100, &[2, 0]).unwrap();
+    assert_eq!(counts.get("01"), 100);
+
+    // the state is left untouched
+    assert!((qureg.get_real_amp(6).unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn sample_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let counts = qureg.sample(1000, &[0, 1]).unwrap();
+    assert_eq!(counts.shots(), 1000);
+    assert_eq!(counts.len(), 4);
+
+    qureg.sample(10, &[0, 2]).unwrap_err();
+    qureg.sample(10, &[0, 0]).unwrap_err();
+
+    // more qubits than in the register
+    assert_eq!(
+        qureg.sample(10, &[0; 40]).unwrap_err(),
+        QuestError::NumQubitsError
+    );
+    assert_eq!(
+        qureg.sample(10, &[0; 64]).unwrap_err(),
+        QuestError::NumQubitsError
+    );
+
+    // all outcome probabilities are zero
+    qureg.init_blank_state();
+    assert_eq!(
+        qureg.sample(10, &[0, 1]).unwrap_err(),
+        QuestError::ProbabilityError
+    );
+}
+
+#[test]
+fn sample_with_rng_01() {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    qureg.init_plus_state();
+
+    let qubits = &[0, 1, 2, 3];
+    let rng = &mut StdRng::seed_from_u64(1);
+    let counts1 = qureg.sample_with_rng(100, qubits, rng).unwrap();
+    let counts2 = qureg.sample_with_rng(100, qubits, rng).unwrap();
+    let rng = &mut StdRng::seed_from_u64(1);
+    let counts3 = qureg.sample_with_rng(100, qubits, rng).unwrap();
+
+    assert_ne!(counts1, counts2);
+    assert_eq!(counts1, counts3);
+}
+
 #[test]
 fn collapse_to_outcome_01() {
     let env = &QuestEnv::new();
@@ -1125,17 +2129,6 @@ fn calc_inner_product_01() {
 //     let _ = calc_inner_product(qureg, other_qureg).unwrap_err();
 // }
 
-#[test]
-fn calc_inner_product_03() {
-    let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_zero_state();
-    let other_qureg = &mut Qureg::try_new_density(2, env).unwrap();
-    other_qureg.init_zero_state();
-
-    let _ = calc_inner_product(qureg, other_qureg).unwrap_err();
-}
-
 #[test]
 fn calc_density_inner_product_01() {
     let env = &QuestEnv::new();
@@ -1159,14 +2152,15 @@ fn calc_density_inner_product_01() {
 // }
 
 #[test]
-fn calc_density_inner_product_03() {
+fn quest_env_info_01() {
     let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
-    qureg.init_zero_state();
-    let other_qureg = &mut Qureg::try_new(2, env).unwrap();
-    other_qureg.init_zero_state();
+    let info = env.info();
+    let env_str = env.get_environment_string().unwrap();
 
-    let _ = calc_density_inner_product(qureg, other_qureg).unwrap_err();
+    assert!(env_str.contains(&format!("threads={}", info.num_threads)));
+    assert!(env_str.contains(&format!("ranks={}", info.num_ranks)));
+    assert!(env_str.contains(&format!("MPI={}", u8::from(info.mpi))));
+    assert!(info.rank < info.num_ranks);
 }
 
 #[test]
@@ -1185,7 +2179,7 @@ fn get_quest_seeds_02() {
     let seeds = get_quest_seeds(env);
 
     assert!(!seeds.is_empty());
-    assert_eq!(seed_array, seeds);
+    assert_eq!(seeds, seed_array);
 }
 
 #[test]
@@ -1200,6 +2194,29 @@ fn start_recording_qasm_01() {
     qureg.print_recorded_qasm();
 }
 
+#[test]
+fn recorded_qasm_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+
+    qureg.start_recording_qasm();
+    qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    qureg.stop_recording_qasm();
+    qureg.pauli_x(1).unwrap();
+
+    let qasm = qureg.recorded_qasm().unwrap();
+    assert!(qasm.starts_with("OPENQASM 2.0;\nqreg q[2];\n"));
+    assert!(qasm.ends_with("h q[0];\ncx q[0],q[1];\n"));
+
+    let buf = &mut Vec::new();
+    qureg.write_recorded_qasm(&mut *buf).unwrap();
+    assert_eq!(buf, qasm.as_bytes());
+
+    qureg.clear_recorded_qasm();
+    let qasm = qureg.recorded_qasm().unwrap();
+    assert!(!qasm.contains("h q[0];"));
+}
+
 #[test]
 fn mix_dephasing_01() {
     let env = &QuestEnv::new();
@@ -1215,17 +2232,6 @@ fn mix_dephasing_01() {
     qureg.mix_dephasing(-10, 0.25).unwrap_err();
 }
 
-#[test]
-fn mix_dephasing_02() {
-    let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_plus_state();
-
-    // qureg is not a density matrix
-    qureg.mix_dephasing(0, 0.5).unwrap_err();
-    qureg.mix_dephasing(1, 0.0).unwrap_err();
-}
-
 #[test]
 fn mix_two_qubit_dephasing_01() {
     let env = &QuestEnv::new();
@@ -1248,17 +2254,6 @@ fn mix_two_qubit_dephasing_01() {
     qureg.mix_two_qubit_dephasing(0, -1, 0.1).unwrap_err();
 }
 
-#[test]
-fn mix_two_qubit_dephasing_02() {
-    let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(3, env).unwrap();
-    qureg.init_plus_state();
-
-    // qureg is not a density matrix
-    qureg.mix_two_qubit_dephasing(0, 1, 0.75).unwrap_err();
-    qureg.mix_two_qubit_dephasing(0, 2, 0.75).unwrap_err();
-}
-
 #[test]
 fn mix_depolarising_01() {
     let env = &QuestEnv::new();
@@ -1295,19 +2290,6 @@ fn mix_damping_01() {
     qureg.mix_damping(-3, 0.5).unwrap_err();
 }
 
-#[test]
-fn mix_damping_02() {
-    let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_plus_state();
-
-    // qureg is not a density matrix
-    qureg.mix_damping(1, 0.).unwrap_err();
-    qureg.mix_damping(0, 0.).unwrap_err();
-    // QuEST seg faults here:
-    qureg.mix_damping(0, 0.5).unwrap_err();
-}
-
 #[test]
 fn mix_two_qubit_depolarising_01() {
     let env = &QuestEnv::new();
@@ -1330,17 +2312,6 @@ fn mix_two_qubit_depolarising_01() {
     qureg.mix_two_qubit_depolarising(0, -1, 0.1).unwrap_err();
 }
 
-#[test]
-fn mix_two_qubit_depolarising_02() {
-    let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(3, env).unwrap();
-    qureg.init_plus_state();
-
-    // qureg is not a density matrix
-    qureg.mix_two_qubit_depolarising(0, 1, 0.75).unwrap_err();
-    qureg.mix_two_qubit_depolarising(0, 2, 0.75).unwrap_err();
-}
-
 #[test]
 fn mix_pauli_01() {
     let env = &QuestEnv::new();
@@ -1367,18 +2338,6 @@ fn mix_pauli_02() {
     qureg.mix_pauli(1, prob_x, prob_y, prob_z).unwrap_err();
 }
 
-#[test]
-fn mix_pauli_03() {
-    let env = &QuestEnv::new();
-    // not a density matrix
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_zero_state();
-
-    let (prob_x, prob_y, prob_z) = (0.25, 0.25, 0.25);
-    qureg.mix_pauli(0, prob_x, prob_y, prob_z).unwrap_err();
-    qureg.mix_pauli(1, prob_x, prob_y, prob_z).unwrap_err();
-}
-
 #[test]
 fn mix_density_matrix_01() {
     let env = &QuestEnv::new();
@@ -1400,36 +2359,6 @@ fn mix_density_matrix_01() {
         .unwrap_err();
 }
 
-#[test]
-fn mix_density_matrix_02() {
-    let env = &QuestEnv::new();
-    // this is not a density matrix
-    let combine_qureg = &mut Qureg::try_new(2, env).unwrap();
-    let other_qureg = &mut Qureg::try_new_density(2, env).unwrap();
-
-    combine_qureg.init_zero_state();
-    other_qureg.init_zero_state();
-
-    combine_qureg
-        .mix_density_matrix(0.0, other_qureg)
-        .unwrap_err();
-}
-
-#[test]
-fn mix_density_matrix_03() {
-    let env = &QuestEnv::new();
-    let combine_qureg = &mut Qureg::try_new_density(2, env).unwrap();
-    // this is not a density matrix
-    let other_qureg = &mut Qureg::try_new(2, env).unwrap();
-
-    combine_qureg.init_zero_state();
-    other_qureg.init_zero_state();
-
-    combine_qureg
-        .mix_density_matrix(0.0, other_qureg)
-        .unwrap_err();
-}
-
 // #[test]
 // fn mix_density_matrix_04() {
 //     let env = &QuestEnv::new();
@@ -1450,10 +2379,6 @@ fn calc_purity_01() {
     qureg.init_zero_state();
 
     let _ = qureg.calc_purity().unwrap();
-
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_zero_state();
-    let _ = qureg.calc_purity().unwrap_err();
 }
 
 #[test]
@@ -1480,18 +2405,6 @@ fn calc_fidelity_01() {
 //     let _ = qureg.calc_fidelity( pure_state).unwrap_err();
 // }
 
-#[test]
-fn calc_fidelity_03() {
-    let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    let pure_state = &mut Qureg::try_new_density(2, env).unwrap();
-
-    qureg.init_zero_state();
-    pure_state.init_zero_state();
-
-    let _ = qureg.calc_fidelity(pure_state).unwrap_err();
-}
-
 #[test]
 fn swap_gate_01() {
     let env = &QuestEnv::new();
@@ -2188,17 +3101,6 @@ fn calc_hilbert_schmidt_distance_01() {
 //     let _ = calc_hilbert_schmidt_distance(a, b).unwrap_err();
 // }
 
-#[test]
-fn calc_hilbert_schmidt_distance_03() {
-    let env = &QuestEnv::new();
-    let a = &mut Qureg::try_new_density(2, env).unwrap();
-    a.init_classical_state(0).unwrap();
-    let b = &mut Qureg::try_new(2, env).unwrap();
-    b.init_classical_state(1).unwrap();
-
-    let _ = calc_hilbert_schmidt_distance(a, b).unwrap_err();
-}
-
 #[test]
 fn mix_nontp_kraus_map_01() {
     let env = &QuestEnv::new();
@@ -3675,26 +4577,6 @@ fn set_weighted_qureg_01() {
     set_weighted_qureg(fac1, qureg1, fac2, qureg2, fac_out, out).unwrap();
 }
 
-#[test]
-fn set_weighted_qureg_04() {
-    let env = &QuestEnv::new();
-    let qureg1 = &mut Qureg::try_new(2, env).unwrap();
-    qureg1.init_zero_state();
-    // all quregs should either state vectors of density matrices
-    let qureg2 = &mut Qureg::try_new_density(2, env).unwrap();
-    qureg2.init_zero_state();
-    qureg2.pauli_x(0).unwrap();
-
-    let out = &mut Qureg::try_new(2, env).unwrap();
-    out.init_zero_state();
-
-    let fac1 = Qcomplex::new(SQRT_2.recip(), 0.);
-    let fac2 = Qcomplex::new(SQRT_2.recip(), 0.);
-    let fac_out = Qcomplex::zero();
-
-    set_weighted_qureg(fac1, qureg1, fac2, qureg2, fac_out, out).unwrap_err();
-}
-
 #[test]
 fn multi_controlled_multi_rotate_z_01() {
     let env = &QuestEnv::new();
@@ -3814,3 +4696,305 @@ fn check_array_length_init_state_from_amps() {
     let imags = [0.; 5];
     qureg.init_state_from_amps(&reals, &imags).unwrap();
 }
+
+#[test]
+fn circuit_apply_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+
+    let mut circuit = Circuit::new();
+    circuit
+        .hadamard(0)
+        .controlled_not(0, 1)
+        .multi_qubit_not(&[2])
+        .measure(0)
+        .measure(1)
+        .measure(2);
+
+    assert_eq!(circuit.len(), 6);
+    assert_eq!(circuit.num_qubits(), 3);
+
+    let outcomes = circuit.apply(qureg).unwrap();
+    assert_eq!(outcomes.len(), 3);
+    assert_eq!(outcomes[0], outcomes[1]);
+    assert_eq!(outcomes[2], 1);
+}
+
+#[test]
+fn circuit_apply_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let mut circuit = Circuit::new();
+    circuit.pauli_x(0).controlled_not(0, 2);
+
+    assert_eq!(circuit.num_qubits(), 3);
+    assert_eq!(circuit.check(qureg), Err(QuestError::QubitIndexError));
+    assert_eq!(circuit.apply(qureg), Err(QuestError::QubitIndexError));
+
+    // No operation should have been applied
+    assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn circuit_apply_03() {
+    let env = &QuestEnv::new();
+    let mut circuit = Circuit::new();
+    circuit
+        .rotate_x(0, 0.5)
+        .mix_dephasing(0, 0.1)
+        .apply_full_qft();
+
+    assert_eq!(circuit.operations()[2].num_qubits(), 0);
+
+    let qureg = &mut Qureg::try_new_density(1, env).unwrap();
+    qureg.init_zero_state();
+    circuit.apply(qureg).unwrap();
+
+    // Mixing is defined only for density matrices
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+    qureg.init_zero_state();
+    let _ = circuit.apply(qureg).unwrap_err();
+}
+
+#[test]
+fn circuit_apply_04() {
+    let env = &QuestEnv::new();
+    let first = &mut Qureg::try_new(2, env).unwrap();
+    let second = &mut Qureg::try_new(2, env).unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit
+        .hadamard(0)
+        .multi_rotate_pauli(
+            &[0, 1],
+            &[PauliOpType::PAULI_Y, PauliOpType::PAULI_X],
+            0.3,
+        )
+        .swap_gate(0, 1);
+
+    first.init_zero_state();
+    second.init_zero_state();
+    circuit.apply(first).unwrap();
+    for op in &circuit {
+        op.apply(second).unwrap();
+    }
+
+    for i in 0..4 {
+        let a = first.get_amp(i).unwrap();
+        let b = second.get_amp(i).unwrap();
+        assert!((a - b).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn qasm_program_run_01() {
+    let program = QasmProgram::parse(
+        r#"
+        OPENQASM 2.0;
+        include "qelib1.inc";
+        qreg q[3];
+        creg c[1];
+        creg d[2];
+        x q[0];
+        measure q[0] -> c[0];
+        if (c == 1) x q[1];
+        u3(pi, 0, pi) q[2];
+        measure q[1] -> d[0];
+        measure q[2] -> d[1];
+        reset q;
+        "#,
+    )
+    .unwrap();
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+
+    let cregs = program.run(qureg).unwrap();
+    assert_eq!(cregs[0].bits, vec![1]);
+    assert_eq!(cregs[1].bits, vec![1, 1]);
+    assert_eq!(cregs[1].value(), 3);
+    assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
+
+    let small = &mut Qureg::try_new(2, env).unwrap();
+    assert_eq!(program.run(small), Err(QuestError::QubitIndexError));
+}
+
+#[test]
+fn dense_state_vector_01() {
+    let sim = &mut DenseStateVector::try_new(2).unwrap();
+    sim.seed(1);
+    assert_eq!(Simulator::num_qubits(sim), 2);
+    assert_eq!(sim.amplitudes().len(), 4);
+
+    sim.init_zero_state();
+    sim.hadamard(0).unwrap();
+    sim.controlled_not(0, 1).unwrap();
+    assert!((sim.get_prob_amp(0).unwrap() - 0.5).abs() < REAL_EPS);
+    assert!((sim.get_prob_amp(3).unwrap() - 0.5).abs() < REAL_EPS);
+    assert!((sim.calc_prob_of_outcome(1, 1).unwrap() - 0.5).abs() < REAL_EPS);
+    assert!((sim.calc_total_prob() - 1.).abs() < REAL_EPS);
+
+    let prob = &mut 0.;
+    let outcome = sim.measure_with_stats(0, prob).unwrap();
+    assert!((*prob - 0.5).abs() < REAL_EPS);
+    assert_eq!(sim.measure(1).unwrap(), outcome);
+    assert!((sim.calc_total_prob() - 1.).abs() < REAL_EPS);
+
+    sim.init_classical_state(2).unwrap();
+    sim.swap_gate(0, 1).unwrap();
+    assert!((sim.get_prob_amp(1).unwrap() - 1.).abs() < REAL_EPS);
+
+    sim.init_plus_state();
+    sim.pauli_z(0).unwrap();
+    sim.hadamard(0).unwrap();
+    assert!((sim.get_prob_amp(1).unwrap() - 0.5).abs() < REAL_EPS);
+}
+
+#[test]
+fn dense_state_vector_02() {
+    fn kind<T: std::fmt::Debug>(res: Result<T, QuestError>) -> QuestErrorKind {
+        res.unwrap_err().kind()
+    }
+
+    assert_eq!(
+        kind(DenseStateVector::try_new(0)),
+        QuestErrorKind::InvalidNumQubits
+    );
+    // too large to be allocated
+    assert_eq!(
+        kind(DenseStateVector::try_new(60)),
+        QuestErrorKind::InvalidNumQubits
+    );
+    assert_eq!(
+        kind(DenseStateVector::try_new(62)),
+        QuestErrorKind::InvalidNumQubits
+    );
+
+    let sim = &mut DenseStateVector::try_new(2).unwrap();
+    assert_eq!(kind(sim.hadamard(2)), QuestErrorKind::InvalidQubitIndex);
+    assert_eq!(kind(sim.hadamard(-1)), QuestErrorKind::InvalidQubitIndex);
+    assert_eq!(
+        kind(sim.controlled_not(1, 1)),
+        QuestErrorKind::RepeatedQubits
+    );
+    assert_eq!(
+        kind(sim.multi_controlled_unitary(&[0, 0], 1, &gates::pauli_x())),
+        QuestErrorKind::RepeatedQubits
+    );
+    assert_eq!(
+        kind(sim.multi_controlled_unitary(&[], 1, &gates::pauli_x())),
+        QuestErrorKind::InvalidNumQubits
+    );
+    let non_unitary =
+        ComplexMatrix2::new([[1., 1.], [0., 1.]], [[0., 0.], [0., 0.]]);
+    assert_eq!(
+        kind(sim.unitary(0, &non_unitary)),
+        QuestErrorKind::NonUnitaryMatrix
+    );
+    assert_eq!(kind(sim.get_amp(4)), QuestErrorKind::InvalidIndex);
+    assert_eq!(
+        kind(sim.init_classical_state(-1)),
+        QuestErrorKind::InvalidIndex
+    );
+    assert_eq!(
+        kind(sim.calc_prob_of_outcome(0, 2)),
+        QuestErrorKind::InvalidIndex
+    );
+    assert_eq!(
+        kind(sim.calc_expec_pauli_prod(&[0], &[])),
+        QuestErrorKind::ArrayLength
+    );
+
+    // the state is left unchanged on failure
+    assert!((sim.get_prob_amp(0).unwrap() - 1.).abs() < REAL_EPS);
+}
+
+#[test]
+fn dense_state_vector_03() {
+    use PauliOpType::*;
+
+    let sim = &mut DenseStateVector::try_new(2).unwrap();
+    sim.init_plus_state();
+    sim.pauli_z(1).unwrap();
+    sim.rotate_y(1, PI / 2.).unwrap();
+
+    // |+>|-> after rotating qubit 1 is |+>|0>, up to a phase
+    let expec = sim
+        .calc_expec_pauli_prod(&[0, 1], &[PAULI_X, PAULI_Z])
+        .unwrap();
+    assert!((expec - 1.).abs() < REAL_EPS);
+    let expec = sim.calc_expec_pauli_prod(&[0], &[PAULI_Y]).unwrap();
+    assert!(expec.abs() < REAL_EPS);
+
+    let expec = sim
+        .calc_expec_pauli_sum(
+            &[PAULI_X, PAULI_I, PAULI_I, PAULI_Z],
+            &[0.5, -2.],
+        )
+        .unwrap();
+    assert!((expec + 1.5).abs() < REAL_EPS);
+}
+
+#[test]
+fn simulator_differential_01() {
+    use PauliOpType::*;
+
+    fn run<S: Simulator>(sim: &mut S) -> Result<(), QuestError> {
+        sim.init_plus_state();
+        sim.hadamard(0)?;
+        sim.pauli_y(1)?;
+        sim.s_gate(2)?;
+        sim.t_gate(0)?;
+        sim.rotate_x(1, 0.3)?;
+        sim.rotate_z(2, -1.1)?;
+        sim.phase_shift(0, 0.7)?;
+        sim.controlled_not(2, 0)?;
+        sim.controlled_phase_shift(0, 1, 0.4)?;
+        sim.controlled_unitary(1, 2, &gates::u3(0.1, 0.2, 0.3))?;
+        sim.multi_controlled_unitary(&[0, 2], 1, &gates::sqrt_x())?;
+        sim.controlled_phase_flip(1, 2)?;
+        sim.swap_gate(0, 2)
+    }
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let dense = &mut DenseStateVector::try_new(3).unwrap();
+    run(qureg).unwrap();
+    run(dense).unwrap();
+
+    for index in 0..8 {
+        let a = Simulator::get_amp(qureg, index).unwrap();
+        let b = Simulator::get_amp(dense, index).unwrap();
+        assert!((a - b).norm() < REAL_EPS);
+    }
+    for qubit in 0..3 {
+        let p = Simulator::calc_prob_of_outcome(qureg, qubit, 1).unwrap();
+        let q = Simulator::calc_prob_of_outcome(dense, qubit, 1).unwrap();
+        assert!((p - q).abs() < REAL_EPS);
+    }
+
+    let codes = &[PAULI_X, PAULI_Y, PAULI_Z, PAULI_Z, PAULI_I, PAULI_X];
+    let coeffs = &[0.3, -1.2];
+    let p = Simulator::calc_expec_pauli_sum(qureg, codes, coeffs).unwrap();
+    let q = Simulator::calc_expec_pauli_sum(dense, codes, coeffs).unwrap();
+    assert!((p - q).abs() < REAL_EPS);
+
+    for sim in [qureg as &mut dyn Simulator, dense] {
+        assert_eq!(
+            sim.controlled_not(0, 0).unwrap_err().kind(),
+            QuestErrorKind::RepeatedQubits
+        );
+        assert_eq!(
+            sim.hadamard(3).unwrap_err().kind(),
+            QuestErrorKind::InvalidQubitIndex
+        );
+        assert_eq!(
+            sim.calc_prob_of_outcome(0, 2).unwrap_err().kind(),
+            QuestErrorKind::InvalidIndex
+        );
+    }
+}